@@ -0,0 +1,82 @@
+//! Bitcoin wallet transaction history
+
+use crate::bitcoin::{BalanceError, BitcoinWallet};
+use crate::types::{TransactionInfo, TxDirection};
+
+/// List all wallet transactions
+///
+/// Walks the canonical transactions in BDK's tx graph and computes the
+/// wallet's view of each one (sent, received, fee, direction). RGB
+/// annotations are left empty; the wallet manager adds them.
+///
+/// Unconfirmed transactions come first, then confirmed ones newest first.
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet
+///
+/// # Example
+///
+/// ```ignore
+/// let transactions = list_transactions(&wallet)?;
+///
+/// for tx in &transactions {
+///     println!("{} {} {} sats", tx.txid, tx.direction, tx.net_sats);
+/// }
+/// ```
+pub fn list_transactions(wallet: &BitcoinWallet) -> Result<Vec<TransactionInfo>, BalanceError> {
+    let bdk_wallet = wallet.inner();
+    let current_height = bdk_wallet.local_chain().tip().height();
+
+    let mut transactions: Vec<TransactionInfo> = bdk_wallet
+        .transactions()
+        .map(|wallet_tx| {
+            let tx = &wallet_tx.tx_node.tx;
+
+            let confirmation_height = match wallet_tx.chain_position {
+                bdk_wallet::chain::ChainPosition::Confirmed { anchor, .. } => {
+                    Some(anchor.block_id.height)
+                }
+                _ => None,
+            };
+            let confirmations = confirmation_height
+                .map(|height| current_height.saturating_sub(height).saturating_add(1))
+                .unwrap_or(0);
+
+            let (sent, received) = bdk_wallet.sent_and_received(tx);
+            let sent_sats = sent.to_sat();
+            let received_sats = received.to_sat();
+            let fee_sats = bdk_wallet.calculate_fee(tx).ok().map(|fee| fee.to_sat());
+
+            let all_outputs_mine = tx
+                .output
+                .iter()
+                .all(|output| bdk_wallet.is_mine(output.script_pubkey.clone()));
+
+            let direction = if sent_sats == 0 {
+                TxDirection::Incoming
+            } else if all_outputs_mine {
+                TxDirection::SelfTransfer
+            } else {
+                TxDirection::Outgoing
+            };
+
+            TransactionInfo {
+                txid: wallet_tx.tx_node.txid.to_string(),
+                confirmation_height,
+                confirmations,
+                sent_sats,
+                received_sats,
+                net_sats: received_sats as i64 - sent_sats as i64,
+                fee_sats,
+                direction,
+                rgb: vec![], // No RGB data at Bitcoin layer
+            }
+        })
+        .collect();
+
+    // Unconfirmed first, then by height descending
+    transactions.sort_by_key(|tx| std::cmp::Reverse(tx.confirmation_height.unwrap_or(u32::MAX)));
+
+    Ok(transactions)
+}
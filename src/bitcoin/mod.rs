@@ -3,7 +3,9 @@
 //! Handles Bitcoin UTXO management, blockchain sync, and transaction operations
 
 pub mod balance;
+pub mod history;
 pub mod network;
+pub mod psbt;
 pub mod sync;
 pub mod utxo;
 pub mod wallet;
@@ -12,10 +14,19 @@ pub use balance::{
     get_addresses, get_balance, get_unused_addresses, is_rgb_occupied, list_utxos,
     mark_rgb_occupied, unmark_rgb_occupied, AddressInfo, Balance, BalanceError, UtxoInfo,
 };
+pub use history::list_transactions;
 pub use network::{default_esplora_url, EsploraClient, NetworkError};
-pub use sync::{sync_wallet, sync_wallet_with_progress, SyncError, SyncResult};
+pub use psbt::{
+    finalize_psbt, load_psbt, save_psbt, sign_psbt, summarize_psbt, PsbtError, PsbtOutputInfo,
+    PsbtSummary,
+};
+pub use sync::{
+    full_scan_wallet, sync_wallet, sync_wallet_with_progress, SyncError, SyncResult,
+    DEFAULT_STOP_GAP,
+};
 pub use utxo::{
-    create_utxo, estimate_fee, get_recommended_fee_rates, unlock_utxo, FeeRateConfig, UtxoError,
-    UtxoOperationResult,
+    build_cpfp, build_fee_bump, bump_fee, cpfp, create_utxo, estimate_fee,
+    get_recommended_fee_rates, psbt_fee, unlock_utxo, CpfpResult, FeeBumpResult, FeeRateConfig,
+    UtxoError, UtxoOperationResult,
 };
 pub use wallet::{BitcoinWallet, BitcoinWalletError};
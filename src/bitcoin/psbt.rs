@@ -0,0 +1,211 @@
+//! PSBT file handling for offline signing
+//!
+//! Witness transactions can be exported as PSBTs, signed on an air-gapped
+//! machine holding the wallet keys, and brought back for broadcast.
+//! PSBT files are written as base64 text; binary PSBTs are accepted on load.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::bitcoin::{BitcoinWallet, BitcoinWalletError};
+use bdk_wallet::bitcoin::{Address, Psbt, Transaction};
+#[allow(deprecated)]
+use bdk_wallet::SignOptions;
+
+/// Errors that can occur during PSBT operations
+#[derive(Debug, thiserror::Error)]
+pub enum PsbtError {
+    #[error("Wallet error: {0}")]
+    Wallet(#[from] BitcoinWalletError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid PSBT: {0}")]
+    Parse(String),
+
+    #[error("PSBT sign failed: {0}")]
+    SignFailed(String),
+
+    #[error("PSBT is not fully signed ({signed} of {total} inputs finalized)")]
+    Incomplete { signed: usize, total: usize },
+
+    #[error("Failed to extract transaction: {0}")]
+    ExtractFailed(String),
+}
+
+/// One output of a PSBT, as shown before signing
+#[derive(Debug, Clone)]
+pub struct PsbtOutputInfo {
+    /// Destination address (None for OP_RETURN and non-standard scripts)
+    pub address: Option<String>,
+
+    /// Amount in satoshis
+    pub amount_sats: u64,
+
+    /// Whether the output pays this wallet
+    pub is_mine: bool,
+}
+
+/// Summary of a PSBT for review before signing or broadcasting
+#[derive(Debug, Clone)]
+pub struct PsbtSummary {
+    /// Transaction ID (unchanged by signing for segwit inputs)
+    pub txid: String,
+
+    /// Number of inputs
+    pub inputs: usize,
+
+    /// Number of inputs with a final witness or script
+    pub finalized_inputs: usize,
+
+    /// Outputs in transaction order
+    pub outputs: Vec<PsbtOutputInfo>,
+
+    /// Fee in satoshis (None if input amounts are missing)
+    pub fee_sats: Option<u64>,
+}
+
+impl PsbtSummary {
+    /// Whether every input is finalized (ready to broadcast)
+    pub fn is_finalized(&self) -> bool {
+        self.finalized_inputs == self.inputs
+    }
+}
+
+/// Write a PSBT to a file as base64 text
+///
+/// # Arguments
+///
+/// * `psbt` - PSBT to save
+/// * `path` - Destination file
+pub fn save_psbt(psbt: &Psbt, path: &Path) -> Result<(), PsbtError> {
+    std::fs::write(path, format!("{}\n", psbt))?;
+    Ok(())
+}
+
+/// Read a PSBT file (base64 text or binary)
+///
+/// # Arguments
+///
+/// * `path` - PSBT file
+///
+/// # Example
+///
+/// ```ignore
+/// let psbt = load_psbt(Path::new("witness.psbt"))?;
+/// println!("Inputs: {}", psbt.inputs.len());
+/// ```
+pub fn load_psbt(path: &Path) -> Result<Psbt, PsbtError> {
+    let bytes = std::fs::read(path)?;
+
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        if let Ok(psbt) = Psbt::from_str(text.trim()) {
+            return Ok(psbt);
+        }
+    }
+
+    Psbt::deserialize(&bytes).map_err(|e| PsbtError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Sign every input of a PSBT the wallet holds keys for
+///
+/// Inputs are finalized once fully signed. Does not touch the network, so
+/// it can run on an offline machine. Fails with `Locked` on a watch-only wallet.
+///
+/// # Arguments
+///
+/// * `wallet` - Bitcoin wallet with the signing keys
+/// * `psbt` - PSBT to sign in place
+///
+/// # Returns
+///
+/// Summary of the signed PSBT
+///
+/// # Example
+///
+/// ```ignore
+/// let mut psbt = load_psbt(Path::new("witness.psbt"))?;
+/// let summary = sign_psbt(&wallet, &mut psbt)?;
+/// assert!(summary.is_finalized());
+/// save_psbt(&psbt, Path::new("witness.signed.psbt"))?;
+/// ```
+pub fn sign_psbt(wallet: &BitcoinWallet, psbt: &mut Psbt) -> Result<PsbtSummary, PsbtError> {
+    if !wallet.can_sign() {
+        return Err(BitcoinWalletError::Locked.into());
+    }
+
+    #[allow(deprecated)]
+    wallet
+        .inner()
+        .sign(psbt, SignOptions::default())
+        .map_err(|e| PsbtError::SignFailed(e.to_string()))?;
+
+    Ok(summarize_psbt(wallet, psbt))
+}
+
+/// Finalize a signed PSBT and extract the transaction
+///
+/// # Arguments
+///
+/// * `wallet` - Bitcoin wallet that built the PSBT
+/// * `psbt` - Signed PSBT
+///
+/// # Errors
+///
+/// Returns `Incomplete` if any input is still unsigned.
+pub fn finalize_psbt(wallet: &BitcoinWallet, mut psbt: Psbt) -> Result<Transaction, PsbtError> {
+    #[allow(deprecated)]
+    let finalized = wallet
+        .inner()
+        .finalize_psbt(&mut psbt, SignOptions::default())
+        .map_err(|e| PsbtError::SignFailed(e.to_string()))?;
+
+    if !finalized {
+        let summary = summarize_psbt(wallet, &psbt);
+        return Err(PsbtError::Incomplete {
+            signed: summary.finalized_inputs,
+            total: summary.inputs,
+        });
+    }
+
+    psbt.extract_tx()
+        .map_err(|e| PsbtError::ExtractFailed(e.to_string()))
+}
+
+/// Summarize a PSBT's outputs and fee
+///
+/// # Arguments
+///
+/// * `wallet` - Bitcoin wallet (for address network and ownership)
+/// * `psbt` - PSBT to summarize
+pub fn summarize_psbt(wallet: &BitcoinWallet, psbt: &Psbt) -> PsbtSummary {
+    let network = wallet.network().to_bitcoin_network();
+
+    let outputs = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .map(|output| PsbtOutputInfo {
+            address: Address::from_script(&output.script_pubkey, network)
+                .ok()
+                .map(|address| address.to_string()),
+            amount_sats: output.value.to_sat(),
+            is_mine: wallet.inner().is_mine(output.script_pubkey.clone()),
+        })
+        .collect();
+
+    let finalized_inputs = psbt
+        .inputs
+        .iter()
+        .filter(|input| input.final_script_witness.is_some() || input.final_script_sig.is_some())
+        .count();
+
+    PsbtSummary {
+        txid: psbt.unsigned_tx.compute_txid().to_string(),
+        inputs: psbt.inputs.len(),
+        finalized_inputs,
+        outputs,
+        fee_sats: psbt.fee().ok().map(|fee| fee.to_sat()),
+    }
+}
@@ -4,6 +4,7 @@ use crate::bitcoin::{BitcoinWallet, BitcoinWalletError, EsploraClient, NetworkEr
 use bdk_esplora::EsploraExt;
 use bdk_wallet::bitcoin::BlockHash;
 use bdk_wallet::KeychainKind;
+use serde::{Deserialize, Serialize};
 
 /// Errors that can occur during sync operations
 #[derive(Debug, thiserror::Error)]
@@ -22,7 +23,7 @@ pub enum SyncError {
 }
 
 /// Result of a blockchain sync operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncResult {
     /// Current blockchain height after sync
     pub height: u32,
@@ -246,3 +247,82 @@ where
 
     Ok(result)
 }
+
+/// Default number of consecutive unused addresses that ends a full scan
+pub const DEFAULT_STOP_GAP: usize = 20;
+
+/// Scan the blockchain for all wallet transactions via Esplora
+///
+/// Unlike `sync_wallet`, which only checks revealed addresses, a full scan
+/// walks every keychain until `stop_gap` consecutive addresses have no
+/// history, and reveals the addresses it finds in use. Use this after
+/// restoring a wallet from its mnemonic.
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet to scan
+/// * `client` - The Esplora client for blockchain queries
+/// * `stop_gap` - Unused addresses in a row before a keychain is considered done
+///
+/// # Returns
+///
+/// Returns `SyncResult` with details about what was discovered.
+///
+/// # Example
+///
+/// ```ignore
+/// let result = full_scan_wallet(&mut wallet, &client, DEFAULT_STOP_GAP)?;
+/// println!("Found {} transactions", result.new_txs);
+/// ```
+pub fn full_scan_wallet(
+    wallet: &mut BitcoinWallet,
+    client: &EsploraClient,
+    stop_gap: usize,
+) -> Result<SyncResult, SyncError> {
+    log::info!("Starting full blockchain scan (stop gap: {})...", stop_gap);
+
+    let height_before = wallet.inner().latest_checkpoint().height();
+    let revealed_before = wallet.inner().spk_index().revealed_spks(..).count();
+
+    let tip_height = client.get_height()?;
+    let tip_hash = client.get_tip_hash()?;
+    log::info!("Blockchain tip: {} (height: {})", tip_hash, tip_height);
+
+    let request = wallet.inner().start_full_scan();
+    let txs_before = wallet.inner().transactions().count();
+
+    let update = client
+        .inner()
+        .full_scan(request, stop_gap, 5) // parallel_requests = 5
+        .map_err(|e| SyncError::Esplora(format!("Full scan failed: {}", e)))?;
+
+    log::debug!("Applying full scan update to wallet...");
+    wallet
+        .inner_mut()
+        .apply_update(update)
+        .map_err(|e| SyncError::Failed(format!("Failed to apply update: {}", e)))?;
+
+    let txs_after = wallet.inner().transactions().count();
+    let new_txs = txs_after.saturating_sub(txs_before);
+    let revealed_after = wallet.inner().spk_index().revealed_spks(..).count();
+
+    wallet.persist()?;
+
+    let result = SyncResult {
+        height: tip_height,
+        tip_hash,
+        new_txs,
+        updated_txs: 0, // BDK handles this internally
+        new_addresses: revealed_after.saturating_sub(revealed_before) as u32,
+    };
+
+    log::info!(
+        "Full scan complete: height={} (was {}), new_txs={}, new_addresses={}",
+        result.height,
+        height_before,
+        result.new_txs,
+        result.new_addresses
+    );
+
+    Ok(result)
+}
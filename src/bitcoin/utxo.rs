@@ -3,7 +3,7 @@
 //! Handles creating and unlocking specific UTXOs for RGB asset management
 
 use crate::bitcoin::{BitcoinWallet, BitcoinWalletError, EsploraClient, NetworkError};
-use bdk_wallet::bitcoin::{Amount, FeeRate, OutPoint};
+use bdk_wallet::bitcoin::{Amount, FeeRate, OutPoint, Psbt, ScriptBuf, Transaction, Txid};
 #[allow(deprecated)]
 use bdk_wallet::{KeychainKind, SignOptions};
 use std::collections::HashSet;
@@ -37,6 +37,9 @@ pub enum UtxoError {
 
     #[error("UTXO not found: {0}")]
     UtxoNotFound(String),
+
+    #[error("Transaction not found in wallet: {0} (sync the wallet first)")]
+    TransactionNotFound(String),
 }
 
 /// Result of a UTXO operation
@@ -152,13 +155,8 @@ pub fn estimate_fee(
     tx_builder.fee_rate(fee_rate.to_bdk_fee_rate());
 
     match tx_builder.finish() {
-        Ok(psbt) => {
-            // Calculate fee from the PSBT
-            let fee = psbt
-                .fee()
-                .map_err(|e| UtxoError::BuildFailed(format!("Failed to calculate fee: {}", e)))?;
-            Ok(fee.to_sat())
-        }
+        // Calculate fee from the PSBT
+        Ok(psbt) => psbt_fee(&psbt),
         Err(e) => Err(UtxoError::BuildFailed(format!(
             "Failed to estimate fee: {}",
             e
@@ -166,6 +164,22 @@ pub fn estimate_fee(
     }
 }
 
+/// Fee paid by a PSBT built by the wallet
+///
+/// # Arguments
+///
+/// * `psbt` - PSBT with the previous outputs of all inputs
+///
+/// # Returns
+///
+/// Fee in satoshis (inputs minus outputs)
+pub fn psbt_fee(psbt: &Psbt) -> Result<u64, UtxoError> {
+    let fee = psbt
+        .fee()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to calculate fee: {}", e)))?;
+    Ok(fee.to_sat())
+}
+
 /// Create a new UTXO by self-sending Bitcoin
 ///
 /// This creates a transaction that sends a specific amount to a new address
@@ -421,6 +435,464 @@ pub fn unlock_utxo(
     ))
 }
 
+/// Result of a replace-by-fee bump
+#[derive(Debug, Clone)]
+pub struct FeeBumpResult {
+    /// Transaction being replaced
+    pub original_txid: Txid,
+
+    /// Signed replacement transaction
+    pub tx: Transaction,
+
+    /// Fee paid by the replacement in satoshis
+    pub fee: u64,
+
+    /// Effective fee rate of the replacement in sat/vB
+    pub fee_rate: f64,
+}
+
+impl FeeBumpResult {
+    /// Replacement transaction ID
+    pub fn txid(&self) -> Txid {
+        self.tx.compute_txid()
+    }
+}
+
+/// Build and sign a fee bump of an unconfirmed transaction (RBF)
+///
+/// Rebuilds the transaction at a higher fee rate. Every non-change output
+/// keeps its index, script and value (the extra fee comes out of the wallet's
+/// change output or additional inputs), so commitments embedded in outputs -
+/// Tapret-tweaked scripts and OP_RETURN data - stay valid.
+///
+/// The replacement is not broadcast, so callers can re-issue anything bound
+/// to the witness before it reaches the network. Use [`bump_fee`] to build
+/// and broadcast in one step.
+///
+/// The transaction must be known to the wallet (sync after broadcasting).
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet
+/// * `txid` - Transaction to replace
+/// * `fee_rate` - New fee rate (must exceed the original)
+/// * `rgb_occupied` - RGB-occupied outpoints, never added as extra inputs
+///
+/// # Example
+///
+/// ```ignore
+/// let bump = build_fee_bump(&mut wallet, txid, &FeeRateConfig::new(20.0)?, &rgb_occupied)?;
+/// // ... re-issue consignments for bump.tx, then broadcast it
+/// ```
+pub fn build_fee_bump(
+    wallet: &mut BitcoinWallet,
+    txid: Txid,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<FeeBumpResult, UtxoError> {
+    // Find the original transaction
+    let original = wallet
+        .inner()
+        .get_tx(txid)
+        .ok_or_else(|| UtxoError::TransactionNotFound(txid.to_string()))?;
+
+    if original.chain_position.is_confirmed() {
+        return Err(UtxoError::BuildFailed(format!(
+            "Transaction {} is already confirmed",
+            txid
+        )));
+    }
+
+    let original_outputs = original.tx_node.tx.output.clone();
+
+    // Build the replacement, keeping outputs in their original order
+    let mut tx_builder = wallet
+        .inner_mut()
+        .build_fee_bump(txid)
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to build fee bump: {}", e)))?;
+    tx_builder.fee_rate(fee_rate.to_bdk_fee_rate());
+    tx_builder.ordering(bdk_wallet::tx_builder::TxOrdering::Untouched);
+
+    // Exclude RGB-occupied UTXOs if extra inputs are needed
+    for occupied_outpoint in rgb_occupied.iter() {
+        tx_builder.add_unspendable(*occupied_outpoint);
+    }
+
+    let mut psbt = tx_builder
+        .finish()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to build fee bump: {}", e)))?;
+
+    // Every output except the wallet's change must be untouched
+    for (index, output) in original_outputs.iter().enumerate() {
+        let is_change = matches!(
+            wallet
+                .inner()
+                .derivation_of_spk(output.script_pubkey.clone()),
+            Some((KeychainKind::Internal, _))
+        );
+        if !is_change && psbt.unsigned_tx.output.get(index) != Some(output) {
+            return Err(UtxoError::BuildFailed(format!(
+                "Fee bump would modify output {}",
+                index
+            )));
+        }
+    }
+
+    // Sign the PSBT
+    #[allow(deprecated)]
+    wallet
+        .inner_mut()
+        .sign(&mut psbt, SignOptions::default())
+        .map_err(|e| UtxoError::SignFailed(format!("Failed to sign transaction: {}", e)))?;
+
+    // Calculate fee before extracting (extract_tx consumes the PSBT)
+    let fee = psbt
+        .fee()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to calculate fee: {}", e)))?
+        .to_sat();
+
+    let tx = psbt
+        .extract_tx()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to extract transaction: {}", e)))?;
+
+    let effective_fee_rate = fee as f64 / tx.vsize() as f64;
+
+    Ok(FeeBumpResult {
+        original_txid: txid,
+        tx,
+        fee,
+        fee_rate: effective_fee_rate,
+    })
+}
+
+/// Bump the fee of an unconfirmed transaction (RBF)
+///
+/// Builds and signs the replacement with [`build_fee_bump`], then
+/// broadcasts it.
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet
+/// * `client` - Esplora client for broadcasting
+/// * `txid` - Transaction to replace
+/// * `fee_rate` - New fee rate (must exceed the original)
+/// * `rgb_occupied` - RGB-occupied outpoints, never added as extra inputs
+///
+/// # Example
+///
+/// ```ignore
+/// let result = bump_fee(&mut wallet, &client, txid, &FeeRateConfig::new(20.0)?, &rgb_occupied)?;
+/// println!("Replaced {} with {}", result.original_txid, result.txid());
+/// ```
+pub fn bump_fee(
+    wallet: &mut BitcoinWallet,
+    client: &EsploraClient,
+    txid: Txid,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<FeeBumpResult, UtxoError> {
+    let bump = build_fee_bump(wallet, txid, fee_rate, rgb_occupied)?;
+
+    // Broadcast the replacement
+    client
+        .inner()
+        .broadcast(&bump.tx)
+        .map_err(|e| UtxoError::BroadcastFailed(format!("Failed to broadcast: {}", e)))?;
+
+    // Persist wallet changes
+    wallet.persist()?;
+
+    Ok(bump)
+}
+
+/// Result of a child-pays-for-parent acceleration
+#[derive(Debug, Clone)]
+pub struct CpfpResult {
+    /// Unconfirmed parent transaction being accelerated
+    pub parent_txid: Txid,
+
+    /// Signed child transaction
+    pub tx: Transaction,
+
+    /// Parent output spent by the child
+    pub spent: OutPoint,
+
+    /// Child output carrying the spent output forward (RGB-occupied parents only)
+    pub carried_to: Option<OutPoint>,
+
+    /// Fee paid by the child in satoshis
+    pub fee: u64,
+
+    /// Parent fee in satoshis
+    pub parent_fee: u64,
+
+    /// Effective fee rate of parent + child in sat/vB
+    pub package_fee_rate: f64,
+}
+
+impl CpfpResult {
+    /// Child transaction ID
+    pub fn txid(&self) -> Txid {
+        self.tx.compute_txid()
+    }
+}
+
+/// Build and sign a CPFP child without broadcasting it
+///
+/// Spends a wallet-owned output of the parent so that parent and child
+/// together pay `fee_rate`. Outputs that are not RGB-occupied are preferred;
+/// if only RGB-occupied outputs are available, the spent output is carried
+/// forward to output 0 of the child (same value, fresh wallet address) so the
+/// caller can move the seal there. Other RGB-occupied UTXOs are never used as
+/// extra inputs.
+///
+/// The parent's fee comes from the wallet when it funded the parent, and
+/// from Esplora otherwise (incoming transactions).
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet
+/// * `client` - Esplora client for the parent fee lookup
+/// * `parent_txid` - Unconfirmed transaction to accelerate
+/// * `fee_rate` - Target fee rate for the parent + child package
+/// * `rgb_occupied` - RGB-occupied outpoints (including seals bound to the parent)
+///
+/// # Example
+///
+/// ```ignore
+/// let (_, _, high) = get_recommended_fee_rates(&client)?;
+/// let child = build_cpfp(&mut wallet, &client, parent_txid, &high, &rgb_occupied)?;
+/// // Record the seal move before the child can reach the network
+/// client.inner().broadcast(&child.tx)?;
+/// ```
+pub fn build_cpfp(
+    wallet: &mut BitcoinWallet,
+    client: &EsploraClient,
+    parent_txid: Txid,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<CpfpResult, UtxoError> {
+    // Find the parent transaction
+    let parent = wallet
+        .inner()
+        .get_tx(parent_txid)
+        .ok_or_else(|| UtxoError::TransactionNotFound(parent_txid.to_string()))?;
+
+    if parent.chain_position.is_confirmed() {
+        return Err(UtxoError::BuildFailed(format!(
+            "Transaction {} is already confirmed",
+            parent_txid
+        )));
+    }
+
+    let parent_tx = parent.tx_node.tx.clone();
+    let parent_vsize = parent_tx.vsize() as f64;
+
+    // Wallet-funded parents have known inputs; otherwise ask Esplora
+    let parent_fee = match wallet.inner().calculate_fee(&parent_tx) {
+        Ok(fee) => fee.to_sat(),
+        Err(_) => {
+            client
+                .inner()
+                .get_tx_info(&parent_txid)
+                .map_err(|e| NetworkError::Request(format!("Failed to get transaction: {}", e)))?
+                .ok_or_else(|| UtxoError::TransactionNotFound(parent_txid.to_string()))?
+                .fee
+        }
+    };
+
+    let parent_target_fee = (fee_rate.sat_per_vb * parent_vsize).ceil() as u64;
+    if parent_fee >= parent_target_fee {
+        return Err(UtxoError::InvalidFeeRate(format!(
+            "Transaction {} already pays {:.2} sat/vB (target {:.2} sat/vB)",
+            parent_txid,
+            parent_fee as f64 / parent_vsize,
+            fee_rate.sat_per_vb
+        )));
+    }
+
+    // Pick the parent output to spend, preferring plain BTC outputs
+    let parent_outputs: Vec<_> = wallet
+        .inner()
+        .list_unspent()
+        .filter(|utxo| utxo.outpoint.txid == parent_txid)
+        .collect();
+
+    let spend = parent_outputs
+        .iter()
+        .filter(|utxo| !rgb_occupied.contains(&utxo.outpoint))
+        .max_by_key(|utxo| utxo.txout.value)
+        .or_else(|| parent_outputs.iter().max_by_key(|utxo| utxo.txout.value))
+        .ok_or_else(|| {
+            UtxoError::UtxoNotFound(format!(
+                "No unspent wallet output in transaction {}",
+                parent_txid
+            ))
+        })?;
+    let spent = spend.outpoint;
+
+    // RGB-occupied outputs are carried forward unchanged
+    let carry = if rgb_occupied.contains(&spent) {
+        let address_info = wallet
+            .inner_mut()
+            .reveal_next_address(KeychainKind::External);
+        Some((address_info.address.script_pubkey(), spend.txout.value))
+    } else {
+        None
+    };
+
+    let drain_script = wallet
+        .inner_mut()
+        .reveal_next_address(KeychainKind::Internal)
+        .address
+        .script_pubkey();
+
+    // First pass at the target rate to size the child
+    let sizing_psbt = build_cpfp_child(
+        wallet,
+        spent,
+        carry.as_ref(),
+        &drain_script,
+        rgb_occupied,
+        fee_rate,
+        None,
+    )?;
+    let sizing_fee = sizing_psbt
+        .fee()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to calculate fee: {}", e)))?
+        .to_sat();
+    wallet.inner_mut().cancel_tx(&sizing_psbt.unsigned_tx);
+
+    // Child pays its own share plus the parent's shortfall
+    let child_fee = sizing_fee + (parent_target_fee - parent_fee);
+
+    let mut psbt = build_cpfp_child(
+        wallet,
+        spent,
+        carry.as_ref(),
+        &drain_script,
+        rgb_occupied,
+        fee_rate,
+        Some(child_fee),
+    )?;
+
+    // Sign the PSBT
+    #[allow(deprecated)]
+    wallet
+        .inner_mut()
+        .sign(&mut psbt, SignOptions::default())
+        .map_err(|e| UtxoError::SignFailed(format!("Failed to sign transaction: {}", e)))?;
+
+    // Calculate fee before extracting (extract_tx consumes the PSBT)
+    let fee = psbt
+        .fee()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to calculate fee: {}", e)))?
+        .to_sat();
+
+    let tx = psbt
+        .extract_tx()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to extract transaction: {}", e)))?;
+
+    let carried_to = carry.map(|_| OutPoint {
+        txid: tx.compute_txid(),
+        vout: 0,
+    });
+    let package_fee_rate = (parent_fee + fee) as f64 / (parent_vsize + tx.vsize() as f64);
+
+    Ok(CpfpResult {
+        parent_txid,
+        tx,
+        spent,
+        carried_to,
+        fee,
+        parent_fee,
+        package_fee_rate,
+    })
+}
+
+/// Accelerate an unconfirmed transaction with a child (CPFP)
+///
+/// Builds and signs the child with [`build_cpfp`], then broadcasts it.
+///
+/// # Arguments
+///
+/// * `wallet` - The Bitcoin wallet
+/// * `client` - Esplora client for fee lookup and broadcasting
+/// * `parent_txid` - Unconfirmed transaction to accelerate
+/// * `fee_rate` - Target fee rate for the parent + child package
+/// * `rgb_occupied` - RGB-occupied outpoints (including seals bound to the parent)
+///
+/// # Example
+///
+/// ```ignore
+/// let (_, _, high) = get_recommended_fee_rates(&client)?;
+/// let result = cpfp(&mut wallet, &client, parent_txid, &high, &rgb_occupied)?;
+/// println!("Child {} pays {} sats", result.txid(), result.fee);
+/// ```
+pub fn cpfp(
+    wallet: &mut BitcoinWallet,
+    client: &EsploraClient,
+    parent_txid: Txid,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<CpfpResult, UtxoError> {
+    let child = build_cpfp(wallet, client, parent_txid, fee_rate, rgb_occupied)?;
+
+    // Broadcast the child
+    client
+        .inner()
+        .broadcast(&child.tx)
+        .map_err(|e| UtxoError::BroadcastFailed(format!("Failed to broadcast: {}", e)))?;
+
+    // Persist wallet changes
+    wallet.persist()?;
+
+    Ok(child)
+}
+
+/// Build an unsigned CPFP child spending `spent`
+///
+/// Pays `fee_absolute` when given, otherwise `fee_rate`. A carried output
+/// always lands at index 0.
+fn build_cpfp_child(
+    wallet: &mut BitcoinWallet,
+    spent: OutPoint,
+    carry: Option<&(ScriptBuf, Amount)>,
+    drain_script: &ScriptBuf,
+    rgb_occupied: &HashSet<OutPoint>,
+    fee_rate: &FeeRateConfig,
+    fee_absolute: Option<u64>,
+) -> Result<Psbt, UtxoError> {
+    let mut tx_builder = wallet.inner_mut().build_tx();
+    tx_builder
+        .add_utxo(spent)
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to add UTXO: {}", e)))?;
+    tx_builder.ordering(bdk_wallet::tx_builder::TxOrdering::Untouched);
+
+    // Exclude other RGB-occupied UTXOs if extra inputs are needed
+    for occupied_outpoint in rgb_occupied.iter().filter(|outpoint| **outpoint != spent) {
+        tx_builder.add_unspendable(*occupied_outpoint);
+    }
+
+    if let Some((script, amount)) = carry {
+        tx_builder.add_recipient(script.clone(), *amount);
+    }
+    tx_builder.drain_to(drain_script.clone());
+
+    match fee_absolute {
+        Some(fee) => {
+            tx_builder.fee_absolute(Amount::from_sat(fee));
+        }
+        None => {
+            tx_builder.fee_rate(fee_rate.to_bdk_fee_rate());
+        }
+    }
+
+    tx_builder
+        .finish()
+        .map_err(|e| UtxoError::BuildFailed(format!("Failed to build transaction: {}", e)))
+}
+
 /// Get recommended fee rates from network
 ///
 /// Queries the Esplora API for current fee rate estimates.
@@ -1,7 +1,9 @@
 //! Bitcoin wallet wrapper using BDK
 
 use crate::config::NetworkType;
+use bdk_wallet::bitcoin::secp256k1::Secp256k1;
 use bdk_wallet::bitcoin::Network as BdkNetwork;
+use bdk_wallet::descriptor::IntoWalletDescriptor;
 use bdk_wallet::rusqlite::Connection;
 use bdk_wallet::{KeychainKind, PersistedWallet, Wallet};
 use std::path::PathBuf;
@@ -33,6 +35,12 @@ pub enum BitcoinWalletError {
     #[error("Wallet not initialized")]
     NotInitialized,
 
+    #[error("Wallet is locked (watch-only): decrypt the wallet keys to sign")]
+    Locked,
+
+    #[error("Signing descriptor does not match the wallet's public descriptor")]
+    DescriptorMismatch,
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -53,6 +61,9 @@ pub struct BitcoinWallet {
 
     /// Path to wallet database
     db_path: PathBuf,
+
+    /// Whether private keys are loaded (false for watch-only wallets)
+    can_sign: bool,
 }
 
 impl BitcoinWallet {
@@ -61,9 +72,13 @@ impl BitcoinWallet {
     /// If a wallet already exists at the specified path, it will be loaded.
     /// Otherwise, a new wallet will be created and persisted to SQLite.
     ///
+    /// A public (xpub) descriptor opens the wallet watch-only: balances,
+    /// addresses and PSBT construction work, signing needs `unlock()`.
+    /// A private (xprv) descriptor opens it ready to sign.
+    ///
     /// # Arguments
     ///
-    /// * `descriptor` - BIP86 taproot descriptor string (e.g., "tr(xprv.../0/*)")
+    /// * `descriptor` - BIP86 taproot descriptor string (e.g., "tr(xpub.../0/*)" or "tr(xprv.../0/*)")
     /// * `network` - Network type (Regtest, Signet, Testnet, Mainnet)
     /// * `wallet_dir` - Directory to store wallet database (bitcoin.db)
     ///
@@ -105,6 +120,13 @@ impl BitcoinWallet {
         // Internal descriptor format: tr(.../1/*) - change addresses
         let internal_descriptor = descriptor.replace("/0/*", "/1/*");
 
+        // A descriptor with secret keys yields a non-empty keymap
+        let (_, keymap) = descriptor
+            .clone()
+            .into_wallet_descriptor(&Secp256k1::new(), bdk_network)
+            .map_err(|e| BitcoinWalletError::InvalidDescriptor(e.to_string()))?;
+        let can_sign = !keymap.is_empty();
+
         // Try to load existing wallet first, fallback to creating new one
         let wallet = match Wallet::load()
             .descriptor(KeychainKind::External, Some(descriptor.clone()))
@@ -130,9 +152,63 @@ impl BitcoinWallet {
             conn,
             network,
             db_path,
+            can_sign,
         })
     }
 
+    /// Enable signing on a wallet opened with its public descriptor
+    ///
+    /// Loads the private keys of `private_descriptor` into both keychains.
+    /// The descriptor must be the private form of the one the wallet was
+    /// opened with. Keys are held in memory only; nothing is persisted.
+    ///
+    /// # Arguments
+    ///
+    /// * `private_descriptor` - Decrypted "tr(xprv.../0/*)" descriptor
+    ///
+    /// # Errors
+    ///
+    /// Returns `DescriptorMismatch` if the keys belong to another wallet.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut wallet = BitcoinWallet::new(keys.bitcoin_public_descriptor.clone(), network, &dir)?;
+    /// assert!(!wallet.can_sign());
+    /// wallet.unlock(&keys.bitcoin_descriptor)?;
+    /// assert!(wallet.can_sign());
+    /// ```
+    pub fn unlock(&mut self, private_descriptor: &str) -> Result<(), BitcoinWalletError> {
+        let secp = Secp256k1::new();
+        let bdk_network = network_type_to_bdk(self.network);
+
+        for (keychain, descriptor) in [
+            (KeychainKind::External, private_descriptor.to_string()),
+            (
+                KeychainKind::Internal,
+                private_descriptor.replace("/0/*", "/1/*"),
+            ),
+        ] {
+            let (public, keymap) = descriptor
+                .into_wallet_descriptor(&secp, bdk_network)
+                .map_err(|e| BitcoinWalletError::InvalidDescriptor(e.to_string()))?;
+
+            if &public != self.wallet.public_descriptor(keychain) {
+                return Err(BitcoinWalletError::DescriptorMismatch);
+            }
+
+            self.wallet.set_keymap(keychain, keymap);
+        }
+
+        self.can_sign = true;
+        Ok(())
+    }
+
+    /// Whether private keys are loaded and the wallet can sign
+    pub fn can_sign(&self) -> bool {
+        self.can_sign
+    }
+
     /// Get the underlying BDK wallet reference
     pub fn inner(&self) -> &Wallet {
         &self.wallet
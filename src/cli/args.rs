@@ -1,6 +1,6 @@
 //! CLI argument definitions using clap
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -38,6 +38,14 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub data_dir: Option<String>,
 
+    /// Daemon socket path (default: FIREFLY_DAEMON_SOCKET or ~/.f1r3fly-rgb-wallet/daemon.sock)
+    #[arg(long, global = true)]
+    pub socket: Option<String>,
+
+    /// Run locally even if a daemon holds the wallet unlocked
+    #[arg(long, global = true)]
+    pub no_daemon: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -56,18 +64,39 @@ pub enum Commands {
         action: WalletAction,
     },
 
+    /// Keep a wallet unlocked in a background daemon (Unix only)
+    ///
+    /// While the daemon holds the --wallet given to a command, the CLI
+    /// forwards the command to it and needs no password.
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
+    },
+
+    /// Serve the JSON-RPC API for --wallet over a Unix socket (Unix only)
+    ///
+    /// Methods: sync_wallet, get_rgb_balance, generate_invoice_with_pubkey,
+    /// send_transfer, preview_transfer, accept_consignment, list_claims,
+    /// list_invoices, list_utxos.
+    Rpc {
+        /// Socket path (default: FIREFLY_RPC_SOCKET or ~/.f1r3fly-rgb-wallet/rpc.sock)
+        #[arg(long)]
+        rpc_socket: Option<String>,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
     /// Sync wallet with blockchain
     Sync {
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Get Bitcoin balance
     GetBalance {
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Get wallet addresses
@@ -76,9 +105,8 @@ pub enum Commands {
         #[arg(short, long, default_value = "5")]
         count: usize,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Create a UTXO via self-send
@@ -91,9 +119,8 @@ pub enum Commands {
         #[arg(long)]
         fee_rate: Option<f32>,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Send Bitcoin to an address
@@ -110,12 +137,68 @@ pub enum Commands {
         #[arg(long)]
         fee_rate: Option<f32>,
 
-        /// Password to decrypt the wallet
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Bump the fee of an unconfirmed transaction (RBF)
+    ///
+    /// RGB witness transactions keep their commitment; the transfer's
+    /// consignments are re-issued for the replacement transaction.
+    BumpFee {
+        /// Transaction ID to replace
         #[arg(short, long)]
-        password: String,
+        txid: String,
+
+        /// New fee rate in sat/vB (must exceed the original)
+        #[arg(long)]
+        fee_rate: f32,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Accelerate an unconfirmed transaction with a child (CPFP)
+    ///
+    /// Spends a wallet-owned output of the transaction (e.g. change or an
+    /// incoming RGB seal, which is moved to the child) at a package fee rate.
+    Cpfp {
+        /// Unconfirmed transaction ID to accelerate
+        #[arg(short, long)]
+        txid: String,
+
+        /// Target package fee rate in sat/vB (default: high-priority estimate)
+        #[arg(long)]
+        fee_rate: Option<f32>,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Sign a PSBT with the wallet's keys (works offline)
+    SignPsbt {
+        /// PSBT file to sign
+        #[arg(long)]
+        psbt: String,
+
+        /// Output file for the signed PSBT (default: overwrite the input)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Broadcast a signed PSBT, completing its RGB transfer if it was prepared with --psbt-out
+    FinalizeAndBroadcast {
+        /// Signed PSBT file
+        #[arg(long)]
+        psbt: String,
+
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
-    /// Issue a new RGB asset
     IssueAsset {
         /// Asset ticker symbol (e.g., "USD")
         #[arg(short, long)]
@@ -137,16 +220,14 @@ pub enum Commands {
         #[arg(short, long)]
         genesis_utxo: String,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// List all RGB assets in the wallet
     ListAssets {
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Get RGB balance for all assets or a specific asset
@@ -155,9 +236,8 @@ pub enum Commands {
         #[arg(short, long)]
         contract_id: Option<String>,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Get detailed information about a specific RGB asset
@@ -166,9 +246,8 @@ pub enum Commands {
         #[arg(short, long)]
         contract_id: String,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Generate RGB invoice for receiving assets
@@ -185,9 +264,17 @@ pub enum Commands {
         #[arg(long)]
         address: Option<String>,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        /// Invoice expires this many seconds from now (default: never)
+        #[arg(long)]
+        expires_in: Option<u64>,
+
+        /// Pay to a blinded seal over an existing wallet UTXO instead of a
+        /// revealed address
+        #[arg(long, conflicts_with = "address")]
+        blinded: bool,
+
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Parse and display RGB invoice details
@@ -201,6 +288,24 @@ pub enum Commands {
         network: Option<String>,
     },
 
+    /// List invoices issued by this wallet with their payment status
+    ListInvoices {
+        /// Optional: filter by contract ID
+        #[arg(short, long)]
+        contract_id: Option<String>,
+
+        /// Optional: filter by status (open, partially-paid, paid, expired)
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Output format: table, json
+        #[arg(long, short, default_value = "table")]
+        format: String,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
     /// List all UTXOs with status and RGB occupation info
     ListUtxos {
         /// Only show available (non-RGB) UTXOs
@@ -223,9 +328,18 @@ pub enum Commands {
         #[arg(long, short, default_value = "table")]
         format: String,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// List wallet transactions with RGB annotations
+    ListTransactions {
+        /// Output format: table, json, compact
+        #[arg(long, short, default_value = "table")]
+        format: String,
+
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Send RGB asset transfer using an invoice
@@ -234,17 +348,68 @@ pub enum Commands {
         #[arg(short, long)]
         invoice: String,
 
-        /// Recipient's F1r3fly public key (hex)
+        /// Recipient's F1r3fly public key (hex); overrides the key embedded
+        /// in the invoice, required for plain RGB invoices
         #[arg(long)]
-        recipient_pubkey: String,
+        recipient_pubkey: Option<String>,
 
         /// Fee rate in sat/vB (optional)
         #[arg(long)]
         fee_rate: Option<f32>,
 
-        /// Password to decrypt the wallet
+        /// Anchor method: tapret or opreturn (default: wallet/config setting)
+        #[arg(long)]
+        anchor: Option<String>,
+
+        /// Write the unsigned witness PSBT to this file instead of signing
+        /// (sign with sign-psbt, then run finalize-and-broadcast)
+        #[arg(long)]
+        psbt_out: Option<String>,
+
+        /// Preview the transfer (amounts, input seals, fee) without executing it
+        #[arg(long, conflicts_with = "psbt_out")]
+        dry_run: bool,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Send RGB transfers to several invoices in one Bitcoin transaction
+    SendTransferBatch {
+        /// JSON file with recipients: [{"invoice": "...", "recipient_pubkey": "..."}, ...]
+        /// (recipient_pubkey is optional for invoices embedding it)
         #[arg(short, long)]
-        password: String,
+        recipients_file: String,
+
+        /// Fee rate in sat/vB (optional)
+        #[arg(long)]
+        fee_rate: Option<f32>,
+
+        /// Anchor method: tapret or opreturn (default: wallet/config setting)
+        #[arg(long)]
+        anchor: Option<String>,
+
+        /// Write the unsigned witness PSBT to this file instead of signing
+        /// (sign with sign-psbt, then run finalize-and-broadcast)
+        #[arg(long)]
+        psbt_out: Option<String>,
+
+        /// Preview the transfer (amounts, input seals, fee) without executing it
+        #[arg(long, conflicts_with = "psbt_out")]
+        dry_run: bool,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Resume or report RGB transfers interrupted by a crash
+    ///
+    /// Transfers are journaled from their first F1r3node call until their
+    /// consignments are written; each is resumed from its last completed
+    /// phase. Safe to run repeatedly.
+    RecoverTransfers {
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Accept RGB consignment (transfer or genesis)
@@ -253,9 +418,8 @@ pub enum Commands {
         #[arg(short, long)]
         consignment_path: String,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// List RGB claim history
@@ -268,9 +432,30 @@ pub enum Commands {
         #[arg(long, short, default_value = "table")]
         format: String,
 
-        /// Password to decrypt the wallet
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// List RGB transfer history (sent and received)
+    ListTransfers {
+        /// Optional: filter by contract ID
         #[arg(short, long)]
-        password: String,
+        contract_id: Option<String>,
+
+        /// Optional: only transfers on or after this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Optional: only transfers on or before this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Output format: table, json
+        #[arg(long, short, default_value = "table")]
+        format: String,
+
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Export genesis consignment for contract sharing
@@ -283,9 +468,8 @@ pub enum Commands {
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Password to decrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 }
 
@@ -297,6 +481,69 @@ pub enum ConfigAction {
         #[arg(short, long)]
         network: Option<String>,
     },
+
+    /// Set the global default RGB anchor method for transfers
+    SetAnchor {
+        /// Anchor method: tapret or opreturn
+        method: String,
+    },
+
+    /// Move the F1r3node master key into the encrypted keystore
+    ImportMasterKey {
+        /// Keystore entry id referenced from the config
+        #[arg(long, default_value = "master")]
+        id: String,
+
+        /// Master key (hex); defaults to FIREFLY_PRIVATE_KEY
+        #[arg(long)]
+        key: Option<String>,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+
+        /// Key file protecting the keystore entry (instead of a password)
+        #[arg(long, conflicts_with_all = ["password", "password_file", "password_fd"])]
+        key_file: Option<String>,
+    },
+
+    /// Set the contract ownership key mode for newly created wallets
+    SetKeyMode {
+        /// Key mode: shared (config master key) or wallet (mnemonic-derived key)
+        mode: String,
+    },
+
+    /// Set the key derivation function protecting wallet secrets
+    ///
+    /// Existing wallets are re-encrypted the next time they are unlocked.
+    SetKdf {
+        /// KDF: pbkdf2 or argon2id (memory-hard)
+        kdf: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DaemonAction {
+    /// Run the daemon in the foreground until stopped
+    Start,
+
+    /// Unlock the wallet given with --wallet in the daemon
+    Unlock {
+        /// Seconds the wallet stays unlocked
+        #[arg(long, default_value_t = crate::daemon::DEFAULT_UNLOCK_TIMEOUT_SECS)]
+        timeout: u64,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Drop the unlocked wallet from the daemon
+    Lock,
+
+    /// Show whether a wallet is unlocked and for how long
+    Status,
+
+    /// Stop the daemon
+    Stop,
 }
 
 #[derive(Subcommand, Debug)]
@@ -306,9 +553,8 @@ pub enum WalletAction {
         /// Name of the wallet
         name: String,
 
-        /// Password to encrypt the wallet
-        #[arg(short, long)]
-        password: String,
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// Import an existing wallet from a mnemonic phrase
@@ -320,14 +566,130 @@ pub enum WalletAction {
         #[arg(short, long)]
         mnemonic: String,
 
-        /// Password to encrypt the wallet
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Restore a wallet from its mnemonic (rescanning the chain) or from a backup file
+    Restore {
+        /// Name of the wallet (default for --backup: the name stored in the backup)
+        #[arg(required_unless_present = "backup")]
+        name: Option<String>,
+
+        /// 12-word BIP39 mnemonic phrase
+        #[arg(short, long, required_unless_present = "backup")]
+        mnemonic: Option<String>,
+
+        /// Backup file created by `wallet backup` (instead of a mnemonic)
+        #[arg(long, conflicts_with = "mnemonic")]
+        backup: Option<String>,
+
+        /// Overwrite an existing wallet that is newer than the backup
+        #[arg(long, requires = "backup")]
+        force: bool,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+
+        /// Directory of consignment files to re-import
+        #[arg(long)]
+        consignments_dir: Option<String>,
+
+        /// Unused addresses in a row that end the blockchain scan
+        #[arg(long, default_value_t = crate::bitcoin::DEFAULT_STOP_GAP)]
+        stop_gap: usize,
+
+        /// First F1r3fly derivation index to search (default: the wallet's first index)
+        #[arg(long)]
+        derivation_start: Option<u32>,
+
+        /// Number of F1r3fly derivation indices to search for issued contracts
+        ///
+        /// The registry URI of each index is matched against contracts
+        /// re-imported from --consignments-dir, or looked up on F1r3node.
+        #[arg(long, default_value_t = crate::f1r3fly::DEFAULT_DERIVATION_SCAN_LIMIT)]
+        derivation_scan_limit: u32,
+    },
+
+    /// Change the password protecting a wallet's keys
+    ChangePassword {
+        /// Name of the wallet
+        name: String,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+
+        /// Current wallet password (deprecated: use --password-file or the prompt)
+        #[arg(long, hide = true, conflicts_with_all = ["password", "password_file", "password_fd"])]
+        old_password: Option<String>,
+
+        #[command(flatten)]
+        new_password: NewPasswordArgs,
+    },
+
+    /// Back up a wallet into a single password-encrypted file
+    Backup {
+        /// Name of the wallet
+        name: String,
+
+        /// Backup file to write (default: <name>.backup)
         #[arg(short, long)]
-        password: String,
+        output: Option<String>,
+
+        #[command(flatten)]
+        password: PasswordArgs,
     },
 
     /// List all wallets
     List,
 
-    /// Get F1r3fly public key for a wallet
+    /// Get F1r3fly public key for a wallet (pass --wallet for wallet key mode)
     GetF1r3flyPubkey,
+
+    /// Set a wallet's default RGB anchor method (overrides the global config)
+    SetAnchor {
+        /// Name of the wallet
+        name: String,
+
+        /// Anchor method: tapret, opreturn, or default (use the global config)
+        method: String,
+    },
+}
+
+/// Password sources for commands that unlock a wallet
+///
+/// Checked in order: `--password`, `--password-file`, `--password-fd`,
+/// `FIREFLY_WALLET_PASSWORD`, then an interactive prompt on the terminal.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PasswordArgs {
+    /// Wallet password (deprecated: visible in shell history and `ps` output)
+    #[arg(short, long)]
+    pub password: Option<String>,
+
+    /// Read the password from the first line of a file
+    #[arg(long, conflicts_with = "password")]
+    pub password_file: Option<String>,
+
+    /// Read the password from an open file descriptor (e.g. `--password-fd 3 3<secret`)
+    #[arg(long, conflicts_with_all = ["password", "password_file"])]
+    pub password_fd: Option<i32>,
+}
+
+/// Password sources for the new password of `wallet change-password`
+///
+/// Checked in order: `--new-password`, `--new-password-file`,
+/// `--new-password-fd`, `FIREFLY_WALLET_NEW_PASSWORD`, then a prompt.
+#[derive(Args, Debug, Clone, Default)]
+pub struct NewPasswordArgs {
+    /// New wallet password (deprecated: visible in shell history and `ps` output)
+    #[arg(long)]
+    pub new_password: Option<String>,
+
+    /// Read the new password from the first line of a file
+    #[arg(long, conflicts_with = "new_password")]
+    pub new_password_file: Option<String>,
+
+    /// Read the new password from an open file descriptor
+    #[arg(long, conflicts_with_all = ["new_password", "new_password_file"])]
+    pub new_password_fd: Option<i32>,
 }
@@ -2,7 +2,10 @@
 
 use crate::bitcoin::FeeRateConfig;
 use crate::config::{load_config, ConfigError, ConfigOverrides};
-use crate::manager::{ManagerError, WalletManager};
+use crate::daemon::session::open_wallet;
+use crate::manager::ManagerError;
+use crate::{errln, outln};
+use std::path::Path;
 
 #[derive(Debug, thiserror::Error)]
 pub enum BitcoinCommandError {
@@ -31,16 +34,15 @@ pub async fn sync(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Sync wallet
     let result = manager.sync_wallet().await?;
 
-    println!("✓ Wallet synced successfully");
-    println!("  Height: {}", result.height);
-    println!("  New transactions: {}", result.new_txs);
-    println!("  Updated transactions: {}", result.updated_txs);
+    outln!("✓ Wallet synced successfully");
+    outln!("  Height: {}", result.height);
+    outln!("  New transactions: {}", result.new_txs);
+    outln!("  Updated transactions: {}", result.updated_txs);
 
     Ok(())
 }
@@ -59,24 +61,23 @@ pub async fn get_balance(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Get balance
     let balance = manager.get_balance()?;
 
-    println!("Bitcoin Balance:");
-    println!(
+    outln!("Bitcoin Balance:");
+    outln!(
         "  Confirmed:   {} sats ({:.8} BTC)",
         balance.confirmed,
         balance.confirmed as f64 / 100_000_000.0
     );
-    println!(
+    outln!(
         "  Unconfirmed: {} sats ({:.8} BTC)",
         balance.unconfirmed,
         balance.unconfirmed as f64 / 100_000_000.0
     );
-    println!(
+    outln!(
         "  Total:       {} sats ({:.8} BTC)",
         balance.total,
         balance.total as f64 / 100_000_000.0
@@ -98,27 +99,27 @@ pub async fn get_balance(
         .count();
 
     // Display UTXO summary
-    println!();
-    println!("UTXO Summary:");
-    println!("  Total UTXOs:    {}", all_utxos.len());
-    println!("  Available:      {}", available);
+    outln!();
+    outln!("UTXO Summary:");
+    outln!("  Total UTXOs:    {}", all_utxos.len());
+    outln!("  Available:      {}", available);
     if rgb_occupied > 0 {
-        println!("  RGB-Occupied:   {} ⚠️  (protected)", rgb_occupied);
+        outln!("  RGB-Occupied:   {} ⚠️  (protected)", rgb_occupied);
     } else {
-        println!("  RGB-Occupied:   {}", rgb_occupied);
+        outln!("  RGB-Occupied:   {}", rgb_occupied);
     }
     if unconfirmed > 0 {
-        println!("  Unconfirmed:    {}", unconfirmed);
+        outln!("  Unconfirmed:    {}", unconfirmed);
     }
 
     // Helpful hints
     if rgb_occupied > 0 || all_utxos.len() > 0 {
-        println!();
+        outln!();
         if rgb_occupied > 0 {
-            println!("💡 Use 'rgb-balance' to view RGB token holdings");
+            outln!("💡 Use 'rgb-balance' to view RGB token holdings");
         }
         if all_utxos.len() > 0 {
-            println!("💡 Use 'list-utxos' for detailed UTXO information");
+            outln!("💡 Use 'list-utxos' for detailed UTXO information");
         }
     }
 
@@ -138,18 +139,17 @@ pub fn get_addresses(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Get addresses
     let addresses = manager.get_addresses(Some(count as u32))?;
 
-    println!("Wallet Addresses:");
-    println!();
+    outln!("Wallet Addresses:");
+    outln!();
 
     for (i, addr_info) in addresses.iter().enumerate() {
         let used = if addr_info.is_used { "used" } else { "unused" };
-        println!(
+        outln!(
             "  {}: {} ({}, index: {})",
             i + 1,
             addr_info.address,
@@ -161,8 +161,8 @@ pub fn get_addresses(
     let display_count = addresses.len();
 
     if addresses.len() > display_count {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "  ... and {} more addresses",
             addresses.len() - display_count
         );
@@ -185,17 +185,16 @@ pub fn create_utxo(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Display RGB warning if applicable
     let rgb_count = manager.rgb_occupied().len();
     if rgb_count > 0 {
-        println!(
+        outln!(
             "⚠️  Notice: {} RGB-occupied UTXO(s) in wallet (will be protected from spending)",
             rgb_count
         );
-        println!();
+        outln!();
     }
 
     // Convert BTC to sats
@@ -207,19 +206,20 @@ pub fn create_utxo(
         .unwrap_or(FeeRateConfig::medium_priority());
     let result = manager.create_utxo(amount_sats, &fee_config, false)?;
 
-    println!("✓ UTXO created successfully");
-    println!("  Transaction ID: {}", result.txid);
-    println!(
+    outln!("✓ UTXO created successfully");
+    outln!("  Transaction ID: {}", result.txid);
+    outln!(
         "  Output: {}:{}",
-        result.outpoint.txid, result.outpoint.vout
+        result.outpoint.txid,
+        result.outpoint.vout
     );
-    println!(
+    outln!(
         "  Amount: {} sats ({:.8} BTC)",
         result.amount,
         result.amount as f64 / 100_000_000.0
     );
-    println!("  Fee: {} sats", result.fee);
-    println!("  Fee rate: {:.2} sat/vB", result.fee_rate);
+    outln!("  Fee: {} sats", result.fee);
+    outln!("  Fee rate: {:.2} sat/vB", result.fee_rate);
 
     Ok(())
 }
@@ -239,18 +239,17 @@ pub fn send_bitcoin(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Display RGB warning if applicable
     let rgb_count = manager.rgb_occupied().len();
     if rgb_count > 0 {
-        println!(
+        outln!(
             "⚠️  SAFETY: {} RGB-occupied UTXO(s) detected - these will NOT be spent",
             rgb_count
         );
-        println!("   RGB assets are safe. Only regular Bitcoin UTXOs will be used.");
-        println!();
+        outln!("   RGB assets are safe. Only regular Bitcoin UTXOs will be used.");
+        outln!();
     }
 
     // Send Bitcoin
@@ -259,10 +258,10 @@ pub fn send_bitcoin(
         .unwrap_or(FeeRateConfig::medium_priority());
     let txid = manager.send_bitcoin(&to_address, amount_sats, &fee_config)?;
 
-    println!("✓ Bitcoin sent successfully");
-    println!("  Transaction ID: {}", txid);
-    println!("  Recipient: {}", to_address);
-    println!(
+    outln!("✓ Bitcoin sent successfully");
+    outln!("  Transaction ID: {}", txid);
+    outln!("  Recipient: {}", to_address);
+    outln!(
         "  Amount: {} sats ({:.8} BTC)",
         amount_sats,
         amount_sats as f64 / 100_000_000.0
@@ -271,6 +270,145 @@ pub fn send_bitcoin(
     Ok(())
 }
 
+/// Bump the fee of an unconfirmed transaction (RBF)
+pub async fn bump_fee(
+    wallet_name: Option<String>,
+    txid: String,
+    fee_rate: f32,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), BitcoinCommandError> {
+    let wallet_name = wallet_name.ok_or(BitcoinCommandError::WalletNotSpecified)?;
+
+    let fee_config = FeeRateConfig::new(fee_rate as f64).map_err(ManagerError::Utxo)?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let response = manager.bump_fee(&txid, &fee_config).await?;
+
+    outln!("✓ Fee bumped successfully");
+    outln!("  Replaced:        {}", response.original_txid);
+    outln!("  Transaction ID:  {}", response.txid);
+    outln!(
+        "  Fee:             {} sats ({:.2} sat/vB)",
+        response.fee_sats,
+        response.fee_rate
+    );
+
+    if response.rgb_witness {
+        outln!();
+        outln!("📦 RGB consignment(s) re-issued for the new witness transaction:");
+        for path in &response.consignments {
+            outln!("  {}", path.display());
+        }
+        outln!();
+        outln!("📋 Next Steps:");
+        outln!("  Send the new consignment(s) to the recipient(s) to accept:");
+        outln!("     accept-consignment --consignment-path <file> --password <password>");
+    }
+
+    Ok(())
+}
+
+/// Accelerate an unconfirmed transaction with a child (CPFP)
+pub async fn cpfp(
+    wallet_name: Option<String>,
+    txid: String,
+    fee_rate: Option<f32>,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), BitcoinCommandError> {
+    let wallet_name = wallet_name.ok_or(BitcoinCommandError::WalletNotSpecified)?;
+
+    let fee_config = fee_rate
+        .map(|rate| FeeRateConfig::new(rate as f64))
+        .transpose()
+        .map_err(ManagerError::Utxo)?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let response = manager.cpfp(&txid, fee_config.as_ref()).await?;
+
+    outln!("✓ Child transaction broadcast");
+    outln!("  Parent:          {}", response.parent_txid);
+    outln!("  Spent output:    {}", response.spent_outpoint);
+    outln!("  Transaction ID:  {}", response.txid);
+    outln!("  Child fee:       {} sats", response.fee_sats);
+    outln!("  Package rate:    {:.2} sat/vB", response.package_fee_rate);
+
+    if let Some(seal) = &response.rebound_seal {
+        outln!();
+        outln!("🔁 RGB seal moved to {}", seal);
+    }
+
+    Ok(())
+}
+
+/// Sign a PSBT with the wallet's keys
+///
+/// Needs no network access, so it can run on an air-gapped machine.
+pub fn sign_psbt(
+    wallet_name: Option<String>,
+    psbt: String,
+    output: Option<String>,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), BitcoinCommandError> {
+    let wallet_name = wallet_name.ok_or(BitcoinCommandError::WalletNotSpecified)?;
+    let output = output.unwrap_or_else(|| psbt.clone());
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let summary = manager.sign_psbt(Path::new(&psbt), Path::new(&output))?;
+
+    outln!("Transaction {}", summary.txid);
+    outln!();
+    outln!("Outputs:");
+    for (vout, output) in summary.outputs.iter().enumerate() {
+        outln!(
+            "  {}: {} {} sats{}",
+            vout,
+            output.address.as_deref().unwrap_or("<non-standard script>"),
+            output.amount_sats,
+            if output.is_mine { " (this wallet)" } else { "" }
+        );
+    }
+    match summary.fee_sats {
+        Some(fee) => outln!("Fee: {} sats", fee),
+        None => outln!("Fee: unknown (input amounts missing)"),
+    }
+    outln!();
+
+    if summary.is_finalized() {
+        outln!("✓ PSBT fully signed: {}", output);
+        outln!();
+        outln!("📋 Next Steps:");
+        outln!("  Move the file back to the online wallet and run:");
+        outln!("     finalize-and-broadcast --psbt {}", output);
+    } else {
+        outln!(
+            "⚠️  PSBT partially signed ({} of {} inputs): {}",
+            summary.finalized_inputs,
+            summary.inputs,
+            output
+        );
+    }
+
+    Ok(())
+}
+
 /// List all UTXOs with filtering and formatting options
 pub async fn list_utxos(
     wallet_name: Option<String>,
@@ -304,8 +442,7 @@ pub async fn list_utxos(
 
     // Load config and wallet
     let config = load_config(None, overrides)?;
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Get UTXOs
     let utxos = manager.list_utxos(filter).await?;
@@ -384,33 +521,41 @@ fn print_summary(utxos: &[crate::types::UtxoInfo]) {
         .map(|u| u.amount_btc)
         .sum();
 
-    println!("Total UTXOs: {}", total);
-    println!("Available: {} ({:.8} BTC)", available, total_available_btc);
-    println!("RGB-Occupied: {} ({:.8} BTC)", rgb_occupied, total_rgb_btc);
+    outln!("Total UTXOs: {}", total);
+    outln!("Available: {} ({:.8} BTC)", available, total_available_btc);
+    outln!("RGB-Occupied: {} ({:.8} BTC)", rgb_occupied, total_rgb_btc);
     if unconfirmed > 0 {
-        println!("Unconfirmed: {}", unconfirmed);
+        outln!("Unconfirmed: {}", unconfirmed);
     }
 }
 
 /// Print UTXOs in table format
 fn print_utxos_table(wallet_name: &str, utxos: &[crate::types::UtxoInfo]) {
-    println!("UTXO List for wallet: {}", wallet_name);
-    println!("=========================================");
-    println!();
+    outln!("UTXO List for wallet: {}", wallet_name);
+    outln!("=========================================");
+    outln!();
 
     if utxos.is_empty() {
-        println!("No UTXOs found.");
+        outln!("No UTXOs found.");
         return;
     }
 
     // Header
-    println!(
+    outln!(
         "{:<22} | {:<12} | {:<13} | {:<13} | {}",
-        "Outpoint", "Amount (BTC)", "Confirmations", "Status", "RGB Assets"
+        "Outpoint",
+        "Amount (BTC)",
+        "Confirmations",
+        "Status",
+        "RGB Assets"
     );
-    println!(
+    outln!(
         "{:-<22}-+-{:-<12}-+-{:-<13}-+-{:-<13}-+-{:-<20}",
-        "", "", "", "", ""
+        "",
+        "",
+        "",
+        "",
+        ""
     );
 
     // Rows
@@ -418,13 +563,17 @@ fn print_utxos_table(wallet_name: &str, utxos: &[crate::types::UtxoInfo]) {
         let outpoint_short = format_outpoint(&utxo.outpoint);
         let rgb_display = format_rgb_assets(&utxo.rgb_assets);
 
-        println!(
+        outln!(
             "{:<22} | {:>12.8} | {:>13} | {:<13} | {}",
-            outpoint_short, utxo.amount_btc, utxo.confirmations, utxo.status, rgb_display
+            outpoint_short,
+            utxo.amount_btc,
+            utxo.confirmations,
+            utxo.status,
+            rgb_display
         );
     }
 
-    println!();
+    outln!();
     print_summary(utxos);
 }
 
@@ -465,8 +614,8 @@ fn print_utxos_json(wallet_name: &str, utxos: &[crate::types::UtxoInfo]) {
     });
 
     match serde_json::to_string_pretty(&output) {
-        Ok(json_str) => println!("{}", json_str),
-        Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        Ok(json_str) => outln!("{}", json_str),
+        Err(e) => errln!("Error serializing to JSON: {}", e),
     }
 }
 
@@ -499,9 +648,180 @@ fn print_utxos_compact(utxos: &[crate::types::UtxoInfo]) {
             format!(" {}", assets_str)
         };
 
-        println!(
+        outln!(
             "{} {:.8} {}{}",
-            utxo.outpoint, utxo.amount_btc, status_str, rgb_info
+            utxo.outpoint,
+            utxo.amount_btc,
+            status_str,
+            rgb_info
+        );
+    }
+}
+
+/// List wallet transactions with RGB annotations
+pub fn list_transactions(
+    wallet_name: Option<String>,
+    password: String,
+    format_str: String,
+    overrides: ConfigOverrides,
+) -> Result<(), BitcoinCommandError> {
+    use crate::types::OutputFormat;
+
+    let wallet_name = wallet_name.ok_or(BitcoinCommandError::WalletNotSpecified)?;
+
+    // Parse output format
+    let format = format_str
+        .parse::<OutputFormat>()
+        .map_err(|e| BitcoinCommandError::InvalidFormat(e))?;
+
+    // Load config and wallet
+    let config = load_config(None, overrides)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let transactions = manager.list_transactions()?;
+
+    match format {
+        OutputFormat::Table => print_transactions_table(&wallet_name, &transactions),
+        OutputFormat::Json => print_transactions_json(&wallet_name, &transactions),
+        OutputFormat::Compact => print_transactions_compact(&transactions),
+    }
+
+    Ok(())
+}
+
+/// Format RGB annotations for display
+fn format_rgb_annotations(annotations: &[crate::types::RgbTxAnnotation]) -> String {
+    if annotations.is_empty() {
+        return "-".to_string();
+    }
+
+    annotations
+        .iter()
+        .map(|a| match &a.contract_id {
+            Some(contract_id) if contract_id.len() > 12 => {
+                format!("{} ({}...)", a.kind, &contract_id[..12])
+            }
+            Some(contract_id) => format!("{} ({})", a.kind, contract_id),
+            None => a.kind.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Print transactions in table format
+fn print_transactions_table(wallet_name: &str, transactions: &[crate::types::TransactionInfo]) {
+    outln!("Transactions for wallet: {}", wallet_name);
+    outln!("=========================================");
+    outln!();
+
+    if transactions.is_empty() {
+        outln!("No transactions found.");
+        return;
+    }
+
+    // Header
+    outln!(
+        "{:<16} | {:>8} | {:<8} | {:>14} | {:>8} | {}",
+        "Txid",
+        "Height",
+        "Type",
+        "Net (BTC)",
+        "Fee",
+        "RGB"
+    );
+    outln!(
+        "{:-<16}-+-{:-<8}-+-{:-<8}-+-{:-<14}-+-{:-<8}-+-{:-<20}",
+        "",
+        "",
+        "",
+        "",
+        "",
+        ""
+    );
+
+    // Rows
+    for tx in transactions {
+        let txid_short = format!("{}...{}", &tx.txid[..6], &tx.txid[tx.txid.len() - 6..]);
+        let height = tx
+            .confirmation_height
+            .map(|h| h.to_string())
+            .unwrap_or_else(|| "pending".to_string());
+        let fee = tx
+            .fee_sats
+            .map(|f| f.to_string())
+            .unwrap_or_else(|| "?".to_string());
+
+        outln!(
+            "{:<16} | {:>8} | {:<8} | {:>+14.8} | {:>8} | {}",
+            txid_short,
+            height,
+            tx.direction,
+            tx.net_sats as f64 / 100_000_000.0,
+            fee,
+            format_rgb_annotations(&tx.rgb)
+        );
+    }
+
+    outln!();
+    let rgb_count = transactions.iter().filter(|tx| !tx.rgb.is_empty()).count();
+    let unconfirmed = transactions
+        .iter()
+        .filter(|tx| tx.confirmation_height.is_none())
+        .count();
+    outln!("Total transactions: {}", transactions.len());
+    outln!("RGB-related: {}", rgb_count);
+    if unconfirmed > 0 {
+        outln!("Unconfirmed: {}", unconfirmed);
+    }
+}
+
+/// Print transactions in JSON format
+fn print_transactions_json(wallet_name: &str, transactions: &[crate::types::TransactionInfo]) {
+    use serde_json::json;
+
+    let output = json!({
+        "wallet": wallet_name,
+        "total_transactions": transactions.len(),
+        "transactions": transactions
+    });
+
+    match serde_json::to_string_pretty(&output) {
+        Ok(json_str) => outln!("{}", json_str),
+        Err(e) => errln!("Error serializing to JSON: {}", e),
+    }
+}
+
+/// Print transactions in compact format (script-friendly)
+fn print_transactions_compact(transactions: &[crate::types::TransactionInfo]) {
+    for tx in transactions {
+        let height = tx
+            .confirmation_height
+            .map(|h| h.to_string())
+            .unwrap_or_else(|| "unconfirmed".to_string());
+
+        let rgb_info = if tx.rgb.is_empty() {
+            String::new()
+        } else {
+            let kinds = tx
+                .rgb
+                .iter()
+                .map(|a| match a.kind {
+                    crate::types::RgbTxKind::Witness => "witness",
+                    crate::types::RgbTxKind::Genesis => "genesis",
+                    crate::types::RgbTxKind::CreateUtxo => "create-utxo",
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(" {}", kinds)
+        };
+
+        outln!(
+            "{} {} {} {}{}",
+            tx.txid,
+            height,
+            tx.net_sats,
+            tx.fee_sats.unwrap_or(0),
+            rgb_info
         );
     }
 }
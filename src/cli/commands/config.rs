@@ -1,6 +1,12 @@
 //! Config command implementations
 
-use crate::config::{ConfigError, GlobalConfig, NetworkType};
+use crate::config::{ConfigError, GlobalConfig, KeyMode, NetworkType};
+use crate::f1r3fly::AnchorMethod;
+use crate::outln;
+use crate::storage::keys::Kdf;
+use crate::storage::keystore::{self, KeystoreUnlock};
+use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Initialize configuration file with network-specific defaults
 pub fn init(network: Option<String>) -> Result<(), ConfigError> {
@@ -27,8 +33,154 @@ pub fn init(network: Option<String>) -> Result<(), ConfigError> {
     crate::config::save_config(&config, None)?;
 
     let config_path = crate::config::default_config_path()?;
-    println!("✓ Configuration initialized for {:?}", network_type);
-    println!("  Config file: {}", config_path.display());
+    outln!("✓ Configuration initialized for {:?}", network_type);
+    outln!("  Config file: {}", config_path.display());
+    outln!();
+    outln!("💡 Store the F1r3node master key encrypted with `config import-master-key`.");
+
+    Ok(())
+}
+
+/// Set the global default RGB anchor method in the configuration file
+pub fn set_anchor(method: String) -> Result<(), ConfigError> {
+    let anchor_method = AnchorMethod::from_str(&method).map_err(ConfigError::Invalid)?;
+
+    let config_path = edit_stored_config(|config| config.anchor_method = Some(anchor_method))?;
+
+    outln!("✓ Default anchor method set to {}", anchor_method);
+    outln!("  Config file: {}", config_path.display());
+
+    Ok(())
+}
+
+/// Set the key mode recorded for wallets created from now on
+///
+/// Existing wallets keep the mode they were created with.
+pub fn set_key_mode(mode: String) -> Result<(), ConfigError> {
+    let key_mode = KeyMode::from_str(&mode).map_err(ConfigError::Invalid)?;
+
+    let config_path = edit_stored_config(|config| config.f1r3node.key_mode = key_mode)?;
+
+    outln!("✓ Key mode for new wallets set to {}", key_mode);
+    outln!("  Config file: {}", config_path.display());
+
+    Ok(())
+}
+
+/// Set the KDF protecting wallet secrets
+///
+/// New wallets are encrypted with it right away; existing wallets are
+/// re-encrypted the next time they are unlocked with their password.
+pub fn set_kdf(kdf: String) -> Result<(), ConfigError> {
+    let kdf = Kdf::from_str(&kdf).map_err(ConfigError::Invalid)?;
+
+    let config_path = edit_stored_config(|config| config.kdf = Some(kdf))?;
+
+    outln!("✓ Wallet KDF set to {}", kdf);
+    outln!("  Existing wallets are re-encrypted the next time they are unlocked.");
+    outln!("  Config file: {}", config_path.display());
+
+    Ok(())
+}
+
+/// Encrypt the F1r3node master key into the keystore and reference it from the config
+///
+/// The key is taken from `--key` or `FIREFLY_PRIVATE_KEY`, falling back to a
+/// plaintext `master_key` left in a legacy config file. The rewritten config
+/// holds only the keystore id (and key file path, if one was used).
+pub fn import_master_key(
+    id: String,
+    key: Option<String>,
+    password: Option<String>,
+    key_file: Option<String>,
+) -> Result<(), ConfigError> {
+    // Edit the file as stored (no env/CLI overrides baked in)
+    let config_path = crate::config::default_config_path()?;
+    if !config_path.exists() {
+        return Err(ConfigError::Invalid(format!(
+            "Config file not found: {}. Run `config init` first",
+            config_path.display()
+        )));
+    }
+
+    let contents = std::fs::read_to_string(&config_path)?;
+    let mut config: GlobalConfig = serde_json::from_str(&contents)?;
+
+    let key_hex = key
+        .or_else(|| std::env::var("FIREFLY_PRIVATE_KEY").ok())
+        .or_else(|| Some(config.f1r3node.master_key.clone()).filter(|k| !k.is_empty()))
+        .ok_or_else(|| {
+            ConfigError::Invalid(
+                "No master key given: pass --key or set FIREFLY_PRIVATE_KEY".to_string(),
+            )
+        })?;
+
+    let unlock = match (password, &key_file) {
+        (_, Some(path)) => KeystoreUnlock::KeyFile(PathBuf::from(path)),
+        (Some(password), None) => KeystoreUnlock::Password(password),
+        (None, None) => {
+            return Err(ConfigError::Invalid(
+                "Pass --password or --key-file to protect the master key".to_string(),
+            ))
+        }
+    };
+
+    let dir = keystore::keystore_dir(config.keystore_dir.as_deref())?;
+    let entry = keystore::import_key(&dir, &id, &key_hex, &unlock)?;
+
+    config.f1r3node.master_key = String::new();
+    config.f1r3node.master_key_id = Some(entry.id.clone());
+    config.f1r3node.master_key_file = key_file;
+    crate::config::save_config(&config, Some(&config_path))?;
+
+    outln!("✓ Master key stored in keystore as '{}'", entry.id);
+    outln!("  Public key: {}", entry.public_key);
+    outln!("  Config file: {}", config_path.display());
+    if config.f1r3node.master_key_file.is_none() {
+        outln!();
+        outln!(
+            "💡 Set {} to unlock the master key for F1r3node operations.",
+            keystore::KEYSTORE_PASSWORD_ENV
+        );
+    }
+
+    Ok(())
+}
+
+/// Load the config file as stored, apply `edit` and save it back
+///
+/// Env/CLI overrides are not baked in. Returns the config file path.
+fn edit_stored_config(edit: impl FnOnce(&mut GlobalConfig)) -> Result<PathBuf, ConfigError> {
+    let config_path = crate::config::default_config_path()?;
+    if !config_path.exists() {
+        return Err(ConfigError::Invalid(format!(
+            "Config file not found: {}. Run `config init` first",
+            config_path.display()
+        )));
+    }
+
+    let contents = std::fs::read_to_string(&config_path)?;
+    let mut config: GlobalConfig = serde_json::from_str(&contents)?;
+    refuse_plaintext_master_key(&config)?;
+    edit(&mut config);
+
+    crate::config::save_config(&config, Some(&config_path))?;
+
+    Ok(config_path)
+}
+
+/// Refuse to rewrite a legacy config that still holds a plaintext master key
+///
+/// The plaintext `master_key` is never serialized, so saving the config
+/// would silently drop it. `config import-master-key` moves it into the
+/// keystore first.
+fn refuse_plaintext_master_key(config: &GlobalConfig) -> Result<(), ConfigError> {
+    if !config.f1r3node.master_key.is_empty() && config.f1r3node.master_key_id.is_none() {
+        return Err(ConfigError::Invalid(
+            "Config file holds a plaintext master_key. Run `config import-master-key` first"
+                .to_string(),
+        ));
+    }
 
     Ok(())
 }
@@ -0,0 +1,124 @@
+//! Daemon command implementations
+
+use crate::config::{load_config, ConfigError, ConfigOverrides};
+use crate::daemon::client::DaemonClient;
+use crate::daemon::protocol::{DaemonRequest, DaemonResponse};
+use crate::daemon::server::{CommandRunner, DaemonServer};
+use crate::daemon::{socket_path, DaemonError};
+use crate::outln;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonCommandError {
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("{0}")]
+    Daemon(#[from] DaemonError),
+
+    #[error("Wallet not specified. Use --wallet <name>")]
+    WalletNotSpecified,
+}
+
+/// Run the daemon in the foreground until `daemon stop`
+pub fn start(
+    socket: Option<String>,
+    overrides: ConfigOverrides,
+    runner: CommandRunner,
+) -> Result<(), DaemonCommandError> {
+    let config = load_config(None, overrides)?;
+    let path = socket_path(socket.as_deref())?;
+
+    let server = DaemonServer::bind(&path, config, runner)?;
+
+    outln!("✓ Daemon listening on {}", path.display());
+    outln!("  Unlock a wallet with: daemon unlock --wallet <name>");
+    outln!("  Stop with: daemon stop");
+
+    server.run()?;
+
+    outln!("✓ Daemon stopped");
+    Ok(())
+}
+
+/// Unlock a wallet in the daemon
+pub fn unlock(
+    wallet_name: Option<String>,
+    timeout_secs: u64,
+    password: String,
+    socket: Option<String>,
+) -> Result<(), DaemonCommandError> {
+    let wallet = wallet_name.ok_or(DaemonCommandError::WalletNotSpecified)?;
+    let client = DaemonClient::new(socket_path(socket.as_deref())?);
+
+    match client.request(&DaemonRequest::Unlock {
+        wallet,
+        password,
+        timeout_secs,
+    })? {
+        DaemonResponse::Unlocked {
+            wallet,
+            expires_in_secs,
+        } => {
+            outln!("✓ Wallet '{}' unlocked in the daemon", wallet);
+            outln!("  Expires in: {}s", expires_in_secs);
+            Ok(())
+        }
+        other => Err(unexpected(other)),
+    }
+}
+
+/// Drop the unlocked wallet from the daemon
+pub fn lock(socket: Option<String>) -> Result<(), DaemonCommandError> {
+    let client = DaemonClient::new(socket_path(socket.as_deref())?);
+
+    match client.request(&DaemonRequest::Lock)? {
+        DaemonResponse::Locked => {
+            outln!("✓ Daemon locked");
+            Ok(())
+        }
+        other => Err(unexpected(other)),
+    }
+}
+
+/// Show the daemon's session state
+pub fn status(socket: Option<String>) -> Result<(), DaemonCommandError> {
+    let client = DaemonClient::new(socket_path(socket.as_deref())?);
+
+    match client.request(&DaemonRequest::Status)? {
+        DaemonResponse::Status {
+            wallet: Some(wallet),
+            expires_in_secs,
+        } => {
+            outln!("Daemon running at {}", client.socket_path().display());
+            outln!("  Unlocked wallet: {}", wallet);
+            outln!(
+                "  Expires in:      {}s",
+                expires_in_secs.unwrap_or_default()
+            );
+            Ok(())
+        }
+        DaemonResponse::Status { wallet: None, .. } => {
+            outln!("Daemon running at {}", client.socket_path().display());
+            outln!("  No wallet unlocked");
+            Ok(())
+        }
+        other => Err(unexpected(other)),
+    }
+}
+
+/// Stop the daemon
+pub fn stop(socket: Option<String>) -> Result<(), DaemonCommandError> {
+    let client = DaemonClient::new(socket_path(socket.as_deref())?);
+
+    match client.request(&DaemonRequest::Stop)? {
+        DaemonResponse::Stopping => {
+            outln!("✓ Daemon stopping");
+            Ok(())
+        }
+        other => Err(unexpected(other)),
+    }
+}
+
+fn unexpected(response: DaemonResponse) -> DaemonCommandError {
+    DaemonCommandError::Daemon(DaemonError::UnexpectedResponse(format!("{:?}", response)))
+}
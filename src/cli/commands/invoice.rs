@@ -3,8 +3,10 @@
 //! Handles RGB invoice generation and parsing via CLI.
 
 use crate::config::{load_config, ConfigOverrides, NetworkType};
+use crate::daemon::session::open_wallet;
 use crate::f1r3fly;
-use crate::manager::WalletManager;
+use crate::storage::{InvoiceRecord, InvoiceStatus};
+use crate::{out, outln};
 
 /// Error type for invoice command operations
 #[derive(Debug, thiserror::Error)]
@@ -24,54 +26,77 @@ pub enum InvoiceCommandError {
     /// Bitcoin wallet error
     #[error("Bitcoin wallet error: {0}")]
     BitcoinWallet(#[from] crate::bitcoin::BitcoinWalletError),
+
+    /// Invalid output format
+    #[error("Invalid output format: {0}")]
+    InvalidFormat(String),
+
+    /// Invalid status filter
+    #[error("Invalid invoice status '{0}': expected open, partially-paid, paid or expired")]
+    InvalidStatus(String),
 }
 
 /// Generate RGB invoice for receiving assets
 ///
-/// Creates an RGB invoice that can be shared with sender.
+/// Creates an RGB invoice that can be shared with sender. The invoice embeds
+/// this wallet's F1r3fly public key (and the expiry, if any), so the sender
+/// needs nothing else. With `blinded`, it pays a blinded seal over an
+/// existing wallet UTXO instead of a fresh address.
 pub async fn generate_invoice_cmd(
     wallet_name: Option<String>,
     contract_id: String,
     amount: u64,
     address: Option<String>,
+    expires_in: Option<u64>,
+    blinded: bool,
     password: String,
     overrides: ConfigOverrides,
 ) -> Result<(), InvoiceCommandError> {
     // Load config
     let config = load_config(None, overrides)?;
 
-    // Create manager
-    let mut manager = WalletManager::new(config)?;
-
     // Determine wallet name
     let wallet_name = wallet_name.unwrap_or_else(|| "default".to_string());
 
-    // Load wallet
-    manager.load_wallet(&wallet_name, &password)?;
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
-    // Get bitcoin wallet
-    let bitcoin_wallet = manager
-        .bitcoin_wallet_mut()
-        .ok_or(crate::manager::ManagerError::WalletNotLoaded)?;
-
-    // Generate invoice
-    let generated = f1r3fly::generate_invoice(bitcoin_wallet, &contract_id, amount, address)?;
+    // Generate invoice (fresh nonce, recorded in the invoice registry)
+    let invoice = if blinded {
+        manager.generate_blinded_invoice(&contract_id, amount, expires_in)?
+    } else {
+        manager.generate_invoice_with_expiry(&contract_id, amount, expires_in, address)?
+    };
 
     // Persist wallet changes (address index incremented)
-    bitcoin_wallet.persist()?;
+    manager
+        .bitcoin_wallet_mut()
+        .ok_or(crate::manager::ManagerError::WalletNotLoaded)?
+        .persist()?;
 
     // Display invoice
-    println!("\n✅ RGB Invoice Generated");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("\n📄 Invoice String:");
-    println!("{}", generated.invoice.to_string());
-    println!("\n📊 Invoice Details:");
-    println!("  Contract ID:  {}", contract_id);
-    println!("  Amount:       {}", amount);
-    println!("  Address:      {}", generated.address);
-    println!("  Seal:         {:?}", generated.seal);
-    println!("\n💡 Share the invoice string with the sender to receive assets.");
-    println!();
+    outln!("\n✅ RGB Invoice Generated");
+    outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    outln!("\n📄 Invoice String:");
+    outln!("{}", invoice.invoice);
+    outln!("\n📊 Invoice Details:");
+    outln!("  Invoice ID:   {}", invoice.id);
+    outln!("  Contract ID:  {}", contract_id);
+    outln!("  Amount:       {}", amount);
+    match &invoice.blinded_utxo {
+        Some(utxo) => {
+            outln!("  Blinded seal: {}", invoice.address);
+            outln!("  UTXO:         {} (kept private)", utxo);
+        }
+        None => outln!("  Address:      {}", invoice.address),
+    }
+    outln!("  Nonce:        {}", invoice.nonce);
+    outln!("  Pubkey:       {}", invoice.recipient_pubkey_hex);
+    if let Some(expiry) = invoice.expiry {
+        outln!("  Expires:      {} (unix time)", expiry);
+    }
+    outln!("\n💡 Share the invoice string with the sender to receive assets.");
+    outln!();
 
     Ok(())
 }
@@ -84,7 +109,8 @@ pub async fn parse_invoice_cmd(
     network: Option<NetworkType>,
 ) -> Result<(), InvoiceCommandError> {
     // Parse invoice
-    let parsed = f1r3fly::parse_invoice(&invoice_str)?;
+    let invoice = f1r3fly::WalletInvoice::decode(&invoice_str)?;
+    let parsed = f1r3fly::parse_invoice(&invoice.rgb_invoice)?;
 
     // Extract address if network provided
     let address_info = if let Some(net) = network {
@@ -98,18 +124,129 @@ pub async fn parse_invoice_cmd(
     };
 
     // Display parsed invoice
-    println!("\n✅ Invoice Parsed Successfully");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("\n📊 Invoice Details:");
-    println!("  Contract ID:  {}", parsed.contract_id);
+    outln!("\n✅ Invoice Parsed Successfully");
+    outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    outln!("\n📊 Invoice Details:");
+    outln!("  Contract ID:  {}", parsed.contract_id);
     if let Some(amt) = parsed.amount {
-        println!("  Amount:       {}", amt);
+        outln!("  Amount:       {}", amt);
     } else {
-        println!("  Amount:       (not specified)");
+        outln!("  Amount:       (not specified)");
+    }
+    out!("{}", address_info);
+    outln!("\n  Beneficiary:  {:?}", parsed.beneficiary);
+    match &invoice.recipient_pubkey_hex {
+        Some(pubkey) => outln!("  Pubkey:       {}", pubkey),
+        None => outln!("  Pubkey:       (not embedded; sender must pass --recipient-pubkey)"),
+    }
+    if let Some(expiry) = invoice.expiry {
+        outln!("  Expires:      {} (unix time)", expiry);
+    }
+    outln!();
+
+    Ok(())
+}
+
+/// List invoices issued by this wallet
+///
+/// Shows each invoice's payment status for reconciliation.
+pub fn list_invoices_cmd(
+    wallet_name: Option<String>,
+    contract_id: Option<String>,
+    status: Option<String>,
+    format: String,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), InvoiceCommandError> {
+    let status = status
+        .map(|s| {
+            s.parse::<InvoiceStatus>()
+                .map_err(|_| InvoiceCommandError::InvalidStatus(s))
+        })
+        .transpose()?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Determine wallet name
+    let wallet_name = wallet_name.unwrap_or_else(|| "default".to_string());
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let invoices = manager.list_invoices(contract_id.as_deref(), status)?;
+
+    match format.as_str() {
+        "table" => print_invoices_table(&invoices),
+        "json" => {
+            let json = serde_json::to_string_pretty(&invoices)
+                .map_err(|e| InvoiceCommandError::InvalidFormat(e.to_string()))?;
+            outln!("{}", json);
+        }
+        _ => return Err(InvoiceCommandError::InvalidFormat(format)),
     }
-    print!("{}", address_info);
-    println!("\n  Beneficiary:  {:?}", parsed.beneficiary);
-    println!();
 
     Ok(())
 }
+
+/// Print invoices in table format
+fn print_invoices_table(invoices: &[InvoiceRecord]) {
+    if invoices.is_empty() {
+        outln!("No invoices found.");
+        return;
+    }
+
+    outln!("RGB Invoices ({}):", invoices.len());
+    outln!();
+    outln!(
+        "{:<6} {:<20} {:<20} {:<12} {:<12} {:<16} {:<20}",
+        "ID",
+        "Date",
+        "Contract",
+        "Amount",
+        "Received",
+        "Status",
+        "Expires"
+    );
+    outln!("{:-<110}", "");
+
+    let format_time = |timestamp: u64| {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| timestamp.to_string())
+    };
+
+    for invoice in invoices {
+        let contract_short = if invoice.contract_id.len() > 20 {
+            format!("{}...", &invoice.contract_id[..17])
+        } else {
+            invoice.contract_id.clone()
+        };
+
+        outln!(
+            "{:<6} {:<20} {:<20} {:<12} {:<12} {:<16} {:<20}",
+            invoice.id.unwrap_or(0),
+            format_time(invoice.created_at),
+            contract_short,
+            invoice.amount,
+            invoice.amount_received,
+            invoice.status,
+            invoice
+                .expiry
+                .map(format_time)
+                .unwrap_or_else(|| "-".to_string())
+        );
+    }
+
+    outln!();
+    outln!("Summary:");
+    for status in [
+        InvoiceStatus::Open,
+        InvoiceStatus::PartiallyPaid,
+        InvoiceStatus::Paid,
+        InvoiceStatus::Expired,
+    ] {
+        let count = invoices.iter().filter(|i| i.status == status).count();
+        outln!("  {:<15} {}", format!("{}:", status), count);
+    }
+}
@@ -2,9 +2,13 @@
 
 pub mod bitcoin;
 pub mod config;
+#[cfg(unix)]
+pub mod daemon;
 pub mod invoice;
 pub mod rgb;
+#[cfg(unix)]
+pub mod rpc;
 pub mod transfer;
 pub mod wallet;
 
-pub use invoice::{generate_invoice_cmd, parse_invoice_cmd};
+pub use invoice::{generate_invoice_cmd, list_invoices_cmd, parse_invoice_cmd};
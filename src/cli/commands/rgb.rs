@@ -4,8 +4,9 @@
 //! balance queries, and asset information retrieval.
 
 use crate::config::{load_config, ConfigOverrides};
+use crate::daemon::session::open_wallet;
 use crate::f1r3fly::{AssetBalance, AssetInfo, AssetListItem, IssueAssetRequest};
-use crate::manager::WalletManager;
+use crate::outln;
 
 /// Error type for RGB command operations
 #[derive(Debug, thiserror::Error)]
@@ -73,11 +74,8 @@ pub async fn issue_asset(
     // Load config
     let config = load_config(None, overrides.clone())?;
 
-    // Create manager
-    let mut manager = WalletManager::new(config)?;
-
-    // Load wallet
-    manager.load_wallet(wallet_name, password)?;
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, wallet_name, password)?;
 
     // Create issuance request
     let request = IssueAssetRequest {
@@ -92,15 +90,15 @@ pub async fn issue_asset(
     let asset_info = manager.issue_asset(request).await?;
 
     // Display result
-    println!("✅ Asset issued successfully!");
-    println!();
-    println!("Contract ID:   {}", asset_info.contract_id);
-    println!("Ticker:        {}", asset_info.ticker);
-    println!("Name:          {}", asset_info.name);
-    println!("Total Supply:  {}", asset_info.supply);
-    println!("Precision:     {}", asset_info.precision);
-    println!("Genesis Seal:  {}", asset_info.genesis_seal);
-    println!("Registry URI:  {}", asset_info.registry_uri);
+    outln!("✅ Asset issued successfully!");
+    outln!();
+    outln!("Contract ID:   {}", asset_info.contract_id);
+    outln!("Ticker:        {}", asset_info.ticker);
+    outln!("Name:          {}", asset_info.name);
+    outln!("Total Supply:  {}", asset_info.supply);
+    outln!("Precision:     {}", asset_info.precision);
+    outln!("Genesis Seal:  {}", asset_info.genesis_seal);
+    outln!("Registry URI:  {}", asset_info.registry_uri);
 
     Ok(asset_info)
 }
@@ -136,27 +134,24 @@ pub fn list_assets(
     // Load config
     let config = load_config(None, overrides.clone())?;
 
-    // Create manager
-    let mut manager = WalletManager::new(config)?;
-
-    // Load wallet
-    manager.load_wallet(wallet_name, password)?;
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, wallet_name, password)?;
 
     // List assets
     let assets = manager.list_assets()?;
 
     // Display results
     if assets.is_empty() {
-        println!("No RGB assets found in this wallet.");
+        outln!("No RGB assets found in this wallet.");
     } else {
-        println!("RGB Assets ({}):", assets.len());
-        println!();
+        outln!("RGB Assets ({}):", assets.len());
+        outln!();
         for asset in &assets {
-            println!("Contract ID: {}", asset.contract_id);
-            println!("  Ticker:      {}", asset.ticker);
-            println!("  Name:        {}", asset.name);
-            println!("  Registry:    {}", asset.registry_uri);
-            println!();
+            outln!("Contract ID: {}", asset.contract_id);
+            outln!("  Ticker:      {}", asset.ticker);
+            outln!("  Name:        {}", asset.name);
+            outln!("  Registry:    {}", asset.registry_uri);
+            outln!();
         }
     }
 
@@ -200,11 +195,8 @@ pub async fn rgb_balance(
     // Load config
     let config = load_config(None, overrides.clone())?;
 
-    // Create manager
-    let mut manager = WalletManager::new(config)?;
-
-    // Load wallet
-    manager.load_wallet(wallet_name, password)?;
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, wallet_name, password)?;
 
     // Get balances
     let balances = if let Some(cid) = contract_id {
@@ -218,30 +210,30 @@ pub async fn rgb_balance(
 
     // Display results
     if balances.is_empty() {
-        println!("No RGB asset balances found.");
+        outln!("No RGB asset balances found.");
     } else {
-        println!("RGB Balances:");
-        println!();
+        outln!("RGB Balances:");
+        outln!();
         for balance in &balances {
-            println!("Asset: {} ({})", balance.name, balance.ticker);
-            println!("  Contract ID: {}", balance.contract_id);
-            println!(
+            outln!("Asset: {} ({})", balance.name, balance.ticker);
+            outln!("  Contract ID: {}", balance.contract_id);
+            outln!(
                 "  Total:       {}",
                 format_amount(balance.total, balance.precision)
             );
-            println!("  Precision:   {}", balance.precision);
+            outln!("  Precision:   {}", balance.precision);
 
             if !balance.utxo_balances.is_empty() {
-                println!("  UTXOs:");
+                outln!("  UTXOs:");
                 for utxo_balance in &balance.utxo_balances {
-                    println!(
+                    outln!(
                         "    {} - {}",
                         utxo_balance.outpoint,
                         format_amount(utxo_balance.amount, balance.precision)
                     );
                 }
             }
-            println!();
+            outln!();
         }
     }
 
@@ -281,25 +273,22 @@ pub fn get_contract_info(
     // Load config
     let config = load_config(None, overrides.clone())?;
 
-    // Create manager
-    let mut manager = WalletManager::new(config)?;
-
-    // Load wallet
-    manager.load_wallet(wallet_name, password)?;
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, wallet_name, password)?;
 
     // Get asset info
     let asset_info = manager.get_asset_info(contract_id)?;
 
     // Display result
-    println!("RGB Asset Information:");
-    println!();
-    println!("Contract ID:   {}", asset_info.contract_id);
-    println!("Ticker:        {}", asset_info.ticker);
-    println!("Name:          {}", asset_info.name);
-    println!("Total Supply:  {}", asset_info.supply);
-    println!("Precision:     {}", asset_info.precision);
-    println!("Genesis Seal:  {}", asset_info.genesis_seal);
-    println!("Registry URI:  {}", asset_info.registry_uri);
+    outln!("RGB Asset Information:");
+    outln!();
+    outln!("Contract ID:   {}", asset_info.contract_id);
+    outln!("Ticker:        {}", asset_info.ticker);
+    outln!("Name:          {}", asset_info.name);
+    outln!("Total Supply:  {}", asset_info.supply);
+    outln!("Precision:     {}", asset_info.precision);
+    outln!("Genesis Seal:  {}", asset_info.genesis_seal);
+    outln!("Registry URI:  {}", asset_info.registry_uri);
 
     Ok(asset_info)
 }
@@ -379,12 +368,11 @@ pub async fn export_genesis(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
-    println!("📤 Exporting genesis consignment...");
-    println!("  Contract ID: {}", contract_id);
-    println!();
+    outln!("📤 Exporting genesis consignment...");
+    outln!("  Contract ID: {}", contract_id);
+    outln!();
 
     // Export genesis (uses existing manager.export_genesis method)
     let response = manager.export_genesis(&contract_id).await?;
@@ -392,21 +380,21 @@ pub async fn export_genesis(
     // If custom output path specified, copy the file
     if let Some(custom_path) = &output_path {
         std::fs::copy(&response.consignment_path, custom_path)?;
-        println!("✓ Genesis consignment exported!");
-        println!();
-        println!("  Source:      {}", response.consignment_path.display());
-        println!("  Copied to:   {}", custom_path);
+        outln!("✓ Genesis consignment exported!");
+        outln!();
+        outln!("  Source:      {}", response.consignment_path.display());
+        outln!("  Copied to:   {}", custom_path);
     } else {
-        println!("✓ Genesis consignment exported!");
-        println!();
-        println!("  Location:    {}", response.consignment_path.display());
+        outln!("✓ Genesis consignment exported!");
+        outln!();
+        outln!("  Location:    {}", response.consignment_path.display());
     }
 
-    println!();
-    println!("📋 Next Steps:");
-    println!("  1. Share this file with recipients who want to receive transfers");
-    println!("  2. Recipients should import using:");
-    println!("     accept-consignment --consignment-path <path>");
+    outln!();
+    outln!("📋 Next Steps:");
+    outln!("  1. Share this file with recipients who want to receive transfers");
+    outln!("  2. Recipients should import using:");
+    outln!("     accept-consignment --consignment-path <path>");
 
     Ok(())
 }
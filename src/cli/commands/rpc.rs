@@ -0,0 +1,52 @@
+//! RPC command implementation
+
+use crate::config::{load_config, ConfigError, ConfigOverrides};
+use crate::manager::{ManagerError, WalletManager};
+use crate::outln;
+use crate::rpc::methods::METHODS;
+use crate::rpc::server::RpcServer;
+use crate::rpc::{socket_path, RpcServerError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcCommandError {
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Manager error: {0}")]
+    Manager(#[from] ManagerError),
+
+    #[error("RPC server error: {0}")]
+    Server(#[from] RpcServerError),
+
+    #[error("Wallet not specified. Use --wallet <name>")]
+    WalletNotSpecified,
+}
+
+/// Serve the JSON-RPC API for a wallet in the foreground
+pub fn serve(
+    wallet_name: Option<String>,
+    rpc_socket: Option<String>,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), RpcCommandError> {
+    let wallet_name = wallet_name.ok_or(RpcCommandError::WalletNotSpecified)?;
+    let config = load_config(None, overrides)?;
+    let path = socket_path(rpc_socket.as_deref())?;
+
+    // Node clients created while loading bind to the shared runtime
+    let _guard = crate::cli::runtime().map_err(RpcServerError::from)?.enter();
+    let mut manager = WalletManager::new(config)?;
+    manager.load_wallet(&wallet_name, &password)?;
+
+    let server = RpcServer::bind(&path, manager)?;
+
+    outln!(
+        "✓ JSON-RPC server for wallet '{}' listening on {}",
+        wallet_name,
+        path.display()
+    );
+    outln!("  Methods: {}", METHODS.join(", "));
+
+    server.run()?;
+    Ok(())
+}
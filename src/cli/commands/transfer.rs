@@ -2,9 +2,15 @@
 
 use crate::bitcoin::FeeRateConfig;
 use crate::config::{load_config, ConfigError, ConfigOverrides};
-use crate::manager::{ManagerError, WalletManager};
-use crate::storage::{ClaimStatus, PendingClaim};
+use crate::daemon::session::open_wallet;
+use crate::f1r3fly::{
+    AnchorMethod, PreparedTransfer, RecoveryOutcome, TransferPreview, TransferRecipient,
+};
+use crate::manager::ManagerError;
+use crate::outln;
+use crate::storage::{ClaimStatus, PendingClaim, TransferDirection, TransferRecord};
 use std::path::Path;
+use std::str::FromStr;
 
 #[derive(Debug, thiserror::Error)]
 pub enum TransferCommandError {
@@ -22,25 +28,39 @@ pub enum TransferCommandError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Invalid recipients file: {0}")]
+    InvalidRecipients(String),
+
+    #[error("{0}")]
+    InvalidAnchor(String),
+
+    #[error("Invalid date '{0}': expected YYYY-MM-DD")]
+    InvalidDate(String),
 }
 
 /// Send RGB asset transfer using an invoice
 pub async fn send_transfer(
     wallet_name: Option<String>,
     invoice: String,
-    recipient_pubkey: String,
+    recipient_pubkey: Option<String>,
     fee_rate: Option<f32>,
+    anchor: Option<String>,
+    psbt_out: Option<String>,
+    dry_run: bool,
     password: String,
     overrides: ConfigOverrides,
 ) -> Result<(), TransferCommandError> {
     let wallet_name = wallet_name.ok_or(TransferCommandError::WalletNotSpecified)?;
 
+    // Parse anchor method (None = wallet/config default)
+    let anchor_method = parse_anchor(anchor)?;
+
     // Load config
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Determine fee rate
     let fee_rate_config = match fee_rate {
@@ -49,38 +69,362 @@ pub async fn send_transfer(
         None => FeeRateConfig::medium_priority(),
     };
 
-    println!("📤 Sending RGB transfer...");
-    println!("  Invoice: {}...", &invoice[..invoice.len().min(50)]);
-    println!(
-        "  Recipient pubkey: {}...",
-        &recipient_pubkey[..recipient_pubkey.len().min(16)]
-    );
-    println!();
+    // Preview only: nothing is executed, signed or broadcast
+    if dry_run {
+        let recipients = [TransferRecipient {
+            invoice,
+            recipient_pubkey_hex: recipient_pubkey,
+        }];
+        let preview = manager
+            .preview_transfer_batch(&recipients, &fee_rate_config, anchor_method)
+            .await?;
+        print_transfer_preview(&preview);
+        return Ok(());
+    }
+
+    // Offline signing: export the witness PSBT instead of sending
+    if let Some(psbt_path) = psbt_out {
+        let recipients = [TransferRecipient {
+            invoice,
+            recipient_pubkey_hex: recipient_pubkey,
+        }];
+        let prepared = manager
+            .prepare_transfer_batch(
+                &recipients,
+                &fee_rate_config,
+                anchor_method,
+                Path::new(&psbt_path),
+            )
+            .await?;
+        print_prepared_transfer(&prepared, &psbt_path);
+        return Ok(());
+    }
+
+    outln!("📤 Sending RGB transfer...");
+    outln!("  Invoice: {}...", &invoice[..invoice.len().min(50)]);
+    if let Some(recipient_pubkey) = &recipient_pubkey {
+        outln!(
+            "  Recipient pubkey: {}...",
+            &recipient_pubkey[..recipient_pubkey.len().min(16)]
+        );
+    }
+    outln!();
 
-    // Send transfer (defaults to Tapret)
+    // Send transfer
     let response = manager
-        .send_transfer(&invoice, recipient_pubkey, &fee_rate_config, None)
+        .send_transfer(&invoice, recipient_pubkey, &fee_rate_config, anchor_method)
         .await?;
 
-    println!("✓ Transfer sent successfully!");
-    println!();
-    println!("Transaction Details:");
-    println!("  Bitcoin TX ID: {}", response.bitcoin_txid);
-    println!("  Consignment:   {}", response.consignment_path.display());
-    println!();
-    println!("Transfer Summary:");
-    println!("  Amount sent:   {}", response.amount);
-    println!("  Change amount: {}", response.change_amount);
-    println!();
-    println!("📋 Next Steps:");
-    println!("  1. Share consignment file with recipient:");
-    println!("     {}", response.consignment_path.display());
-    println!("  2. Recipient should accept consignment using:");
-    println!("     accept-consignment --consignment-path <path>");
+    outln!("✓ Transfer sent successfully!");
+    outln!();
+    outln!("Transaction Details:");
+    outln!("  Bitcoin TX ID: {}", response.bitcoin_txid);
+    outln!("  Anchor:        {}", response.anchor_method);
+    outln!("  Consignment:   {}", response.consignment_path.display());
+    outln!();
+    outln!("Transfer Summary:");
+    outln!("  Amount sent:   {}", response.amount);
+    outln!("  Change amount: {}", response.change_amount);
+    outln!();
+    outln!("📋 Next Steps:");
+    outln!("  1. Share consignment file with recipient:");
+    outln!("     {}", response.consignment_path.display());
+    outln!("  2. Recipient should accept consignment using:");
+    outln!("     accept-consignment --consignment-path <path>");
 
     Ok(())
 }
 
+/// Send RGB transfers to several invoices in one Bitcoin transaction
+pub async fn send_transfer_batch(
+    wallet_name: Option<String>,
+    recipients_file: String,
+    fee_rate: Option<f32>,
+    anchor: Option<String>,
+    psbt_out: Option<String>,
+    dry_run: bool,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), TransferCommandError> {
+    let wallet_name = wallet_name.ok_or(TransferCommandError::WalletNotSpecified)?;
+
+    // Read recipients list
+    let recipients_json = std::fs::read_to_string(&recipients_file)?;
+    let recipients: Vec<TransferRecipient> =
+        serde_json::from_str(&recipients_json).map_err(|e| {
+            TransferCommandError::InvalidRecipients(format!("{}: {}", recipients_file, e))
+        })?;
+
+    if recipients.is_empty() {
+        return Err(TransferCommandError::InvalidRecipients(format!(
+            "{}: no recipients",
+            recipients_file
+        )));
+    }
+
+    // Parse anchor method (None = wallet/config default)
+    let anchor_method = parse_anchor(anchor)?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    // Determine fee rate
+    let fee_rate_config = match fee_rate {
+        Some(rate) => FeeRateConfig::new(rate as f64)
+            .map_err(|e| TransferCommandError::Manager(ManagerError::Utxo(e)))?,
+        None => FeeRateConfig::medium_priority(),
+    };
+
+    // Preview only: nothing is executed, signed or broadcast
+    if dry_run {
+        let preview = manager
+            .preview_transfer_batch(&recipients, &fee_rate_config, anchor_method)
+            .await?;
+        print_transfer_preview(&preview);
+        return Ok(());
+    }
+
+    // Offline signing: export the witness PSBT instead of sending
+    if let Some(psbt_path) = psbt_out {
+        let prepared = manager
+            .prepare_transfer_batch(
+                &recipients,
+                &fee_rate_config,
+                anchor_method,
+                Path::new(&psbt_path),
+            )
+            .await?;
+        print_prepared_transfer(&prepared, &psbt_path);
+        return Ok(());
+    }
+
+    outln!("📤 Sending RGB batch transfer...");
+    outln!("  Recipients: {}", recipients.len());
+    outln!();
+
+    // Send batch transfer
+    let response = manager
+        .send_transfer_batch(&recipients, &fee_rate_config, anchor_method)
+        .await?;
+
+    outln!("✓ Batch transfer sent successfully!");
+    outln!();
+    outln!("Transaction Details:");
+    outln!("  Bitcoin TX ID: {}", response.bitcoin_txid);
+    outln!("  Contract ID:   {}", response.contract_id);
+    outln!("  Anchor:        {}", response.anchor_method);
+    outln!();
+    outln!("Transfers:");
+    for transfer in &response.transfers {
+        outln!(
+            "  {} → {}...",
+            transfer.amount,
+            &transfer.invoice[..transfer.invoice.len().min(50)]
+        );
+        outln!("     Consignment: {}", transfer.consignment_path.display());
+    }
+    outln!();
+    outln!("Transfer Summary:");
+    outln!("  Total sent:    {}", response.total_amount);
+    outln!("  Change amount: {}", response.change_amount);
+    outln!();
+    outln!("📋 Next Steps:");
+    outln!("  1. Share each consignment file with its recipient");
+    outln!("  2. Recipients should accept their consignment using:");
+    outln!("     accept-consignment --consignment-path <path>");
+
+    Ok(())
+}
+
+/// Print a transfer preview (`--dry-run`)
+fn print_transfer_preview(preview: &TransferPreview) {
+    outln!("🔍 Transfer preview (dry run, nothing executed)");
+    outln!();
+    outln!("Transfers:");
+    for payment in &preview.payments {
+        outln!(
+            "  {} → {} (output {})",
+            payment.amount,
+            payment.recipient_address,
+            payment.vout
+        );
+    }
+    outln!();
+    outln!("Transfer Summary:");
+    outln!("  Contract ID:    {}", preview.contract_id);
+    outln!("  Total sent:     {}", preview.total_amount);
+    outln!(
+        "  Change amount:  {}{}",
+        preview.change_amount,
+        if preview.uses_change_seal {
+            " (to a new change seal)"
+        } else {
+            ""
+        }
+    );
+    outln!("  Contract calls: {}", preview.contract_calls);
+    outln!("  Anchor:         {}", preview.anchor_method);
+    outln!();
+    outln!("Input Seals:");
+    for input in &preview.inputs {
+        outln!("  {} ({})", input.outpoint, input.amount);
+    }
+    outln!();
+    outln!("Witness Transaction:");
+    outln!(
+        "  Fee:            {} sats ({} sat/vB)",
+        preview.fee_sats,
+        preview.fee_rate_sat_per_vb
+    );
+    for outpoint in &preview.bitcoin_inputs {
+        outln!("  Spends:         {}", outpoint);
+    }
+    outln!();
+    if preview.rgb_occupied_touched.is_empty() {
+        outln!("RGB-occupied UTXOs touched: none");
+    } else {
+        outln!("RGB-occupied UTXOs touched:");
+        for outpoint in &preview.rgb_occupied_touched {
+            outln!("  {}", outpoint);
+        }
+    }
+}
+
+/// Print the result of preparing a transfer for offline signing
+fn print_prepared_transfer(prepared: &PreparedTransfer, psbt_path: &str) {
+    outln!("✓ Transfer prepared for offline signing");
+    outln!();
+    outln!("Transaction Details:");
+    outln!("  Bitcoin TX ID: {}", prepared.txid);
+    outln!("  Contract ID:   {}", prepared.contract_id);
+    outln!("  Anchor:        {}", prepared.anchor_method);
+    outln!("  Unsigned PSBT: {}", psbt_path);
+    outln!();
+    outln!("Transfer Summary:");
+    outln!("  Total sent:    {}", prepared.total_amount);
+    outln!("  Change amount: {}", prepared.change_amount);
+    outln!();
+    outln!("📋 Next Steps:");
+    outln!("  1. Sign the PSBT on the signing machine:");
+    outln!("     sign-psbt --psbt {} --password <password>", psbt_path);
+    outln!("  2. Broadcast it here to complete the transfer:");
+    outln!("     finalize-and-broadcast --psbt <signed psbt> --password <password>");
+}
+
+/// Broadcast a signed PSBT, completing its RGB transfer if one was prepared
+pub async fn finalize_and_broadcast(
+    wallet_name: Option<String>,
+    psbt: String,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), TransferCommandError> {
+    let wallet_name = wallet_name.ok_or(TransferCommandError::WalletNotSpecified)?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let response = manager.finalize_and_broadcast(Path::new(&psbt)).await?;
+
+    outln!("✓ Transaction broadcasted: {}", response.txid);
+
+    if let Some(transfer) = &response.transfer {
+        outln!();
+        outln!("Transfers:");
+        for item in &transfer.transfers {
+            outln!(
+                "  {} → {}...",
+                item.amount,
+                &item.invoice[..item.invoice.len().min(50)]
+            );
+            outln!("     Consignment: {}", item.consignment_path.display());
+        }
+        outln!();
+        outln!("Transfer Summary:");
+        outln!("  Total sent:    {}", transfer.total_amount);
+        outln!("  Change amount: {}", transfer.change_amount);
+        outln!();
+        outln!("📋 Next Steps:");
+        outln!("  1. Share each consignment file with its recipient");
+        outln!("  2. Recipients should accept their consignment using:");
+        outln!("     accept-consignment --consignment-path <path>");
+    }
+
+    Ok(())
+}
+
+/// Resume or report RGB transfers interrupted by a crash
+pub async fn recover_transfers(
+    wallet_name: Option<String>,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), TransferCommandError> {
+    let wallet_name = wallet_name.ok_or(TransferCommandError::WalletNotSpecified)?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let recovered = manager.recover_transfers().await?;
+    if recovered.is_empty() {
+        outln!("✓ No interrupted transfers");
+        return Ok(());
+    }
+
+    for transfer in &recovered {
+        outln!(
+            "Transfer {} (contract {}, phase: {})",
+            transfer.id,
+            transfer.contract_id,
+            transfer.phase
+        );
+        match &transfer.outcome {
+            RecoveryOutcome::Completed(response) => {
+                outln!("  ✓ Completed: witness {}", response.bitcoin_txid);
+                for item in &response.transfers {
+                    outln!("     {} → {}", item.amount, item.consignment_path.display());
+                }
+            }
+            RecoveryOutcome::Discarded => {
+                outln!("  ✓ Discarded: interrupted before any tokens moved");
+            }
+            RecoveryOutcome::NeedsAttention(reason) => {
+                outln!("  ⚠️  Needs attention: {}", reason);
+            }
+            RecoveryOutcome::Failed(error) => {
+                outln!("  ✗ Failed (will retry on next run): {}", error);
+            }
+        }
+    }
+
+    let completed = recovered
+        .iter()
+        .filter(|transfer| matches!(transfer.outcome, RecoveryOutcome::Completed(_)))
+        .count();
+    if completed > 0 {
+        outln!();
+        outln!("📋 Next Steps:");
+        outln!("  Share each recovered consignment file with its recipient");
+    }
+
+    Ok(())
+}
+
+/// Parse the `--anchor` option
+fn parse_anchor(anchor: Option<String>) -> Result<Option<AnchorMethod>, TransferCommandError> {
+    anchor
+        .map(|method| AnchorMethod::from_str(&method))
+        .transpose()
+        .map_err(TransferCommandError::InvalidAnchor)
+}
+
 /// Accept RGB consignment (transfer or genesis)
 pub async fn accept_consignment(
     wallet_name: Option<String>,
@@ -94,8 +438,7 @@ pub async fn accept_consignment(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Validate consignment file exists
     let consignment_file = Path::new(&consignment_path);
@@ -106,28 +449,28 @@ pub async fn accept_consignment(
         )));
     }
 
-    println!("📥 Accepting consignment...");
-    println!("  File: {}", consignment_path);
-    println!();
+    outln!("📥 Accepting consignment...");
+    outln!("  File: {}", consignment_path);
+    outln!();
 
     // Accept consignment
     let response = manager.accept_consignment(&consignment_path).await?;
 
-    println!("✓ Consignment accepted successfully!");
-    println!();
-    println!("Contract Details:");
-    println!("  Contract ID: {}", response.contract_id);
-    println!("  Ticker:      {}", response.ticker);
-    println!("  Name:        {}", response.name);
-    println!("  Seals:       {} imported", response.seals_imported);
-    println!();
-    println!("📋 Next Steps:");
-    println!("  1. Sync wallet to finalize claim:");
-    println!("     sync --password <password>");
-    println!("  2. Check balance:");
-    println!("     rgb-balance --password <password>");
-    println!("  3. View claim status:");
-    println!("     list-claims --password <password>");
+    outln!("✓ Consignment accepted successfully!");
+    outln!();
+    outln!("Contract Details:");
+    outln!("  Contract ID: {}", response.contract_id);
+    outln!("  Ticker:      {}", response.ticker);
+    outln!("  Name:        {}", response.name);
+    outln!("  Seals:       {} imported", response.seals_imported);
+    outln!();
+    outln!("📋 Next Steps:");
+    outln!("  1. Sync wallet to finalize claim:");
+    outln!("     sync --password <password>");
+    outln!("  2. Check balance:");
+    outln!("     rgb-balance --password <password>");
+    outln!("  3. View claim status:");
+    outln!("     list-claims --password <password>");
 
     Ok(())
 }
@@ -146,8 +489,7 @@ pub async fn list_claims(
     let config = load_config(None, overrides)?;
 
     // Create manager and load wallet
-    let mut manager = WalletManager::new(config)?;
-    manager.load_wallet(&wallet_name, &password)?;
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
 
     // Get claims
     let claims = manager.list_claims(contract_id.as_deref())?;
@@ -166,17 +508,21 @@ pub async fn list_claims(
 /// Print claims in table format
 fn print_claims_table(claims: &[PendingClaim]) {
     if claims.is_empty() {
-        println!("No claims found.");
+        outln!("No claims found.");
         return;
     }
 
-    println!("RGB Claims ({}):", claims.len());
-    println!();
-    println!(
+    outln!("RGB Claims ({}):", claims.len());
+    outln!();
+    outln!(
         "{:<8} {:<45} {:<12} {:<20} {:<68}",
-        "ID", "Witness ID", "Status", "Contract", "Actual UTXO"
+        "ID",
+        "Witness ID",
+        "Status",
+        "Contract",
+        "Actual UTXO"
     );
-    println!("{:-<160}", "");
+    outln!("{:-<160}", "");
 
     for claim in claims {
         let status_str = match claim.status {
@@ -198,7 +544,7 @@ fn print_claims_table(claims: &[PendingClaim]) {
             claim.contract_id.clone()
         };
 
-        println!(
+        outln!(
             "{:<8} {:<45} {:<12} {:<20} {:<68}",
             claim.id.unwrap_or(0),
             &claim.witness_id[..claim.witness_id.len().min(45)],
@@ -208,8 +554,8 @@ fn print_claims_table(claims: &[PendingClaim]) {
         );
     }
 
-    println!();
-    println!("Summary:");
+    outln!();
+    outln!("Summary:");
     let pending = claims
         .iter()
         .filter(|c| c.status == ClaimStatus::Pending)
@@ -222,9 +568,9 @@ fn print_claims_table(claims: &[PendingClaim]) {
         .iter()
         .filter(|c| c.status == ClaimStatus::Failed)
         .count();
-    println!("  Pending: {}", pending);
-    println!("  Claimed: {}", claimed);
-    println!("  Failed:  {}", failed);
+    outln!("  Pending: {}", pending);
+    outln!("  Claimed: {}", claimed);
+    outln!("  Failed:  {}", failed);
 }
 
 /// Print claims in JSON format
@@ -235,6 +581,142 @@ fn print_claims_json(claims: &[PendingClaim]) -> Result<(), TransferCommandError
             format!("JSON serialization error: {}", e),
         ))
     })?;
-    println!("{}", json);
+    outln!("{}", json);
+    Ok(())
+}
+
+/// List RGB transfer history
+pub fn list_transfers(
+    wallet_name: Option<String>,
+    contract_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    format: String,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), TransferCommandError> {
+    let wallet_name = wallet_name.ok_or(TransferCommandError::WalletNotSpecified)?;
+
+    // Date range is inclusive of both days
+    let from = from.as_deref().map(|d| parse_date(d, false)).transpose()?;
+    let until = to.as_deref().map(|d| parse_date(d, true)).transpose()?;
+
+    // Load config
+    let config = load_config(None, overrides)?;
+
+    // Create manager and load wallet
+    let mut manager = open_wallet(config, &wallet_name, &password)?;
+
+    let transfers = manager.list_transfers(contract_id.as_deref(), from, until)?;
+
+    match format.as_str() {
+        "table" => print_transfers_table(&transfers),
+        "json" => print_transfers_json(&transfers)?,
+        _ => {
+            return Err(TransferCommandError::InvalidFormat(format));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a `YYYY-MM-DD` date into Unix seconds (UTC)
+///
+/// `end_of_day` selects 23:59:59 instead of midnight.
+fn parse_date(date: &str, end_of_day: bool) -> Result<u64, TransferCommandError> {
+    let day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| TransferCommandError::InvalidDate(date.to_string()))?;
+    let time = if end_of_day {
+        day.and_hms_opt(23, 59, 59)
+    } else {
+        day.and_hms_opt(0, 0, 0)
+    }
+    .ok_or_else(|| TransferCommandError::InvalidDate(date.to_string()))?;
+
+    Ok(time.and_utc().timestamp().max(0) as u64)
+}
+
+/// Print transfers in table format
+fn print_transfers_table(transfers: &[TransferRecord]) {
+    if transfers.is_empty() {
+        outln!("No transfers found.");
+        return;
+    }
+
+    outln!("RGB Transfers ({}):", transfers.len());
+    outln!();
+    outln!(
+        "{:<6} {:<20} {:<10} {:<12} {:<12} {:<20} {:<10} {:<18}",
+        "ID",
+        "Date",
+        "Direction",
+        "Amount",
+        "Status",
+        "Contract",
+        "Anchor",
+        "Witness TX"
+    );
+    outln!("{:-<120}", "");
+
+    for transfer in transfers {
+        let date = chrono::DateTime::from_timestamp(transfer.created_at as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| transfer.created_at.to_string());
+
+        let amount = transfer
+            .amount
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| "?".to_string());
+
+        let contract_short = if transfer.contract_id.len() > 20 {
+            format!("{}...", &transfer.contract_id[..17])
+        } else {
+            transfer.contract_id.clone()
+        };
+
+        let witness_short = if transfer.witness_txid.len() > 16 {
+            format!("{}...", &transfer.witness_txid[..15])
+        } else {
+            transfer.witness_txid.clone()
+        };
+
+        outln!(
+            "{:<6} {:<20} {:<10} {:<12} {:<12} {:<20} {:<10} {:<18}",
+            transfer.id.unwrap_or(0),
+            date,
+            transfer.direction,
+            amount,
+            transfer.status,
+            contract_short,
+            transfer.anchor_method.as_deref().unwrap_or("-"),
+            witness_short
+        );
+    }
+
+    outln!();
+    outln!("Summary:");
+    let sent: u64 = transfers
+        .iter()
+        .filter(|t| t.direction == TransferDirection::Outgoing)
+        .filter_map(|t| t.amount)
+        .sum();
+    let received: u64 = transfers
+        .iter()
+        .filter(|t| t.direction == TransferDirection::Incoming)
+        .filter_map(|t| t.amount)
+        .sum();
+    outln!("  Sent:     {}", sent);
+    outln!("  Received: {}", received);
+}
+
+/// Print transfers in JSON format
+fn print_transfers_json(transfers: &[TransferRecord]) -> Result<(), TransferCommandError> {
+    let json = serde_json::to_string_pretty(&transfers).map_err(|e| {
+        TransferCommandError::Io(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("JSON serialization error: {}", e),
+        ))
+    })?;
+    outln!("{}", json);
     Ok(())
 }
@@ -1,11 +1,18 @@
 //! Wallet command implementations
 
-use crate::config::{load_config, ConfigError, ConfigOverrides, NetworkType};
+use crate::config::{load_config, ConfigError, ConfigOverrides, KeyMode, NetworkType};
+use crate::f1r3fly::{AnchorMethod, RestoreOptions};
 use crate::manager::WalletManager;
-use crate::storage::file_system::{list_wallets as list_wallets_from_fs, FileSystemError};
+use crate::outln;
+use crate::storage::backup::{create_backup, restore_backup, BackupError};
+use crate::storage::file_system::{
+    change_password as change_wallet_password, list_wallets as list_wallets_from_fs,
+    load_encrypted_keys, load_wallet_metadata, update_wallet_metadata, FileSystemError,
+};
 use crate::storage::keys::KeyError;
 use crate::storage::models::WalletKeys;
 use bip39::Mnemonic;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Debug, thiserror::Error)]
@@ -22,11 +29,17 @@ pub enum WalletCommandError {
     #[error("Key error: {0}")]
     Key(#[from] KeyError),
 
+    #[error("Backup error: {0}")]
+    Backup(#[from] BackupError),
+
     #[error("Wallet '{0}' already exists")]
     WalletExists(String),
 
     #[error("Invalid mnemonic: {0}")]
     InvalidMnemonic(String),
+
+    #[error("{0}")]
+    InvalidAnchor(String),
 }
 
 /// Create a new wallet with a generated mnemonic
@@ -51,16 +64,16 @@ pub fn create(
         .map_err(|e| WalletCommandError::InvalidMnemonic(e.to_string()))?;
     let keys = WalletKeys::from_mnemonic(&mnemonic, network)?;
 
-    println!("✓ Wallet '{}' created successfully", name);
-    println!();
-    println!("  Network:           {:?}", network);
-    println!("  First Address:     {}", first_address);
-    println!("  F1r3fly Public Key: {}", keys.f1r3fly_public_key);
-    println!();
-    println!("  IMPORTANT: Write down your recovery phrase:");
-    println!("  {}", mnemonic_str);
-    println!();
-    println!("  Keep this phrase safe and secret!");
+    outln!("✓ Wallet '{}' created successfully", name);
+    outln!();
+    outln!("  Network:           {:?}", network);
+    outln!("  First Address:     {}", first_address);
+    outln!("  F1r3fly Public Key: {}", keys.f1r3fly_public_key);
+    outln!();
+    outln!("  IMPORTANT: Write down your recovery phrase:");
+    outln!("  {}", mnemonic_str);
+    outln!();
+    outln!("  Keep this phrase safe and secret!");
 
     Ok(())
 }
@@ -88,11 +101,143 @@ pub fn import(
         .map_err(|e| WalletCommandError::InvalidMnemonic(e.to_string()))?;
     let keys = WalletKeys::from_mnemonic(&mnemonic, network)?;
 
-    println!("✓ Wallet '{}' imported successfully", name);
-    println!();
-    println!("  Network:           {:?}", network);
-    println!("  First Address:     {}", first_address);
-    println!("  F1r3fly Public Key: {}", keys.f1r3fly_public_key);
+    outln!("✓ Wallet '{}' imported successfully", name);
+    outln!();
+    outln!("  Network:           {:?}", network);
+    outln!("  First Address:     {}", first_address);
+    outln!("  F1r3fly Public Key: {}", keys.f1r3fly_public_key);
+
+    Ok(())
+}
+
+/// Restore a wallet from its mnemonic, including RGB assets
+pub async fn restore(
+    name: String,
+    mnemonic_str: String,
+    password: String,
+    options: RestoreOptions,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
+    // Load config
+    let config = load_config(None, overrides)?;
+    let network = config.bitcoin.network;
+
+    outln!("Restoring wallet '{}' (scanning blockchain)...", name);
+
+    let mut manager = WalletManager::new(config)?;
+    let report = manager
+        .restore_wallet(&name, &mnemonic_str, &password, options)
+        .await?;
+    let balance = manager.get_balance()?;
+
+    outln!("✓ Wallet '{}' restored successfully", name);
+    outln!();
+    outln!("  Network:           {:?}", network);
+    outln!("  Scanned to height: {}", report.scan_height);
+    outln!("  Transactions:      {}", report.transactions_found);
+    outln!("  Bitcoin balance:   {} sats", balance.confirmed);
+    outln!("  Contracts:         {}", report.contracts_restored.len());
+    for contract_id in &report.contracts_restored {
+        match report.issued_contracts.get(contract_id) {
+            Some(index) => outln!("    {} (issued, derivation index {})", contract_id, index),
+            None => outln!("    {}", contract_id),
+        }
+    }
+    outln!("  Received transfers: {}", report.transfers_restored);
+
+    if !report.skipped.is_empty() {
+        outln!();
+        outln!("  Skipped consignments ({}):", report.skipped.len());
+        for (path, reason) in &report.skipped {
+            outln!("    {}: {}", path.display(), reason);
+        }
+    }
+
+    if report.contracts_restored.is_empty() {
+        outln!();
+        outln!("  No RGB assets restored. Pass --consignments-dir with the genesis");
+        outln!("  and received transfer consignments to recover received assets.");
+    }
+
+    Ok(())
+}
+
+/// Change the password protecting a wallet's keys
+pub fn change_password(
+    name: String,
+    old_password: String,
+    new_password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
+    // Load config to get wallets directory
+    let config = load_config(None, overrides)?;
+    let custom_base = config.wallets_dir.as_deref();
+
+    change_wallet_password(&name, &old_password, &new_password, custom_base)?;
+
+    outln!("✓ Password changed for wallet '{}'", name);
+    outln!();
+    outln!("  Backups taken before this change still use the old password.");
+
+    Ok(())
+}
+
+/// Back up a wallet into a single encrypted file
+pub fn backup(
+    name: String,
+    output: Option<String>,
+    password: String,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
+    // Load config to get wallets directory
+    let config = load_config(None, overrides)?;
+    let custom_base = config.wallets_dir.as_deref();
+
+    let output = PathBuf::from(output.unwrap_or_else(|| format!("{}.backup", name)));
+    let manifest = create_backup(&name, &password, custom_base, &output)?;
+
+    outln!("✓ Wallet '{}' backed up successfully", name);
+    outln!();
+    outln!("  Backup file:       {}", output.display());
+    outln!("  Network:           {:?}", manifest.network);
+    outln!("  Files:             {}", manifest.files.len());
+    outln!("  Size:              {} bytes", manifest.total_size());
+    outln!();
+    outln!("  The backup is encrypted with the wallet password.");
+
+    Ok(())
+}
+
+/// Restore a wallet from a backup file created by `wallet backup`
+pub fn restore_from_backup(
+    backup: String,
+    name: Option<String>,
+    password: String,
+    force: bool,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
+    // Load config to get wallets directory
+    let config = load_config(None, overrides)?;
+    let custom_base = config.wallets_dir.as_deref();
+
+    let manifest = restore_backup(
+        &PathBuf::from(&backup),
+        &password,
+        name.as_deref(),
+        custom_base,
+        force,
+    )?;
+
+    outln!("✓ Wallet '{}' restored from backup", manifest.wallet_name);
+    outln!();
+    outln!("  Network:           {:?}", manifest.network);
+    outln!(
+        "  Backup taken:      {}",
+        manifest.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    outln!("  Files:             {}", manifest.files.len());
+    outln!();
+    outln!("  Run `sync` to pick up activity since the backup was taken.");
 
     Ok(())
 }
@@ -106,26 +251,65 @@ pub fn list(overrides: ConfigOverrides) -> Result<(), WalletCommandError> {
     let wallets = list_wallets_from_fs(custom_base)?;
 
     if wallets.is_empty() {
-        println!("No wallets found.");
-        println!();
-        println!("Create a new wallet with:");
-        println!("  f1r3fly-rgb-wallet wallet create <name> --password <password>");
+        outln!("No wallets found.");
+        outln!();
+        outln!("Create a new wallet with:");
+        outln!("  f1r3fly-rgb-wallet wallet create <name> --password <password>");
         return Ok(());
     }
 
-    println!("Wallets ({}):", wallets.len());
-    println!();
+    outln!("Wallets ({}):", wallets.len());
+    outln!();
 
     for wallet in wallets {
-        println!("  {} [{}]", wallet.name, format_network(wallet.network));
-        println!(
+        outln!("  {} [{}]", wallet.name, format_network(wallet.network));
+        outln!(
             "    Created: {}",
             wallet.created_at.format("%Y-%m-%d %H:%M:%S")
         );
         if let Some(last_sync) = wallet.last_sync {
-            println!("    Last Sync: {}", last_sync.format("%Y-%m-%d %H:%M:%S"));
+            outln!("    Last Sync: {}", last_sync.format("%Y-%m-%d %H:%M:%S"));
+        }
+        if let Some(anchor_method) = wallet.anchor_method {
+            outln!("    Anchor: {}", anchor_method);
         }
-        println!();
+        outln!();
+    }
+
+    Ok(())
+}
+
+/// Set a wallet's default RGB anchor method
+///
+/// Stored in the wallet's plaintext metadata, so no password is required.
+/// `default` clears the wallet setting so the global config applies.
+pub fn set_anchor(
+    name: String,
+    method: String,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
+    let anchor_method = match method.to_lowercase().as_str() {
+        "default" => None,
+        other => Some(AnchorMethod::from_str(other).map_err(WalletCommandError::InvalidAnchor)?),
+    };
+
+    // Load config to get wallets directory
+    let config = load_config(None, overrides)?;
+    let custom_base = config.wallets_dir.as_deref();
+
+    let mut metadata = load_wallet_metadata(&name, custom_base)?;
+    metadata.anchor_method = anchor_method;
+    update_wallet_metadata(&name, &metadata, custom_base)?;
+
+    match anchor_method {
+        Some(anchor_method) => {
+            outln!("✓ Wallet '{}' anchor method set to {}", name, anchor_method)
+        }
+        None => outln!(
+            "✓ Wallet '{}' anchor method cleared (using config default: {})",
+            name,
+            config.anchor_method.unwrap_or_default()
+        ),
     }
 
     Ok(())
@@ -133,26 +317,42 @@ pub fn list(overrides: ConfigOverrides) -> Result<(), WalletCommandError> {
 
 /// Get F1r3fly public key
 ///
-/// Returns the public key from the F1r3fly executor (master key).
-/// This is the key used for all RGB transfer and claim signatures.
+/// Returns the public key used for RGB transfer and claim signatures:
+/// the wallet's own F1r3fly key for wallets in `KeyMode::Wallet`, otherwise
+/// the F1r3fly executor's (master key) public key.
 /// No password required since it's public information meant to be shared.
-pub fn get_f1r3fly_pubkey(overrides: ConfigOverrides) -> Result<(), WalletCommandError> {
+pub fn get_f1r3fly_pubkey(
+    wallet_name: Option<String>,
+    overrides: ConfigOverrides,
+) -> Result<(), WalletCommandError> {
     // Load config
     let config = load_config(None, overrides)?;
 
+    // Wallet key mode: the identity is the wallet's F1r3fly key (public, in keys.json)
+    if let Some(name) = wallet_name.as_deref() {
+        let custom_base = config.wallets_dir.as_deref();
+        if load_wallet_metadata(name, custom_base)?.key_mode == KeyMode::Wallet {
+            let encrypted_keys = load_encrypted_keys(name, custom_base)?;
+            print_f1r3fly_pubkey(&encrypted_keys.f1r3fly_public_key);
+            return Ok(());
+        }
+    }
+
     // IMPORTANT: Get the public key from the F1r3flyExecutor (using config master key)
-    // This is the ACTUAL key used for signing claims and transfers.
-    // The wallet-specific F1r3fly key stored in keys.json is reserved for future use.
+    // This is the ACTUAL key used for signing claims and transfers in shared key mode.
     //
     // Create executor using the config's master key (same as what's used for actual operations)
     use f1r3fly_rgb::F1r3flyExecutor;
     use node_cli::connection_manager::{ConnectionConfig, F1r3flyConnectionManager};
 
+    let master_key =
+        crate::storage::keystore::resolve_master_key(&config).map_err(ConfigError::from)?;
+
     let connection_config = ConnectionConfig::new(
         config.f1r3node.host.clone(),
         config.f1r3node.grpc_port,
         config.f1r3node.http_port,
-        config.f1r3node.master_key.clone(),
+        master_key,
     );
 
     let connection = F1r3flyConnectionManager::new(connection_config);
@@ -166,15 +366,19 @@ pub fn get_f1r3fly_pubkey(overrides: ConfigOverrides) -> Result<(), WalletComman
     // Use uncompressed format (matches invoice generation)
     let pubkey_hex = hex::encode(pubkey.serialize_uncompressed());
 
-    // Display public key (not encrypted, safe to show)
-    println!("F1r3fly Public Key:");
-    println!("  {}", pubkey_hex);
-    println!();
-    println!("💡 Share this public key with senders who want to transfer RGB assets to you.");
+    print_f1r3fly_pubkey(&pubkey_hex);
 
     Ok(())
 }
 
+/// Display a F1r3fly public key (not encrypted, safe to show)
+fn print_f1r3fly_pubkey(pubkey_hex: &str) {
+    outln!("F1r3fly Public Key:");
+    outln!("  {}", pubkey_hex);
+    outln!();
+    outln!("💡 Share this public key with senders who want to transfer RGB assets to you.");
+}
+
 fn format_network(network: NetworkType) -> &'static str {
     match network {
         NetworkType::Regtest => "Regtest",
@@ -4,3 +4,21 @@
 
 pub mod args;
 pub mod commands;
+pub mod output;
+pub mod password;
+
+use std::sync::OnceLock;
+
+/// Shared async runtime
+///
+/// One runtime per process, so a daemon keeps its F1r3node and Esplora
+/// connections alive across forwarded commands.
+pub fn runtime() -> std::io::Result<&'static tokio::runtime::Runtime> {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Runtime::new()?;
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
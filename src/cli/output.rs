@@ -0,0 +1,73 @@
+//! Command output
+//!
+//! Commands print through `outln!`/`errln!` instead of `println!`/`eprintln!`
+//! so the daemon can capture a forwarded command's output and send it back
+//! to the client. Outside a capture the macros print as usual.
+
+use std::cell::RefCell;
+use std::fmt;
+
+thread_local! {
+    static CAPTURE: RefCell<Option<CapturedOutput>> = const { RefCell::new(None) };
+}
+
+/// Output captured while running a command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run `f`, capturing everything it prints through the output macros
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, CapturedOutput) {
+    let previous = CAPTURE.with(|c| c.replace(Some(CapturedOutput::default())));
+    let result = f();
+    let captured = CAPTURE.with(|c| c.replace(previous)).unwrap_or_default();
+    (result, captured)
+}
+
+#[doc(hidden)]
+pub fn write_stdout(args: fmt::Arguments) {
+    CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some(captured) => fmt::Write::write_fmt(&mut captured.stdout, args).unwrap_or(()),
+        None => print!("{}", args),
+    })
+}
+
+#[doc(hidden)]
+pub fn write_stderr(args: fmt::Arguments) {
+    CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some(captured) => fmt::Write::write_fmt(&mut captured.stderr, args).unwrap_or(()),
+        None => eprint!("{}", args),
+    })
+}
+
+/// Print command output (like `print!`)
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::cli::output::write_stdout(format_args!($($arg)*))
+    };
+}
+
+/// Print a line of command output (like `println!`)
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::cli::output::write_stdout(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::cli::output::write_stdout(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Print a line of command diagnostics (like `eprintln!`)
+#[macro_export]
+macro_rules! errln {
+    () => {
+        $crate::cli::output::write_stderr(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::cli::output::write_stderr(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
@@ -0,0 +1,201 @@
+//! Password acquisition for CLI commands
+//!
+//! Keeps passwords off the command line: they are read from a file, an
+//! inherited file descriptor, an environment variable, or a terminal prompt.
+//! The `--password` flag still works but prints a warning.
+
+use crate::errln;
+use std::fs::File;
+use std::io::Read;
+
+use crate::cli::args::{NewPasswordArgs, PasswordArgs};
+
+/// Environment variable holding the wallet password
+pub const WALLET_PASSWORD_ENV: &str = "FIREFLY_WALLET_PASSWORD";
+
+/// Environment variable holding the new password for `wallet change-password`
+pub const NEW_WALLET_PASSWORD_ENV: &str = "FIREFLY_WALLET_NEW_PASSWORD";
+
+/// Password acquisition errors
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordError {
+    #[error("Failed to read password from {source_name}: {error}")]
+    Read {
+        source_name: String,
+        error: std::io::Error,
+    },
+
+    #[error("File descriptors are not supported on this platform (use --password-file)")]
+    FdUnsupported,
+
+    #[error("File descriptor {0} is a standard stream: pass a descriptor of 3 or above")]
+    StandardStreamFd(i32),
+
+    #[error("Passwords do not match")]
+    Mismatch,
+
+    #[error(
+        "No password given and no terminal to prompt on: use --password-file, --password-fd or {0}"
+    )]
+    NoPassword(String),
+}
+
+impl PasswordArgs {
+    /// Read the password of an existing wallet
+    ///
+    /// Commands the daemon runs against its unlocked wallet need none.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_name` - Wallet the command opens
+    pub fn wallet_password(&self, wallet_name: Option<&str>) -> Result<String, PasswordError> {
+        if wallet_name.is_some_and(crate::daemon::session::is_lent) {
+            return Ok(String::new());
+        }
+        self.read(WALLET_PASSWORD_ENV, "Wallet password", false)
+    }
+
+    /// Read the password for a new wallet (the prompt asks twice)
+    pub fn new_wallet_password(&self) -> Result<String, PasswordError> {
+        self.read(WALLET_PASSWORD_ENV, "New wallet password", true)
+    }
+
+    /// Read a password from the first source given
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Environment variable checked after the flags
+    /// * `prompt` - Terminal prompt shown when no other source is given
+    /// * `confirm` - Ask twice when prompting (for passwords being set)
+    pub fn read(&self, env: &str, prompt: &str, confirm: bool) -> Result<String, PasswordError> {
+        read_password(
+            PasswordSources {
+                flag: self.password.as_deref(),
+                file: self.password_file.as_deref(),
+                fd: self.password_fd,
+                env,
+            },
+            prompt,
+            confirm,
+        )
+    }
+}
+
+impl NewPasswordArgs {
+    /// Read the new password for `wallet change-password` (the prompt asks twice)
+    pub fn new_wallet_password(&self) -> Result<String, PasswordError> {
+        read_password(
+            PasswordSources {
+                flag: self.new_password.as_deref(),
+                file: self.new_password_file.as_deref(),
+                fd: self.new_password_fd,
+                env: NEW_WALLET_PASSWORD_ENV,
+            },
+            "New wallet password",
+            true,
+        )
+    }
+}
+
+/// Where a password may come from, in priority order
+struct PasswordSources<'a> {
+    flag: Option<&'a str>,
+    file: Option<&'a str>,
+    fd: Option<i32>,
+    env: &'a str,
+}
+
+fn read_password(
+    sources: PasswordSources<'_>,
+    prompt: &str,
+    confirm: bool,
+) -> Result<String, PasswordError> {
+    if let Some(password) = sources.flag {
+        errln!("⚠️  Passwords on the command line leak into shell history and `ps` output.");
+        errln!(
+            "   Use a password file, a file descriptor, {} or the prompt instead.",
+            sources.env
+        );
+        return Ok(password.to_string());
+    }
+
+    if let Some(path) = sources.file {
+        let file = File::open(path).map_err(|error| PasswordError::Read {
+            source_name: path.to_string(),
+            error,
+        })?;
+        return read_first_line(file, path);
+    }
+
+    if let Some(fd) = sources.fd {
+        return read_fd(fd);
+    }
+
+    if let Ok(password) = std::env::var(sources.env) {
+        return Ok(password);
+    }
+
+    prompt_password(prompt, confirm, sources.env)
+}
+
+/// Read the first line (without line ending) from a password source
+fn read_first_line(mut reader: impl Read, source_name: &str) -> Result<String, PasswordError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|error| PasswordError::Read {
+            source_name: source_name.to_string(),
+            error,
+        })?;
+
+    Ok(contents.lines().next().unwrap_or_default().to_string())
+}
+
+#[cfg(unix)]
+fn read_fd(fd: i32) -> Result<String, PasswordError> {
+    use std::os::unix::io::FromRawFd;
+
+    // Standard streams belong to the process: reading one to EOF and
+    // closing it would break later prompts and output
+    if (0..=2).contains(&fd) {
+        return Err(PasswordError::StandardStreamFd(fd));
+    }
+
+    let source_name = format!("file descriptor {}", fd);
+
+    // SAFETY: F_GETFD only queries the descriptor flags; an invalid
+    // descriptor is reported as -1/EBADF
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(PasswordError::Read {
+            source_name,
+            error: std::io::Error::last_os_error(),
+        });
+    }
+
+    // SAFETY: the descriptor is open (checked above) and is not a standard
+    // stream. Naming it with --password-fd hands it over to this function,
+    // which takes ownership: nothing else in the process uses it, and it is
+    // closed when the file is dropped
+    let file = unsafe { File::from_raw_fd(fd) };
+    read_first_line(file, &source_name)
+}
+
+#[cfg(not(unix))]
+fn read_fd(_fd: i32) -> Result<String, PasswordError> {
+    Err(PasswordError::FdUnsupported)
+}
+
+/// Prompt on the terminal without echoing
+fn prompt_password(prompt: &str, confirm: bool, env: &str) -> Result<String, PasswordError> {
+    let ask = |prompt: &str| {
+        rpassword::prompt_password(format!("{}: ", prompt))
+            .map_err(|_| PasswordError::NoPassword(env.to_string()))
+    };
+
+    let password = ask(prompt)?;
+    if confirm && ask(&format!("Confirm {}", prompt.to_lowercase()))? != password {
+        return Err(PasswordError::Mismatch);
+    }
+
+    Ok(password)
+}
@@ -6,6 +6,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::f1r3fly::AnchorMethod;
+use crate::storage::keys::Kdf;
+
 /// Global wallet configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalConfig {
@@ -13,6 +16,20 @@ pub struct GlobalConfig {
     pub bitcoin: BitcoinConfig,
     /// Optional custom wallets directory
     pub wallets_dir: Option<String>,
+    /// Optional custom keystore directory (see `storage::keystore`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore_dir: Option<String>,
+    /// Default RGB anchor method for transfers ("tapret" or "opreturn")
+    ///
+    /// Used when neither the command nor the wallet specifies one.
+    /// Tapret if unset.
+    pub anchor_method: Option<AnchorMethod>,
+    /// Key derivation function for wallet secrets (see `storage::keys::Kdf`)
+    ///
+    /// New wallets are encrypted with it; existing wallets using another
+    /// KDF are re-encrypted the next time they are unlocked. PBKDF2 if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<Kdf>,
 }
 
 /// F1r3node connection configuration
@@ -29,7 +46,61 @@ pub struct F1r3nodeConfig {
     ///
     /// Must be a funded key with sufficient REV balance.
     /// Typically loaded from FIREFLY_PRIVATE_KEY environment variable.
+    /// Never written back to the config file: persist it in the keystore
+    /// and reference it with `master_key_id` instead.
+    #[serde(default, skip_serializing)]
     pub master_key: String,
+    /// Keystore entry holding the encrypted master key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub master_key_id: Option<String>,
+    /// Key file unlocking `master_key_id` (instead of a password)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub master_key_file: Option<String>,
+    /// Contract ownership key mode for newly created wallets
+    #[serde(default)]
+    pub key_mode: KeyMode,
+}
+
+/// Which key owns a wallet's contracts and seals
+///
+/// Recorded per wallet at creation (see `WalletMetadata::key_mode`);
+/// changing it afterwards would orphan existing contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyMode {
+    /// Child keys of the config master key (shared by every wallet on the machine)
+    #[default]
+    Shared,
+    /// Child keys of the wallet's mnemonic-derived F1r3fly key
+    ///
+    /// The config master key only pays phlo, so restoring the mnemonic
+    /// restores contract ownership.
+    Wallet,
+}
+
+impl std::fmt::Display for KeyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shared => write!(f, "shared"),
+            Self::Wallet => write!(f, "wallet"),
+        }
+    }
+}
+
+impl std::str::FromStr for KeyMode {
+    type Err = String;
+
+    /// Parse a key mode name (`shared` or `wallet`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shared" => Ok(Self::Shared),
+            "wallet" => Ok(Self::Wallet),
+            _ => Err(format!(
+                "Invalid key mode '{}': expected 'shared' or 'wallet'",
+                s
+            )),
+        }
+    }
 }
 
 /// Bitcoin network and blockchain configuration
@@ -57,14 +128,19 @@ impl GlobalConfig {
                 host: "localhost".to_string(),
                 grpc_port: 40401,
                 http_port: 40403,
-                master_key: std::env::var("FIREFLY_PRIVATE_KEY")
-                    .expect("FIREFLY_PRIVATE_KEY environment variable must be set"),
+                master_key: std::env::var("FIREFLY_PRIVATE_KEY").unwrap_or_default(),
+                master_key_id: None,
+                master_key_file: None,
+                key_mode: KeyMode::Shared,
             },
             bitcoin: BitcoinConfig {
                 network: NetworkType::Regtest,
                 esplora_url: "http://localhost:3002".to_string(),
             },
             wallets_dir: None,
+            keystore_dir: None,
+            anchor_method: None,
+            kdf: None,
         }
     }
 
@@ -75,14 +151,19 @@ impl GlobalConfig {
                 host: "localhost".to_string(),
                 grpc_port: 40401,
                 http_port: 40403,
-                master_key: std::env::var("FIREFLY_PRIVATE_KEY")
-                    .expect("FIREFLY_PRIVATE_KEY environment variable must be set"),
+                master_key: std::env::var("FIREFLY_PRIVATE_KEY").unwrap_or_default(),
+                master_key_id: None,
+                master_key_file: None,
+                key_mode: KeyMode::Shared,
             },
             bitcoin: BitcoinConfig {
                 network: NetworkType::Signet,
                 esplora_url: "https://mempool.space/signet/api".to_string(),
             },
             wallets_dir: None,
+            keystore_dir: None,
+            anchor_method: None,
+            kdf: None,
         }
     }
 
@@ -93,14 +174,19 @@ impl GlobalConfig {
                 host: "localhost".to_string(),
                 grpc_port: 40401,
                 http_port: 40403,
-                master_key: std::env::var("FIREFLY_PRIVATE_KEY")
-                    .expect("FIREFLY_PRIVATE_KEY environment variable must be set"),
+                master_key: std::env::var("FIREFLY_PRIVATE_KEY").unwrap_or_default(),
+                master_key_id: None,
+                master_key_file: None,
+                key_mode: KeyMode::Shared,
             },
             bitcoin: BitcoinConfig {
                 network: NetworkType::Testnet,
                 esplora_url: "https://mempool.space/testnet/api".to_string(),
             },
             wallets_dir: None,
+            keystore_dir: None,
+            anchor_method: None,
+            kdf: None,
         }
     }
 
@@ -111,14 +197,19 @@ impl GlobalConfig {
                 host: "localhost".to_string(),
                 grpc_port: 40401,
                 http_port: 40403,
-                master_key: std::env::var("FIREFLY_PRIVATE_KEY")
-                    .expect("FIREFLY_PRIVATE_KEY environment variable must be set"),
+                master_key: std::env::var("FIREFLY_PRIVATE_KEY").unwrap_or_default(),
+                master_key_id: None,
+                master_key_file: None,
+                key_mode: KeyMode::Shared,
             },
             bitcoin: BitcoinConfig {
                 network: NetworkType::Mainnet,
                 esplora_url: "https://mempool.space/api".to_string(),
             },
             wallets_dir: None,
+            keystore_dir: None,
+            anchor_method: None,
+            kdf: None,
         }
     }
 }
@@ -146,6 +237,9 @@ pub enum ConfigError {
 
     #[error("Config directory not found")]
     DirectoryNotFound,
+
+    #[error("Keystore error: {0}")]
+    Keystore(#[from] crate::storage::keystore::KeystoreError),
 }
 
 /// Configuration overrides from CLI arguments or environment variables
@@ -157,6 +251,7 @@ pub struct ConfigOverrides {
     pub f1r3node_http_port: Option<u16>,
     pub esplora_url: Option<String>,
     pub wallets_dir: Option<String>,
+    pub f1r3node_master_key: Option<String>,
 }
 
 impl ConfigOverrides {
@@ -194,6 +289,7 @@ impl ConfigOverrides {
                 .and_then(|s| s.parse().ok()),
             esplora_url: std::env::var("ESPLORA_URL").ok(),
             wallets_dir: std::env::var("WALLETS_DIR").ok(),
+            f1r3node_master_key: std::env::var("FIREFLY_PRIVATE_KEY").ok(),
         }
     }
 
@@ -214,6 +310,9 @@ impl ConfigOverrides {
         if other.esplora_url.is_some() {
             self.esplora_url = other.esplora_url;
         }
+        if other.f1r3node_master_key.is_some() {
+            self.f1r3node_master_key = other.f1r3node_master_key;
+        }
         self
     }
 }
@@ -358,6 +457,9 @@ fn apply_overrides(config: &mut GlobalConfig, overrides: ConfigOverrides) {
     if let Some(port) = overrides.f1r3node_http_port {
         config.f1r3node.http_port = port;
     }
+    if let Some(master_key) = overrides.f1r3node_master_key {
+        config.f1r3node.master_key = master_key;
+    }
 
     // Apply esplora URL override (highest priority)
     if let Some(url) = overrides.esplora_url {
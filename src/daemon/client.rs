@@ -0,0 +1,87 @@
+//! Daemon client
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+use super::protocol::{DaemonRequest, DaemonResponse};
+use super::DaemonError;
+
+/// Connection settings for a running daemon
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    socket_path: PathBuf,
+}
+
+impl DaemonClient {
+    /// Create a client for the daemon listening on `socket_path`
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+        }
+    }
+
+    /// Socket this client connects to
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Send one request and wait for its response
+    ///
+    /// # Errors
+    ///
+    /// `DaemonError::NotRunning` if nothing listens on the socket
+    pub fn request(&self, request: &DaemonRequest) -> Result<DaemonResponse, DaemonError> {
+        let mut stream = UnixStream::connect(&self.socket_path)
+            .map_err(|_| DaemonError::NotRunning(self.socket_path.display().to_string()))?;
+
+        writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+
+        match serde_json::from_str(&line)? {
+            DaemonResponse::Error { message } => Err(DaemonError::Remote(message)),
+            response => Ok(response),
+        }
+    }
+
+    /// Forward a CLI invocation to the daemon
+    ///
+    /// # Returns
+    ///
+    /// The command's output, or `None` if no daemon is running or it does
+    /// not hold the command's wallet (the command should then run locally)
+    pub fn forward(&self, args: Vec<String>) -> Option<ForwardedOutput> {
+        if !self.socket_path.exists() {
+            return None;
+        }
+
+        let cwd = std::env::current_dir().ok()?;
+        match self.request(&DaemonRequest::Run { args, cwd }) {
+            Ok(DaemonResponse::Output {
+                stdout,
+                stderr,
+                error,
+            }) => Some(ForwardedOutput {
+                stdout,
+                stderr,
+                error,
+            }),
+            Ok(_) => None,
+            Err(e) => {
+                log::debug!("Not forwarding to daemon: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Output of a command run by the daemon
+#[derive(Debug, Clone)]
+pub struct ForwardedOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Error message if the command failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,67 @@
+//! Wallet daemon
+//!
+//! Keeps an unlocked `WalletManager` in a long-running process behind a
+//! local Unix socket, so scripted flows pay the key derivation, contract
+//! state load and F1r3node connection cost once per unlock instead of once
+//! per CLI call. The CLI forwards commands for the unlocked wallet to the
+//! daemon and prints the output it sends back.
+
+pub mod session;
+
+#[cfg(unix)]
+pub mod client;
+#[cfg(unix)]
+pub mod protocol;
+#[cfg(unix)]
+pub mod server;
+
+use std::path::PathBuf;
+
+use crate::config::ConfigError;
+
+/// Environment variable overriding the daemon socket path
+pub const DAEMON_SOCKET_ENV: &str = "FIREFLY_DAEMON_SOCKET";
+
+/// Default time a wallet stays unlocked in the daemon (15 minutes)
+pub const DEFAULT_UNLOCK_TIMEOUT_SECS: u64 = 900;
+
+/// Daemon errors
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] serde_json::Error),
+
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Manager error: {0}")]
+    Manager(#[from] crate::manager::ManagerError),
+
+    #[error("Daemon already running at {0}")]
+    AlreadyRunning(String),
+
+    #[error("Daemon not running at {0}")]
+    NotRunning(String),
+
+    #[error("Daemon error: {0}")]
+    Remote(String),
+
+    #[error("Unexpected daemon response: {0}")]
+    UnexpectedResponse(String),
+}
+
+/// Get the daemon socket path (custom, `FIREFLY_DAEMON_SOCKET`, or default)
+///
+/// Default: `~/.f1r3fly-rgb-wallet/daemon.sock`
+pub fn socket_path(custom: Option<&str>) -> Result<PathBuf, DaemonError> {
+    if let Some(path) = custom {
+        return Ok(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var(DAEMON_SOCKET_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(crate::config::default_config_dir()?.join("daemon.sock"))
+}
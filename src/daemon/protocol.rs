@@ -0,0 +1,73 @@
+//! Daemon wire protocol
+//!
+//! One JSON request per connection, answered by one JSON response, each
+//! terminated by a newline.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Request sent to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// Report the unlocked wallet, if any
+    Status,
+
+    /// Load and decrypt a wallet, keeping it unlocked for `timeout_secs`
+    Unlock {
+        wallet: String,
+        password: String,
+        timeout_secs: u64,
+    },
+
+    /// Drop the unlocked wallet
+    Lock,
+
+    /// Shut the daemon down
+    Stop,
+
+    /// Run a CLI invocation against the unlocked wallet
+    Run {
+        /// Full argument list, including the program name
+        args: Vec<String>,
+        /// Client working directory (relative paths resolve against it)
+        cwd: PathBuf,
+    },
+}
+
+/// Response sent by the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    /// Session state
+    Status {
+        wallet: Option<String>,
+        expires_in_secs: Option<u64>,
+    },
+
+    /// Wallet unlocked
+    Unlocked {
+        wallet: String,
+        expires_in_secs: u64,
+    },
+
+    /// Session dropped
+    Locked,
+
+    /// Daemon is shutting down
+    Stopping,
+
+    /// Output of a forwarded command
+    Output {
+        stdout: String,
+        stderr: String,
+        /// Error message if the command failed
+        error: Option<String>,
+    },
+
+    /// The daemon does not hold the command's wallet; run it locally
+    NotUnlocked,
+
+    /// Request failed
+    Error { message: String },
+}
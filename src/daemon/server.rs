@@ -0,0 +1,296 @@
+//! Daemon server
+//!
+//! Serves one connection at a time, so a forwarded command has exclusive
+//! use of the unlocked wallet. The session expires `timeout_secs` after
+//! unlocking, dropping the decrypted keys.
+
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+
+use super::protocol::{DaemonRequest, DaemonResponse};
+use super::{session, DaemonError};
+use crate::cli::args::Cli;
+use crate::cli::output;
+use crate::config::GlobalConfig;
+use crate::manager::WalletManager;
+
+/// Runs a parsed CLI invocation (the binary's command dispatcher)
+pub type CommandRunner = fn(Cli) -> Result<(), Box<dyn std::error::Error>>;
+
+/// How often the accept loop checks for session expiry
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a client may take to send its request line
+///
+/// Connections are served one at a time, so a silent client would otherwise
+/// block the daemon (and session expiry) indefinitely.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Unlocked wallet held by the daemon
+struct Session {
+    wallet: String,
+    manager: WalletManager,
+    expires_at: Instant,
+}
+
+/// Daemon listening on a Unix socket
+pub struct DaemonServer {
+    listener: UnixListener,
+    socket_path: PathBuf,
+    config: GlobalConfig,
+    runner: CommandRunner,
+    session: Option<Session>,
+}
+
+impl DaemonServer {
+    /// Bind the daemon socket
+    ///
+    /// A stale socket left by a crashed daemon is replaced; a live one is an
+    /// error. The socket is only accessible to the current user.
+    ///
+    /// # Arguments
+    ///
+    /// * `socket_path` - Socket to listen on
+    /// * `config` - Configuration used to unlock wallets
+    /// * `runner` - Dispatcher for forwarded CLI invocations
+    pub fn bind(
+        socket_path: &Path,
+        config: GlobalConfig,
+        runner: CommandRunner,
+    ) -> Result<Self, DaemonError> {
+        let listener = bind_private_socket(socket_path).map_err(|e| match e.kind() {
+            ErrorKind::AddrInUse => DaemonError::AlreadyRunning(socket_path.display().to_string()),
+            _ => e.into(),
+        })?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            socket_path: socket_path.to_path_buf(),
+            config,
+            runner,
+            session: None,
+        })
+    }
+
+    /// Serve requests until a `Stop` request arrives
+    pub fn run(mut self) -> Result<(), DaemonError> {
+        loop {
+            self.expire_session();
+
+            match self.listener.accept() {
+                Ok((stream, _)) => match self.serve_connection(stream) {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(e) => log::warn!("Daemon connection failed: {}", e),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Answer one request
+    ///
+    /// # Returns
+    ///
+    /// `true` if the daemon should stop
+    fn serve_connection(&mut self, mut stream: UnixStream) -> Result<bool, DaemonError> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+
+        let (response, stop) = match serde_json::from_str::<DaemonRequest>(&line) {
+            Ok(DaemonRequest::Stop) => (DaemonResponse::Stopping, true),
+            Ok(request) => (self.handle(request), false),
+            Err(e) => (
+                DaemonResponse::Error {
+                    message: format!("Invalid request: {}", e),
+                },
+                false,
+            ),
+        };
+
+        writeln!(stream, "{}", serde_json::to_string(&response)?)?;
+        Ok(stop)
+    }
+
+    fn handle(&mut self, request: DaemonRequest) -> DaemonResponse {
+        match request {
+            DaemonRequest::Status => DaemonResponse::Status {
+                wallet: self.session.as_ref().map(|s| s.wallet.clone()),
+                expires_in_secs: self.session.as_ref().map(|s| remaining_secs(s.expires_at)),
+            },
+
+            DaemonRequest::Unlock {
+                wallet,
+                password,
+                timeout_secs,
+            } => match self.unlock(&wallet, &password, timeout_secs) {
+                Ok(()) => DaemonResponse::Unlocked {
+                    wallet,
+                    expires_in_secs: timeout_secs,
+                },
+                Err(e) => DaemonResponse::Error {
+                    message: e.to_string(),
+                },
+            },
+
+            DaemonRequest::Lock => {
+                self.session = None;
+                DaemonResponse::Locked
+            }
+
+            DaemonRequest::Stop => DaemonResponse::Stopping,
+
+            DaemonRequest::Run { args, cwd } => self.run_command(args, &cwd),
+        }
+    }
+
+    /// Load and decrypt a wallet, replacing any unlocked one
+    fn unlock(
+        &mut self,
+        wallet: &str,
+        password: &str,
+        timeout_secs: u64,
+    ) -> Result<(), DaemonError> {
+        self.session = None;
+
+        // Node clients created while loading bind to the shared runtime
+        let _guard = crate::cli::runtime()?.enter();
+        let mut manager = WalletManager::new(self.config.clone())?;
+        manager.load_wallet(wallet, password)?;
+
+        self.session = Some(Session {
+            wallet: wallet.to_string(),
+            manager,
+            expires_at: Instant::now() + Duration::from_secs(timeout_secs),
+        });
+        log::info!("Wallet '{}' unlocked for {}s", wallet, timeout_secs);
+
+        Ok(())
+    }
+
+    /// Run a forwarded CLI invocation against the unlocked wallet
+    fn run_command(&mut self, args: Vec<String>, cwd: &Path) -> DaemonResponse {
+        let cli = match Cli::try_parse_from(&args) {
+            Ok(cli) => cli,
+            Err(e) => {
+                return DaemonResponse::Error {
+                    message: e.to_string(),
+                }
+            }
+        };
+
+        let Session {
+            wallet,
+            manager,
+            expires_at,
+        } = match self.session.take() {
+            Some(session) if cli.wallet.as_deref() == Some(session.wallet.as_str()) => session,
+            other => {
+                self.session = other;
+                return DaemonResponse::NotUnlocked;
+            }
+        };
+
+        // Resolve the client's relative paths while the command runs
+        let daemon_cwd = std::env::current_dir();
+        if let Err(e) = std::env::set_current_dir(cwd) {
+            self.session = Some(Session {
+                wallet,
+                manager,
+                expires_at,
+            });
+            return DaemonResponse::Error {
+                message: format!("Cannot enter {}: {}", cwd.display(), e),
+            };
+        }
+
+        let runner = self.runner;
+        session::lend(&wallet, manager);
+        let (result, captured) = output::capture(|| runner(cli));
+        let manager = session::reclaim();
+
+        if let Ok(dir) = daemon_cwd {
+            let _ = std::env::set_current_dir(dir);
+        }
+
+        match manager {
+            Some(manager) => {
+                self.session = Some(Session {
+                    wallet,
+                    manager,
+                    expires_at,
+                })
+            }
+            None => log::warn!("Wallet '{}' was not released; session dropped", wallet),
+        }
+
+        DaemonResponse::Output {
+            stdout: captured.stdout,
+            stderr: captured.stderr,
+            error: result.err().map(|e| e.to_string()),
+        }
+    }
+
+    /// Drop the session once its timeout has passed
+    fn expire_session(&mut self) {
+        if let Some(session) = &self.session {
+            if Instant::now() >= session.expires_at {
+                log::info!("Wallet '{}' session expired", session.wallet);
+                self.session = None;
+            }
+        }
+    }
+}
+
+impl Drop for DaemonServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+fn remaining_secs(expires_at: Instant) -> u64 {
+    expires_at
+        .saturating_duration_since(Instant::now())
+        .as_secs()
+}
+
+/// Bind a Unix socket only the current user can connect to
+///
+/// A stale socket file (nothing accepting on it) is replaced. Shared by the
+/// daemon and the RPC server.
+///
+/// # Errors
+///
+/// `ErrorKind::AddrInUse` if a live server already listens on `socket_path`
+pub(crate) fn bind_private_socket(socket_path: &Path) -> std::io::Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(std::io::Error::new(
+                ErrorKind::AddrInUse,
+                format!("{} is in use", socket_path.display()),
+            ));
+        }
+        fs::remove_file(socket_path)?;
+    }
+    if let Some(parent) = socket_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)?;
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600))?;
+
+    Ok(listener)
+}
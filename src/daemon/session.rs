@@ -0,0 +1,127 @@
+//! Unlocked wallet lent to a forwarded command
+//!
+//! While the daemon runs a forwarded command it lends its unlocked
+//! `WalletManager` to the current thread. Commands open their wallet with
+//! `open_wallet`, which takes the lent manager instead of decrypting the
+//! keys again, and hand it back when the returned handle is dropped.
+
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+
+use crate::config::GlobalConfig;
+use crate::manager::{ManagerError, WalletManager};
+
+thread_local! {
+    static LENT: RefCell<Option<LentWallet>> = const { RefCell::new(None) };
+}
+
+struct LentWallet {
+    wallet: String,
+    /// `None` while a command holds the manager
+    manager: Option<WalletManager>,
+}
+
+/// Lend an unlocked wallet to commands run on this thread
+pub fn lend(wallet: &str, manager: WalletManager) {
+    LENT.with(|lent| {
+        *lent.borrow_mut() = Some(LentWallet {
+            wallet: wallet.to_string(),
+            manager: Some(manager),
+        })
+    });
+}
+
+/// Take back the lent wallet
+///
+/// # Returns
+///
+/// The manager, or `None` if nothing was lent or a command never released it
+pub fn reclaim() -> Option<WalletManager> {
+    LENT.with(|lent| lent.borrow_mut().take().and_then(|lent| lent.manager))
+}
+
+/// Whether `wallet_name` is lent to this thread (commands then need no password)
+pub fn is_lent(wallet_name: &str) -> bool {
+    LENT.with(|lent| {
+        lent.borrow()
+            .as_ref()
+            .is_some_and(|lent| lent.wallet == wallet_name)
+    })
+}
+
+/// A loaded wallet, either owned or borrowed from the daemon session
+pub struct WalletHandle {
+    manager: Option<WalletManager>,
+    lent: bool,
+}
+
+impl Deref for WalletHandle {
+    type Target = WalletManager;
+
+    fn deref(&self) -> &WalletManager {
+        self.manager
+            .as_ref()
+            .expect("wallet handle already released")
+    }
+}
+
+impl DerefMut for WalletHandle {
+    fn deref_mut(&mut self) -> &mut WalletManager {
+        self.manager
+            .as_mut()
+            .expect("wallet handle already released")
+    }
+}
+
+impl Drop for WalletHandle {
+    fn drop(&mut self) {
+        if !self.lent {
+            return;
+        }
+        if let Some(manager) = self.manager.take() {
+            LENT.with(|lent| {
+                if let Some(lent) = lent.borrow_mut().as_mut() {
+                    lent.manager = Some(manager);
+                }
+            });
+        }
+    }
+}
+
+/// Open a wallet for a command
+///
+/// Uses the daemon's unlocked manager when it holds `wallet_name` (the
+/// daemon's configuration then applies and `password` is ignored);
+/// otherwise creates a manager from `config` and loads the wallet.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut manager = open_wallet(config, "my_wallet", &password)?;
+/// let balance = manager.get_balance()?;
+/// ```
+pub fn open_wallet(
+    config: GlobalConfig,
+    wallet_name: &str,
+    password: &str,
+) -> Result<WalletHandle, ManagerError> {
+    let lent = LENT.with(|lent| {
+        lent.borrow_mut()
+            .as_mut()
+            .filter(|lent| lent.wallet == wallet_name)
+            .and_then(|lent| lent.manager.take())
+    });
+    if let Some(manager) = lent {
+        return Ok(WalletHandle {
+            manager: Some(manager),
+            lent: true,
+        });
+    }
+
+    let mut manager = WalletManager::new(config)?;
+    manager.load_wallet(wallet_name, password)?;
+    Ok(WalletHandle {
+        manager: Some(manager),
+        lent: false,
+    })
+}
@@ -288,12 +288,11 @@ pub async fn issue_asset(
         .get_child_key_at_index(deployment_index)
         .map_err(|e| AssetError::DeploymentFailed(format!("Failed to get signing key: {}", e)))?;
 
-    // Get the F1r3fly public key used for this contract
-    // This is the deployer's public key, also used as the owner's public key for genesis UTXO
+    // Get the owner public key for the genesis UTXO
+    // Shared key mode: the deployer's public key itself
+    // Wallet key mode: the wallet chain key at the same index (the deployer only pays phlo)
     let f1r3fly_pubkey = contracts_manager
-        .contracts()
-        .executor()
-        .get_public_key_at_index(deployment_index)
+        .owner_public_key_at_index(deployment_index)
         .map_err(|e| AssetError::DeploymentFailed(format!("Failed to get public key: {}", e)))?;
 
     let f1r3fly_pubkey_hex = hex::encode(f1r3fly_pubkey.serialize_uncompressed());
@@ -0,0 +1,105 @@
+//! RGB Coin Selection
+//!
+//! Selects which RGB seals (UTXOs holding tokens) fund a transfer.
+//! Operates purely on per-seal balances as computed by
+//! `balance::get_asset_balance`, so it has no F1r3node or Bitcoin dependencies.
+
+use serde::{Deserialize, Serialize};
+
+use crate::f1r3fly::UtxoBalance;
+
+/// Result of RGB coin selection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RgbCoinSelection {
+    /// Selected input seals (in spending order)
+    pub inputs: Vec<UtxoBalance>,
+
+    /// Sum of token amounts held by the selected inputs
+    pub total: u64,
+
+    /// Amount requested by the transfer
+    pub amount: u64,
+}
+
+impl RgbCoinSelection {
+    /// Tokens left over after paying `amount`
+    pub fn change(&self) -> u64 {
+        self.total - self.amount
+    }
+
+    /// Whether more than one input seal is spent
+    ///
+    /// Multi-input transfers consolidate their remainder onto a fresh
+    /// change seal; single-input transfers leave it on the input seal.
+    pub fn is_multi_input(&self) -> bool {
+        self.inputs.len() > 1
+    }
+}
+
+/// Select input seals covering `amount`
+///
+/// Strategy:
+/// 1. If any single seal covers the amount, pick the smallest such seal
+///    (keeps larger seals intact and avoids extra contract calls).
+/// 2. Otherwise accumulate seals largest-first until the amount is covered
+///    (minimizes the number of inputs).
+///
+/// Zero-balance seals are never selected.
+///
+/// # Arguments
+///
+/// * `utxo_balances` - Per-seal balances for one contract
+/// * `amount` - Amount to transfer
+///
+/// # Returns
+///
+/// `Some(RgbCoinSelection)` if the seals cover the amount, `None` otherwise
+///
+/// # Example
+///
+/// ```ignore
+/// let balance = get_asset_balance(&mut contracts_manager, &wallet, contract_id).await?;
+/// let selection = select_rgb_inputs(&balance.utxo_balances, 2_500)
+///     .ok_or(TransferError::InsufficientBalance { need: 2_500, have: balance.total })?;
+/// ```
+pub fn select_rgb_inputs(utxo_balances: &[UtxoBalance], amount: u64) -> Option<RgbCoinSelection> {
+    if amount == 0 {
+        return None;
+    }
+
+    // Fast path: smallest single seal that covers the amount
+    if let Some(single) = utxo_balances
+        .iter()
+        .filter(|utxo| utxo.amount >= amount)
+        .min_by_key(|utxo| utxo.amount)
+    {
+        return Some(RgbCoinSelection {
+            inputs: vec![single.clone()],
+            total: single.amount,
+            amount,
+        });
+    }
+
+    // Multi-input: largest-first accumulation
+    let mut candidates: Vec<&UtxoBalance> = utxo_balances
+        .iter()
+        .filter(|utxo| utxo.amount > 0)
+        .collect();
+    candidates.sort_by(|a, b| b.amount.cmp(&a.amount));
+
+    let mut inputs = Vec::new();
+    let mut total = 0u64;
+    for utxo in candidates {
+        inputs.push(utxo.clone());
+        total += utxo.amount;
+        if total >= amount {
+            return Some(RgbCoinSelection {
+                inputs,
+                total,
+                amount,
+            });
+        }
+    }
+
+    None
+}
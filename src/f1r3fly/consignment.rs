@@ -7,12 +7,15 @@ use std::path::{Path, PathBuf};
 use crate::bitcoin::network::{EsploraClient, NetworkError};
 use crate::bitcoin::BitcoinWallet;
 use crate::f1r3fly::{AssetError, F1r3flyContractsManager};
-use crate::storage::{ClaimStatus, PendingClaim, StorageError};
+use crate::storage::{
+    ClaimStatus, PendingClaim, StorageError, TransferDirection, TransferRecord, TransferStatus,
+};
 
 use amplify::confinement::{Confined, SmallOrdMap};
 use bp::seals::{Noise, TxoSeal, TxoSealExt, WOutpoint, WTxoSeal};
 use bp::{Outpoint, Txid};
 use hypersonic::ContractId;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::str::FromStr;
 use strict_types::{StrictDumb, StrictVal};
@@ -117,7 +120,7 @@ pub struct ExportGenesisResponse {
 }
 
 /// Response from accepting consignment
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcceptConsignmentResponse {
     /// Contract ID
     pub contract_id: String,
@@ -459,65 +462,84 @@ pub async fn accept_consignment(
             // Convert bp::Txid to String for storage
             let actual_txid_str = actual_txid.to_string();
 
-            let claim = PendingClaim {
-                id: None,
-                witness_id: mapping.witness_id.clone(),
-                recipient_address: mapping.recipient_address.clone(),
-                expected_vout: mapping.expected_vout,
-                contract_id: contract_id_str.clone(),
-                consignment_file: consignment_path.to_path_buf(),
-                status: ClaimStatus::Pending,
-                error: None,
-                created_at: std::time::SystemTime::now()
+            // A consignment for a known witness id was re-issued by the sender
+            // after replacing the witness transaction (RBF)
+            let existing_claim = contracts_manager
+                .claim_storage()
+                .get_all_claims(&contract_id_str)?
+                .into_iter()
+                .find(|claim| claim.witness_id == mapping.witness_id);
+
+            if let Some(existing) = existing_claim {
+                if existing.actual_txid.as_deref() == Some(actual_txid_str.as_str()) {
+                    return Err(ConsignmentError::Invalid(format!(
+                        "Consignment already accepted (witness {})",
+                        mapping.witness_id
+                    )));
+                }
+
+                accept_replaced_witness(
+                    contracts_manager,
+                    contract_id,
+                    &existing,
+                    &actual_txid_str,
+                    consignment_path,
+                )
+                .await?;
+            } else {
+                let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
-                    .as_secs(),
-                claimed_at: None,
-                actual_txid: Some(actual_txid_str),
-                actual_vout: Some(actual_vout),
-            };
-
-            let claim_id = contracts_manager
-                .claim_storage_mut()
-                .insert_pending_claim(&claim)?;
-
-            log::info!(
-                "✓ Actual UTXO extracted and stored ({}:{})",
-                actual_txid,
-                actual_vout
-            );
-
-            // Attempt to claim immediately (with actual UTXO from consignment)
-            log::info!("🔄 Attempting to claim witness balance...");
-
-            match attempt_claim(contracts_manager, bitcoin_wallet, contract_id, &claim).await {
-                Ok(claim_result) => {
-                    log::info!(
-                        "✅ Claim successful! Migrated {} tokens from {} to {}",
-                        claim_result.migrated_balance,
-                        claim_result.from,
-                        claim_result.to
-                    );
-
-                    // Mark as claimed in database
-                    contracts_manager
-                        .claim_storage_mut()
-                        .mark_claim_completed(claim_id)?;
-                }
-                Err(ClaimError::UtxoNotFound) => {
-                    log::warn!("⏳ UTXO not found yet, will retry on next sync");
-                    // Keep as Pending in database
-                }
-                Err(e) => {
-                    log::error!("❌ Claim failed: {}", e);
-                    // Update database with error
-                    contracts_manager.claim_storage_mut().update_claim_status(
-                        claim_id,
-                        ClaimStatus::Failed,
-                        Some(e.to_string()),
-                    )?;
-                }
+                    .as_secs();
+
+                let claim = PendingClaim {
+                    id: None,
+                    witness_id: mapping.witness_id.clone(),
+                    recipient_address: mapping.recipient_address.clone(),
+                    expected_vout: mapping.expected_vout,
+                    contract_id: contract_id_str.clone(),
+                    consignment_file: consignment_path.to_path_buf(),
+                    status: ClaimStatus::Pending,
+                    error: None,
+                    created_at: now,
+                    claimed_at: None,
+                    actual_txid: Some(actual_txid_str.clone()),
+                    actual_vout: Some(actual_vout),
+                };
+
+                let claim_id = record_incoming_claim(
+                    contracts_manager,
+                    &claim,
+                    actual_txid_str,
+                    consignment_path,
+                    now,
+                )?;
+
+                log::info!(
+                    "✓ Actual UTXO extracted and stored ({}:{})",
+                    actual_txid,
+                    actual_vout
+                );
+
+                claim_incoming(
+                    contracts_manager,
+                    bitcoin_wallet,
+                    contract_id,
+                    claim_id,
+                    &claim,
+                )
+                .await?;
             }
+        } else {
+            // No witness output: the payment may be to one of our blinded seals
+            accept_blinded_payments(
+                contracts_manager,
+                bitcoin_wallet,
+                contract_id,
+                &consignment,
+                consignment_path,
+            )
+            .await?;
         }
 
         let genesis_info = contracts_manager
@@ -694,6 +716,273 @@ pub async fn accept_consignment(
     })
 }
 
+/// Claim tokens a transfer paid to this wallet's blinded seals
+///
+/// The sender never learns a blinded seal's UTXO, so its consignment has no
+/// witness mapping. Instead, every open blinded seal of the contract is
+/// looked up on F1r3node, and those holding tokens are claimed onto their
+/// UTXO (which becomes RGB-occupied once claimed).
+///
+/// # Returns
+///
+/// Number of blinded seals claimed
+///
+/// # Errors
+///
+/// Returns error if a seal's balance cannot be queried on F1r3node, so a
+/// failed lookup is never mistaken for an unpaid seal
+pub(crate) async fn accept_blinded_payments(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    contract_id: ContractId,
+    consignment: &f1r3fly_rgb::F1r3flyConsignment,
+    consignment_path: &Path,
+) -> Result<usize, ConsignmentError> {
+    let contract_id_str = contract_id.to_string();
+    let open_seals = contracts_manager
+        .claim_storage()
+        .get_open_blinded_seals(Some(&contract_id_str))?;
+    if open_seals.is_empty() {
+        return Ok(0);
+    }
+
+    let witness_txid = consignment
+        .witness_txs
+        .first()
+        .ok_or_else(|| {
+            ConsignmentError::Invalid(
+                "Transfer consignment missing witness transaction".to_string(),
+            )
+        })?
+        .txid()
+        .to_string();
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let mut claimed = 0;
+    for seal in open_seals {
+        let seal_id = crate::f1r3fly::blinded_seal_id(&seal.auth_token);
+        let balance =
+            crate::f1r3fly::restore::query_balance(contracts_manager, contract_id, &seal_id)
+                .await?;
+        if balance == 0 {
+            continue;
+        }
+
+        log::info!(
+            "📝 Blinded seal {} received {} tokens (UTXO {}:{})",
+            seal.auth_token,
+            balance,
+            seal.txid,
+            seal.vout
+        );
+
+        let claim = PendingClaim {
+            id: None,
+            witness_id: seal_id,
+            recipient_address: seal.auth_token.clone(),
+            expected_vout: seal.vout,
+            contract_id: contract_id_str.clone(),
+            consignment_file: consignment_path.to_path_buf(),
+            status: ClaimStatus::Pending,
+            error: None,
+            created_at: now,
+            claimed_at: None,
+            actual_txid: Some(seal.txid.clone()),
+            actual_vout: Some(seal.vout),
+        };
+
+        let claim_id = record_incoming_claim(
+            contracts_manager,
+            &claim,
+            witness_txid.clone(),
+            consignment_path,
+            now,
+        )?;
+        if let Some(seal_db_id) = seal.id {
+            contracts_manager
+                .claim_storage_mut()
+                .set_blinded_seal_claim(seal_db_id, claim_id)?;
+        }
+
+        claim_incoming(
+            contracts_manager,
+            bitcoin_wallet,
+            contract_id,
+            claim_id,
+            &claim,
+        )
+        .await?;
+        claimed += 1;
+    }
+
+    if claimed == 0 {
+        log::info!("No blinded seal of this wallet was paid by this transfer");
+    }
+
+    Ok(claimed)
+}
+
+/// Store an incoming claim, record it in the transfer history and match it
+/// to the invoice it pays
+///
+/// Invoices are matched by the claim's recipient address: the invoice
+/// address, or the auth token of a blinded seal.
+///
+/// # Returns
+///
+/// Database ID of the stored claim
+fn record_incoming_claim(
+    contracts_manager: &mut F1r3flyContractsManager,
+    claim: &PendingClaim,
+    witness_txid: String,
+    consignment_path: &Path,
+    now: u64,
+) -> Result<i64, ConsignmentError> {
+    let claim_id = contracts_manager
+        .claim_storage_mut()
+        .insert_pending_claim(claim)?;
+
+    // Record in transfer history (status follows the linked claim)
+    let record = TransferRecord {
+        id: None,
+        direction: TransferDirection::Incoming,
+        contract_id: claim.contract_id.clone(),
+        amount: None,
+        counterparty_pubkey: None,
+        witness_txid,
+        anchor_method: None,
+        consignment_path: consignment_path.to_path_buf(),
+        status: TransferStatus::Pending,
+        claim_id: Some(claim_id),
+        created_at: now,
+        updated_at: now,
+    };
+    if let Err(e) = contracts_manager
+        .claim_storage_mut()
+        .insert_transfer_record(&record)
+    {
+        log::warn!("⚠️  Failed to record transfer history: {}", e);
+    }
+
+    // Match the payment to the invoice we issued for this address;
+    // the invoice is credited once the claim completes
+    let storage = contracts_manager.claim_storage_mut();
+    if let Err(e) = storage.expire_invoices(now) {
+        log::warn!("⚠️  Failed to expire invoices: {}", e);
+    }
+    match storage.match_invoice_payment(&claim.contract_id, &claim.recipient_address, claim_id) {
+        Ok(Some(invoice_id)) => {
+            log::info!("🧾 Transfer matched to invoice {}", invoice_id)
+        }
+        Ok(None) => log::info!("No issued invoice matches this transfer"),
+        Err(e) => log::warn!("⚠️  Failed to match invoice: {}", e),
+    }
+
+    Ok(claim_id)
+}
+
+/// Try to claim a stored incoming claim right away
+///
+/// A claim whose UTXO is not found yet stays pending for the next sync;
+/// other failures are recorded on the claim.
+async fn claim_incoming(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    contract_id: ContractId,
+    claim_id: i64,
+    claim: &PendingClaim,
+) -> Result<(), ConsignmentError> {
+    // Attempt to claim immediately (with actual UTXO from consignment)
+    log::info!("🔄 Attempting to claim witness balance...");
+
+    match attempt_claim(contracts_manager, bitcoin_wallet, contract_id, claim).await {
+        Ok(claim_result) => {
+            log::info!(
+                "✅ Claim successful! Migrated {} tokens from {} to {}",
+                claim_result.migrated_balance,
+                claim_result.from,
+                claim_result.to
+            );
+
+            // Mark as claimed in database
+            contracts_manager
+                .claim_storage_mut()
+                .mark_claim_completed(claim_id)?;
+            contracts_manager
+                .claim_storage_mut()
+                .set_transfer_amount_for_claim(claim_id, claim_result.migrated_balance)?;
+        }
+        Err(ClaimError::UtxoNotFound) => {
+            log::warn!("⏳ UTXO not found yet, will retry on next sync");
+            // Keep as Pending in database
+        }
+        Err(e) => {
+            log::error!("❌ Claim failed: {}", e);
+            // Update database with error
+            contracts_manager.claim_storage_mut().update_claim_status(
+                claim_id,
+                ClaimStatus::Failed,
+                Some(e.to_string()),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Accept a consignment re-issued for a replaced witness transaction
+///
+/// Moves the existing claim (and its transfer history record) to the new
+/// witness txid. Claimed tokens are rebound to the new seal; pending claims
+/// are completed by the next sync.
+async fn accept_replaced_witness(
+    contracts_manager: &mut F1r3flyContractsManager,
+    contract_id: ContractId,
+    claim: &PendingClaim,
+    new_txid: &str,
+    consignment_path: &Path,
+) -> Result<(), ConsignmentError> {
+    let claim_id = claim
+        .id
+        .ok_or_else(|| ConsignmentError::Invalid("Stored claim has no ID".to_string()))?;
+
+    log::info!(
+        "🔁 Witness transaction replaced: {} -> {}",
+        claim.actual_txid.as_deref().unwrap_or("unknown"),
+        new_txid
+    );
+
+    // RBF keeps the seal's vout
+    let vout = claim.actual_vout.unwrap_or(claim.expected_vout);
+
+    rebind_claim(contracts_manager, contract_id, claim, new_txid, vout)
+        .await
+        .map_err(|e| ConsignmentError::Invalid(format!("Failed to rebind claim: {}", e)))?;
+
+    contracts_manager
+        .claim_storage_mut()
+        .update_claim_actual_utxo(claim_id, new_txid, vout)?;
+
+    let record = contracts_manager
+        .claim_storage()
+        .get_transfers(Some(&claim.contract_id), None, None)?
+        .into_iter()
+        .find(|record| record.claim_id == Some(claim_id));
+    if let Some(record_id) = record.and_then(|record| record.id) {
+        contracts_manager
+            .claim_storage_mut()
+            .update_transfer_witness(record_id, new_txid, consignment_path)?;
+    }
+
+    log::info!("✓ Claim moved to replacement witness transaction");
+
+    Ok(())
+}
+
 /// Attempt to claim witness balance to real UTXO
 ///
 /// Uses the actual UTXO from consignment (RGB Protocol approach) instead of wallet discovery.
@@ -773,9 +1062,7 @@ pub async fn attempt_claim(
     // Step 3: Generate claim signature
     // Sign message: (witness_id, real_utxo)
     let signing_key = contracts_manager
-        .contracts()
-        .executor()
-        .get_child_key()
+        .identity_key()
         .map_err(|e| ClaimError::SignatureFailed(e.to_string()))?;
 
     let signature =
@@ -871,3 +1158,117 @@ pub async fn attempt_claim(
         to: real_utxo,
     })
 }
+
+/// Move a claim onto a new seal outpoint
+///
+/// When a witness transaction is replaced (RBF), the seal keeps its vout but
+/// the txid changes; when a CPFP child spends the seal output, the seal moves
+/// to the child's output. A pending claim only needs its stored outpoint
+/// updated; a completed claim has already migrated its tokens to the old
+/// `txid:vout`, so they are moved to the new seal with an owner-signed
+/// `transfer`.
+///
+/// Does not touch claim storage - the caller updates `actual_txid`/`actual_vout`.
+///
+/// # Arguments
+///
+/// * `contracts_manager` - Contracts manager with the contract
+/// * `contract_id` - Contract ID
+/// * `claim` - Claim bound to the old seal
+/// * `new_txid` - TXID of the transaction now holding the seal
+/// * `new_vout` - Output index of the seal in that transaction
+///
+/// # Returns
+///
+/// Amount of tokens moved (0 for pending claims)
+pub async fn rebind_claim(
+    contracts_manager: &mut F1r3flyContractsManager,
+    contract_id: ContractId,
+    claim: &crate::storage::PendingClaim,
+    new_txid: &str,
+    new_vout: u32,
+) -> Result<u64, ClaimError> {
+    let (old_txid, old_vout) = match (&claim.actual_txid, claim.actual_vout) {
+        (Some(txid), Some(vout)) => (txid, vout),
+        _ => return Ok(0),
+    };
+
+    if claim.status != ClaimStatus::Claimed || (old_txid == new_txid && old_vout == new_vout) {
+        return Ok(0);
+    }
+
+    let old_seal = format!("{}:{}", old_txid, old_vout);
+    let new_seal = format!("{}:{}", new_txid, new_vout);
+
+    log::info!("🔁 Rebinding claimed seal {} -> {}", old_seal, new_seal);
+
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&contract_id)
+        .ok_or_else(|| ClaimError::ContractCallFailed("Contract not found".to_string()))?;
+
+    let balance = contract
+        .executor()
+        .query_state(
+            contract_id,
+            "balanceOf",
+            &[("address", StrictVal::from(old_seal.as_str()))],
+        )
+        .await
+        .ok()
+        .and_then(|v| v.as_u64().or_else(|| v.as_i64().map(|i| i as u64)))
+        .unwrap_or(0);
+
+    if balance == 0 {
+        log::warn!("⚠️  Nothing to rebind: {} holds no tokens", old_seal);
+        return Ok(0);
+    }
+
+    // The claimed seal is owned by this wallet's F1r3fly key
+    let signing_key = contracts_manager
+        .identity_key()
+        .map_err(|e| ClaimError::SignatureFailed(e.to_string()))?;
+    let owner_pubkey = contracts_manager
+        .identity_public_key()
+        .map_err(|e| ClaimError::SignatureFailed(e.to_string()))?;
+    let owner_pubkey_hex = hex::encode(owner_pubkey.serialize_uncompressed());
+
+    let nonce = f1r3fly_rgb::generate_nonce();
+    let signature = f1r3fly_rgb::generate_transfer_signature(
+        &old_seal,
+        &new_seal,
+        balance,
+        nonce,
+        &signing_key,
+    )
+    .map_err(|e| ClaimError::SignatureFailed(e.to_string()))?;
+
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&contract_id)
+        .ok_or_else(|| ClaimError::ContractCallFailed("Contract not found".to_string()))?;
+
+    let empty_seals: BTreeMap<u16, f1r3fly_rgb::WTxoSeal> = BTreeMap::new();
+    let seals_map = Confined::try_from(empty_seals)
+        .map_err(|e| ClaimError::ContractCallFailed(format!("Seals map error: {}", e)))?;
+
+    contract
+        .call_method(
+            "transfer",
+            &[
+                ("from", StrictVal::from(old_seal.as_str())),
+                ("to", StrictVal::from(new_seal.as_str())),
+                ("amount", StrictVal::from(balance)),
+                ("toPubKey", StrictVal::from(owner_pubkey_hex.as_str())),
+                ("nonce", StrictVal::from(nonce)),
+                ("fromSignatureHex", StrictVal::from(signature.as_str())),
+            ],
+            seals_map,
+        )
+        .await
+        .map_err(|e| ClaimError::ContractCallFailed(e.to_string()))?;
+
+    log::info!("✓ Rebound {} tokens to {}", balance, new_seal);
+
+    Ok(balance)
+}
@@ -9,9 +9,11 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 // Re-exports from f1r3fly-rgb
-use f1r3fly_rgb::{BitcoinAnchorTracker, ContractMetadata, F1r3flyRgbContracts, TxoSeal};
+use f1r3fly_rgb::{
+    BitcoinAnchorTracker, ContractMetadata, F1r3flyRgbContracts, F1r3flyRgbError, TxoSeal,
+};
 
-use crate::f1r3fly::executor::F1r3flyExecutorManager;
+use crate::f1r3fly::executor::{F1r3flyExecutorManager, OwnerKeys};
 use crate::storage::claim_storage::ClaimStorage;
 
 /// Error type for contracts manager operations
@@ -194,6 +196,9 @@ pub struct F1r3flyContractsManager {
 
     /// Hybrid storage for witness claim tracking (SQLite + in-memory cache)
     claim_storage: ClaimStorage,
+
+    /// Keys owning this wallet's contracts and seals
+    owner_keys: OwnerKeys,
 }
 
 impl F1r3flyContractsManager {
@@ -220,7 +225,10 @@ impl F1r3flyContractsManager {
         executor_manager: &F1r3flyExecutorManager,
         wallet_dir: P,
     ) -> Result<Self, ContractsManagerError> {
-        let executor = executor_manager.create_executor();
+        let mut executor = executor_manager.create_executor();
+        if let Some(index) = executor_manager.initial_derivation_index() {
+            executor.set_derivation_index(index);
+        }
         let contracts = F1r3flyRgbContracts::new(executor);
         let tracker = BitcoinAnchorTracker::new();
 
@@ -241,6 +249,7 @@ impl F1r3flyContractsManager {
             contract_derivation_indices: HashMap::new(),
             state_path,
             claim_storage,
+            owner_keys: executor_manager.owner_keys().clone(),
         })
     }
 
@@ -341,6 +350,7 @@ impl F1r3flyContractsManager {
             contract_derivation_indices: state.contract_derivation_indices,
             state_path,
             claim_storage,
+            owner_keys: executor_manager.owner_keys().clone(),
         })
     }
 
@@ -364,6 +374,11 @@ impl F1r3flyContractsManager {
             .insert(contract_id.to_string(), derivation_index);
     }
 
+    /// Get all stored deployment derivation indices (contract ID -> index)
+    pub fn contract_derivation_indices(&self) -> &HashMap<String, u32> {
+        &self.contract_derivation_indices
+    }
+
     /// Get the derivation index used for a contract's deployment
     ///
     /// Returns the derivation index that was used when the contract was deployed.
@@ -522,6 +537,39 @@ impl F1r3flyContractsManager {
         &self.contracts
     }
 
+    /// Get the keys owning this wallet's contracts and seals
+    pub fn owner_keys(&self) -> &OwnerKeys {
+        &self.owner_keys
+    }
+
+    /// Signing key of the contract deployed at `index`
+    ///
+    /// Signs genesis-seal transfers and `issue()` for that contract.
+    pub fn owner_key_at_index(&self, index: u32) -> Result<secp256k1::SecretKey, F1r3flyRgbError> {
+        self.owner_keys
+            .child_key_at_index(self.contracts.executor(), index)
+    }
+
+    /// Public key of the contract deployed at `index` (genesis seal owner)
+    pub fn owner_public_key_at_index(
+        &self,
+        index: u32,
+    ) -> Result<secp256k1::PublicKey, F1r3flyRgbError> {
+        self.owner_keys
+            .public_key_at_index(self.contracts.executor(), index)
+    }
+
+    /// Receiving identity key (signs claims and transfers of received seals)
+    pub fn identity_key(&self) -> Result<secp256k1::SecretKey, F1r3flyRgbError> {
+        self.owner_keys.identity_key(self.contracts.executor())
+    }
+
+    /// Receiving identity public key (invoices and change seals)
+    pub fn identity_public_key(&self) -> Result<secp256k1::PublicKey, F1r3flyRgbError> {
+        self.owner_keys
+            .identity_public_key(self.contracts.executor())
+    }
+
     /// Get mutable reference to F1r3flyRgbContracts
     ///
     /// Use this to issue assets, send transfers, or perform write operations.
@@ -3,10 +3,14 @@
 //! Manages F1r3flyExecutor instances with wallet configuration and key management.
 //! Creates executors with explicit connection config instead of environment variables.
 
-use f1r3fly_rgb::F1r3flyExecutor;
+use bitcoin::bip32::Xpriv;
+use f1r3fly_rgb::{F1r3flyExecutor, F1r3flyRgbError};
 use node_cli::connection_manager::{ConnectionConfig, F1r3flyConnectionManager};
+use sha2::{Digest, Sha256};
 
-use crate::config::GlobalConfig;
+use crate::config::{GlobalConfig, KeyMode};
+use crate::storage::keys::{derive_f1r3fly_chain, f1r3fly_child_key};
+use crate::storage::keystore::resolve_master_key;
 use crate::storage::models::WalletKeys;
 
 /// Error type for F1r3fly executor operations
@@ -15,6 +19,83 @@ pub enum F1r3flyExecutorError {
     /// Connection failed
     #[error("F1r3node connection failed: {0}")]
     ConnectionFailed(String),
+
+    /// Master key missing or keystore locked
+    #[error("F1r3node master key unavailable: {0}")]
+    MasterKey(String),
+
+    /// Wallet key chain derivation failed
+    #[error("Wallet key derivation failed: {0}")]
+    KeyDerivation(String),
+}
+
+/// Keys that own a wallet's contracts and seals
+///
+/// Deployments are always signed (and paid for) by the executor's master
+/// key; this only decides which key signs `transfer`/`claim` calls and
+/// appears as owner in genesis allocations, invoices and change seals.
+#[derive(Clone)]
+pub enum OwnerKeys {
+    /// Child keys of the executor's master key (`KeyMode::Shared`)
+    Executor,
+
+    /// Child keys of the wallet's F1r3fly chain m/1337'/0'/0'/0 (`KeyMode::Wallet`)
+    ///
+    /// Child 0 is the wallet's `f1r3fly_private_key` and serves as the
+    /// receiving identity.
+    Wallet(Xpriv),
+}
+
+impl OwnerKeys {
+    /// Signing key for a contract deployed at `index`
+    pub fn child_key_at_index(
+        &self,
+        executor: &F1r3flyExecutor,
+        index: u32,
+    ) -> Result<secp256k1::SecretKey, F1r3flyRgbError> {
+        match self {
+            Self::Executor => executor.get_child_key_at_index(index),
+            Self::Wallet(chain) => f1r3fly_child_key(chain, index)
+                .map_err(|e| F1r3flyRgbError::InvalidResponse(e.to_string())),
+        }
+    }
+
+    /// Public key of a contract deployed at `index`
+    pub fn public_key_at_index(
+        &self,
+        executor: &F1r3flyExecutor,
+        index: u32,
+    ) -> Result<secp256k1::PublicKey, F1r3flyRgbError> {
+        match self {
+            Self::Executor => executor.get_public_key_at_index(index),
+            Self::Wallet(_) => {
+                let secret_key = self.child_key_at_index(executor, index)?;
+                Ok(secret_key.public_key(&secp256k1::Secp256k1::new()))
+            }
+        }
+    }
+
+    /// Receiving identity key (owns received and change seals)
+    pub fn identity_key(
+        &self,
+        executor: &F1r3flyExecutor,
+    ) -> Result<secp256k1::SecretKey, F1r3flyRgbError> {
+        match self {
+            Self::Executor => executor.get_child_key(),
+            Self::Wallet(_) => self.child_key_at_index(executor, 0),
+        }
+    }
+
+    /// Receiving identity public key (shared in invoices)
+    pub fn identity_public_key(
+        &self,
+        executor: &F1r3flyExecutor,
+    ) -> Result<secp256k1::PublicKey, F1r3flyRgbError> {
+        match self {
+            Self::Executor => executor.get_public_key(),
+            Self::Wallet(_) => self.public_key_at_index(executor, 0),
+        }
+    }
 }
 
 /// Manages F1r3flyExecutor creation with wallet configuration
@@ -28,12 +109,18 @@ pub enum F1r3flyExecutorError {
 /// ```ignore
 /// use f1r3fly_rgb_wallet::f1r3fly::F1r3flyExecutorManager;
 ///
-/// let manager = F1r3flyExecutorManager::new(&config, &wallet_keys)?;
+/// let manager = F1r3flyExecutorManager::new(&config, &wallet_keys, KeyMode::Shared)?;
 /// let executor = manager.create_executor();
 /// ```
 pub struct F1r3flyExecutorManager {
     /// F1r3node connection manager
     connection: F1r3flyConnectionManager,
+
+    /// Keys owning the wallet's contracts and seals
+    owner_keys: OwnerKeys,
+
+    /// First derivation index for a fresh wallet (None: start at 0)
+    initial_derivation_index: Option<u32>,
 }
 
 impl F1r3flyExecutorManager {
@@ -46,6 +133,7 @@ impl F1r3flyExecutorManager {
     ///
     /// * `config` - Global wallet configuration (contains F1r3node host/ports)
     /// * `wallet_keys` - Decrypted wallet keys (contains F1r3fly private key)
+    /// * `key_mode` - The wallet's key mode (see `WalletMetadata::key_mode`)
     ///
     /// # Returns
     ///
@@ -53,10 +141,12 @@ impl F1r3flyExecutorManager {
     ///
     /// # Errors
     ///
-    /// Returns error if F1r3node connection cannot be established
+    /// Returns error if the master key cannot be resolved (see
+    /// `keystore::resolve_master_key`) or the F1r3node connection cannot be established
     pub fn new(
         config: &GlobalConfig,
-        _wallet_keys: &WalletKeys,
+        wallet_keys: &WalletKeys,
+        key_mode: KeyMode,
     ) -> Result<Self, F1r3flyExecutorError> {
         // Use master key from configuration for phlo payment and gRPC signing
         //
@@ -66,9 +156,12 @@ impl F1r3flyExecutorManager {
         //
         // Child keys are derived from this master for unique contract URIs.
         //
-        // The wallet-derived F1r3fly key (from mnemonic) is reserved for future
-        // wallet-specific features like authentication or namespacing.
-        let f1r3fly_key_hex = config.f1r3node.master_key.clone();
+        // In KeyMode::Wallet, contract and seal ownership moves to the wallet's
+        // mnemonic-derived F1r3fly chain; the master key then only pays phlo.
+        //
+        // The key comes from FIREFLY_PRIVATE_KEY or the encrypted keystore.
+        let f1r3fly_key_hex = resolve_master_key(config)
+            .map_err(|e| F1r3flyExecutorError::MasterKey(e.to_string()))?;
 
         // Create connection configuration from wallet config
         let connection_config = ConnectionConfig::new(
@@ -81,7 +174,21 @@ impl F1r3flyExecutorManager {
         // Create connection manager
         let connection = F1r3flyConnectionManager::new(connection_config);
 
-        Ok(Self { connection })
+        let (owner_keys, initial_derivation_index) = match key_mode {
+            KeyMode::Shared => (OwnerKeys::Executor, None),
+            KeyMode::Wallet => {
+                let chain = derive_f1r3fly_chain(&wallet_keys.mnemonic)
+                    .map_err(|e| F1r3flyExecutorError::KeyDerivation(e.to_string()))?;
+                let start = wallet_derivation_start(&wallet_keys.f1r3fly_public_key);
+                (OwnerKeys::Wallet(chain), Some(start))
+            }
+        };
+
+        Ok(Self {
+            connection,
+            owner_keys,
+            initial_derivation_index,
+        })
     }
 
     /// Create a new F1r3flyExecutor instance
@@ -109,4 +216,24 @@ impl F1r3flyExecutorManager {
     pub fn connection(&self) -> &F1r3flyConnectionManager {
         &self.connection
     }
+
+    /// Keys owning the wallet's contracts and seals
+    pub fn owner_keys(&self) -> &OwnerKeys {
+        &self.owner_keys
+    }
+
+    /// First derivation index for a fresh wallet
+    ///
+    /// Wallets in `KeyMode::Wallet` start at an index derived from their
+    /// F1r3fly public key, so wallets sharing a master key deploy to
+    /// distinct registry URIs and a restored wallet walks the same range.
+    pub fn initial_derivation_index(&self) -> Option<u32> {
+        self.initial_derivation_index
+    }
+}
+
+/// Wallet-specific first derivation index (24-bit, like the test harness)
+pub fn wallet_derivation_start(f1r3fly_public_key_hex: &str) -> u32 {
+    let digest = Sha256::digest(f1r3fly_public_key_hex.as_bytes());
+    u32::from_be_bytes([0, digest[0], digest[1], digest[2]])
 }
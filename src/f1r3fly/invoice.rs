@@ -4,19 +4,51 @@
 //! Handles wallet-specific concerns: address selection, Bitcoin integration, error conversion.
 //!
 //! All RGB protocol logic is delegated to the core library.
+//!
+//! # Wallet Invoice Encoding
+//!
+//! A plain RGB invoice does not say who may receive the tokens on F1r3node,
+//! so the wallet extends it with query parameters:
+//!
+//! ```text
+//! contract:tb@<contract>/<amount>@at:<seal>/?pubkey=<hex>&expiry=<unix>
+//! ```
+//!
+//! * `pubkey` - Recipient's F1r3fly public key (uncompressed hex)
+//! * `expiry` - Unix timestamp after which the invoice must not be paid
+//!
+//! Both are optional and stripped before the invoice reaches the core
+//! parser; any other query parameters are kept. Plain RGB invoices remain
+//! valid.
+//!
+//! # Blinded Invoices
+//!
+//! By default the beneficiary is a witness output paying a fresh wallet
+//! address, revealed to the sender. A blinded invoice instead names an
+//! existing wallet UTXO concealed with secret noise: the sender only sees
+//! the seal's auth token (`at:...`), pays it on F1r3node under
+//! [`blinded_seal_id`], and the recipient claims the tokens onto the UTXO
+//! when accepting the consignment. The outpoint and noise are kept in the
+//! wallet's claims database.
 
-use bitcoin::Address;
+use amplify::Wrapper;
+use bitcoin::{Address, OutPoint};
+use bp::seals::{Noise, TxoSealExt, WOutpoint, WTxoSeal};
 use f1r3fly_rgb::{
     extract_seal, generate_invoice as core_generate, get_recipient_address,
     parse_invoice as core_parse, GeneratedInvoice, ParsedInvoice, RgbBeneficiary,
 };
 use hypersonic::ContractId;
-use rgb::Consensus;
+use rand::RngCore;
+use rgb::{Consensus, RgbSealDef};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use crate::bitcoin::BitcoinWallet;
 use crate::config::NetworkType;
 use crate::f1r3fly::F1r3flyContractsManager;
+use crate::storage::{BlindedSeal, InvoiceRecord, InvoiceStatus};
 
 /// Wallet-specific invoice error type
 #[derive(Debug, thiserror::Error)]
@@ -40,10 +72,184 @@ pub enum InvoiceError {
     /// Invalid network
     #[error("Invalid network: {0}")]
     InvalidNetwork(String),
+
+    /// Invalid wallet invoice parameter
+    #[error("Invalid invoice parameter: {0}")]
+    InvalidParameter(String),
+
+    /// Invoice past its expiry
+    #[error("Invoice expired at {0} (unix time)")]
+    Expired(u64),
+
+    /// No recipient public key in the invoice and none given
+    #[error("Invoice carries no recipient public key; pass one explicitly")]
+    MissingPubkey,
+
+    /// Invoice registry error
+    #[error("Invoice registry error: {0}")]
+    Storage(#[from] crate::storage::StorageError),
+}
+
+/// Query parameter carrying the recipient's F1r3fly public key
+const PUBKEY_PARAM: &str = "pubkey";
+
+/// Query parameter carrying the expiry timestamp
+const EXPIRY_PARAM: &str = "expiry";
+
+/// RGB invoice with the wallet's extension parameters
+///
+/// See the module docs for the encoding. `Display` produces the extended
+/// invoice string; [`WalletInvoice::decode`] reads it back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletInvoice {
+    /// Plain RGB invoice (extension parameters removed)
+    pub rgb_invoice: String,
+
+    /// Recipient's F1r3fly public key (hex), if embedded
+    pub recipient_pubkey_hex: Option<String>,
+
+    /// Unix timestamp after which the invoice must not be paid
+    pub expiry: Option<u64>,
+}
+
+impl WalletInvoice {
+    /// Decode a wallet or plain RGB invoice string
+    ///
+    /// Only the extension parameters are validated here; the RGB part is
+    /// left to [`parse_invoice`].
+    pub fn decode(invoice_str: &str) -> Result<Self, InvoiceError> {
+        let invoice_str = invoice_str.trim();
+        let Some((base, query)) = invoice_str.split_once('?') else {
+            return Ok(Self {
+                rgb_invoice: invoice_str.to_string(),
+                recipient_pubkey_hex: None,
+                expiry: None,
+            });
+        };
+
+        let mut recipient_pubkey_hex = None;
+        let mut expiry = None;
+        let mut kept = Vec::new();
+
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            match param.split_once('=') {
+                Some((PUBKEY_PARAM, value)) => {
+                    let bytes = hex::decode(value).map_err(|e| {
+                        InvoiceError::InvalidParameter(format!("{}: {}", PUBKEY_PARAM, e))
+                    })?;
+                    if bytes.is_empty() {
+                        return Err(InvoiceError::InvalidParameter(format!(
+                            "{}: empty public key",
+                            PUBKEY_PARAM
+                        )));
+                    }
+                    recipient_pubkey_hex = Some(value.to_lowercase());
+                }
+                Some((EXPIRY_PARAM, value)) => {
+                    let timestamp = value.parse::<u64>().map_err(|e| {
+                        InvoiceError::InvalidParameter(format!("{}: {}", EXPIRY_PARAM, e))
+                    })?;
+                    expiry = Some(timestamp);
+                }
+                _ => kept.push(param),
+            }
+        }
+
+        let rgb_invoice = if kept.is_empty() {
+            base.to_string()
+        } else {
+            format!("{}?{}", base, kept.join("&"))
+        };
+
+        Ok(Self {
+            rgb_invoice,
+            recipient_pubkey_hex,
+            expiry,
+        })
+    }
+
+    /// Whether the invoice has expired at `now` (unix time)
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expiry.is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Recipient public key to pay, preferring an explicit override
+    ///
+    /// # Errors
+    ///
+    /// `MissingPubkey` if neither the override nor the invoice has one
+    pub fn resolve_pubkey(&self, override_hex: Option<&str>) -> Result<String, InvoiceError> {
+        match (override_hex, &self.recipient_pubkey_hex) {
+            (Some(override_hex), embedded) => {
+                if embedded
+                    .as_deref()
+                    .is_some_and(|embedded| !embedded.eq_ignore_ascii_case(override_hex))
+                {
+                    log::warn!("Recipient public key override differs from the invoice's key");
+                }
+                Ok(override_hex.to_string())
+            }
+            (None, Some(embedded)) => Ok(embedded.clone()),
+            (None, None) => Err(InvoiceError::MissingPubkey),
+        }
+    }
+}
+
+impl std::fmt::Display for WalletInvoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut params = Vec::new();
+        if let Some(pubkey) = &self.recipient_pubkey_hex {
+            params.push(format!("{}={}", PUBKEY_PARAM, pubkey));
+        }
+        if let Some(expiry) = self.expiry {
+            params.push(format!("{}={}", EXPIRY_PARAM, expiry));
+        }
+
+        if params.is_empty() {
+            write!(f, "{}", self.rgb_invoice)
+        } else {
+            let separator = if self.rgb_invoice.contains('?') {
+                '&'
+            } else {
+                '?'
+            };
+            write!(f, "{}{}{}", self.rgb_invoice, separator, params.join("&"))
+        }
+    }
 }
 
 /// Generate RGB invoice for receiving assets
 ///
+/// Uses a random nonce and records nothing; see
+/// [`generate_invoice_with_pubkey`] for invoices tracked in the registry.
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet to get receiving address from
+/// * `contract_id_str` - RGB contract ID as string
+/// * `amount` - Amount to receive (in smallest unit)
+/// * `address_override` - Optional specific address to use (for testing/advanced use)
+///
+/// # Returns
+///
+/// `GeneratedInvoice` with invoice string, seal, and metadata
+pub fn generate_invoice(
+    bitcoin_wallet: &mut BitcoinWallet,
+    contract_id_str: &str,
+    amount: u64,
+    address_override: Option<String>,
+) -> Result<GeneratedInvoice, InvoiceError> {
+    generate_invoice_with_nonce(
+        bitcoin_wallet,
+        contract_id_str,
+        amount,
+        address_override,
+        rand::random(),
+    )
+}
+
+/// Generate RGB invoice with a given nonce
+///
 /// Thin wrapper that:
 /// 1. Gets a Bitcoin address from the wallet (or uses provided address)
 /// 2. Delegates to core library's `generate_invoice()`
@@ -55,15 +261,17 @@ pub enum InvoiceError {
 /// * `contract_id_str` - RGB contract ID as string
 /// * `amount` - Amount to receive (in smallest unit)
 /// * `address_override` - Optional specific address to use (for testing/advanced use)
+/// * `nonce` - Invoice nonce (must not repeat across invoices)
 ///
 /// # Returns
 ///
 /// `GeneratedInvoice` with invoice string, seal, and metadata
-pub fn generate_invoice(
+pub fn generate_invoice_with_nonce(
     bitcoin_wallet: &mut BitcoinWallet,
     contract_id_str: &str,
     amount: u64,
     address_override: Option<String>,
+    nonce: u64,
 ) -> Result<GeneratedInvoice, InvoiceError> {
     // Parse contract ID
     let contract_id = ContractId::from_str(contract_id_str)
@@ -97,9 +305,6 @@ pub fn generate_invoice(
     let network = network_type.to_bitcoin_network();
     let testnet = network != bitcoin::Network::Bitcoin;
 
-    // Use nonce=0 for simplicity (could be enhanced with timestamp-based nonce)
-    let nonce = 0u64;
-
     // Delegate to core library
     let generated = core_generate(
         contract_id,
@@ -118,8 +323,9 @@ pub fn generate_invoice(
 
 /// Parse RGB invoice string
 ///
-/// Thin passthrough to core library's `parse_invoice()`.
-/// No wallet-specific logic needed for parsing.
+/// Accepts plain RGB invoices and wallet invoices; extension parameters
+/// are stripped before delegating to core library's `parse_invoice()`.
+/// Use [`WalletInvoice::decode`] to read them.
 ///
 /// # Arguments
 ///
@@ -129,8 +335,8 @@ pub fn generate_invoice(
 ///
 /// `ParsedInvoice` with contract ID, beneficiary, and amount
 pub fn parse_invoice(invoice_str: &str) -> Result<ParsedInvoice, InvoiceError> {
-    // Direct delegation to core library
-    Ok(core_parse(invoice_str)?)
+    let invoice = WalletInvoice::decode(invoice_str)?;
+    Ok(core_parse(&invoice.rgb_invoice)?)
 }
 
 /// Extract seal from invoice beneficiary
@@ -156,32 +362,50 @@ pub fn get_address_from_invoice(
 ///
 /// Returns both the RGB invoice string and the recipient's F1r3fly public key.
 /// The public key is needed for transfer authorization in RHO20 contracts.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InvoiceWithPubkey {
+    /// Registry ID of the invoice
+    pub id: i64,
+    /// Wallet invoice embedding the public key (and expiry); pay with this alone
+    pub invoice: String,
     /// Standard RGB invoice string
     pub invoice_string: String,
     /// Recipient's F1r3fly public key (hex-encoded, uncompressed)
     pub recipient_pubkey_hex: String,
+    /// Unix timestamp after which the invoice must not be paid
+    pub expiry: Option<u64>,
+    /// Bitcoin address receiving the seal output, or the auth token of a
+    /// blinded seal
+    pub address: String,
+    /// Invoice nonce (unique per wallet)
+    pub nonce: u64,
+    /// Wallet UTXO behind a blinded seal (txid:vout); never shared with the sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blinded_utxo: Option<String>,
 }
 
 /// Generate RGB invoice with recipient's public key
 ///
-/// Generates a standard RGB invoice and includes the recipient's F1r3fly public key
-/// for transfer authorization. The public key is returned separately (not embedded
-/// in the invoice string) due to RGB invoice format limitations.
+/// Generates a standard RGB invoice and embeds the recipient's F1r3fly public key
+/// for transfer authorization (see [`WalletInvoice`]). The plain invoice and the
+/// key are returned as well, for senders that pass the key separately.
 ///
 /// # Workflow
 ///
-/// 1. Generate standard RGB invoice (amount only)
-/// 2. Get recipient's public key from F1r3fly executor
-/// 3. Return both in `InvoiceWithPubkey` struct
+/// 1. Allocate the next unused nonce from the invoice registry
+/// 2. Generate standard RGB invoice (amount only)
+/// 3. Get recipient's public key from F1r3fly executor
+/// 4. Record the invoice in the registry
+/// 5. Return both in `InvoiceWithPubkey` struct
 ///
 /// # Arguments
 ///
 /// * `bitcoin_wallet` - Bitcoin wallet to get receiving address from
-/// * `contracts_manager` - F1r3fly contracts manager to get public key
+/// * `contracts_manager` - F1r3fly contracts manager (public key, invoice registry)
 /// * `contract_id_str` - RGB contract ID as string
 /// * `amount` - Amount to receive (in smallest unit)
+/// * `address_override` - Optional specific address to use
+/// * `expiry` - Optional unix timestamp after which the invoice must not be paid
 ///
 /// # Returns
 ///
@@ -195,34 +419,254 @@ pub struct InvoiceWithPubkey {
 ///     &contracts_manager,
 ///     "contract:...",
 ///     1000,
+///     None,
+///     None,
 /// )?;
 ///
-/// // Recipient shares both fields with sender:
-/// // - invoice_data.invoice_string (for RGB transfer)
-/// // - invoice_data.recipient_pubkey_hex (for RHO20 authorization)
+/// // Recipient shares invoice_data.invoice with the sender
 /// ```
 pub fn generate_invoice_with_pubkey(
     bitcoin_wallet: &mut BitcoinWallet,
-    contracts_manager: &F1r3flyContractsManager,
+    contracts_manager: &mut F1r3flyContractsManager,
     contract_id_str: &str,
     amount: u64,
+    address_override: Option<String>,
+    expiry: Option<u64>,
 ) -> Result<InvoiceWithPubkey, InvoiceError> {
+    // Unique nonce per issued invoice
+    let nonce = contracts_manager.claim_storage().next_invoice_nonce()?;
+
     // Generate standard RGB invoice
-    let generated = generate_invoice(bitcoin_wallet, contract_id_str, amount, None)?;
+    let generated = generate_invoice_with_nonce(
+        bitcoin_wallet,
+        contract_id_str,
+        amount,
+        address_override,
+        nonce,
+    )?;
 
     // Get recipient's F1r3fly public key from executor
     // Each user uses their own F1r3fly key (current derivation index, typically 0)
     let pubkey = contracts_manager
-        .contracts()
-        .executor()
-        .get_public_key()
+        .identity_public_key()
         .map_err(|e| InvoiceError::Core(e))?;
 
     let pubkey_hex = hex::encode(pubkey.serialize_uncompressed());
 
+    record_invoice(
+        contracts_manager,
+        contract_id_str,
+        amount,
+        generated.invoice.to_string(),
+        generated.address.to_string(),
+        nonce,
+        pubkey_hex,
+        expiry,
+        None,
+    )
+}
+
+/// Generate RGB invoice paying a blinded seal over an existing wallet UTXO
+///
+/// The sender learns neither the UTXO nor a Bitcoin address: the invoice
+/// beneficiary is the auth token of the seal, concealed with random noise
+/// that is kept in the claims database (see the module docs).
+///
+/// # Workflow
+///
+/// 1. Pick the smallest wallet UTXO that is neither RGB-occupied nor
+///    already blinded (open blinded seals are RGB-occupied)
+/// 2. Conceal it with fresh noise and derive the seal's auth token
+/// 3. Generate the RGB invoice with the token as beneficiary
+/// 4. Record the seal and the invoice
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet owning the UTXO
+/// * `contracts_manager` - F1r3fly contracts manager (public key, invoice registry)
+/// * `contract_id_str` - RGB contract ID as string
+/// * `amount` - Amount to receive (in smallest unit)
+/// * `expiry` - Optional unix timestamp after which the invoice must not be paid
+/// * `rgb_occupied` - UTXOs that must not be blinded
+///
+/// # Returns
+///
+/// `InvoiceWithPubkey` with the token as `address` and the UTXO as `blinded_utxo`
+pub fn generate_blinded_invoice(
+    bitcoin_wallet: &BitcoinWallet,
+    contracts_manager: &mut F1r3flyContractsManager,
+    contract_id_str: &str,
+    amount: u64,
+    expiry: Option<u64>,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<InvoiceWithPubkey, InvoiceError> {
+    let contract_id = ContractId::from_str(contract_id_str)
+        .map_err(|e| InvoiceError::InvalidContractId(format!("{}", e)))?;
+
+    // Smallest free UTXO, so larger coins stay available for fees
+    let outpoint = bitcoin_wallet
+        .inner()
+        .list_unspent()
+        .filter(|utxo| !rgb_occupied.contains(&utxo.outpoint))
+        .min_by_key(|utxo| utxo.txout.value)
+        .map(|utxo| utxo.outpoint)
+        .ok_or_else(|| {
+            InvoiceError::BitcoinWallet(
+                "No free UTXO to blind; fund the wallet or use a regular invoice".to_string(),
+            )
+        })?;
+
+    let mut noise = [0u8; 40];
+    rand::thread_rng().fill_bytes(&mut noise);
+    let auth_token = blinded_seal(outpoint, noise)?.auth_token();
+
+    // The core library only builds witness-output invoices: build one for
+    // an unrevealed address and replace its beneficiary with the auth token
+    let nonce = contracts_manager.claim_storage().next_invoice_nonce()?;
+    let network = bitcoin_wallet.network().to_bitcoin_network();
+    let mut rgb_invoice = core_generate(
+        contract_id,
+        amount,
+        bitcoin_wallet
+            .peek_address()
+            .map_err(|e| InvoiceError::BitcoinWallet(e.to_string()))?,
+        nonce,
+        Consensus::Bitcoin,
+        network != bitcoin::Network::Bitcoin,
+    )?
+    .invoice;
+    rgb_invoice.auth = RgbBeneficiary::Token(auth_token);
+    let token = rgb_invoice.auth.to_string();
+    let invoice_string = rgb_invoice.to_string();
+
+    if !matches!(
+        core_parse(&invoice_string)?.beneficiary,
+        RgbBeneficiary::Token(_)
+    ) {
+        return Err(InvoiceError::InvalidParameter(format!(
+            "Blinded invoice does not carry the seal token: {}",
+            invoice_string
+        )));
+    }
+
+    log::debug!("🔍 Blinded seal {} over {}", token, outpoint);
+
+    let pubkey = contracts_manager
+        .identity_public_key()
+        .map_err(|e| InvoiceError::Core(e))?;
+    let pubkey_hex = hex::encode(pubkey.serialize_uncompressed());
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let seal = BlindedSeal {
+        id: None,
+        auth_token: token.clone(),
+        contract_id: contract_id_str.to_string(),
+        txid: outpoint.txid.to_string(),
+        vout: outpoint.vout,
+        noise: hex::encode(noise),
+        claim_id: None,
+        created_at: now,
+    };
+
+    let mut invoice = record_invoice(
+        contracts_manager,
+        contract_id_str,
+        amount,
+        invoice_string,
+        token,
+        nonce,
+        pubkey_hex,
+        expiry,
+        Some(&seal),
+    )?;
+    invoice.blinded_utxo = Some(outpoint.to_string());
+
+    Ok(invoice)
+}
+
+/// Seal id a blinded seal's tokens are held under on F1r3node until claimed
+///
+/// Format: `blinded:<auth token>`
+pub fn blinded_seal_id(auth_token: &str) -> String {
+    format!("blinded:{}", auth_token)
+}
+
+/// Blinded seal over a wallet UTXO, concealed with `noise`
+fn blinded_seal(outpoint: OutPoint, noise: [u8; 40]) -> Result<WTxoSeal, InvoiceError> {
+    // bp uses the same display format for txids
+    let txid = bp::Txid::from_str(&outpoint.txid.to_string())
+        .map_err(|e| InvoiceError::BitcoinWallet(format!("Invalid txid: {}", e)))?;
+
+    Ok(WTxoSeal {
+        primary: WOutpoint::Extern(bp::Outpoint::new(txid, outpoint.vout)),
+        secondary: TxoSealExt::Noise(Noise::from_inner(noise.into())),
+    })
+}
+
+/// Wrap an RGB invoice with the wallet's public key and record it
+///
+/// `address` is the key incoming consignments are matched by: the invoice
+/// address, or the auth token of a blinded seal. A blinded seal is recorded
+/// together with its invoice.
+#[allow(clippy::too_many_arguments)]
+fn record_invoice(
+    contracts_manager: &mut F1r3flyContractsManager,
+    contract_id_str: &str,
+    amount: u64,
+    invoice_string: String,
+    address: String,
+    nonce: u64,
+    pubkey_hex: String,
+    expiry: Option<u64>,
+    blinded_seal: Option<&BlindedSeal>,
+) -> Result<InvoiceWithPubkey, InvoiceError> {
+    let invoice = WalletInvoice {
+        rgb_invoice: invoice_string.clone(),
+        recipient_pubkey_hex: Some(pubkey_hex.clone()),
+        expiry,
+    }
+    .to_string();
+
+    // Record in the registry so incoming consignments can be matched to it
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let record = InvoiceRecord {
+        id: None,
+        invoice: invoice.clone(),
+        contract_id: contract_id_str.to_string(),
+        amount,
+        address: address.clone(),
+        nonce,
+        recipient_pubkey: Some(pubkey_hex.clone()),
+        expiry,
+        status: InvoiceStatus::Open,
+        amount_received: 0,
+        created_at: now,
+        updated_at: now,
+    };
+    let id = match blinded_seal {
+        Some(seal) => contracts_manager
+            .claim_storage_mut()
+            .insert_blinded_invoice(&record, seal)?,
+        None => contracts_manager
+            .claim_storage_mut()
+            .insert_invoice(&record)?,
+    };
+
     Ok(InvoiceWithPubkey {
-        invoice_string: generated.invoice.to_string(),
+        id,
+        invoice,
+        invoice_string,
         recipient_pubkey_hex: pubkey_hex,
+        expiry,
+        address,
+        nonce,
+        blinded_utxo: None,
     })
 }
 
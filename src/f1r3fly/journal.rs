@@ -0,0 +1,285 @@
+//! Transfer journal
+//!
+//! An inline transfer moves token balances on F1r3node before its witness
+//! transaction exists. Every such transfer is journaled from just before
+//! the first contract call until its consignments and history are written,
+//! so an interrupted transfer is resumed (or reported) by
+//! [`recover_transfers`](crate::f1r3fly::transfer::recover_transfers)
+//! instead of silently stranding tokens.
+//!
+//! Each entry is a JSON file (`<id>.journal.json`) replaced atomically on
+//! every phase change; its staged consignments sit in `<id>/` next to it.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use bdk_wallet::bitcoin::consensus::encode::deserialize_hex;
+use bdk_wallet::bitcoin::{OutPoint, Transaction};
+use serde::{Deserialize, Serialize};
+
+use crate::f1r3fly::{
+    AnchorMethod, GenesisExecutionData, PreparedChange, PreparedTransferItem, TransferRecipient,
+    UtxoBalance,
+};
+
+/// Suffix of journal entry files
+const ENTRY_SUFFIX: &str = ".journal.json";
+
+/// Journal errors
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Journal entry not found: {0}")]
+    NotFound(String),
+}
+
+/// Progress of a journaled transfer
+///
+/// Each phase is recorded once its step is complete, so recovery resumes
+/// with the next step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferPhase {
+    /// Contract calls in progress; legs with an opid have executed
+    Executing,
+    /// All contract calls executed; balances sit on witness placeholders
+    Executed,
+    /// Witness transaction signed and consignments staged
+    Signed,
+    /// Witness transaction broadcast
+    Broadcast,
+    /// Anchor registered and contract state saved
+    Anchored,
+    /// Consignments written to the consignments directory
+    ConsignmentWritten,
+}
+
+impl std::fmt::Display for TransferPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Executing => write!(f, "executing"),
+            Self::Executed => write!(f, "executed"),
+            Self::Signed => write!(f, "signed"),
+            Self::Broadcast => write!(f, "broadcast"),
+            Self::Anchored => write!(f, "anchored"),
+            Self::ConsignmentWritten => write!(f, "consignment written"),
+        }
+    }
+}
+
+/// One `transfer` contract call of a journaled transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalLeg {
+    pub from_seal_id: String,
+    pub to_seal_id: String,
+    pub amount: u64,
+    pub to_pubkey_hex: String,
+
+    /// Operation ID (hex), set once the call has executed
+    pub opid: Option<String>,
+}
+
+/// Journaled transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferJournalEntry {
+    /// Random entry ID (hex)
+    pub id: String,
+
+    pub phase: TransferPhase,
+
+    /// Contract ID of the transferred asset
+    pub contract_id: String,
+
+    pub anchor_method: AnchorMethod,
+
+    /// Fee rate of the witness transaction (sat/vB)
+    pub fee_rate_sat_per_vb: f64,
+
+    /// Recipients, in batch order
+    pub recipients: Vec<TransferRecipient>,
+
+    /// Input seals spent by this transfer
+    pub inputs: Vec<UtxoBalance>,
+
+    /// Total amount transferred
+    pub total_amount: u64,
+
+    /// Change amount (kept by sender)
+    pub change_amount: u64,
+
+    /// Change seal output (multi-input only)
+    pub change: Option<PreparedChange>,
+
+    /// Contract calls, in execution order
+    pub legs: Vec<JournalLeg>,
+
+    /// Result of the last contract call (committed in the witness)
+    #[serde(default)]
+    pub execution: Option<GenesisExecutionData>,
+
+    /// Witness transaction ID (from `Signed`)
+    #[serde(default)]
+    pub txid: Option<String>,
+
+    /// Signed witness transaction, consensus-encoded hex (from `Signed`)
+    #[serde(default)]
+    pub signed_tx_hex: Option<String>,
+
+    /// Per-recipient consignments, in batch order (from `Signed`)
+    #[serde(default)]
+    pub transfers: Vec<PreparedTransferItem>,
+
+    /// Error that interrupted the last attempt
+    #[serde(default)]
+    pub last_error: Option<String>,
+
+    /// Unix timestamp when the transfer started
+    pub created_at: u64,
+
+    /// Unix timestamp of the last phase change
+    pub updated_at: u64,
+}
+
+impl TransferJournalEntry {
+    /// Operation IDs of the executed legs (hex)
+    pub fn opids(&self) -> Vec<String> {
+        self.legs
+            .iter()
+            .filter_map(|leg| leg.opid.clone())
+            .collect()
+    }
+
+    /// Outpoints spent by the signed witness transaction (from `Signed`)
+    ///
+    /// The Bitcoin wallet only learns about the witness once it is broadcast
+    /// and synced, so until the entry completes these must stay out of coin
+    /// selection: spending one elsewhere would invalidate the witness.
+    pub fn reserved_outpoints(&self) -> Vec<OutPoint> {
+        let Some(tx_hex) = self.signed_tx_hex.as_deref() else {
+            return Vec::new();
+        };
+
+        match deserialize_hex::<Transaction>(tx_hex) {
+            Ok(tx) => tx.input.iter().map(|input| input.previous_output).collect(),
+            Err(e) => {
+                log::warn!(
+                    "⚠️  Transfer {} has an invalid signed witness: {}",
+                    self.id,
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Directory of journaled transfers
+#[derive(Debug, Clone)]
+pub struct TransferJournal {
+    dir: PathBuf,
+}
+
+impl TransferJournal {
+    /// Journal stored in `dir` (created on first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Journal directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Generate a fresh entry ID
+    pub fn new_id() -> String {
+        hex::encode(rand::random::<[u8; 8]>())
+    }
+
+    /// Directory holding an entry's staged consignments
+    pub fn staging_dir(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    /// Write an entry, replacing the previous version atomically
+    ///
+    /// Updates `updated_at`. The file is synced before it replaces the old
+    /// one, so a crash leaves either the old or the new phase on disk.
+    pub fn save(&self, entry: &mut TransferJournalEntry) -> Result<(), JournalError> {
+        entry.updated_at = now();
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.entry_path(&entry.id);
+        let tmp_path = path.with_extension("tmp");
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(&serde_json::to_vec_pretty(entry)?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &path)?;
+
+        log::debug!("Transfer {} journaled as {}", entry.id, entry.phase);
+        Ok(())
+    }
+
+    /// Load one entry
+    pub fn load(&self, id: &str) -> Result<TransferJournalEntry, JournalError> {
+        let path = self.entry_path(id);
+        if !path.exists() {
+            return Err(JournalError::NotFound(id.to_string()));
+        }
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// All incomplete transfers, oldest first
+    pub fn list(&self) -> Result<Vec<TransferJournalEntry>, JournalError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            let is_entry = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(ENTRY_SUFFIX));
+            if is_entry {
+                entries.push(serde_json::from_slice(&fs::read(&path)?)?);
+            }
+        }
+
+        entries.sort_by_key(|entry: &TransferJournalEntry| entry.created_at);
+        Ok(entries)
+    }
+
+    /// Remove a completed or abandoned entry and its staged consignments
+    pub fn remove(&self, id: &str) -> Result<(), JournalError> {
+        let staging_dir = self.staging_dir(id);
+        if staging_dir.exists() {
+            fs::remove_dir_all(staging_dir)?;
+        }
+
+        let path = self.entry_path(id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}{}", id, ENTRY_SUFFIX))
+    }
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
@@ -5,10 +5,13 @@
 
 pub mod asset;
 pub mod balance;
+pub mod coin_selection;
 pub mod consignment;
 pub mod contracts;
 pub mod executor;
 pub mod invoice;
+pub mod journal;
+pub mod restore;
 pub mod transfer;
 
 // Re-exports
@@ -20,22 +23,38 @@ pub use balance::{
     get_asset_balance, get_occupied_utxos, get_rgb_balance, get_rgb_seal_info, AssetBalance,
     BalanceError, RgbOccupiedUtxo, UtxoBalance,
 };
+pub use coin_selection::{select_rgb_inputs, RgbCoinSelection};
 pub use contracts::{
     ContractsManagerError, F1r3flyContractsManager, F1r3flyState, GenesisExecutionData,
     GenesisUtxoInfo,
 };
-pub use executor::F1r3flyExecutorManager;
+pub use executor::{F1r3flyExecutorManager, OwnerKeys};
 pub use invoice::{
-    extract_seal_from_invoice, generate_invoice, generate_invoice_with_pubkey,
-    get_address_from_invoice, parse_invoice, InvoiceError, InvoiceWithPubkey,
+    blinded_seal_id, extract_seal_from_invoice, generate_blinded_invoice, generate_invoice,
+    generate_invoice_with_nonce, generate_invoice_with_pubkey, get_address_from_invoice,
+    parse_invoice, InvoiceError, InvoiceWithPubkey, WalletInvoice,
 };
 
 pub use consignment::{
-    accept_consignment, attempt_claim, export_genesis, AcceptConsignmentResponse, ClaimError,
-    ConsignmentError, ExportGenesisResponse,
+    accept_consignment, attempt_claim, export_genesis, rebind_claim, AcceptConsignmentResponse,
+    ClaimError, ConsignmentError, ExportGenesisResponse,
 };
 
-pub use transfer::{send_transfer, AnchorMethod, TransferError, TransferResponse};
+pub use journal::{JournalError, JournalLeg, TransferJournal, TransferJournalEntry, TransferPhase};
+
+pub use restore::{
+    lookup_registry_contract, recover_issued_contracts, registry_contract_id,
+    registry_uri_for_public_key, restore_consignments, RegistryContract, RestoreError,
+    RestoreOptions, RestoreReport, DEFAULT_DERIVATION_SCAN_LIMIT,
+};
+
+pub use transfer::{
+    finalize_transfer, load_prepared_transfer, prepare_transfer_batch, preview_transfer_batch,
+    recover_transfers, replace_witness_transaction, send_transfer, send_transfer_batch,
+    AnchorMethod, BatchTransferItem, BatchTransferResponse, PreparedChange, PreparedTransfer,
+    PreparedTransferItem, RecoveredTransfer, RecoveryOutcome, TransferError, TransferPreview,
+    TransferPreviewItem, TransferRecipient, TransferResponse,
+};
 
 // Re-export core library types for convenience
 pub use f1r3fly_rgb::{GeneratedInvoice, ParsedInvoice, RgbBeneficiary, RgbInvoice};
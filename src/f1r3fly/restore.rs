@@ -0,0 +1,786 @@
+//! Wallet restore from a seed phrase
+//!
+//! Rebuilds the F1r3fly side of a wallet recreated from its mnemonic:
+//! `f1r3fly_state.json` (contract metadata, genesis UTXOs, tracker anchors,
+//! deployment derivation indices) and the claims database.
+//!
+//! Contracts issued by this wallet are recovered by walking the deployer's
+//! child keys: the registry URI each key would have registered is matched
+//! against contracts imported from consignments, or looked up on F1r3node's
+//! registry. The registry holds no RGB contract ids, so contracts found only
+//! there get a local id derived from their URI.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use bdk_wallet::KeychainKind;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use f1r3fly_rgb::{ContractMetadata, F1r3flyExecutor};
+use hypersonic::ContractId;
+use strict_types::StrictVal;
+
+use crate::bitcoin::{BitcoinWallet, BitcoinWalletError, DEFAULT_STOP_GAP};
+use crate::f1r3fly::consignment::{
+    accept_blinded_payments, accept_consignment, attempt_claim, ClaimError, ConsignmentError,
+};
+use crate::f1r3fly::contracts::{ContractsManagerError, F1r3flyContractsManager, GenesisUtxoInfo};
+use crate::storage::{
+    ClaimStatus, PendingClaim, StorageError, TransferDirection, TransferRecord, TransferStatus,
+};
+
+/// Default number of derivation indices walked when matching registry URIs
+pub const DEFAULT_DERIVATION_SCAN_LIMIT: u32 = 1000;
+
+/// Number of receive addresses checked when matching invoice addresses
+const INVOICE_ADDRESS_SCAN_LIMIT: u32 = 1000;
+
+/// Methods of the asset contracts this wallet deploys
+const REGISTRY_CONTRACT_METHODS: &[&str] =
+    &["issue", "transfer", "claim", "balanceOf", "getMetadata"];
+
+/// zbase32 alphabet used by F1r3node registry URIs
+const ZBASE32_ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+/// Error type for restore operations
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreError {
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Bitcoin wallet error
+    #[error("Wallet error: {0}")]
+    Wallet(#[from] BitcoinWalletError),
+
+    /// Consignment import failed
+    #[error("Consignment error: {0}")]
+    Consignment(#[from] ConsignmentError),
+
+    /// Contracts manager error
+    #[error("Contracts manager error: {0}")]
+    ContractsManager(#[from] ContractsManagerError),
+
+    /// Claim storage error
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    /// F1r3fly RGB error
+    #[error("F1r3fly RGB error: {0}")]
+    F1r3flyRgb(#[from] f1r3fly_rgb::F1r3flyRgbError),
+
+    /// F1r3node registry lookup failed
+    #[error("Registry error: {0}")]
+    Registry(String),
+}
+
+/// Options for restoring a wallet from its mnemonic
+#[derive(Debug, Clone)]
+pub struct RestoreOptions {
+    /// Directory of consignment files to re-import (genesis and received transfers)
+    pub consignments_dir: Option<PathBuf>,
+
+    /// Unused addresses in a row that end the Bitcoin full scan
+    pub stop_gap: usize,
+
+    /// First derivation index to walk (None: the wallet's initial index)
+    pub derivation_start: Option<u32>,
+
+    /// Number of derivation indices to walk
+    pub derivation_scan_limit: u32,
+}
+
+impl Default for RestoreOptions {
+    fn default() -> Self {
+        Self {
+            consignments_dir: None,
+            stop_gap: DEFAULT_STOP_GAP,
+            derivation_start: None,
+            derivation_scan_limit: DEFAULT_DERIVATION_SCAN_LIMIT,
+        }
+    }
+}
+
+/// Outcome of a wallet restore
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    /// Blockchain height reached by the full scan
+    pub scan_height: u32,
+
+    /// Bitcoin transactions found by the full scan
+    pub transactions_found: usize,
+
+    /// Contracts imported from genesis consignments
+    pub contracts_restored: Vec<String>,
+
+    /// Received transfers re-imported (one claim each)
+    pub transfers_restored: usize,
+
+    /// Contracts issued by this wallet, with their deployment derivation index
+    pub issued_contracts: HashMap<String, u32>,
+
+    /// Consignment files not imported, with the reason
+    pub skipped: Vec<(PathBuf, String)>,
+
+    /// Derivation index the next deployment will use
+    pub next_derivation_index: u32,
+}
+
+/// Registry URI that `insertSigned` assigns to a deployer public key
+///
+/// Mirrors F1r3node's registry: `rho:id:` followed by the zbase32 encoding
+/// (270 bits) of `blake2b256(pubkey) || crc14`.
+///
+/// # Arguments
+///
+/// * `public_key` - Deployer public key (hashed in uncompressed form)
+///
+/// # Example
+///
+/// ```ignore
+/// let public_key = executor.get_public_key_at_index(index)?;
+/// println!("Contract URI: {}", registry_uri_for_public_key(&public_key));
+/// ```
+pub fn registry_uri_for_public_key(public_key: &secp256k1::PublicKey) -> String {
+    let hash = Blake2b::<U32>::digest(public_key.serialize_uncompressed());
+
+    let mut full_key = [0u8; 34];
+    full_key[..32].copy_from_slice(&hash);
+
+    let crc = crc14(&full_key[..32]);
+    full_key[32] = (crc & 0xff) as u8;
+    full_key[33] = ((crc & 0xff00) >> 6) as u8;
+
+    format!("rho:id:{}", zbase32_encode(&full_key, 270))
+}
+
+/// CRC-14 as computed by the registry (polynomial 0x4805, sign-extended input)
+fn crc14(bytes: &[u8]) -> u16 {
+    let mut rem: i16 = 0;
+    for &byte in bytes {
+        rem ^= ((byte as i8 as i32) << 6) as i16;
+        for _ in 0..8 {
+            let shifted = ((rem as i32) << 1) as i16;
+            rem = if shifted & 0x4000 != 0 {
+                shifted ^ 0x4805
+            } else {
+                shifted
+            };
+        }
+    }
+    rem as u16
+}
+
+/// Encode the first `bit_length` bits of `data` (most significant first)
+fn zbase32_encode(data: &[u8], bit_length: usize) -> String {
+    (0..bit_length.div_ceil(5))
+        .map(|chunk| {
+            let value = (0..5).fold(0usize, |acc, offset| {
+                let bit = chunk * 5 + offset;
+                let set = bit < bit_length && data[bit / 8] & (0x80 >> (bit % 8)) != 0;
+                (acc << 1) | set as usize
+            });
+            ZBASE32_ALPHABET[value] as char
+        })
+        .collect()
+}
+
+/// Registry URI without the `rho:id:` scheme
+fn uri_key(uri: &str) -> &str {
+    uri.trim_start_matches("rho:id:")
+}
+
+/// Recover contracts issued by this wallet and their derivation indices
+///
+/// Walks `limit` indices from `start`, deriving the deployer child key at
+/// each and its registry URI. A URI matching an imported contract (e.g. from
+/// a genesis consignment) recovers that contract's derivation index. Any
+/// other URI is looked up on F1r3node's registry, and a contract found there
+/// is imported (see `import_registry_contract`). The executor's derivation
+/// index is then moved past the highest match so new deployments do not
+/// reuse a registry URI.
+///
+/// # Arguments
+///
+/// * `contracts_manager` - Contracts manager with imported contracts
+/// * `bitcoin_wallet` - Bitcoin wallet (fully scanned)
+/// * `start` - First derivation index to check
+/// * `limit` - Number of indices to check
+/// * `report` - Report receiving recovered indices and imported contracts
+pub async fn recover_issued_contracts(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    start: u32,
+    limit: u32,
+    report: &mut RestoreReport,
+) -> Result<(), RestoreError> {
+    // Registry URI -> contract ID, and whether its index is already known
+    let known: HashMap<String, (String, bool)> = contracts_manager
+        .contracts()
+        .executor()
+        .contracts_metadata()
+        .iter()
+        .map(|(id, metadata)| {
+            let id = id.to_string();
+            let indexed = contracts_manager
+                .contract_derivation_indices()
+                .contains_key(&id);
+            (uri_key(&metadata.registry_uri).to_string(), (id, indexed))
+        })
+        .collect();
+
+    log::info!(
+        "🔍 Walking derivation indices {}..{} for issued contracts",
+        start,
+        start.saturating_add(limit)
+    );
+
+    // Lookups go through a throwaway executor so probes are never persisted
+    let mut probe = contracts_manager.contracts().executor().clone();
+    let mut highest = None;
+
+    for index in start..start.saturating_add(limit) {
+        let public_key = contracts_manager
+            .contracts()
+            .executor()
+            .get_public_key_at_index(index)?;
+        let uri = registry_uri_for_public_key(&public_key);
+
+        let contract_id = match known.get(uri_key(&uri)) {
+            Some((_, true)) => {
+                highest = highest.max(Some(index));
+                continue;
+            }
+            Some((contract_id, false)) => {
+                log::info!("✓ Contract {} deployed at index {}", contract_id, index);
+                contracts_manager.store_contract_derivation_index(contract_id, index);
+                contract_id.clone()
+            }
+            None => match lookup_registry_contract(&mut probe, &uri).await? {
+                Some(found) => {
+                    let contract_id =
+                        import_registry_contract(contracts_manager, bitcoin_wallet, index, found)
+                            .await?;
+                    report.contracts_restored.push(contract_id.clone());
+                    contract_id
+                }
+                None => continue,
+            },
+        };
+
+        report.issued_contracts.insert(contract_id, index);
+        highest = highest.max(Some(index));
+    }
+
+    // Never redeploy onto a recovered URI
+    let executor = contracts_manager.contracts_mut().executor_mut();
+    if let Some(highest) = highest {
+        if executor.derivation_index() <= highest {
+            executor.set_derivation_index(highest + 1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Contract found on F1r3node's registry at a derived URI
+pub struct RegistryContract {
+    /// Metadata the executor queries the contract with
+    pub metadata: ContractMetadata,
+    /// Asset ticker reported by the contract
+    pub ticker: String,
+    /// Asset name reported by the contract
+    pub name: String,
+    /// Total supply reported by the contract
+    pub supply: u64,
+    /// Decimal precision reported by the contract
+    pub precision: u8,
+}
+
+/// Local ID of a contract known only from F1r3node's registry
+///
+/// The registry holds no RGB contract id, so the blake2b-256 hash of the
+/// registry URI stands in for it.
+pub fn registry_contract_id(uri: &str) -> ContractId {
+    let hash = Blake2b::<U32>::digest(uri.as_bytes());
+    let mut id = [0u8; 32];
+    id.copy_from_slice(&hash);
+    ContractId::from(id)
+}
+
+/// Look up a registry URI on F1r3node
+///
+/// Asks the contract registered at `uri` for its metadata. An unregistered
+/// URI answers with no data.
+///
+/// # Arguments
+///
+/// * `probe` - Executor the lookup registers a temporary contract with
+/// * `uri` - Registry URI (`rho:id:...`)
+///
+/// # Returns
+///
+/// The contract, or `None` if nothing is registered at `uri`
+///
+/// # Errors
+///
+/// Returns error if F1r3node cannot be queried or the contract's metadata
+/// is not an asset's
+pub async fn lookup_registry_contract(
+    probe: &mut F1r3flyExecutor,
+    uri: &str,
+) -> Result<Option<RegistryContract>, RestoreError> {
+    let metadata: ContractMetadata = serde_json::from_value(serde_json::json!({
+        "registry_uri": uri,
+        "methods": REGISTRY_CONTRACT_METHODS,
+        "rholang_source": "",
+    }))
+    .map_err(|e| RestoreError::Registry(format!("Invalid contract metadata: {}", e)))?;
+
+    let probe_id = registry_contract_id(uri);
+    probe.register_contract(probe_id, metadata.clone());
+
+    let response = probe
+        .query_state(probe_id, "getMetadata", &[])
+        .await
+        .map_err(|e| RestoreError::Registry(format!("Lookup of {} failed: {}", uri, e)))?;
+    if response.is_null() {
+        return Ok(None);
+    }
+
+    let invalid = || {
+        RestoreError::Registry(format!(
+            "Contract at {} has invalid metadata: {}",
+            uri, response
+        ))
+    };
+    Ok(Some(RegistryContract {
+        metadata,
+        ticker: response["ticker"].as_str().ok_or_else(invalid)?.to_string(),
+        name: response["name"].as_str().ok_or_else(invalid)?.to_string(),
+        supply: response["supply"].as_u64().ok_or_else(invalid)?,
+        precision: response["decimals"].as_u64().ok_or_else(invalid)? as u8,
+    }))
+}
+
+/// Import a contract found on F1r3node's registry
+///
+/// The contract is keyed by [`registry_contract_id`]. Its genesis seal is not
+/// on the registry: the wallet UTXO holding most of its tokens stands in for
+/// it, so the asset is listed and its balance can be queried. Without such a
+/// UTXO only the derivation index is recovered.
+///
+/// # Returns
+///
+/// Local contract ID
+async fn import_registry_contract(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    index: u32,
+    found: RegistryContract,
+) -> Result<String, RestoreError> {
+    let contract_id = registry_contract_id(&found.metadata.registry_uri);
+    let contract_id_str = contract_id.to_string();
+
+    log::info!(
+        "✓ Contract {} ({}) found on the registry at index {}",
+        found.ticker,
+        found.metadata.registry_uri,
+        index
+    );
+
+    contracts_manager
+        .contracts_mut()
+        .executor_mut()
+        .register_contract(contract_id, found.metadata.clone());
+    let contract = f1r3fly_rgb::F1r3flyRgbContract::new(
+        contract_id,
+        contracts_manager.contracts().executor().clone(),
+        found.metadata,
+    )?;
+    contracts_manager.contracts_mut().register(contract);
+    contracts_manager.store_contract_derivation_index(&contract_id_str, index);
+
+    let outpoints: Vec<_> = bitcoin_wallet
+        .inner()
+        .list_unspent()
+        .map(|utxo| utxo.outpoint)
+        .collect();
+    let mut holding = None;
+    for outpoint in outpoints {
+        let balance = query_balance(contracts_manager, contract_id, &outpoint.to_string()).await?;
+        if balance > holding.map_or(0, |(_, most)| most) {
+            holding = Some((outpoint, balance));
+        }
+    }
+
+    match holding {
+        Some((outpoint, _)) => contracts_manager.add_genesis_utxo(GenesisUtxoInfo {
+            contract_id: contract_id_str.clone(),
+            txid: outpoint.txid.to_string(),
+            vout: outpoint.vout,
+            ticker: found.ticker,
+            name: found.name,
+            precision: found.precision,
+            supply: found.supply,
+            genesis_execution_result: None,
+        }),
+        None => log::warn!(
+            "⚠️  No wallet UTXO holds {} tokens; only its derivation index is restored",
+            found.ticker
+        ),
+    }
+
+    Ok(contract_id_str)
+}
+
+/// Re-import the consignments in a directory
+///
+/// Genesis consignments are imported first (through `accept_consignment`),
+/// then transfer consignments paying one of this wallet's receive addresses.
+/// Transfers that were already claimed before the wallet was lost are
+/// recorded as claimed without calling the contract again; the others are
+/// claimed now or left pending for the next sync. Files that are not
+/// consignments, are already imported, or pay someone else are skipped, as
+/// are payments to blinded seals the claims database no longer knows.
+///
+/// # Arguments
+///
+/// * `contracts_manager` - Contracts manager to import into
+/// * `bitcoin_wallet` - Bitcoin wallet (fully scanned)
+/// * `dir` - Directory of consignment files
+/// * `report` - Report receiving imported contracts, transfers and skipped files
+pub async fn restore_consignments(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &mut BitcoinWallet,
+    dir: &Path,
+    report: &mut RestoreReport,
+) -> Result<(), RestoreError> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    // Parse once to order genesis before transfers
+    let mut genesis = Vec::new();
+    let mut transfers = Vec::new();
+    for path in paths {
+        let parsed = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                f1r3fly_rgb::F1r3flyConsignment::from_bytes(&bytes).map_err(|e| e.to_string())
+            });
+        match parsed {
+            Ok(consignment) if consignment.is_genesis => genesis.push(path),
+            Ok(consignment) => transfers.push((path, consignment)),
+            Err(e) => report
+                .skipped
+                .push((path, format!("not a consignment: {}", e))),
+        }
+    }
+
+    log::info!(
+        "📥 Restoring {} genesis and {} transfer consignment(s)",
+        genesis.len(),
+        transfers.len()
+    );
+
+    for path in genesis {
+        match accept_consignment(contracts_manager, &path, bitcoin_wallet).await {
+            Ok(response) => report.contracts_restored.push(response.contract_id),
+            Err(e) => report.skipped.push((path, e.to_string())),
+        }
+    }
+
+    let receive_addresses = receive_address_indices(bitcoin_wallet);
+
+    for (path, consignment) in transfers {
+        let Some(mapping) = consignment.witness_mapping.as_ref() else {
+            // No witness output: a payment to a blinded seal
+            match restore_blinded_transfer(contracts_manager, bitcoin_wallet, &path, &consignment)
+                .await
+            {
+                Ok(0) => report.skipped.push((
+                    path,
+                    "paid to a blinded seal unknown to this wallet (seal noise is not \
+                     derived from the mnemonic; tokens already claimed are found by the \
+                     balance scan)"
+                        .to_string(),
+                )),
+                Ok(_) => report.transfers_restored += 1,
+                Err(e) => report.skipped.push((path, e.to_string())),
+            }
+            continue;
+        };
+
+        let Some(&address_index) = receive_addresses.get(&mapping.recipient_address) else {
+            report
+                .skipped
+                .push((path, "not addressed to this wallet".to_string()));
+            continue;
+        };
+
+        // Keep future invoices off the address that received the transfer
+        let _revealed: Vec<_> = bitcoin_wallet
+            .inner_mut()
+            .reveal_addresses_to(KeychainKind::External, address_index)
+            .collect();
+
+        match restore_transfer(contracts_manager, bitcoin_wallet, &path, &consignment).await {
+            Ok(true) => report.transfers_restored += 1,
+            Ok(false) => report
+                .skipped
+                .push((path, "transfer already imported".to_string())),
+            Err(e) => report.skipped.push((path, e.to_string())),
+        }
+    }
+
+    bitcoin_wallet.persist()?;
+
+    Ok(())
+}
+
+/// Receive addresses of the wallet mapped to their derivation index
+fn receive_address_indices(bitcoin_wallet: &BitcoinWallet) -> HashMap<String, u32> {
+    (0..INVOICE_ADDRESS_SCAN_LIMIT)
+        .map(|index| {
+            let info = bitcoin_wallet
+                .inner()
+                .peek_address(KeychainKind::External, index);
+            (info.address.to_string(), index)
+        })
+        .collect()
+}
+
+/// Claim a transfer paid to this wallet's blinded seals
+///
+/// Goes through the same path as `accept_consignment`. Blinded seals live
+/// only in the claims database, so a wallet restored from its mnemonic
+/// alone knows none and claims nothing here.
+///
+/// # Returns
+///
+/// Number of blinded seals claimed
+async fn restore_blinded_transfer(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    path: &Path,
+    consignment: &f1r3fly_rgb::F1r3flyConsignment,
+) -> Result<usize, RestoreError> {
+    let contract_id = consignment.contract_id();
+
+    if contracts_manager.contracts().get(&contract_id).is_none() {
+        return Err(ConsignmentError::ContractNotFound(format!(
+            "{} (import its genesis consignment)",
+            contract_id
+        ))
+        .into());
+    }
+
+    consignment
+        .validate(contracts_manager.contracts().executor())
+        .await
+        .map_err(|e| ConsignmentError::Invalid(format!("Validation failed: {}", e)))?;
+
+    accept_blinded_payments(
+        contracts_manager,
+        bitcoin_wallet,
+        contract_id,
+        consignment,
+        path,
+    )
+    .await
+    .map_err(Into::into)
+}
+
+/// Rebuild the claim and history record of one received transfer
+///
+/// # Returns
+///
+/// `false` if the transfer's witness is already in claim storage
+async fn restore_transfer(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    path: &Path,
+    consignment: &f1r3fly_rgb::F1r3flyConsignment,
+) -> Result<bool, RestoreError> {
+    let contract_id = consignment.contract_id();
+    let contract_id_str = contract_id.to_string();
+
+    if contracts_manager.contracts().get(&contract_id).is_none() {
+        return Err(ConsignmentError::ContractNotFound(format!(
+            "{} (import its genesis consignment)",
+            contract_id_str
+        ))
+        .into());
+    }
+
+    consignment
+        .validate(contracts_manager.contracts().executor())
+        .await
+        .map_err(|e| ConsignmentError::Invalid(format!("Validation failed: {}", e)))?;
+
+    let mapping = consignment
+        .witness_mapping
+        .as_ref()
+        .ok_or_else(|| ConsignmentError::Invalid("Missing witness mapping".to_string()))?;
+    let witness_tx = consignment.witness_txs.first().ok_or_else(|| {
+        ConsignmentError::Invalid("Transfer consignment missing witness transaction".to_string())
+    })?;
+    let actual_txid = witness_tx.txid().to_string();
+
+    let already_imported = contracts_manager
+        .claim_storage()
+        .get_all_claims(&contract_id_str)?
+        .iter()
+        .any(|claim| claim.witness_id == mapping.witness_id);
+    if already_imported {
+        return Ok(false);
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let claim = PendingClaim {
+        id: None,
+        witness_id: mapping.witness_id.clone(),
+        recipient_address: mapping.recipient_address.clone(),
+        expected_vout: mapping.expected_vout,
+        contract_id: contract_id_str.clone(),
+        consignment_file: path.to_path_buf(),
+        status: ClaimStatus::Pending,
+        error: None,
+        created_at: now,
+        claimed_at: None,
+        actual_txid: Some(actual_txid.clone()),
+        actual_vout: Some(mapping.expected_vout),
+    };
+    let claim_id = contracts_manager
+        .claim_storage_mut()
+        .insert_pending_claim(&claim)?;
+
+    let record = TransferRecord {
+        id: None,
+        direction: TransferDirection::Incoming,
+        contract_id: contract_id_str.clone(),
+        amount: None,
+        counterparty_pubkey: None,
+        witness_txid: actual_txid.clone(),
+        anchor_method: None,
+        consignment_path: path.to_path_buf(),
+        status: TransferStatus::Pending,
+        claim_id: Some(claim_id),
+        created_at: now,
+        updated_at: now,
+    };
+    contracts_manager
+        .claim_storage_mut()
+        .insert_transfer_record(&record)?;
+
+    // An empty witness balance means the claim already ran before the wallet was lost.
+    // If F1r3node cannot be asked, the claim stays pending for the next sync.
+    let real_utxo = format!("{}:{}", actual_txid, mapping.expected_vout);
+    let balances = match query_balance(contracts_manager, contract_id, &mapping.witness_id).await {
+        Ok(0) => query_balance(contracts_manager, contract_id, &real_utxo)
+            .await
+            .map(Some),
+        Ok(_) => Ok(None),
+        Err(e) => Err(e),
+    };
+    let already_claimed = match balances {
+        Ok(already_claimed) => already_claimed,
+        Err(e) => {
+            log::warn!(
+                "⏳ Balance query for {} failed, will retry on next sync: {}",
+                mapping.witness_id,
+                e
+            );
+            return Ok(true);
+        }
+    };
+
+    if let Some(real_balance) = already_claimed {
+        log::info!(
+            "✓ Transfer {} already claimed ({} tokens at {})",
+            mapping.witness_id,
+            real_balance,
+            real_utxo
+        );
+        contracts_manager
+            .claim_storage_mut()
+            .mark_claim_completed(claim_id)?;
+        contracts_manager
+            .claim_storage_mut()
+            .set_transfer_amount_for_claim(claim_id, real_balance)?;
+        return Ok(true);
+    }
+
+    match attempt_claim(contracts_manager, bitcoin_wallet, contract_id, &claim).await {
+        Ok(claim_result) => {
+            contracts_manager
+                .claim_storage_mut()
+                .mark_claim_completed(claim_id)?;
+            contracts_manager
+                .claim_storage_mut()
+                .set_transfer_amount_for_claim(claim_id, claim_result.migrated_balance)?;
+        }
+        Err(ClaimError::UtxoNotFound) => {
+            log::warn!("⏳ UTXO not found yet, will retry on next sync");
+        }
+        Err(e) => {
+            contracts_manager.claim_storage_mut().update_claim_status(
+                claim_id,
+                ClaimStatus::Failed,
+                Some(e.to_string()),
+            )?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Token balance of a seal or witness id on F1r3node
+///
+/// An address the contract has never seen has a balance of 0.
+///
+/// # Errors
+///
+/// Returns error if F1r3node cannot be queried or answers with something
+/// other than a balance
+pub(crate) async fn query_balance(
+    contracts_manager: &F1r3flyContractsManager,
+    contract_id: ContractId,
+    address: &str,
+) -> Result<u64, f1r3fly_rgb::F1r3flyRgbError> {
+    let value = contracts_manager
+        .contracts()
+        .executor()
+        .query_state(
+            contract_id,
+            "balanceOf",
+            &[("address", StrictVal::from(address))],
+        )
+        .await
+        .map_err(|e| {
+            f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                "Balance query for {} failed: {}",
+                address, e
+            ))
+        })?;
+
+    if value.is_null() {
+        return Ok(0);
+    }
+    value
+        .as_u64()
+        .or_else(|| value.as_i64().map(|i| i.max(0) as u64))
+        .ok_or_else(|| {
+            f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                "Balance of {} is not a number: {}",
+                address, value
+            ))
+        })
+}
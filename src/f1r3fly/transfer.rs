@@ -7,26 +7,51 @@
 //! - Embed Tapret commitment
 //! - Create and serialize consignment
 //! - Broadcast and persist state
+//!
+//! Several invoices for the same contract can be paid in one witness
+//! transaction with `send_transfer_batch`; `preview_transfer_batch` reports
+//! what it would do without executing anything.
+//!
+//! For offline signing, `prepare_transfer_batch` exports the unsigned
+//! witness PSBT and `finalize_transfer` completes the transfer once the
+//! signed transaction comes back.
+//!
+//! Inline transfers are journaled phase by phase (see
+//! [`journal`](crate::f1r3fly::journal)); `recover_transfers` resumes the
+//! ones interrupted by a crash.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use amplify::confinement::SmallOrdMap;
 use bdk_wallet::bitcoin::Amount;
 use bp::seals::{Noise, TxoSeal, TxoSealExt, WOutpoint, WTxoSeal};
 use bp::{ConsensusDecode, Outpoint, Txid};
 use bpstd::psbt::Psbt as BpPsbt;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use strict_types::{StrictDumb, StrictVal};
 
 use crate::bitcoin::utxo::FeeRateConfig;
 use crate::bitcoin::{BitcoinWallet, EsploraClient};
-use crate::f1r3fly::F1r3flyContractsManager;
+use crate::f1r3fly::journal::{
+    JournalError, JournalLeg, TransferJournal, TransferJournalEntry, TransferPhase,
+};
+use crate::f1r3fly::{
+    attempt_claim, rebind_claim, select_rgb_inputs, ClaimError, F1r3flyContractsManager,
+    GenesisExecutionData, RgbBeneficiary, UtxoBalance,
+};
+use crate::storage::{
+    ClaimStatus, PendingClaim, TransferDirection, TransferRecord, TransferStatus,
+};
 
 use bdk_wallet::bitcoin::OutPoint;
 use std::collections::HashSet;
 
 /// RGB anchoring method for Bitcoin transactions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialized as `"tapret"` / `"opreturn"` (config files, transfer results).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AnchorMethod {
     /// Tapret commitment (default, privacy-preserving)
     /// Uses Taproot to hide RGB commitment in script tree
@@ -42,6 +67,31 @@ impl Default for AnchorMethod {
     }
 }
 
+impl std::fmt::Display for AnchorMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tapret => write!(f, "tapret"),
+            Self::OpReturn => write!(f, "opreturn"),
+        }
+    }
+}
+
+impl FromStr for AnchorMethod {
+    type Err = String;
+
+    /// Parse an anchor method name (`tapret`, `opreturn` or `op_return`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tapret" => Ok(Self::Tapret),
+            "opreturn" | "op_return" | "op-return" => Ok(Self::OpReturn),
+            _ => Err(format!(
+                "Invalid anchor method '{}': expected 'tapret' or 'opreturn'",
+                s
+            )),
+        }
+    }
+}
+
 /// Error type for transfer operations
 #[derive(Debug, thiserror::Error)]
 pub enum TransferError {
@@ -57,6 +107,14 @@ pub enum TransferError {
     #[error("Insufficient balance: need {need}, have {have}")]
     InsufficientBalance { need: u64, have: u64 },
 
+    /// Invalid transfer amount
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    /// RGB balance query error
+    #[error("Balance error: {0}")]
+    Balance(#[from] crate::f1r3fly::BalanceError),
+
     /// Bitcoin wallet error
     #[error("Bitcoin wallet error: {0}")]
     BitcoinWallet(#[from] crate::bitcoin::BitcoinWalletError),
@@ -100,10 +158,23 @@ pub enum TransferError {
     /// Invalid seal
     #[error("Invalid seal: {0}")]
     InvalidSeal(String),
+
+    /// Invalid batch transfer request
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(String),
+
+    #[error("Witness transaction not found: {0}")]
+    WitnessNotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] crate::storage::StorageError),
+
+    #[error("Transfer journal error: {0}")]
+    Journal(#[from] JournalError),
 }
 
 /// Transfer response with transaction and consignment details
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferResponse {
     /// Bitcoin transaction ID (witness transaction)
     pub bitcoin_txid: String,
@@ -125,28 +196,187 @@ pub struct TransferResponse {
 
     /// Change amount (kept by sender)
     pub change_amount: u64,
+
+    /// Input seals spent by this transfer
+    pub inputs: Vec<UtxoBalance>,
+
+    /// Change seal ("txid:vout") holding the remainder
+    ///
+    /// Only set for multi-input transfers; a single input keeps its
+    /// remainder on the input seal.
+    pub change_seal: Option<String>,
+
+    /// How the state commitment was embedded in the witness transaction
+    pub anchor_method: AnchorMethod,
+}
+
+/// A single payment in a batch transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRecipient {
+    /// RGB invoice string from recipient
+    pub invoice: String,
+
+    /// Recipient's F1r3fly public key (for transfer authorization)
+    ///
+    /// Overrides the key embedded in the invoice; required for plain RGB
+    /// invoices.
+    #[serde(default, alias = "recipient_pubkey")]
+    pub recipient_pubkey_hex: Option<String>,
+}
+
+impl TransferRecipient {
+    /// Public key the transfer is authorized for (override, else the invoice's)
+    pub fn resolve_pubkey(&self) -> Result<String, TransferError> {
+        let invoice = crate::f1r3fly::WalletInvoice::decode(&self.invoice)?;
+        Ok(invoice.resolve_pubkey(self.recipient_pubkey_hex.as_deref())?)
+    }
+}
+
+/// Per-recipient result of a batch transfer
+#[derive(Debug, Clone)]
+pub struct BatchTransferItem {
+    /// Invoice paid
+    pub invoice: String,
+
+    /// Amount transferred to this recipient
+    pub amount: u64,
+
+    /// Recipient output index in the witness transaction (before commitment)
+    pub vout: u32,
+
+    /// Consignment filename
+    pub consignment_filename: String,
+
+    /// Path to consignment file
+    pub consignment_path: PathBuf,
+
+    /// Consignment size in bytes
+    pub consignment_size: usize,
+}
+
+/// Batch transfer response: one witness transaction, one consignment per recipient
+#[derive(Debug, Clone)]
+pub struct BatchTransferResponse {
+    /// Bitcoin transaction ID (witness transaction shared by all recipients)
+    pub bitcoin_txid: String,
+
+    /// Contract ID of the transferred asset
+    pub contract_id: String,
+
+    /// Transfer status
+    pub status: String,
+
+    /// Per-recipient results, in request order
+    pub transfers: Vec<BatchTransferItem>,
+
+    /// Total amount transferred
+    pub total_amount: u64,
+
+    /// Change amount (kept by sender)
+    pub change_amount: u64,
+
+    /// Input seals spent by this transfer
+    pub inputs: Vec<UtxoBalance>,
+
+    /// Change seal ("txid:vout") holding the remainder (multi-input only)
+    pub change_seal: Option<String>,
+
+    /// How the state commitment was embedded in the witness transaction
+    pub anchor_method: AnchorMethod,
+}
+
+/// Transfer prepared for offline signing, awaiting its signed witness
+///
+/// Saved as `<txid>.transfer.json` next to the staged consignments by
+/// [`prepare_transfer_batch`] and consumed by [`finalize_transfer`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedTransfer {
+    /// Witness transaction ID (unchanged by signing)
+    pub txid: String,
+
+    /// Contract ID of the transferred asset
+    pub contract_id: String,
+
+    /// How the state commitment was embedded in the witness transaction
+    pub anchor_method: AnchorMethod,
+
+    /// Recipients, in batch order
+    pub recipients: Vec<TransferRecipient>,
+
+    /// Per-recipient staged consignments, in batch order
+    pub transfers: Vec<PreparedTransferItem>,
+
+    /// Total amount transferred
+    pub total_amount: u64,
+
+    /// Change amount (kept by sender)
+    pub change_amount: u64,
+
+    /// Input seals spent by this transfer
+    pub inputs: Vec<UtxoBalance>,
+
+    /// Change seal output (multi-input only)
+    pub change: Option<PreparedChange>,
+
+    /// Transfer operation IDs (hex), anchored on finalize
+    pub opids: Vec<String>,
+
+    /// Unix timestamp when the transfer was prepared
+    pub created_at: u64,
+}
+
+/// Staged consignment of a prepared transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedTransferItem {
+    /// Amount transferred to this recipient
+    pub amount: u64,
+
+    /// Recipient output index in the witness transaction (before commitment)
+    pub vout: u32,
+
+    /// Consignment filename (same in the staging and consignments directories)
+    pub consignment_filename: String,
+}
+
+/// Change seal output of a prepared transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedChange {
+    /// Wallet address receiving the change dust output
+    pub address: String,
+
+    /// Output index of the change seal
+    pub vout: u32,
+
+    /// This wallet's F1r3fly public key (uncompressed hex)
+    pub owner_pubkey_hex: String,
 }
 
 /// Send RGB asset transfer
 ///
+/// Single-recipient form of [`send_transfer_batch`]: the consignment is saved
+/// as `<txid>.json`.
+///
 /// Complete transfer flow:
 /// 1. Parse invoice and validate
-/// 2. Execute F1r3fly contract transfer method
-/// 3. Build Bitcoin witness transaction
-/// 4. Embed Tapret commitment with state hash
-/// 5. Sign and broadcast witness transaction
-/// 6. Create F1r3flyConsignment
-/// 7. Serialize and save consignment to disk
-/// 8. Register change seals in tracker
-/// 9. Persist state
+/// 2. Select input seals covering the amount (genesis and/or claimed UTXOs)
+/// 3. Execute F1r3fly contract transfer method (one call per input)
+/// 4. Build Bitcoin witness transaction
+/// 5. Embed Tapret commitment with state hash
+/// 6. Sign and broadcast witness transaction
+/// 7. Create F1r3flyConsignment
+/// 8. Serialize and save consignment to disk
+/// 9. Register change seals in tracker (and claim the multi-input change seal)
+/// 10. Persist state
 ///
 /// # Arguments
 ///
 /// * `bitcoin_wallet` - Bitcoin wallet for witness transaction
 /// * `esplora_client` - Esplora client for broadcasting
 /// * `contracts_manager` - F1r3fly contracts manager
+/// * `journal` - Transfer journal recording progress until completion
 /// * `invoice_str` - RGB invoice string from recipient
-/// * `recipient_pubkey_hex` - Recipient's F1r3fly public key (for transfer authorization)
+/// * `recipient_pubkey_hex` - Recipient's F1r3fly public key (for transfer authorization;
+///   `None` uses the key embedded in the invoice)
 /// * `fee_rate` - Bitcoin transaction fee rate
 /// * `consignments_dir` - Directory to save consignment files
 /// * `rgb_occupied` - Set of RGB-occupied UTXOs to protect from spending
@@ -168,319 +398,1888 @@ pub async fn send_transfer(
     bitcoin_wallet: &mut BitcoinWallet,
     esplora_client: &EsploraClient,
     contracts_manager: &mut F1r3flyContractsManager,
+    journal: &TransferJournal,
     invoice_str: &str,
-    recipient_pubkey_hex: String,
+    recipient_pubkey_hex: Option<String>,
     fee_rate: &FeeRateConfig,
     consignments_dir: PathBuf,
     rgb_occupied: &HashSet<OutPoint>,
     anchor_method: Option<AnchorMethod>,
 ) -> Result<TransferResponse, TransferError> {
-    // Use Tapret as default if not specified (backward compatible)
-    let anchor_method = anchor_method.unwrap_or_default();
-    log::info!("🚀 Starting RGB transfer");
-    log::debug!("  Invoice: {}", invoice_str);
-
-    // ========================================================================
-    // Step 1: Parse and Validate Invoice
-    // ========================================================================
-    log::info!("📄 Step 1: Parsing invoice...");
-    let parsed = crate::f1r3fly::parse_invoice(invoice_str)?;
-    log::debug!("  Contract ID: {}", parsed.contract_id);
-    log::debug!("  Amount: {:?}", parsed.amount);
-
-    let amount = parsed.amount.ok_or_else(|| {
-        TransferError::Invoice(crate::f1r3fly::InvoiceError::Core(
-            f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(
-                "Invoice must specify amount".to_string(),
-            ),
-        ))
-    })?;
+    let recipients = [TransferRecipient {
+        invoice: invoice_str.to_string(),
+        recipient_pubkey_hex,
+    }];
+
+    let batch = send_transfer_batch(
+        bitcoin_wallet,
+        esplora_client,
+        contracts_manager,
+        journal,
+        &recipients,
+        fee_rate,
+        consignments_dir,
+        rgb_occupied,
+        anchor_method,
+    )
+    .await?;
 
-    // Get contract ID string and genesis info first (before mutable borrow)
-    let contract_id_str = parsed.contract_id.to_string();
+    let transfer =
+        batch.transfers.into_iter().next().ok_or_else(|| {
+            TransferError::ConsignmentFailed("No consignment created".to_string())
+        })?;
 
-    // Get genesis UTXO for seal info
-    let genesis_info = contracts_manager
-        .get_genesis_utxo(&contract_id_str)
-        .ok_or_else(|| {
-            TransferError::ContractNotFound(format!(
-                "Genesis UTXO not found for contract {}",
-                contract_id_str
-            ))
-        })?
-        .clone(); // Clone to avoid lifetime issues
+    Ok(TransferResponse {
+        bitcoin_txid: batch.bitcoin_txid,
+        consignment_filename: transfer.consignment_filename,
+        consignment_path: transfer.consignment_path,
+        consignment_size: transfer.consignment_size,
+        status: batch.status,
+        amount: transfer.amount,
+        change_amount: batch.change_amount,
+        inputs: batch.inputs,
+        change_seal: batch.change_seal,
+        anchor_method: batch.anchor_method,
+    })
+}
 
-    log::info!(
-        "✓ Invoice parsed: {} {} tokens",
-        amount,
-        genesis_info.ticker
-    );
+/// Send RGB asset transfers to several invoices in one witness transaction
+///
+/// All invoices must be for the same contract. Every payment is executed on
+/// F1r3node, the resulting state is committed under a single anchor, and one
+/// consignment is emitted per recipient (`<txid>_<n>.json`, or `<txid>.json`
+/// for a single recipient).
+///
+/// Witness transaction outputs, before the commitment is embedded:
+/// - `0..r`: one dust output per recipient, in batch order
+/// - `r`: change seal output (multi-input transfers only)
+/// - BDK's Bitcoin change output
+///
+/// The transfer is journaled from just before its first contract call until
+/// it completes. If it is interrupted (crash, broadcast or storage failure),
+/// the entry stays in the journal with its last completed phase and
+/// [`recover_transfers`] picks it up from there.
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet for witness transaction
+/// * `esplora_client` - Esplora client for broadcasting
+/// * `contracts_manager` - F1r3fly contracts manager
+/// * `journal` - Transfer journal recording progress until completion
+/// * `recipients` - Invoices and recipient F1r3fly public keys
+/// * `fee_rate` - Bitcoin transaction fee rate
+/// * `consignments_dir` - Directory to save consignment files
+/// * `rgb_occupied` - Set of RGB-occupied UTXOs to protect from spending
+/// * `anchor_method` - Optional anchoring method (defaults to Tapret if None)
+///
+/// # Returns
+///
+/// `BatchTransferResponse` with transaction ID and per-recipient consignments
+///
+/// # Errors
+///
+/// Returns error if:
+/// - The batch is empty, mixes contracts or repeats a recipient address
+/// - Invoice parsing fails
+/// - Contract not found
+/// - Insufficient balance for the batch total
+/// - Transaction build/sign/broadcast fails
+/// - Consignment creation fails
+///
+/// # Example
+///
+/// ```ignore
+/// let recipients = vec![
+///     TransferRecipient { invoice: bob_invoice, recipient_pubkey_hex: None },
+///     TransferRecipient { invoice: carol_invoice, recipient_pubkey_hex: None },
+/// ];
+/// let response = send_transfer_batch(
+///     &mut wallet, &esplora, &mut contracts_manager, &journal, &recipients,
+///     &fee_rate, consignments_dir, &rgb_occupied, None,
+/// ).await?;
+/// for transfer in &response.transfers {
+///     println!("{} -> {}", transfer.amount, transfer.consignment_path.display());
+/// }
+/// ```
+pub async fn send_transfer_batch(
+    bitcoin_wallet: &mut BitcoinWallet,
+    esplora_client: &EsploraClient,
+    contracts_manager: &mut F1r3flyContractsManager,
+    journal: &TransferJournal,
+    recipients: &[TransferRecipient],
+    fee_rate: &FeeRateConfig,
+    consignments_dir: PathBuf,
+    rgb_occupied: &HashSet<OutPoint>,
+    anchor_method: Option<AnchorMethod>,
+) -> Result<BatchTransferResponse, TransferError> {
+    let outcome = execute_transfer_batch(
+        bitcoin_wallet,
+        WitnessSigning::Inline {
+            esplora_client,
+            journal,
+        },
+        contracts_manager,
+        recipients,
+        fee_rate,
+        consignments_dir,
+        rgb_occupied,
+        anchor_method,
+    )
+    .await?;
 
-    // ========================================================================
-    // Step 2: Check Balance
-    // ========================================================================
-    log::info!("💰 Step 2: Checking balance...");
+    Ok(outcome.response)
+}
 
-    // Get genesis seal as TxoSeal for seal map and balance query
-    let genesis_txid = Txid::from_str(&genesis_info.txid)
-        .map_err(|e| TransferError::InvalidSeal(format!("Invalid genesis txid: {}", e)))?;
-    let genesis_outpoint = Outpoint::new(genesis_txid, genesis_info.vout);
-    let genesis_seal = TxoSeal {
-        primary: genesis_outpoint,
-        secondary: TxoSealExt::Noise(Noise::strict_dumb()),
-    };
+/// Preview of an RGB transfer: what `send_transfer_batch` would do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferPreview {
+    /// Contract ID of the transferred asset
+    pub contract_id: String,
 
-    // Query balance using actual genesis UTXO identifier
-    // RGB tracks balances by Bitcoin UTXO identifiers (txid:vout)
-    // Do this BEFORE getting mutable reference to contract
-    // CRITICAL: Use serialize_seal() to ensure correct big-endian format
-    let genesis_seal_id = f1r3fly_rgb::contract::F1r3flyRgbContract::serialize_seal(&genesis_seal);
+    /// Per-recipient payments, in request order
+    pub payments: Vec<TransferPreviewItem>,
 
-    log::debug!("Querying balance for genesis UTXO: {}", genesis_seal_id);
+    /// Total amount transferred
+    pub total_amount: u64,
 
-    let balance_result = contracts_manager
-        .contracts()
-        .executor()
-        .query_state(
-            parsed.contract_id,
-            "balanceOf",
-            &[("seal", StrictVal::from(genesis_seal_id.as_str()))],
-        )
-        .await
-        .map_err(|e| TransferError::F1r3flyRgb(e))?;
-
-    let balance = balance_result
-        .as_u64()
-        .or_else(|| {
-            balance_result
-                .as_i64()
-                .and_then(|n| if n >= 0 { Some(n as u64) } else { None })
-        })
-        .ok_or_else(|| {
-            TransferError::InvalidSeal(format!("Invalid balance response: {:?}", balance_result))
-        })?;
+    /// Change amount (kept by sender)
+    pub change_amount: u64,
 
-    if balance < amount {
-        return Err(TransferError::InsufficientBalance {
-            need: amount,
-            have: balance,
-        });
-    }
+    /// Input seals whose balances would move
+    pub inputs: Vec<UtxoBalance>,
 
-    let change_amount = balance - amount;
-    log::info!(
-        "✓ Balance sufficient: {} (sending {}+ change {})",
-        balance,
-        amount,
-        change_amount
-    );
+    /// Whether the remainder would move to a fresh change seal (multi-input only)
+    pub uses_change_seal: bool,
 
-    // ========================================================================
-    // Step 3: Prepare Transfer Parameters
-    // ========================================================================
-    log::info!("📞 Step 3: Preparing transfer parameters...");
+    /// Number of `transfer` contract calls that would be executed on F1r3node
+    pub contract_calls: usize,
 
-    // Extract recipient seal from parsed invoice
-    let recipient_seal = f1r3fly_rgb::extract_seal(&parsed.beneficiary)?;
+    /// How the state commitment would be embedded in the witness transaction
+    pub anchor_method: AnchorMethod,
 
-    // Prepare seals map for transfer
-    // Index 0: Genesis seal (input - being spent)
-    // Index 1: Recipient seal (output - receiving transferred amount)
-    let mut seals_map = SmallOrdMap::new();
+    /// Fee rate of the witness transaction (sat/vB)
+    pub fee_rate_sat_per_vb: f64,
 
-    let genesis_wtxo_seal = WTxoSeal {
-        primary: WOutpoint::Extern(genesis_seal.primary),
-        secondary: genesis_seal.secondary,
-    };
-    seals_map
-        .insert(0u16, genesis_wtxo_seal)
-        .map_err(|_| TransferError::InvalidSeal("Failed to insert genesis seal".to_string()))?;
-
-    seals_map
-        .insert(1u16, recipient_seal.clone())
-        .map_err(|_| TransferError::InvalidSeal("Failed to insert recipient seal".to_string()))?;
-
-    // Serialize seals to UTXO identifiers (txid:vout format) for Rholang
-    // RGB tracks balances by actual Bitcoin UTXO identifiers
-
-    // From: Genesis UTXO (already computed above)
-    let from_seal_id = genesis_seal_id.clone();
-
-    // To: Recipient UTXO - doesn't exist yet, so use a deterministic placeholder
-    // We use a witness identifier based on the recipient address + vout from invoice
-    // Returns (seal_id, witness_mapping) where witness_mapping is captured for claim process
-    let (to_seal_id, witness_mapping) = match &recipient_seal.primary {
-        WOutpoint::Extern(outpoint) => {
-            // Recipient UTXO already exists (rare case)
-            // CRITICAL: Use serialize_seal() to ensure correct big-endian format
-            let recipient_txo_seal = TxoSeal {
-                primary: *outpoint,
-                secondary: recipient_seal.secondary.clone(),
-            };
-            let seal_id =
-                f1r3fly_rgb::contract::F1r3flyRgbContract::serialize_seal(&recipient_txo_seal);
-            (seal_id, None) // No witness mapping needed
-        }
-        WOutpoint::Wout(vout) => {
-            // Witness output: UTXO will be created in this transfer
-            // Use recipient address as stable identifier
-            let network = bitcoin_wallet.network().to_bitcoin_network();
-            let recipient_addr = f1r3fly_rgb::get_recipient_address(&parsed.beneficiary, network)?;
-
-            // Create deterministic identifier: address_hash:vout
-            // After broadcast, Bob will query his actual UTXO (the real txid:vout)
-            use sha2::{Digest, Sha256};
-            let mut hasher = Sha256::new();
-            hasher.update(recipient_addr.as_bytes());
-            let addr_hash = hex::encode(&hasher.finalize()[0..16]); // Use first 16 bytes
-            let witness_id = format!("witness:{}:{}", addr_hash, vout.into_u32());
-
-            // Create witness mapping for claim process
-            let mapping = f1r3fly_rgb::WitnessMapping {
-                witness_id: witness_id.clone(),
-                recipient_address: recipient_addr,
-                expected_vout: vout.into_u32(),
-            };
+    /// Estimated witness transaction fee in satoshis
+    pub fee_sats: u64,
 
-            (witness_id, Some(mapping))
-        }
-    };
+    /// Bitcoin outpoints the witness transaction would spend
+    pub bitcoin_inputs: Vec<String>,
 
-    log::debug!("  From UTXO: {}", from_seal_id);
-    log::debug!("  To UTXO: {}", to_seal_id);
+    /// RGB-occupied UTXOs the transfer would touch: input seals whose token
+    /// state moves (the witness transaction never spends them)
+    pub rgb_occupied_touched: Vec<String>,
+}
 
-    // ========================================================================
-    // Generate Transfer Authorization Signature
-    // ========================================================================
+/// One payment of a [`TransferPreview`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferPreviewItem {
+    /// Invoice paid
+    pub invoice: String,
 
-    // Generate nonce for replay protection
-    use f1r3fly_rgb::generate_nonce;
-    let transfer_nonce = generate_nonce();
+    /// Amount transferred to this recipient
+    pub amount: u64,
 
-    // Get the contract's derivation index (used when it was deployed)
-    let contract_derivation_index = contracts_manager
-        .get_contract_derivation_index(&contract_id_str)
-        .map_err(|e| {
-            TransferError::ContractNotFound(format!(
-                "Derivation index not found for contract {}: {}",
-                contract_id_str, e
-            ))
-        })?;
+    /// Recipient address receiving the dust output, or the auth token of a
+    /// blinded seal
+    pub recipient_address: String,
 
-    // Get the child key at that index (this is the owner's signing key)
-    let signing_key = contracts_manager
-        .contracts()
-        .executor()
-        .get_child_key_at_index(contract_derivation_index)
-        .map_err(|e| TransferError::F1r3flyRgb(e))?;
-
-    // Generate transfer signature: sign(blake2b256((from, to, amount, nonce)))
-    use f1r3fly_rgb::generate_transfer_signature;
-    let transfer_signature = generate_transfer_signature(
-        &from_seal_id,
-        &to_seal_id,
-        amount,
-        transfer_nonce,
-        &signing_key,
-    )
-    .map_err(|e| {
-        TransferError::F1r3flyRgb(f1r3fly_rgb::F1r3flyRgbError::InvalidRholangSource(format!(
-            "Failed to generate transfer signature: {}",
-            e
-        )))
-    })?;
+    /// Recipient output index in the witness transaction (before commitment)
+    pub vout: u32,
+}
 
+/// Preview RGB transfers without executing them
+///
+/// Runs the read-only part of [`send_transfer_batch`]: parses the invoices,
+/// queries balances, selects input seals and builds the unsigned witness
+/// transaction to estimate its fee. No contract method is called on
+/// F1r3node, nothing is signed or broadcast, and the wallet's address and
+/// change indices are left untouched.
+///
+/// The Tapret or OP_RETURN commitment does not change the fee: Tapret
+/// tweaks an existing output and OP_RETURN adds a zero-value output after
+/// the fee is fixed.
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet that would fund the witness transaction
+/// * `contracts_manager` - F1r3fly contracts manager
+/// * `recipients` - Invoices and recipient F1r3fly public keys
+/// * `fee_rate` - Bitcoin transaction fee rate
+/// * `rgb_occupied` - Set of RGB-occupied UTXOs to protect from spending
+/// * `anchor_method` - Optional anchoring method (defaults to Tapret if None)
+///
+/// # Errors
+///
+/// Same validation errors as [`send_transfer_batch`] (invalid batch or
+/// invoice, unknown contract, insufficient RGB or Bitcoin balance).
+///
+/// # Example
+///
+/// ```ignore
+/// let preview = preview_transfer_batch(
+///     &mut wallet, &mut contracts_manager, &recipients, &fee_rate, &rgb_occupied, None,
+/// ).await?;
+/// println!("Would send {} (fee {} sats)", preview.total_amount, preview.fee_sats);
+/// ```
+pub async fn preview_transfer_batch(
+    bitcoin_wallet: &mut BitcoinWallet,
+    contracts_manager: &mut F1r3flyContractsManager,
+    recipients: &[TransferRecipient],
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+    anchor_method: Option<AnchorMethod>,
+) -> Result<TransferPreview, TransferError> {
+    let anchor_method = anchor_method.unwrap_or_default();
     log::info!(
-        "Generated transfer signature for authorization. Nonce: {}, Signature: {}...",
-        transfer_nonce,
-        &transfer_signature[..16]
+        "🔍 Previewing RGB transfer ({} recipient(s))",
+        recipients.len()
     );
 
-    // ========================================================================
-    // Execute F1r3fly Contract Transfer Method
-    // ========================================================================
+    let network = bitcoin_wallet.network().to_bitcoin_network();
+    let payments = parse_payments(recipients, network)?;
+    reject_expired(&payments)?;
 
-    // Now get mutable contract reference for transfer
-    let contract = contracts_manager
-        .contracts_mut()
-        .get_mut(&parsed.contract_id)
-        .ok_or_else(|| TransferError::ContractNotFound(contract_id_str.clone()))?;
-
-    // Call transfer method on contract
-    // The Rholang contract will verify signature, deduct from sender, and add to recipient
-    let result = contract
-        .call_method(
-            "transfer",
-            &[
-                ("from", StrictVal::from(from_seal_id.as_str())),
-                ("to", StrictVal::from(to_seal_id.as_str())),
-                ("amount", StrictVal::from(amount)),
-                ("toPubKey", StrictVal::from(recipient_pubkey_hex.as_str())),
-                ("nonce", StrictVal::from(transfer_nonce)),
-                (
-                    "fromSignatureHex",
-                    StrictVal::from(transfer_signature.as_str()),
-                ),
-            ],
-            seals_map.clone(),
-        )
-        .await?;
+    let contract_id_str = payments[0].parsed.contract_id.to_string();
+    let total_amount: u64 = payments.iter().map(|payment| payment.amount).sum();
 
-    log::info!("✓ F1r3fly transfer executed");
-    log::debug!("  State hash: {}", hex::encode(result.state_hash));
-    log::debug!(
-        "  Block hash: {}",
-        result.block_hash_string().unwrap_or_default()
-    );
+    if contracts_manager
+        .get_genesis_utxo(&contract_id_str)
+        .is_none()
+    {
+        return Err(TransferError::ContractNotFound(format!(
+            "Genesis UTXO not found for contract {}",
+            contract_id_str
+        )));
+    }
 
-    // ========================================================================
-    // Step 4: Build Bitcoin Witness Transaction
-    // ========================================================================
-    log::info!("⛓️  Step 4: Building Bitcoin witness transaction...");
+    let asset_balance =
+        crate::f1r3fly::get_asset_balance(contracts_manager, bitcoin_wallet, &contract_id_str)
+            .await?;
 
-    // Get recipient address from invoice
-    let network = bitcoin_wallet.network().to_bitcoin_network();
-    let recipient_addr_str = f1r3fly_rgb::get_recipient_address(&parsed.beneficiary, network)?;
+    let selection = select_rgb_inputs(&asset_balance.utxo_balances, total_amount).ok_or(
+        TransferError::InsufficientBalance {
+            need: total_amount,
+            have: asset_balance.total,
+        },
+    )?;
 
-    // DIAGNOSTIC: Log the address extracted from invoice
-    log::debug!("🔍 Address from Invoice: {}", recipient_addr_str);
+    let change_amount = selection.change();
+    let uses_change_seal = selection.is_multi_input() && change_amount > 0;
+
+    // Stand-in change seal: a peeked address has the same script type as the
+    // fresh one a real transfer reveals, so the fee is the same
+    let change_output = if uses_change_seal {
+        let pubkey = contracts_manager.identity_public_key()?;
+        Some(ChangeOutput::new(
+            bitcoin_wallet.peek_address()?,
+            change_output_vout(&payments),
+            hex::encode(pubkey.serialize_uncompressed()),
+        ))
+    } else {
+        None
+    };
 
-    let recipient_addr = recipient_addr_str
-        .parse::<bdk_wallet::bitcoin::Address<bdk_wallet::bitcoin::address::NetworkUnchecked>>()
-        .map_err(|e| TransferError::BuildFailed(format!("Invalid recipient address: {}", e)))?
-        .assume_checked();
+    let (_, input_seal_ids) = build_input_seals(&selection.inputs)?;
+    let legs = plan_legs(
+        &selection.inputs,
+        &input_seal_ids,
+        &payments,
+        change_output.as_ref(),
+    );
 
-    // Build transaction sending small amount to recipient address
-    // This creates the UTXO that will be bound to the RGB tokens
-    const DUST_AMOUNT: u64 = 1_000; // 1000 sats minimum
+    // Stand-in anchor host for the same reason; the preview is never broadcast
+    let anchor_host = if needs_anchor_host(&payments) {
+        Some(bitcoin_wallet.peek_address()?)
+    } else {
+        None
+    };
 
-    let mut tx_builder = bitcoin_wallet.inner_mut().build_tx();
-    tx_builder.add_recipient(
-        recipient_addr.script_pubkey(),
-        Amount::from_sat(DUST_AMOUNT),
-    );
-    tx_builder.fee_rate(fee_rate.to_bdk_fee_rate());
+    let psbt = build_witness_tx(
+        bitcoin_wallet,
+        &payments,
+        anchor_host.as_ref(),
+        change_output.as_ref(),
+        fee_rate,
+        rgb_occupied,
+    )?;
+    // Release the change address the builder reserved for this preview
+    bitcoin_wallet.inner_mut().cancel_tx(&psbt.unsigned_tx);
 
-    // CRITICAL SAFETY: Exclude RGB-occupied UTXOs from coin selection
-    // This prevents accidental spending of UTXOs that hold RGB assets
-    if !rgb_occupied.is_empty() {
-        log::debug!(
-            "  Protecting {} RGB-occupied UTXO(s) from spending",
-            rgb_occupied.len()
+    let fee_sats = crate::bitcoin::psbt_fee(&psbt)
+        .map_err(|e| TransferError::BuildFailed(format!("{}", e)))?;
+
+    let bitcoin_inputs: Vec<OutPoint> = psbt
+        .unsigned_tx
+        .input
+        .iter()
+        .map(|input| input.previous_output)
+        .collect();
+
+    let mut rgb_occupied_touched: Vec<String> = selection
+        .inputs
+        .iter()
+        .filter(|input| {
+            OutPoint::from_str(&input.outpoint)
+                .is_ok_and(|outpoint| rgb_occupied.contains(&outpoint))
+        })
+        .map(|input| input.outpoint.clone())
+        .collect();
+    // Defensive: coin selection excludes occupied UTXOs, so this stays empty
+    for outpoint in bitcoin_inputs
+        .iter()
+        .filter(|outpoint| rgb_occupied.contains(outpoint))
+    {
+        log::warn!(
+            "⚠️  Witness transaction would spend RGB-occupied UTXO {}",
+            outpoint
         );
-        for occupied_outpoint in rgb_occupied {
-            tx_builder.add_unspendable(*occupied_outpoint);
-        }
+        rgb_occupied_touched.push(outpoint.to_string());
     }
 
-    let mut psbt = tx_builder
-        .finish()
-        .map_err(|e| TransferError::BuildFailed(format!("{}", e)))?;
+    log::info!(
+        "✓ Preview: {} tokens in {} contract call(s), fee {} sats",
+        total_amount,
+        legs.len(),
+        fee_sats
+    );
 
-    log::info!("✓ PSBT built");
-    log::debug!("  Outputs: {}", psbt.unsigned_tx.output.len());
+    Ok(TransferPreview {
+        contract_id: contract_id_str,
+        payments: payments
+            .iter()
+            .zip(recipients)
+            .enumerate()
+            .map(|(position, (payment, recipient))| TransferPreviewItem {
+                invoice: recipient.invoice.clone(),
+                amount: payment.amount,
+                recipient_address: payment.address_str.clone(),
+                vout: position as u32,
+            })
+            .collect(),
+        total_amount,
+        change_amount,
+        inputs: selection.inputs,
+        uses_change_seal,
+        contract_calls: legs.len(),
+        anchor_method,
+        fee_rate_sat_per_vb: fee_rate.sat_per_vb,
+        fee_sats,
+        bitcoin_inputs: bitcoin_inputs
+            .iter()
+            .map(|outpoint| outpoint.to_string())
+            .collect(),
+        rgb_occupied_touched,
+    })
+}
 
-    // ========================================================================
+/// Prepare RGB transfers for offline signing
+///
+/// Runs steps 1-5 of [`send_transfer_batch`] (F1r3fly contract calls and the
+/// witness PSBT with its commitment) but leaves the PSBT unsigned. The
+/// consignments are staged in `pending_dir` together with a
+/// [`PreparedTransfer`] record (`<txid>.transfer.json`); the wallet's inputs
+/// are segwit, so the txid is already final.
+///
+/// The PSBT is signed elsewhere (`sign-psbt`) and handed to
+/// [`finalize_transfer`], which broadcasts it and completes anchor
+/// registration, consignments, history and the change claim.
+///
+/// Until finalized, the transferred tokens sit on their witness placeholders
+/// on F1r3node.
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet funding the witness transaction
+/// * `contracts_manager` - F1r3fly contracts manager
+/// * `recipients` - Invoices and recipient F1r3fly public keys
+/// * `fee_rate` - Bitcoin transaction fee rate
+/// * `pending_dir` - Directory for staged consignments and transfer records
+/// * `rgb_occupied` - Set of RGB-occupied UTXOs to protect from spending
+/// * `anchor_method` - Optional anchoring method (defaults to Tapret if None)
+///
+/// # Returns
+///
+/// The prepared transfer record and the unsigned witness PSBT
+///
+/// # Example
+///
+/// ```ignore
+/// let (prepared, psbt) = prepare_transfer_batch(
+///     &mut wallet, &mut contracts_manager, &recipients,
+///     &fee_rate, pending_dir, &rgb_occupied, None,
+/// ).await?;
+/// save_psbt(&psbt, Path::new("witness.psbt"))?;
+/// println!("Sign witness {} offline", prepared.txid);
+/// ```
+pub async fn prepare_transfer_batch(
+    bitcoin_wallet: &mut BitcoinWallet,
+    contracts_manager: &mut F1r3flyContractsManager,
+    recipients: &[TransferRecipient],
+    fee_rate: &FeeRateConfig,
+    pending_dir: PathBuf,
+    rgb_occupied: &HashSet<OutPoint>,
+    anchor_method: Option<AnchorMethod>,
+) -> Result<(PreparedTransfer, bdk_wallet::bitcoin::Psbt), TransferError> {
+    let outcome = execute_transfer_batch(
+        bitcoin_wallet,
+        WitnessSigning::Offline,
+        contracts_manager,
+        recipients,
+        fee_rate,
+        pending_dir.clone(),
+        rgb_occupied,
+        anchor_method,
+    )
+    .await?;
+
+    let psbt = outcome
+        .psbt
+        .ok_or_else(|| TransferError::BuildFailed("No PSBT to export".to_string()))?;
+    let response = outcome.response;
+
+    let prepared = PreparedTransfer {
+        txid: response.bitcoin_txid.clone(),
+        contract_id: response.contract_id,
+        anchor_method: response.anchor_method,
+        recipients: recipients.to_vec(),
+        transfers: response
+            .transfers
+            .iter()
+            .map(|transfer| PreparedTransferItem {
+                amount: transfer.amount,
+                vout: transfer.vout,
+                consignment_filename: transfer.consignment_filename.clone(),
+            })
+            .collect(),
+        total_amount: response.total_amount,
+        change_amount: response.change_amount,
+        inputs: response.inputs,
+        change: outcome
+            .change_output
+            .as_ref()
+            .map(ChangeOutput::to_prepared),
+        opids: outcome.opids.iter().map(hex::encode).collect(),
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    };
+
+    let record_path = prepared_transfer_path(&pending_dir, &prepared.txid);
+    std::fs::write(&record_path, serde_json::to_vec_pretty(&prepared)?)?;
+
+    log::info!("✓ Prepared transfer saved: {}", record_path.display());
+
+    Ok((prepared, psbt))
+}
+
+/// Broadcast an offline-signed witness transaction and complete its transfer
+///
+/// Counterpart of [`prepare_transfer_batch`]. The signed transaction is
+/// matched to its [`PreparedTransfer`] by txid, then:
+/// 1. Broadcast the witness transaction
+/// 2. Register the anchor for every transfer operation
+/// 3. Write the final consignments (staged ones with the signed witness tx)
+/// 4. Record transfer history and claim the change seal
+/// 5. Persist state and remove the staged files
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet that prepared the transfer
+/// * `esplora_client` - Esplora client for broadcasting
+/// * `contracts_manager` - F1r3fly contracts manager
+/// * `tx` - Signed, finalized witness transaction
+/// * `pending_dir` - Directory holding the staged transfer
+/// * `consignments_dir` - Directory to save consignment files
+///
+/// # Returns
+///
+/// `BatchTransferResponse` as returned by an inline transfer
+///
+/// # Errors
+///
+/// Returns `WitnessNotFound` if no prepared transfer matches the txid.
+///
+/// # Example
+///
+/// ```ignore
+/// let tx = finalize_psbt(&mut wallet, load_psbt(Path::new("witness.signed.psbt"))?)?;
+/// let response = finalize_transfer(
+///     &mut wallet, &esplora, &mut contracts_manager, &tx, pending_dir, consignments_dir,
+/// ).await?;
+/// ```
+pub async fn finalize_transfer(
+    bitcoin_wallet: &mut BitcoinWallet,
+    esplora_client: &EsploraClient,
+    contracts_manager: &mut F1r3flyContractsManager,
+    tx: &bdk_wallet::bitcoin::Transaction,
+    pending_dir: PathBuf,
+    consignments_dir: PathBuf,
+) -> Result<BatchTransferResponse, TransferError> {
+    let txid = tx.compute_txid().to_string();
+    let prepared = load_prepared_transfer(&pending_dir, &txid)?.ok_or_else(|| {
+        TransferError::WitnessNotFound(format!("No prepared transfer for transaction {}", txid))
+    })?;
+    let contract_id = f1r3fly_rgb::ContractId::from_str(&prepared.contract_id)
+        .map_err(|e| TransferError::ContractNotFound(format!("{}: {}", prepared.contract_id, e)))?;
+
+    log::info!("🚀 Finalizing prepared transfer {}", txid);
+
+    // ========================================================================
+    // Step 1: Broadcast Signed Witness Transaction
+    // ========================================================================
+    esplora_client
+        .inner()
+        .broadcast(tx)
+        .map_err(|e| TransferError::BroadcastFailed(format!("{}", e)))?;
+
+    log::info!("✓ Transaction broadcasted: {}", txid);
+
+    // ========================================================================
+    // Step 2: Register Anchor and Write Consignments
+    // ========================================================================
+    let bp_tx = to_bp_tx(tx)?;
+    std::fs::create_dir_all(&consignments_dir)?;
+
+    let mut transfers = Vec::with_capacity(prepared.transfers.len());
+    let mut anchor = None;
+    for (item, recipient) in prepared.transfers.iter().zip(&prepared.recipients) {
+        let staged_path = pending_dir.join(&item.consignment_filename);
+        let mut consignment =
+            f1r3fly_rgb::F1r3flyConsignment::from_bytes(&std::fs::read(&staged_path)?)?;
+        consignment.witness_txs = vec![bp_tx.clone()];
+
+        if anchor.is_none() {
+            anchor = Some(consignment.bitcoin_anchor.clone());
+        }
+
+        let consignment_bytes = consignment.to_bytes()?;
+        let consignment_path = consignments_dir.join(&item.consignment_filename);
+        std::fs::write(&consignment_path, &consignment_bytes)?;
+
+        log::debug!("  Path: {}", consignment_path.display());
+
+        transfers.push(BatchTransferItem {
+            invoice: recipient.invoice.clone(),
+            amount: item.amount,
+            vout: item.vout,
+            consignment_filename: item.consignment_filename.clone(),
+            consignment_path,
+            consignment_size: consignment_bytes.len(),
+        });
+    }
+
+    let anchor = anchor.ok_or_else(|| {
+        TransferError::ConsignmentFailed("Prepared transfer has no consignments".to_string())
+    })?;
+
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&contract_id)
+        .ok_or_else(|| TransferError::ContractNotFound(prepared.contract_id.clone()))?;
+    for opid_hex in &prepared.opids {
+        contract
+            .tracker_mut()
+            .add_anchor(parse_opid(opid_hex)?, anchor.clone());
+    }
+
+    log::info!(
+        "✓ Anchor registered, {} consignment(s) saved",
+        transfers.len()
+    );
+
+    // ========================================================================
+    // Step 3: Record History and Claim Change
+    // ========================================================================
+    record_outgoing_transfers(
+        contracts_manager,
+        &prepared.contract_id,
+        &txid,
+        prepared.anchor_method,
+        &prepared.recipients,
+        &transfers,
+        &prepared.opids,
+    );
+
+    let change_seal = match &prepared.change {
+        Some(change) => {
+            let change_output = ChangeOutput::from_prepared(change)?;
+
+            Some(
+                claim_change_seal(
+                    contracts_manager,
+                    bitcoin_wallet,
+                    contract_id,
+                    &prepared.contract_id,
+                    &change_output,
+                    tx,
+                    &transfers[0].consignment_path,
+                )
+                .await?,
+            )
+        }
+        None => None,
+    };
+
+    // ========================================================================
+    // Step 4: Persist State
+    // ========================================================================
+    bitcoin_wallet.persist()?;
+    contracts_manager
+        .save_state()
+        .map_err(|e| TransferError::ConsignmentFailed(format!("State save failed: {}", e)))?;
+
+    for item in &prepared.transfers {
+        let _ = std::fs::remove_file(pending_dir.join(&item.consignment_filename));
+    }
+    let _ = std::fs::remove_file(prepared_transfer_path(&pending_dir, &txid));
+
+    log::info!("✅ Transfer complete!");
+
+    Ok(BatchTransferResponse {
+        bitcoin_txid: txid,
+        contract_id: prepared.contract_id,
+        status: "broadcasted".to_string(),
+        transfers,
+        total_amount: prepared.total_amount,
+        change_amount: prepared.change_amount,
+        inputs: prepared.inputs,
+        change_seal,
+        anchor_method: prepared.anchor_method,
+    })
+}
+
+/// Load the prepared transfer for a witness txid, if any
+///
+/// # Arguments
+///
+/// * `pending_dir` - Directory holding staged transfers
+/// * `txid` - Witness transaction ID
+pub fn load_prepared_transfer(
+    pending_dir: &std::path::Path,
+    txid: &str,
+) -> Result<Option<PreparedTransfer>, TransferError> {
+    let record_path = prepared_transfer_path(pending_dir, txid);
+    if !record_path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_slice(&std::fs::read(&record_path)?)?))
+}
+
+/// Path of the prepared-transfer record for a witness txid
+fn prepared_transfer_path(pending_dir: &std::path::Path, txid: &str) -> PathBuf {
+    pending_dir.join(format!("{}.transfer.json", txid))
+}
+
+/// Shared implementation of inline and offline-signed transfers
+///
+/// Inline transfers are journaled from just before the first contract call
+/// and completed by [`complete_transfer`] from the journal entry, the same
+/// path [`recover_transfers`] takes after a crash.
+async fn execute_transfer_batch(
+    bitcoin_wallet: &mut BitcoinWallet,
+    signing: WitnessSigning<'_>,
+    contracts_manager: &mut F1r3flyContractsManager,
+    recipients: &[TransferRecipient],
+    fee_rate: &FeeRateConfig,
+    consignments_dir: PathBuf,
+    rgb_occupied: &HashSet<OutPoint>,
+    anchor_method: Option<AnchorMethod>,
+) -> Result<TransferOutcome, TransferError> {
+    // Use Tapret as default if not specified (backward compatible)
+    let anchor_method = anchor_method.unwrap_or_default();
+    log::info!(
+        "🚀 Starting RGB transfer ({} recipient(s))",
+        recipients.len()
+    );
+
+    // ========================================================================
+    // Step 1: Parse and Validate Invoices
+    // ========================================================================
+    log::info!("📄 Step 1: Parsing invoice(s)...");
+
+    let network = bitcoin_wallet.network().to_bitcoin_network();
+    let payments = parse_payments(recipients, network)?;
+    reject_expired(&payments)?;
+
+    let contract_id = payments[0].parsed.contract_id;
+    let total_amount: u64 = payments.iter().map(|payment| payment.amount).sum();
+
+    // Get contract ID string and genesis info first (before mutable borrow)
+    let contract_id_str = contract_id.to_string();
+
+    // Get genesis UTXO for seal info
+    let genesis_info = contracts_manager
+        .get_genesis_utxo(&contract_id_str)
+        .ok_or_else(|| {
+            TransferError::ContractNotFound(format!(
+                "Genesis UTXO not found for contract {}",
+                contract_id_str
+            ))
+        })?
+        .clone(); // Clone to avoid lifetime issues
+
+    log::info!(
+        "✓ Invoice(s) parsed: {} {} tokens to {} recipient(s)",
+        total_amount,
+        genesis_info.ticker,
+        payments.len()
+    );
+
+    // ========================================================================
+    // Step 2: Select Input Seals
+    // ========================================================================
+    log::info!("💰 Step 2: Selecting input seals...");
+
+    // Genesis seal identifies self-issued tokens (owned by the deployment key)
+    // CRITICAL: Use serialize_seal() to ensure correct big-endian format
+    let genesis_seal =
+        seal_from_outpoint_str(&format!("{}:{}", genesis_info.txid, genesis_info.vout))?;
+    let genesis_seal_id = f1r3fly_rgb::contract::F1r3flyRgbContract::serialize_seal(&genesis_seal);
+
+    // Per-seal balances over every seal the wallet owns:
+    // BDK UTXOs (incl. genesis) and claimed UTXOs from claim storage
+    let asset_balance =
+        crate::f1r3fly::get_asset_balance(contracts_manager, bitcoin_wallet, &contract_id_str)
+            .await?;
+
+    let selection = select_rgb_inputs(&asset_balance.utxo_balances, total_amount).ok_or(
+        TransferError::InsufficientBalance {
+            need: total_amount,
+            have: asset_balance.total,
+        },
+    )?;
+
+    let change_amount = selection.change();
+
+    // Single-input transfers leave the remainder on the input seal.
+    // Multi-input transfers consolidate it onto a fresh change seal.
+    let use_change_seal = selection.is_multi_input() && change_amount > 0;
+
+    log::info!(
+        "✓ {} input seal(s) selected: {} (sending {} + change {})",
+        selection.inputs.len(),
+        selection.total,
+        total_amount,
+        change_amount
+    );
+    for input in &selection.inputs {
+        log::debug!("  Input seal: {} ({})", input.outpoint, input.amount);
+    }
+
+    // ========================================================================
+    // Step 3: Prepare Transfer Parameters
+    // ========================================================================
+    log::info!("📞 Step 3: Preparing transfer parameters...");
+
+    // Seals map layout (n inputs, r recipients):
+    // Index 0..n:    Input seals (being spent)
+    // Index n..n+r:  Recipient seals (outputs - receiving transferred amounts)
+    // Index n+r:     Change seal (multi-input only - receiving the remainder)
+    let (input_seals, input_seal_ids) = build_input_seals(&selection.inputs)?;
+
+    let recipient_base = selection.inputs.len() as u16;
+    let mut seals_map = input_seals.clone();
+    for (position, payment) in payments.iter().enumerate() {
+        let Some(seal) = &payment.seal else {
+            continue;
+        };
+        seals_map
+            .insert(recipient_base + position as u16, seal.clone())
+            .map_err(|_| {
+                TransferError::InvalidSeal("Failed to insert recipient seal".to_string())
+            })?;
+    }
+
+    // Change: dust output to a fresh wallet address, right after the recipient
+    // outputs (or the anchor host output when every seal is blinded). Its txid
+    // is unknown until the witness tx is signed, so it uses the
+    // same witness placeholder as the recipients and is claimed after broadcast.
+    let change_output = if use_change_seal {
+        let address = bitcoin_wallet.get_new_address()?;
+        let vout = change_output_vout(&payments);
+
+        // Change is owned by this wallet's F1r3fly key (same key used for claims)
+        let pubkey = contracts_manager.identity_public_key()?;
+
+        Some(ChangeOutput::new(
+            address,
+            vout,
+            hex::encode(pubkey.serialize_uncompressed()),
+        ))
+    } else {
+        None
+    };
+
+    let change_index = recipient_base + payments.len() as u16;
+    if let Some(change) = &change_output {
+        seals_map
+            .insert(change_index, change.seal.clone())
+            .map_err(|_| TransferError::InvalidSeal("Failed to insert change seal".to_string()))?;
+        log::debug!("  Change UTXO: {} ({})", change.witness_id, change.address);
+    }
+
+    let legs = plan_legs(
+        &selection.inputs,
+        &input_seal_ids,
+        &payments,
+        change_output.as_ref(),
+    );
+
+    // Journal inline transfers before any balance moves on F1r3node
+    let mut journaled = match signing {
+        WitnessSigning::Inline {
+            esplora_client,
+            journal,
+        } => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let mut entry = TransferJournalEntry {
+                id: TransferJournal::new_id(),
+                phase: TransferPhase::Executing,
+                contract_id: contract_id_str.clone(),
+                anchor_method,
+                fee_rate_sat_per_vb: fee_rate.sat_per_vb,
+                recipients: recipients.to_vec(),
+                inputs: selection.inputs.clone(),
+                total_amount,
+                change_amount,
+                change: change_output.as_ref().map(ChangeOutput::to_prepared),
+                legs: legs
+                    .iter()
+                    .map(|leg| JournalLeg {
+                        from_seal_id: leg.from_seal_id.clone(),
+                        to_seal_id: leg.to_seal_id.clone(),
+                        amount: leg.amount,
+                        to_pubkey_hex: leg.to_pubkey_hex.clone(),
+                        opid: None,
+                    })
+                    .collect(),
+                execution: None,
+                txid: None,
+                signed_tx_hex: None,
+                transfers: Vec::new(),
+                last_error: None,
+                created_at: now,
+                updated_at: now,
+            };
+            journal.save(&mut entry)?;
+            log::debug!("  Journal entry: {}", entry.id);
+
+            Some((esplora_client, journal, entry))
+        }
+        WitnessSigning::Offline => None,
+    };
+
+    // ========================================================================
+    // Execute F1r3fly Contract Transfer Method
+    // ========================================================================
+
+    let mut opids = Vec::with_capacity(legs.len());
+    let mut last_result = None;
+
+    for (index, leg) in legs.iter().enumerate() {
+        log::debug!(
+            "  Transfer leg: {} -> {} ({})",
+            leg.from_seal_id,
+            leg.to_seal_id,
+            leg.amount
+        );
+
+        // Generate transfer authorization signature with the key owning the input
+        let signing_key = input_signing_key(
+            contracts_manager,
+            &contract_id_str,
+            &leg.from_seal_id,
+            &genesis_seal_id,
+        )?;
+
+        // Generate nonce for replay protection
+        use f1r3fly_rgb::generate_nonce;
+        let transfer_nonce = generate_nonce();
+
+        // Generate transfer signature: sign(blake2b256((from, to, amount, nonce)))
+        use f1r3fly_rgb::generate_transfer_signature;
+        let transfer_signature = generate_transfer_signature(
+            &leg.from_seal_id,
+            &leg.to_seal_id,
+            leg.amount,
+            transfer_nonce,
+            &signing_key,
+        )
+        .map_err(|e| {
+            TransferError::F1r3flyRgb(f1r3fly_rgb::F1r3flyRgbError::InvalidRholangSource(format!(
+                "Failed to generate transfer signature: {}",
+                e
+            )))
+        })?;
+
+        log::info!(
+            "Generated transfer signature for authorization. Nonce: {}, Signature: {}...",
+            transfer_nonce,
+            &transfer_signature[..16]
+        );
+
+        let contract = contracts_manager
+            .contracts_mut()
+            .get_mut(&contract_id)
+            .ok_or_else(|| TransferError::ContractNotFound(contract_id_str.clone()))?;
+
+        // Call transfer method on contract
+        // The Rholang contract will verify signature, deduct from sender, and add to recipient
+        let leg_result = contract
+            .call_method(
+                "transfer",
+                &[
+                    ("from", StrictVal::from(leg.from_seal_id.as_str())),
+                    ("to", StrictVal::from(leg.to_seal_id.as_str())),
+                    ("amount", StrictVal::from(leg.amount)),
+                    ("toPubKey", StrictVal::from(leg.to_pubkey_hex.as_str())),
+                    ("nonce", StrictVal::from(transfer_nonce)),
+                    (
+                        "fromSignatureHex",
+                        StrictVal::from(transfer_signature.as_str()),
+                    ),
+                ],
+                seals_map.clone(),
+            )
+            .await?;
+
+        if let Some((_, journal, entry)) = &mut journaled {
+            entry.legs[index].opid = Some(hex::encode(&leg_result.opid));
+            journal.save(entry)?;
+        }
+
+        opids.push(leg_result.opid);
+        last_result = Some(leg_result);
+    }
+
+    // The last execution's state hash reflects the contract state after all
+    // legs, so it is the one committed in the witness transaction
+    let result = last_result
+        .ok_or_else(|| TransferError::InvalidSeal("No transfer legs to execute".to_string()))?;
+
+    log::info!(
+        "✓ F1r3fly transfer executed ({} contract call(s))",
+        legs.len()
+    );
+    log::debug!("  State hash: {}", hex::encode(result.state_hash));
+    log::debug!(
+        "  Block hash: {}",
+        result.block_hash_string().unwrap_or_default()
+    );
+
+    // Inline: the journal entry holds everything needed from here on
+    if let Some((esplora_client, journal, mut entry)) = journaled {
+        entry.execution = Some(execution_data(&result)?);
+        entry.phase = TransferPhase::Executed;
+        journal.save(&mut entry)?;
+
+        let response = complete_transfer(
+            bitcoin_wallet,
+            esplora_client,
+            contracts_manager,
+            journal,
+            entry,
+            &consignments_dir,
+            rgb_occupied,
+        )
+        .await?;
+
+        return Ok(TransferOutcome {
+            response,
+            psbt: None,
+            change_output,
+            opids,
+        });
+    }
+
+    let transfer = ExecutedTransfer {
+        contract_id,
+        anchor_method,
+        payments,
+        input_seals,
+        change_output,
+        result,
+        opids,
+    };
+
+    // ========================================================================
+    // Steps 4-5: Build Witness Transaction and Embed Commitment
+    // ========================================================================
+    let psbt = build_witness_psbt(
+        bitcoin_wallet,
+        contracts_manager,
+        &transfer,
+        fee_rate,
+        rgb_occupied,
+    )?;
+
+    // ========================================================================
+    // Step 6: Export Unsigned PSBT
+    // ========================================================================
+    log::info!("✍️  Step 6: Exporting unsigned PSBT for offline signing...");
+
+    // Segwit inputs: the txid does not change when the PSBT is signed
+    let tx = psbt.unsigned_tx.clone();
+    let txid = tx.compute_txid();
+    log::debug!("  Witness txid: {}", txid);
+
+    // ========================================================================
+    // Step 7: Stage Consignments
+    // ========================================================================
+    // The anchor is registered in memory only so the staged consignments carry
+    // it; `finalize_transfer` registers and persists it after broadcast.
+    let transfers = write_consignments(
+        contracts_manager,
+        &transfer,
+        recipients,
+        &tx,
+        &consignments_dir,
+    )?;
+
+    // Not broadcast yet: history, change claim and contract state are
+    // completed by `finalize_transfer`
+    bitcoin_wallet.persist()?;
+
+    log::info!("✅ Transfer prepared for offline signing");
+
+    Ok(TransferOutcome {
+        response: BatchTransferResponse {
+            bitcoin_txid: txid.to_string(),
+            contract_id: contract_id_str,
+            status: "prepared".to_string(),
+            transfers,
+            total_amount,
+            change_amount,
+            inputs: selection.inputs,
+            change_seal: None,
+            anchor_method,
+        },
+        psbt: Some(psbt),
+        change_output: transfer.change_output,
+        opids: transfer.opids,
+    })
+}
+
+/// Drive a journaled transfer from its recorded phase to completion
+///
+/// Each step records its phase once done; a step interrupted before its
+/// phase was recorded is safe to repeat:
+/// - `Executed`: build, commit and sign the witness; stage the consignments
+/// - `Signed`: broadcast (skipped if Esplora already has the transaction)
+/// - `Broadcast`: register the anchor and save contract state
+/// - `Anchored`: write the consignments
+/// - `ConsignmentWritten`: record history, claim change and persist, then
+///   remove the entry
+///
+/// On failure the entry keeps its phase and records the error for a retry.
+async fn complete_transfer(
+    bitcoin_wallet: &mut BitcoinWallet,
+    esplora_client: &EsploraClient,
+    contracts_manager: &mut F1r3flyContractsManager,
+    journal: &TransferJournal,
+    mut entry: TransferJournalEntry,
+    consignments_dir: &Path,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<BatchTransferResponse, TransferError> {
+    let result = advance_transfer(
+        bitcoin_wallet,
+        esplora_client,
+        contracts_manager,
+        journal,
+        &mut entry,
+        consignments_dir,
+        rgb_occupied,
+    )
+    .await;
+
+    if let Err(e) = &result {
+        log::warn!(
+            "⚠️  Transfer {} stopped at phase '{}': {}",
+            entry.id,
+            entry.phase,
+            e
+        );
+        entry.last_error = Some(e.to_string());
+        if let Err(journal_error) = journal.save(&mut entry) {
+            log::warn!("⚠️  Failed to record transfer error: {}", journal_error);
+        }
+    }
+
+    result
+}
+
+/// Steps of [`complete_transfer`]
+async fn advance_transfer(
+    bitcoin_wallet: &mut BitcoinWallet,
+    esplora_client: &EsploraClient,
+    contracts_manager: &mut F1r3flyContractsManager,
+    journal: &TransferJournal,
+    entry: &mut TransferJournalEntry,
+    consignments_dir: &Path,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<BatchTransferResponse, TransferError> {
+    let network = bitcoin_wallet.network().to_bitcoin_network();
+    let transfer = ExecutedTransfer::from_entry(entry, network)?;
+    let staging_dir = journal.staging_dir(&entry.id);
+
+    // ========================================================================
+    // Steps 4-6: Build, Commit and Sign Witness; Stage Consignments
+    // ========================================================================
+    if entry.phase == TransferPhase::Executed {
+        let fee_rate = FeeRateConfig {
+            sat_per_vb: entry.fee_rate_sat_per_vb,
+        };
+        let mut psbt = build_witness_psbt(
+            bitcoin_wallet,
+            contracts_manager,
+            &transfer,
+            &fee_rate,
+            rgb_occupied,
+        )?;
+
+        log::info!("✍️  Step 6: Signing...");
+
+        #[allow(deprecated)]
+        let sign_options = bdk_wallet::SignOptions::default();
+        bitcoin_wallet
+            .inner_mut()
+            .sign(&mut psbt, sign_options)
+            .map_err(|e| TransferError::SignFailed(format!("{}", e)))?;
+
+        let tx = psbt
+            .extract_tx()
+            .map_err(|e| TransferError::BuildFailed(format!("Extract failed: {}", e)))?;
+
+        let transfers = write_consignments(
+            contracts_manager,
+            &transfer,
+            &entry.recipients,
+            &tx,
+            &staging_dir,
+        )?;
+
+        // Keep the revealed change address across restarts. The spent coins
+        // are not known to the wallet until broadcast: the manager keeps them
+        // out of coin selection from the journaled witness
+        bitcoin_wallet.persist()?;
+
+        entry.txid = Some(tx.compute_txid().to_string());
+        entry.signed_tx_hex = Some(bdk_wallet::bitcoin::consensus::encode::serialize_hex(&tx));
+        entry.transfers = transfers
+            .iter()
+            .map(|transfer| PreparedTransferItem {
+                amount: transfer.amount,
+                vout: transfer.vout,
+                consignment_filename: transfer.consignment_filename.clone(),
+            })
+            .collect();
+        entry.phase = TransferPhase::Signed;
+        journal.save(entry)?;
+    }
+
+    let tx_hex = entry.signed_tx_hex.as_deref().ok_or_else(|| {
+        TransferError::WitnessNotFound(format!("Transfer {} has no signed witness", entry.id))
+    })?;
+    let tx: bdk_wallet::bitcoin::Transaction =
+        bdk_wallet::bitcoin::consensus::encode::deserialize_hex(tx_hex).map_err(|e| {
+            TransferError::BuildFailed(format!("Invalid journaled witness transaction: {}", e))
+        })?;
+    let txid = tx.compute_txid().to_string();
+    log::debug!("  Witness txid: {}", txid);
+
+    // ========================================================================
+    // Step 7: Broadcast Transaction
+    // ========================================================================
+    if entry.phase == TransferPhase::Signed {
+        log::info!("📡 Step 7: Broadcasting...");
+        broadcast_witness(esplora_client, &tx)?;
+
+        entry.phase = TransferPhase::Broadcast;
+        journal.save(entry)?;
+    }
+
+    // ========================================================================
+    // Step 8: Register Anchor in Tracker
+    // ========================================================================
+    if entry.phase == TransferPhase::Broadcast {
+        log::info!("📌 Step 8: Registering anchor...");
+
+        // The in-memory registration made while signing does not survive a
+        // restart, so the anchor is taken from a staged consignment
+        let first = entry.transfers.first().ok_or_else(|| {
+            TransferError::ConsignmentFailed("Transfer has no consignments".to_string())
+        })?;
+        let staged = f1r3fly_rgb::F1r3flyConsignment::from_bytes(&std::fs::read(
+            staging_dir.join(&first.consignment_filename),
+        )?)?;
+
+        // CRITICAL: Register anchor with the contract's tracker (not contracts_manager tracker)
+        let contract = contracts_manager
+            .contracts_mut()
+            .get_mut(&transfer.contract_id)
+            .ok_or_else(|| TransferError::ContractNotFound(entry.contract_id.clone()))?;
+        for opid in &transfer.opids {
+            contract
+                .tracker_mut()
+                .add_anchor(*opid, staged.bitcoin_anchor.clone());
+        }
+        contracts_manager
+            .save_state()
+            .map_err(|e| TransferError::ConsignmentFailed(format!("State save failed: {}", e)))?;
+
+        log::info!(
+            "✓ Anchor registered ({} operation(s))",
+            transfer.opids.len()
+        );
+
+        entry.phase = TransferPhase::Anchored;
+        journal.save(entry)?;
+    }
+
+    // ========================================================================
+    // Step 9: Save Consignments
+    // ========================================================================
+    if entry.phase == TransferPhase::Anchored {
+        log::info!("💾 Step 9: Saving consignment(s)...");
+
+        std::fs::create_dir_all(consignments_dir)?;
+        for item in &entry.transfers {
+            std::fs::copy(
+                staging_dir.join(&item.consignment_filename),
+                consignments_dir.join(&item.consignment_filename),
+            )?;
+        }
+
+        log::info!("✓ {} consignment(s) saved", entry.transfers.len());
+
+        entry.phase = TransferPhase::ConsignmentWritten;
+        journal.save(entry)?;
+    }
+
+    let transfers: Vec<BatchTransferItem> = entry
+        .transfers
+        .iter()
+        .zip(&entry.recipients)
+        .map(|(item, recipient)| {
+            let consignment_path = consignments_dir.join(&item.consignment_filename);
+            let consignment_size = std::fs::metadata(&consignment_path)
+                .map(|metadata| metadata.len() as usize)
+                .unwrap_or_default();
+            BatchTransferItem {
+                invoice: recipient.invoice.clone(),
+                amount: item.amount,
+                vout: item.vout,
+                consignment_filename: item.consignment_filename.clone(),
+                consignment_path,
+                consignment_size,
+            }
+        })
+        .collect();
+
+    // ========================================================================
+    // Step 10: Record History, Claim Change and Persist State
+    // ========================================================================
+    log::info!("💾 Step 10: Persisting state...");
+
+    let already_recorded = contracts_manager
+        .claim_storage()
+        .get_transfers(Some(&entry.contract_id), None, None)?
+        .iter()
+        .any(|record| {
+            record.direction == TransferDirection::Outgoing && record.witness_txid == txid
+        });
+    if !already_recorded {
+        record_outgoing_transfers(
+            contracts_manager,
+            &entry.contract_id,
+            &txid,
+            entry.anchor_method,
+            &entry.recipients,
+            &transfers,
+            &entry.opids(),
+        );
+    }
+
+    // Claim the change seal: move the remainder from its witness placeholder
+    // to the real change UTXO, exactly as a recipient claims on accept
+    let change_seal = match &transfer.change_output {
+        Some(change) => Some(
+            claim_change_seal(
+                contracts_manager,
+                bitcoin_wallet,
+                transfer.contract_id,
+                &entry.contract_id,
+                change,
+                &tx,
+                &transfers[0].consignment_path,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    bitcoin_wallet.persist()?;
+    contracts_manager
+        .save_state()
+        .map_err(|e| TransferError::ConsignmentFailed(format!("State save failed: {}", e)))?;
+
+    journal.remove(&entry.id)?;
+
+    log::info!("✓ State persisted");
+    log::info!("✅ Transfer complete!");
+
+    Ok(BatchTransferResponse {
+        bitcoin_txid: txid,
+        contract_id: entry.contract_id.clone(),
+        status: "broadcasted".to_string(),
+        transfers,
+        total_amount: entry.total_amount,
+        change_amount: entry.change_amount,
+        inputs: entry.inputs.clone(),
+        change_seal,
+        anchor_method: entry.anchor_method,
+    })
+}
+
+/// Outcome of recovering one journaled transfer
+#[derive(Debug, Clone)]
+pub enum RecoveryOutcome {
+    /// Resumed and completed
+    Completed(BatchTransferResponse),
+
+    /// Interrupted before any balance moved; the entry was dropped
+    Discarded,
+
+    /// Balances moved but the transfer cannot be resumed automatically;
+    /// the entry is kept
+    NeedsAttention(String),
+
+    /// Resuming failed; the entry is kept for a later retry
+    Failed(String),
+}
+
+/// A journaled transfer handled by [`recover_transfers`]
+#[derive(Debug, Clone)]
+pub struct RecoveredTransfer {
+    /// Journal entry ID
+    pub id: String,
+
+    /// Contract ID of the transferred asset
+    pub contract_id: String,
+
+    /// Phase the transfer was in before recovery
+    pub phase: TransferPhase,
+
+    pub outcome: RecoveryOutcome,
+}
+
+/// Resume or report every incomplete inline transfer in the journal
+///
+/// Transfers past their contract calls are completed from their recorded
+/// phase. A transfer interrupted during its contract calls is dropped if
+/// every input still holds its balance; otherwise tokens already sit on
+/// witness placeholders without a witness transaction, and it is reported
+/// for attention rather than guessed at.
+///
+/// # Arguments
+///
+/// * `bitcoin_wallet` - Bitcoin wallet that started the transfers
+/// * `esplora_client` - Esplora client for broadcasting
+/// * `contracts_manager` - F1r3fly contracts manager
+/// * `journal` - Transfer journal
+/// * `consignments_dir` - Directory to save consignment files
+/// * `rgb_occupied` - Set of RGB-occupied UTXOs to protect from spending
+///
+/// # Returns
+///
+/// One result per journaled transfer, oldest first
+///
+/// # Example
+///
+/// ```ignore
+/// for recovered in recover_transfers(
+///     &mut wallet, &esplora, &mut contracts_manager, &journal, consignments_dir, &rgb_occupied,
+/// ).await? {
+///     println!("{} ({}): {:?}", recovered.id, recovered.phase, recovered.outcome);
+/// }
+/// ```
+pub async fn recover_transfers(
+    bitcoin_wallet: &mut BitcoinWallet,
+    esplora_client: &EsploraClient,
+    contracts_manager: &mut F1r3flyContractsManager,
+    journal: &TransferJournal,
+    consignments_dir: PathBuf,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<Vec<RecoveredTransfer>, TransferError> {
+    let mut recovered = Vec::new();
+
+    for entry in journal.list()? {
+        let id = entry.id.clone();
+        let contract_id = entry.contract_id.clone();
+        let phase = entry.phase;
+        log::info!("🔧 Recovering transfer {} (phase '{}')", id, phase);
+
+        let outcome = if phase == TransferPhase::Executing {
+            match inputs_untouched(contracts_manager, &entry).await {
+                Ok(true) => {
+                    journal.remove(&id)?;
+                    RecoveryOutcome::Discarded
+                }
+                Ok(false) => RecoveryOutcome::NeedsAttention(format!(
+                    "Interrupted during contract calls ({} of {} recorded as executed); \
+                     tokens moved to witness placeholders without a witness transaction",
+                    entry.opids().len(),
+                    entry.legs.len()
+                )),
+                Err(e) => RecoveryOutcome::Failed(e.to_string()),
+            }
+        } else {
+            match complete_transfer(
+                bitcoin_wallet,
+                esplora_client,
+                contracts_manager,
+                journal,
+                entry,
+                &consignments_dir,
+                rgb_occupied,
+            )
+            .await
+            {
+                Ok(response) => RecoveryOutcome::Completed(response),
+                Err(e) => RecoveryOutcome::Failed(e.to_string()),
+            }
+        };
+
+        recovered.push(RecoveredTransfer {
+            id,
+            contract_id,
+            phase,
+            outcome,
+        });
+    }
+
+    Ok(recovered)
+}
+
+/// Whether every input seal of a journaled transfer still holds its balance
+async fn inputs_untouched(
+    contracts_manager: &mut F1r3flyContractsManager,
+    entry: &TransferJournalEntry,
+) -> Result<bool, TransferError> {
+    let contract_id = f1r3fly_rgb::ContractId::from_str(&entry.contract_id)
+        .map_err(|e| TransferError::ContractNotFound(format!("{}: {}", entry.contract_id, e)))?;
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&contract_id)
+        .ok_or_else(|| TransferError::ContractNotFound(entry.contract_id.clone()))?;
+
+    for input in &entry.inputs {
+        let seal = seal_from_outpoint_str(&input.outpoint)?;
+        if contract.balance(&seal).await? != input.amount {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Parse and validate the invoices of a (batch) transfer
+///
+/// All invoices must be for the same contract, request a non-zero amount
+/// and pay distinct addresses.
+/// In a batch, every recipient's witness output sits at its batch position.
+/// Blinded seal invoices are paid on their own (no output, no batch).
+fn parse_payments(
+    recipients: &[TransferRecipient],
+    network: bdk_wallet::bitcoin::Network,
+) -> Result<Vec<Payment>, TransferError> {
+    if recipients.is_empty() {
+        return Err(TransferError::InvalidBatch(
+            "At least one recipient is required".to_string(),
+        ));
+    }
+
+    let is_batch = recipients.len() > 1;
+
+    let mut payments: Vec<Payment> = Vec::with_capacity(recipients.len());
+    for (position, recipient) in recipients.iter().enumerate() {
+        log::debug!("  Invoice: {}", recipient.invoice);
+        let invoice = crate::f1r3fly::WalletInvoice::decode(&recipient.invoice)?;
+        let pubkey_hex = invoice.resolve_pubkey(recipient.recipient_pubkey_hex.as_deref())?;
+        let parsed = crate::f1r3fly::parse_invoice(&invoice.rgb_invoice)?;
+        log::debug!("  Contract ID: {}", parsed.contract_id);
+        log::debug!("  Amount: {:?}", parsed.amount);
+
+        let amount = parsed.amount.ok_or_else(|| {
+            TransferError::Invoice(crate::f1r3fly::InvoiceError::Core(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(
+                    "Invoice must specify amount".to_string(),
+                ),
+            ))
+        })?;
+        if amount == 0 {
+            return Err(TransferError::InvalidAmount(format!(
+                "Invoice {} requests 0 tokens",
+                position
+            )));
+        }
+
+        if let Some(first) = payments.first() {
+            if first.parsed.contract_id != parsed.contract_id {
+                return Err(TransferError::InvalidBatch(format!(
+                    "Invoice {} is for contract {}, expected {}",
+                    position, parsed.contract_id, first.parsed.contract_id
+                )));
+            }
+        }
+
+        // Blinded seal: the recipient UTXO is concealed behind an auth token,
+        // so there is no output to create and no seal to reveal. Tokens are
+        // held under the token's seal id until the recipient claims them.
+        if let RgbBeneficiary::Token(_) = &parsed.beneficiary {
+            if is_batch {
+                return Err(TransferError::InvalidBatch(
+                    "Blinded invoices cannot be paid in a batch".to_string(),
+                ));
+            }
+
+            let token = parsed.beneficiary.to_string();
+            let to_seal_id = crate::f1r3fly::blinded_seal_id(&token);
+            log::debug!("  To blinded seal: {}", to_seal_id);
+
+            payments.push(Payment {
+                parsed,
+                amount,
+                pubkey_hex,
+                expiry: invoice.expiry,
+                address: None,
+                address_str: token,
+                seal: None,
+                to_seal_id,
+                witness_mapping: None,
+            });
+            continue;
+        }
+
+        // Get recipient address from invoice
+        let recipient_addr_str = f1r3fly_rgb::get_recipient_address(&parsed.beneficiary, network)?;
+
+        // DIAGNOSTIC: Log the address extracted from invoice
+        log::debug!("🔍 Address from Invoice: {}", recipient_addr_str);
+
+        if payments
+            .iter()
+            .any(|payment| payment.address_str == recipient_addr_str)
+        {
+            return Err(TransferError::InvalidBatch(format!(
+                "Duplicate recipient address: {}",
+                recipient_addr_str
+            )));
+        }
+
+        let address = recipient_addr_str
+            .parse::<bdk_wallet::bitcoin::Address<bdk_wallet::bitcoin::address::NetworkUnchecked>>()
+            .map_err(|e| TransferError::BuildFailed(format!("Invalid recipient address: {}", e)))?
+            .assume_checked();
+
+        // Extract recipient seal from parsed invoice
+        let mut seal = f1r3fly_rgb::extract_seal(&parsed.beneficiary)?;
+
+        // To: Recipient UTXO - doesn't exist yet, so use a deterministic placeholder
+        // We use a witness identifier based on the recipient address + vout from invoice
+        // Returns (seal_id, witness_mapping) where witness_mapping is captured for claim process
+        let (to_seal_id, witness_mapping) = match &seal.primary {
+            WOutpoint::Extern(outpoint) => {
+                // Recipient UTXO already exists (rare case)
+                // CRITICAL: Use serialize_seal() to ensure correct big-endian format
+                let recipient_txo_seal = TxoSeal {
+                    primary: *outpoint,
+                    secondary: seal.secondary.clone(),
+                };
+                let seal_id =
+                    f1r3fly_rgb::contract::F1r3flyRgbContract::serialize_seal(&recipient_txo_seal);
+                (seal_id, None) // No witness mapping needed
+            }
+            WOutpoint::Wout(vout) => {
+                // Witness output: UTXO will be created in this transfer
+                // Use recipient address as stable identifier
+                // After broadcast, the recipient claims the actual UTXO (the real txid:vout)
+                let witness_id = witness_seal_id(&recipient_addr_str, vout.into_u32());
+
+                // In a batch every recipient output sits at its batch position
+                let expected_vout = if is_batch {
+                    position as u32
+                } else {
+                    vout.into_u32()
+                };
+
+                // Create witness mapping for claim process
+                let mapping = f1r3fly_rgb::WitnessMapping {
+                    witness_id: witness_id.clone(),
+                    recipient_address: recipient_addr_str.clone(),
+                    expected_vout,
+                };
+
+                (witness_id, Some(mapping))
+            }
+        };
+
+        // Only witness-output seals carry a mapping
+        if let Some(mapping) = &witness_mapping {
+            seal.primary = WOutpoint::Wout(bp::Vout::from_u32(mapping.expected_vout));
+        }
+
+        log::debug!("  To UTXO: {}", to_seal_id);
+
+        payments.push(Payment {
+            parsed,
+            amount,
+            pubkey_hex,
+            expiry: invoice.expiry,
+            address: Some(address),
+            address_str: recipient_addr_str,
+            seal: Some(seal),
+            to_seal_id,
+            witness_mapping,
+        });
+    }
+
+    Ok(payments)
+}
+
+/// Number of recipient dust outputs (blinded seals have none)
+///
+/// The change seal output follows them.
+fn recipient_output_count(payments: &[Payment]) -> u32 {
+    payments
+        .iter()
+        .filter(|payment| payment.address.is_some())
+        .count() as u32
+}
+
+/// Whether the witness tx needs a wallet dust output to host the anchor
+///
+/// Output 0 carries the Tapret commitment. When every payment is blinded
+/// there is no recipient output, and the change seal must not take its place.
+fn needs_anchor_host(payments: &[Payment]) -> bool {
+    recipient_output_count(payments) == 0
+}
+
+/// Vout of the change seal output
+///
+/// It follows the recipient outputs, or the anchor host output when there
+/// are none, so the change seal never sits at output 0.
+fn change_output_vout(payments: &[Payment]) -> u32 {
+    recipient_output_count(payments).max(1)
+}
+
+/// Refuse to start paying an expired invoice
+///
+/// Only checked before a transfer starts: recovering one that expired
+/// midway must still complete it.
+fn reject_expired(payments: &[Payment]) -> Result<(), TransferError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    match payments
+        .iter()
+        .filter_map(|payment| payment.expiry)
+        .find(|expiry| now >= *expiry)
+    {
+        Some(expiry) => Err(crate::f1r3fly::InvoiceError::Expired(expiry).into()),
+        None => Ok(()),
+    }
+}
+
+/// Build the input seals (indices `0..n` of the seals map) and their seal ids
+///
+/// Seal ids are the UTXO identifiers (txid:vout) RGB tracks balances by in
+/// Rholang.
+fn build_input_seals(
+    inputs: &[UtxoBalance],
+) -> Result<(SmallOrdMap<u16, WTxoSeal>, Vec<String>), TransferError> {
+    let mut input_seals = SmallOrdMap::new();
+    let mut input_seal_ids = Vec::with_capacity(inputs.len());
+
+    for (idx, input) in inputs.iter().enumerate() {
+        let input_seal = seal_from_outpoint_str(&input.outpoint)?;
+        let input_wtxo_seal = WTxoSeal {
+            primary: WOutpoint::Extern(input_seal.primary),
+            secondary: input_seal.secondary,
+        };
+        input_seals
+            .insert(idx as u16, input_wtxo_seal)
+            .map_err(|_| TransferError::InvalidSeal("Failed to insert input seal".to_string()))?;
+
+        // CRITICAL: Use serialize_seal() to ensure correct big-endian format
+        input_seal_ids.push(f1r3fly_rgb::contract::F1r3flyRgbContract::serialize_seal(
+            &input_seal,
+        ));
+    }
+
+    Ok((input_seals, input_seal_ids))
+}
+
+/// Split a transfer into contract calls, one per (input, destination)
+///
+/// Inputs pay the recipients in order; the remainder of the last input goes
+/// to the change seal when one is used.
+fn plan_legs(
+    inputs: &[UtxoBalance],
+    input_seal_ids: &[String],
+    payments: &[Payment],
+    change_output: Option<&ChangeOutput>,
+) -> Vec<TransferLeg> {
+    let mut legs = Vec::new();
+    let mut outstanding: Vec<u64> = payments.iter().map(|payment| payment.amount).collect();
+    let mut next_payment = 0;
+    for (input, from_seal_id) in inputs.iter().zip(input_seal_ids) {
+        let mut available = input.amount;
+
+        while available > 0 && next_payment < payments.len() {
+            if outstanding[next_payment] == 0 {
+                next_payment += 1;
+                continue;
+            }
+
+            let payment = &payments[next_payment];
+            let leg_amount = available.min(outstanding[next_payment]);
+            legs.push(TransferLeg {
+                from_seal_id: from_seal_id.clone(),
+                to_seal_id: payment.to_seal_id.clone(),
+                amount: leg_amount,
+                to_pubkey_hex: payment.pubkey_hex.clone(),
+            });
+
+            available -= leg_amount;
+            outstanding[next_payment] -= leg_amount;
+        }
+
+        if let Some(change) = change_output.filter(|_| available > 0) {
+            legs.push(TransferLeg {
+                from_seal_id: from_seal_id.clone(),
+                to_seal_id: change.witness_id.clone(),
+                amount: available,
+                to_pubkey_hex: change.owner_pubkey_hex.clone(),
+            });
+        }
+    }
+
+    legs
+}
+
+/// Build the unsigned witness transaction, before its commitment (step 4)
+///
+/// Outputs: one dust output per recipient in batch order, then the change
+/// seal output, then BDK's Bitcoin change. A transfer to a blinded seal
+/// alone gets a wallet dust output to carry the commitment instead.
+/// RGB-occupied UTXOs are never selected as inputs.
+fn build_witness_tx(
+    bitcoin_wallet: &mut BitcoinWallet,
+    payments: &[Payment],
+    anchor_host: Option<&bdk_wallet::bitcoin::Address>,
+    change_output: Option<&ChangeOutput>,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<bdk_wallet::bitcoin::Psbt, TransferError> {
+    // Build transaction sending small amount to each recipient address
+    // This creates the UTXOs that will be bound to the RGB tokens
+    const DUST_AMOUNT: u64 = 1_000; // 1000 sats minimum
+
+    // A blinded seal has no output: the anchor host dust output stands in
+    // for the recipients so output 0 can still carry the commitment
+    if needs_anchor_host(payments) != anchor_host.is_some() {
+        return Err(TransferError::BuildFailed(
+            "Anchor host must be set exactly when no recipient output exists".to_string(),
+        ));
+    }
+
+    let mut tx_builder = bitcoin_wallet.inner_mut().build_tx();
+    for address in payments
+        .iter()
+        .filter_map(|payment| payment.address.as_ref())
+        .chain(anchor_host)
+    {
+        tx_builder.add_recipient(address.script_pubkey(), Amount::from_sat(DUST_AMOUNT));
+    }
+
+    // Change seal output goes right after the recipient (or anchor host) outputs
+    if let Some(change) = change_output {
+        tx_builder.add_recipient(
+            change.address.script_pubkey(),
+            Amount::from_sat(DUST_AMOUNT),
+        );
+    }
+
+    // Keep outputs in insertion order so seal vouts in the seals map stay valid
+    tx_builder.ordering(bdk_wallet::tx_builder::TxOrdering::Untouched);
+    tx_builder.fee_rate(fee_rate.to_bdk_fee_rate());
+
+    // CRITICAL SAFETY: Exclude RGB-occupied UTXOs from coin selection
+    // This prevents accidental spending of UTXOs that hold RGB assets
+    if !rgb_occupied.is_empty() {
+        log::debug!(
+            "  Protecting {} RGB-occupied UTXO(s) from spending",
+            rgb_occupied.len()
+        );
+        for occupied_outpoint in rgb_occupied {
+            tx_builder.add_unspendable(*occupied_outpoint);
+        }
+    }
+
+    tx_builder
+        .finish()
+        .map_err(|e| TransferError::BuildFailed(format!("{}", e)))
+}
+
+/// Build the witness PSBT and embed the state commitment (steps 4-5)
+///
+/// The resulting anchor is registered in memory for every transfer
+/// operation so consignments created from the contract carry it.
+fn build_witness_psbt(
+    bitcoin_wallet: &mut BitcoinWallet,
+    contracts_manager: &mut F1r3flyContractsManager,
+    transfer: &ExecutedTransfer,
+    fee_rate: &FeeRateConfig,
+    rgb_occupied: &HashSet<OutPoint>,
+) -> Result<bdk_wallet::bitcoin::Psbt, TransferError> {
+    let anchor_method = transfer.anchor_method;
+    let state_hash = transfer.result.state_hash;
+
+    // ========================================================================
+    // Step 4: Build Bitcoin Witness Transaction
+    // ========================================================================
+    log::info!("⛓️  Step 4: Building Bitcoin witness transaction...");
+
+    // Blinded-only transfer: host the anchor on a freshly revealed (and
+    // persisted) wallet address rather than a reused one
+    let anchor_host = if needs_anchor_host(&transfer.payments) {
+        Some(bitcoin_wallet.get_new_address()?)
+    } else {
+        None
+    };
+
+    let mut psbt = build_witness_tx(
+        bitcoin_wallet,
+        &transfer.payments,
+        anchor_host.as_ref(),
+        transfer.change_output.as_ref(),
+        fee_rate,
+        rgb_occupied,
+    )?;
+
+    log::info!("✓ PSBT built");
+    log::debug!("  Outputs: {}", psbt.unsigned_tx.output.len());
+
+    // ========================================================================
     // Step 5: Embed State Commitment in Transaction
     // ========================================================================
     log::info!(
@@ -542,8 +2341,7 @@ pub async fn send_transfer(
             }
 
             // Embed Tapret commitment in the first output (index 0)
-            let tapret_proof =
-                f1r3fly_rgb::embed_tapret_commitment(&mut bp_psbt, 0, result.state_hash)?;
+            let tapret_proof = f1r3fly_rgb::embed_tapret_commitment(&mut bp_psbt, 0, state_hash)?;
 
             // Convert BP PSBT back to BDK PSBT
             let bp_psbt_bytes = bp_psbt.serialize(bp_psbt.version);
@@ -562,7 +2360,7 @@ pub async fn send_transfer(
 
             // Modify the PSBT's unsigned transaction directly to preserve witness data
             let output_index =
-                f1r3fly_rgb::embed_opreturn_commitment(&mut psbt.unsigned_tx, 0, result.state_hash)
+                f1r3fly_rgb::embed_opreturn_commitment(&mut psbt.unsigned_tx, 0, state_hash)
                     .map_err(|e| TransferError::OpReturn(e))?;
 
             // Update PSBT outputs to match the modified unsigned_tx
@@ -575,7 +2373,7 @@ pub async fn send_transfer(
             );
 
             // Create OP_RETURN anchor
-            f1r3fly_rgb::create_opreturn_anchor(result.state_hash, output_index)
+            f1r3fly_rgb::create_opreturn_anchor(state_hash, output_index)
         }
     };
 
@@ -586,31 +2384,16 @@ pub async fn send_transfer(
             AnchorMethod::OpReturn => "OP_RETURN",
         }
     );
-    log::debug!("  State hash: {}", hex::encode(result.state_hash));
+    log::debug!("  State hash: {}", hex::encode(state_hash));
     log::debug!("  Output index: 0");
 
-    // ========================================================================
-    // Step 6: Sign and Broadcast Transaction
-    // ========================================================================
-    log::info!("✍️  Step 6: Signing and broadcasting...");
-
-    #[allow(deprecated)]
-    let sign_options = bdk_wallet::SignOptions::default();
-    bitcoin_wallet
-        .inner_mut()
-        .sign(&mut psbt, sign_options)
-        .map_err(|e| TransferError::SignFailed(format!("{}", e)))?;
-
-    let tx = psbt
-        .extract_tx()
-        .map_err(|e| TransferError::BuildFailed(format!("Extract failed: {}", e)))?;
-
-    let txid = tx.compute_txid();
-    log::debug!("  Witness txid: {}", txid);
-
     // DIAGNOSTIC: Log transaction outputs to verify recipient address
-    log::debug!("🔍 Bitcoin TX Outputs ({} outputs):", tx.output.len());
-    for (vout, output) in tx.output.iter().enumerate() {
+    let network = bitcoin_wallet.network().to_bitcoin_network();
+    log::debug!(
+        "🔍 Bitcoin TX Outputs ({} outputs):",
+        psbt.unsigned_tx.output.len()
+    );
+    for (vout, output) in psbt.unsigned_tx.output.iter().enumerate() {
         use bdk_wallet::bitcoin::Address;
         if let Ok(addr) = Address::from_script(&output.script_pubkey, network) {
             log::debug!(
@@ -628,103 +2411,770 @@ pub async fn send_transfer(
         }
     }
 
+    // CRITICAL: Register anchor with the contract's tracker (not contracts_manager tracker)
+    // Each contract has its own tracker instance for its operations.
+    // Every transfer leg is anchored to the same witness transaction.
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&transfer.contract_id)
+        .ok_or_else(|| TransferError::ContractNotFound(transfer.contract_id.to_string()))?;
+    for opid in &transfer.opids {
+        contract.tracker_mut().add_anchor(*opid, anchor.clone());
+    }
+
+    Ok(psbt)
+}
+
+/// Create and save one consignment per recipient for a witness transaction
+///
+/// Files are named `<txid>_<n>.json`, or `<txid>.json` for a single
+/// recipient. Each recipient sees the inputs, its own seal and the change
+/// seal, at the same indices as in the executed seals map.
+fn write_consignments(
+    contracts_manager: &mut F1r3flyContractsManager,
+    transfer: &ExecutedTransfer,
+    recipients: &[TransferRecipient],
+    tx: &bdk_wallet::bitcoin::Transaction,
+    dir: &Path,
+) -> Result<Vec<BatchTransferItem>, TransferError> {
+    let txid = tx.compute_txid();
+    let is_batch = transfer.payments.len() > 1;
+    let recipient_base = transfer.input_seals.len() as u16;
+    let change_index = recipient_base + transfer.payments.len() as u16;
+
+    // The consignment must contain the real witness transaction with the commitment
+    let bp_tx = to_bp_tx(tx)?;
+    log::debug!("  Using actual witness TX in consignment: {}", txid);
+
+    let contract = contracts_manager
+        .contracts_mut()
+        .get_mut(&transfer.contract_id)
+        .ok_or_else(|| TransferError::ContractNotFound(transfer.contract_id.to_string()))?;
+
+    std::fs::create_dir_all(dir)?;
+
+    let mut transfers = Vec::with_capacity(transfer.payments.len());
+    for (position, payment) in transfer.payments.iter().enumerate() {
+        let mut consignment_seals = transfer.input_seals.clone();
+        if let Some(seal) = &payment.seal {
+            consignment_seals
+                .insert(recipient_base + position as u16, seal.clone())
+                .map_err(|_| {
+                    TransferError::InvalidSeal("Failed to insert recipient seal".to_string())
+                })?;
+        }
+        if let Some(change) = &transfer.change_output {
+            consignment_seals
+                .insert(change_index, change.seal.clone())
+                .map_err(|_| {
+                    TransferError::InvalidSeal("Failed to insert change seal".to_string())
+                })?;
+        }
+
+        // Create consignment with seals and actual witness transaction
+        let mut consignment = f1r3fly_rgb::F1r3flyConsignment::new(
+            &contract,
+            copy_execution_result(&transfer.result),
+            consignment_seals,
+            vec![bp_tx.clone()],
+            false, // is_genesis - this is a transfer, not genesis
+        )?;
+
+        // Add witness mapping if this is a witness transfer
+        consignment.witness_mapping =
+            payment
+                .witness_mapping
+                .as_ref()
+                .map(|mapping| f1r3fly_rgb::WitnessMapping {
+                    witness_id: mapping.witness_id.clone(),
+                    recipient_address: mapping.recipient_address.clone(),
+                    expected_vout: mapping.expected_vout,
+                });
+
+        let consignment_bytes = consignment.to_bytes()?;
+        let consignment_filename = if is_batch {
+            format!("{}_{}.json", txid, position)
+        } else {
+            format!("{}.json", txid)
+        };
+
+        let consignment_path = dir.join(&consignment_filename);
+        std::fs::write(&consignment_path, &consignment_bytes)?;
+
+        log::debug!("  Path: {}", consignment_path.display());
+        log::debug!("  Size: {} bytes", consignment_bytes.len());
+
+        transfers.push(BatchTransferItem {
+            invoice: recipients[position].invoice.clone(),
+            amount: payment.amount,
+            vout: position as u32,
+            consignment_filename,
+            consignment_path,
+            consignment_size: consignment_bytes.len(),
+        });
+    }
+
+    log::info!("✓ {} consignment(s) saved", transfers.len());
+
+    Ok(transfers)
+}
+
+/// Broadcast a witness transaction unless Esplora already has it
+///
+/// Makes the broadcast step safe to repeat after a crash.
+fn broadcast_witness(
+    esplora_client: &EsploraClient,
+    tx: &bdk_wallet::bitcoin::Transaction,
+) -> Result<(), TransferError> {
+    let txid = tx.compute_txid();
+    if let Ok(Some(_)) = esplora_client.inner().get_tx(&txid) {
+        log::info!("✓ Transaction already broadcast: {}", txid);
+        return Ok(());
+    }
+
     esplora_client
         .inner()
-        .broadcast(&tx)
+        .broadcast(tx)
         .map_err(|e| TransferError::BroadcastFailed(format!("{}", e)))?;
 
     log::info!("✓ Transaction broadcasted: {}", txid);
+    Ok(())
+}
 
-    // ========================================================================
-    // Step 7: Register Anchor in Tracker
-    // ========================================================================
-    log::info!("📌 Step 7: Registering anchor...");
+/// Record sent transfers in the wallet's transfer history
+///
+/// History is informational: the transfer is already broadcast, so a
+/// storage failure is logged rather than returned.
+fn record_outgoing_transfers(
+    contracts_manager: &mut F1r3flyContractsManager,
+    contract_id: &str,
+    witness_txid: &str,
+    anchor_method: AnchorMethod,
+    recipients: &[TransferRecipient],
+    transfers: &[BatchTransferItem],
+    opids: &[String],
+) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    for (recipient, transfer) in recipients.iter().zip(transfers) {
+        let record = TransferRecord {
+            id: None,
+            direction: TransferDirection::Outgoing,
+            contract_id: contract_id.to_string(),
+            amount: Some(transfer.amount),
+            counterparty_pubkey: recipient.resolve_pubkey().ok(),
+            witness_txid: witness_txid.to_string(),
+            anchor_method: Some(anchor_method.to_string()),
+            consignment_path: transfer.consignment_path.clone(),
+            status: TransferStatus::Broadcasted,
+            claim_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        if let Err(e) = contracts_manager
+            .claim_storage_mut()
+            .insert_transfer_record(&record)
+        {
+            log::warn!("⚠️  Failed to record transfer history: {}", e);
+        }
+    }
 
-    // Use the opid from the F1r3fly execution result
-    // (state_hash is for Bitcoin commitment, opid is for RGB tracking)
-    let opid = result.opid;
+    if let Err(e) = contracts_manager
+        .claim_storage_mut()
+        .record_transfer_opids(witness_txid, opids)
+    {
+        log::warn!("⚠️  Failed to record transfer operations: {}", e);
+    }
+}
 
-    // CRITICAL: Register anchor with the contract's tracker (not contracts_manager tracker)
-    // Each contract has its own tracker instance for its operations
-    // Get mutable reference to contract and register anchor with its tracker
-    let contract = contracts_manager
-        .contracts_mut()
-        .get_mut(&parsed.contract_id)
-        .ok_or_else(|| TransferError::ContractNotFound(contract_id_str.clone()))?;
+/// Parse a "txid:vout" string into an RGB `TxoSeal`
+///
+/// # Arguments
+///
+/// * `outpoint_str` - Outpoint in display format ("txid:vout")
+///
+/// # Returns
+///
+/// `TxoSeal` with the outpoint as primary and no fallback
+fn seal_from_outpoint_str(outpoint_str: &str) -> Result<TxoSeal, TransferError> {
+    let (txid_str, vout_str) = outpoint_str.split_once(':').ok_or_else(|| {
+        TransferError::InvalidSeal(format!(
+            "Invalid outpoint format '{}', expected 'txid:vout'",
+            outpoint_str
+        ))
+    })?;
+
+    let txid = Txid::from_str(txid_str)
+        .map_err(|e| TransferError::InvalidSeal(format!("Invalid txid '{}': {}", txid_str, e)))?;
+    let vout: u32 = vout_str
+        .parse()
+        .map_err(|e| TransferError::InvalidSeal(format!("Invalid vout '{}': {}", vout_str, e)))?;
+
+    Ok(TxoSeal {
+        primary: Outpoint::new(txid, vout),
+        secondary: TxoSealExt::Noise(Noise::strict_dumb()),
+    })
+}
+
+/// A parsed invoice in a (batch) transfer
+struct Payment {
+    parsed: crate::f1r3fly::ParsedInvoice,
+    amount: u64,
+    pubkey_hex: String,
+    /// Unix timestamp after which the invoice must not be paid
+    expiry: Option<u64>,
+    /// Recipient address receiving the dust output (None for a blinded seal)
+    address: Option<bdk_wallet::bitcoin::Address>,
+    /// Recipient address, or the auth token of a blinded seal
+    address_str: String,
+    /// Recipient seal (witness vout set to the recipient's output); a
+    /// blinded seal is never revealed to the sender
+    seal: Option<WTxoSeal>,
+    /// Seal id used in the contract call (real UTXO or witness placeholder)
+    to_seal_id: String,
+    witness_mapping: Option<f1r3fly_rgb::WitnessMapping>,
+}
+
+/// Change seal created by a multi-input transfer
+struct ChangeOutput {
+    /// Fresh wallet address receiving the change dust output
+    address: bdk_wallet::bitcoin::Address,
+    /// Output index of the change seal (right after the recipient outputs)
+    vout: u32,
+    /// Witness placeholder used as the seal id until claimed
+    witness_id: String,
+    /// Change seal as committed in the seals map
+    seal: WTxoSeal,
+    /// This wallet's F1r3fly public key (uncompressed hex), owner of the change
+    owner_pubkey_hex: String,
+}
+
+impl ChangeOutput {
+    /// Change seal at `vout`, paid to `address`
+    fn new(address: bdk_wallet::bitcoin::Address, vout: u32, owner_pubkey_hex: String) -> Self {
+        let witness_id = witness_seal_id(&address.to_string(), vout);
+        let seal = WTxoSeal {
+            primary: WOutpoint::Wout(bp::Vout::from_u32(vout)),
+            secondary: TxoSealExt::Noise(Noise::strict_dumb()),
+        };
+
+        Self {
+            address,
+            vout,
+            witness_id,
+            seal,
+            owner_pubkey_hex,
+        }
+    }
+
+    /// Rebuild from a prepared transfer or journal entry
+    fn from_prepared(change: &PreparedChange) -> Result<Self, TransferError> {
+        let address = change
+            .address
+            .parse::<bdk_wallet::bitcoin::Address<bdk_wallet::bitcoin::address::NetworkUnchecked>>()
+            .map_err(|e| TransferError::InvalidSeal(format!("Invalid change address: {}", e)))?
+            .assume_checked();
+
+        Ok(Self::new(
+            address,
+            change.vout,
+            change.owner_pubkey_hex.clone(),
+        ))
+    }
+
+    /// Serializable form, for prepared transfers and journal entries
+    fn to_prepared(&self) -> PreparedChange {
+        PreparedChange {
+            address: self.address.to_string(),
+            vout: self.vout,
+            owner_pubkey_hex: self.owner_pubkey_hex.clone(),
+        }
+    }
+}
+
+/// How the witness transaction of a transfer is signed
+#[derive(Clone, Copy)]
+enum WitnessSigning<'a> {
+    /// Sign with the wallet's keys and broadcast through Esplora, journaling
+    /// each phase
+    Inline {
+        esplora_client: &'a EsploraClient,
+        journal: &'a TransferJournal,
+    },
+    /// Leave the PSBT unsigned for offline signing
+    Offline,
+}
+
+/// Result of `execute_transfer_batch`
+struct TransferOutcome {
+    response: BatchTransferResponse,
+    /// Unsigned witness PSBT (offline signing only)
+    psbt: Option<bdk_wallet::bitcoin::Psbt>,
+    change_output: Option<ChangeOutput>,
+    /// Operations anchored to the witness transaction
+    opids: Vec<rgb::Opid>,
+}
+
+/// A transfer whose contract calls have executed, awaiting its witness
+struct ExecutedTransfer {
+    contract_id: f1r3fly_rgb::ContractId,
+    anchor_method: AnchorMethod,
+    payments: Vec<Payment>,
+    /// Input seals (indices `0..n` of the seals map)
+    input_seals: SmallOrdMap<u16, WTxoSeal>,
+    change_output: Option<ChangeOutput>,
+    /// Result of the last contract call (committed in the witness)
+    result: f1r3fly_rgb::F1r3flyExecutionResult,
+    /// Operations anchored to the witness transaction
+    opids: Vec<rgb::Opid>,
+}
+
+impl ExecutedTransfer {
+    /// Rebuild from a journal entry in phase `Executed` or later
+    ///
+    /// Invoices are parsed again; payments, seals and the change output are
+    /// derived from them exactly as when the transfer started.
+    fn from_entry(
+        entry: &TransferJournalEntry,
+        network: bdk_wallet::bitcoin::Network,
+    ) -> Result<Self, TransferError> {
+        let payments = parse_payments(&entry.recipients, network)?;
+        let contract_id = payments[0].parsed.contract_id;
+        if contract_id.to_string() != entry.contract_id {
+            return Err(TransferError::InvalidBatch(format!(
+                "Journaled invoices are for contract {}, expected {}",
+                contract_id, entry.contract_id
+            )));
+        }
+
+        let (input_seals, _) = build_input_seals(&entry.inputs)?;
+        let change_output = entry
+            .change
+            .as_ref()
+            .map(ChangeOutput::from_prepared)
+            .transpose()?;
+
+        let execution = entry.execution.as_ref().ok_or_else(|| {
+            TransferError::InvalidBatch(format!("Transfer {} has no recorded execution", entry.id))
+        })?;
+        let opids = entry
+            .opids()
+            .iter()
+            .map(|opid_hex| parse_opid(opid_hex))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            contract_id,
+            anchor_method: entry.anchor_method,
+            payments,
+            input_seals,
+            change_output,
+            result: execution_result(execution)?,
+            opids,
+        })
+    }
+}
+
+/// One `transfer` contract call: a single input seal paying one destination
+struct TransferLeg {
+    from_seal_id: String,
+    to_seal_id: String,
+    amount: u64,
+    to_pubkey_hex: String,
+}
+
+/// Build the witness placeholder seal id for an output that does not exist yet
+///
+/// Format: `witness:<first 16 bytes of sha256(address) as hex>:<vout>`
+fn witness_seal_id(address: &str, vout: u32) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(address.as_bytes());
+    let addr_hash = hex::encode(&hasher.finalize()[0..16]); // Use first 16 bytes
+    format!("witness:{}:{}", addr_hash, vout)
+}
+
+/// Get the key that owns an input seal
+///
+/// - Genesis seal: the child key at the contract's deployment derivation index
+/// - Received seal: the wallet's F1r3fly key (shared with the sender in the
+///   invoice and used to claim the seal)
+fn input_signing_key(
+    contracts_manager: &F1r3flyContractsManager,
+    contract_id_str: &str,
+    from_seal_id: &str,
+    genesis_seal_id: &str,
+) -> Result<secp256k1::SecretKey, TransferError> {
+    if from_seal_id == genesis_seal_id {
+        // Get the contract's derivation index (used when it was deployed)
+        let contract_derivation_index = contracts_manager
+            .get_contract_derivation_index(contract_id_str)
+            .map_err(|e| {
+                TransferError::ContractNotFound(format!(
+                    "Derivation index not found for contract {}: {}",
+                    contract_id_str, e
+                ))
+            })?;
+
+        contracts_manager
+            .owner_key_at_index(contract_derivation_index)
+            .map_err(TransferError::F1r3flyRgb)
+    } else {
+        contracts_manager
+            .identity_key()
+            .map_err(TransferError::F1r3flyRgb)
+    }
+}
+
+/// Record and claim the change seal of a multi-input transfer
+///
+/// The change output is located in the broadcast transaction by its script,
+/// stored as a pending claim and claimed immediately. If the claim cannot
+/// complete yet, it stays pending and is retried on the next sync.
+///
+/// # Returns
+///
+/// The change seal identifier ("txid:vout")
+async fn claim_change_seal(
+    contracts_manager: &mut F1r3flyContractsManager,
+    bitcoin_wallet: &BitcoinWallet,
+    contract_id: f1r3fly_rgb::ContractId,
+    contract_id_str: &str,
+    change: &ChangeOutput,
+    tx: &bdk_wallet::bitcoin::Transaction,
+    consignment_path: &std::path::Path,
+) -> Result<String, TransferError> {
+    let change_spk = change.address.script_pubkey();
+    let actual_vout = tx
+        .output
+        .iter()
+        .position(|output| output.script_pubkey == change_spk)
+        .ok_or_else(|| {
+            TransferError::InvalidSeal("Change output missing from witness transaction".to_string())
+        })? as u32;
+    let txid = tx.compute_txid();
+
+    // Already recorded by an earlier, interrupted attempt: pending claims are
+    // retried on sync
+    let already_claimed = contracts_manager
+        .claim_storage()
+        .get_all_claims(contract_id_str)?
+        .iter()
+        .any(|claim| claim.witness_id == change.witness_id);
+    if already_claimed {
+        log::debug!("  Change claim already recorded: {}", change.witness_id);
+        return Ok(format!("{}:{}", txid, actual_vout));
+    }
 
-    contract.tracker_mut().add_anchor(opid, anchor.clone());
+    let claim = PendingClaim {
+        id: None,
+        witness_id: change.witness_id.clone(),
+        recipient_address: change.address.to_string(),
+        expected_vout: change.vout,
+        contract_id: contract_id_str.to_string(),
+        consignment_file: consignment_path.to_path_buf(),
+        status: ClaimStatus::Pending,
+        error: None,
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        claimed_at: None,
+        actual_txid: Some(txid.to_string()),
+        actual_vout: Some(actual_vout),
+    };
 
-    log::info!("✓ Anchor registered");
+    let claim_id = contracts_manager
+        .claim_storage_mut()
+        .insert_pending_claim(&claim)
+        .map_err(|e| TransferError::ConsignmentFailed(format!("Change claim failed: {}", e)))?;
+
+    match attempt_claim(contracts_manager, bitcoin_wallet, contract_id, &claim).await {
+        Ok(claim_result) => {
+            log::info!(
+                "✓ Change claimed: {} tokens to {}",
+                claim_result.migrated_balance,
+                claim_result.to
+            );
+            contracts_manager
+                .claim_storage_mut()
+                .mark_claim_completed(claim_id)
+                .map_err(|e| {
+                    TransferError::ConsignmentFailed(format!("Change claim failed: {}", e))
+                })?;
+        }
+        Err(ClaimError::UtxoNotFound) => {
+            log::warn!("⏳ Change UTXO not found yet, will retry on next sync");
+        }
+        Err(e) => {
+            // Keep as Pending so the next sync retries the claim
+            log::warn!("⏳ Change claim deferred to next sync: {}", e);
+        }
+    }
 
-    // Contract reference will be used for consignment creation below
+    Ok(format!("{}:{}", txid, actual_vout))
+}
 
-    // Convert the actual broadcasted witness transaction to bp::Tx for consignment
-    // The consignment must contain the real witness transaction with the Tapret commitment
-    // We serialize through BDK's format and parse with bpstd's consensus format
-    let tx_bytes = bdk_wallet::bitcoin::consensus::encode::serialize(&tx);
+/// Convert a BDK transaction to `bp::Tx` for consignments
+///
+/// Serializes through BDK's consensus format and parses with bpstd's.
+fn to_bp_tx(tx: &bdk_wallet::bitcoin::Transaction) -> Result<bp::Tx, TransferError> {
+    let tx_bytes = bdk_wallet::bitcoin::consensus::encode::serialize(tx);
     let bc_tx = bpstd::Tx::consensus_deserialize(&tx_bytes[..]).map_err(|e| {
         TransferError::ConsignmentFailed(format!("Failed to deserialize witness TX: {:?}", e))
     })?;
-    let bp_tx: bp::Tx = bc_tx.into();
+    Ok(bc_tx.into())
+}
 
-    log::debug!("  Using actual witness TX in consignment: {}", txid);
+/// Copy an execution result so each batch consignment can own one
+///
+/// All consignments of a batch carry the final execution result, whose
+/// state hash is the one committed in the witness transaction.
+fn copy_execution_result(
+    result: &f1r3fly_rgb::F1r3flyExecutionResult,
+) -> f1r3fly_rgb::F1r3flyExecutionResult {
+    f1r3fly_rgb::F1r3flyExecutionResult {
+        opid: result.opid,
+        deploy_id: result.deploy_id.clone(),
+        finalized_block_hash: result.finalized_block_hash.clone(),
+        rholang_source: result.rholang_source.clone(),
+        state_hash: result.state_hash,
+    }
+}
 
-    // Create consignment with seals and actual witness transaction
-    let mut consignment = f1r3fly_rgb::F1r3flyConsignment::new(
-        &contract,
-        result,
-        seals_map,
-        vec![bp_tx],
-        false, // is_genesis - this is a transfer, not genesis
-    )?;
+/// Serializable form of an execution result, for the transfer journal
+fn execution_data(
+    result: &f1r3fly_rgb::F1r3flyExecutionResult,
+) -> Result<GenesisExecutionData, TransferError> {
+    Ok(GenesisExecutionData {
+        opid: hex::encode(&result.opid),
+        deploy_id: result
+            .deploy_id_string()
+            .map_err(|e| TransferError::ConsignmentFailed(format!("Invalid deploy ID: {}", e)))?,
+        finalized_block_hash: result
+            .block_hash_string()
+            .map_err(|e| TransferError::ConsignmentFailed(format!("Invalid block hash: {}", e)))?,
+        state_hash: result.state_hash,
+        rholang_source: String::from_utf8(result.rholang_source.to_vec()).map_err(|e| {
+            TransferError::ConsignmentFailed(format!("Invalid Rholang source: {}", e))
+        })?,
+    })
+}
+
+/// Rebuild an execution result from its journaled form
+fn execution_result(
+    data: &GenesisExecutionData,
+) -> Result<f1r3fly_rgb::F1r3flyExecutionResult, TransferError> {
+    use amplify::confinement::SmallVec;
+
+    Ok(f1r3fly_rgb::F1r3flyExecutionResult {
+        opid: parse_opid(&data.opid)?,
+        deploy_id: SmallVec::try_from(data.deploy_id.as_bytes().to_vec()).map_err(|e| {
+            TransferError::ConsignmentFailed(format!("Failed to create deploy_id: {:?}", e))
+        })?,
+        finalized_block_hash: SmallVec::try_from(data.finalized_block_hash.as_bytes().to_vec())
+            .map_err(|e| {
+                TransferError::ConsignmentFailed(format!("Failed to create block_hash: {:?}", e))
+            })?,
+        rholang_source: SmallVec::try_from(data.rholang_source.as_bytes().to_vec()).map_err(
+            |e| {
+                TransferError::ConsignmentFailed(format!(
+                    "Failed to create rholang_source: {:?}",
+                    e
+                ))
+            },
+        )?,
+        state_hash: data.state_hash,
+    })
+}
+
+/// Parse a hex operation ID
+fn parse_opid(opid_hex: &str) -> Result<rgb::Opid, TransferError> {
+    let opid_bytes: [u8; 32] = hex::decode(opid_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| TransferError::ConsignmentFailed(format!("Invalid opid: {}", opid_hex)))?;
+    Ok(rgb::Opid::from(opid_bytes))
+}
+
+/// Move a sent transfer onto a replacement witness transaction (RBF)
+///
+/// The replacement keeps every RGB output (and the embedded Tapret/OP_RETURN
+/// commitment), so the F1r3fly state hash and anchor remain valid. This:
+/// 1. Re-issues each consignment of the transfer with the new witness tx
+///    (`<new txid>[_i].json`) and points its history record at it
+/// 2. Re-registers the anchor for every leg operation in the contract's tracker
+/// 3. Rebinds this wallet's change claim to the new witness txid
+///
+/// Recipients must accept the re-issued consignment.
+///
+/// # Arguments
+///
+/// * `contracts_manager` - Contracts manager holding the transfer records
+/// * `original_txid` - Replaced witness transaction ID
+/// * `replacement` - Signed replacement transaction
+/// * `consignments_dir` - Directory for the re-issued consignments
+///
+/// # Returns
+///
+/// Paths of the re-issued consignments
+///
+/// # Example
+///
+/// ```ignore
+/// let bump = bump_fee(&mut wallet, &esplora, txid, &fee_rate, &rgb_occupied)?;
+/// let consignments = replace_witness_transaction(
+///     &mut contracts_manager, &txid.to_string(), &bump.tx, consignments_dir,
+/// ).await?;
+/// ```
+pub async fn replace_witness_transaction(
+    contracts_manager: &mut F1r3flyContractsManager,
+    original_txid: &str,
+    replacement: &bdk_wallet::bitcoin::Transaction,
+    consignments_dir: PathBuf,
+) -> Result<Vec<PathBuf>, TransferError> {
+    let new_txid = replacement.compute_txid().to_string();
+    log::info!(
+        "🔁 Replacing witness transaction {} -> {}",
+        original_txid,
+        new_txid
+    );
+
+    let records: Vec<TransferRecord> = contracts_manager
+        .claim_storage()
+        .get_transfers(None, None, None)?
+        .into_iter()
+        .filter(|record| {
+            record.direction == TransferDirection::Outgoing && record.witness_txid == original_txid
+        })
+        .collect();
 
-    // Add witness mapping if this is a witness transfer
-    consignment.witness_mapping = witness_mapping;
+    let first_record = records.first().ok_or_else(|| {
+        TransferError::WitnessNotFound(format!(
+            "No sent transfer uses witness transaction {}",
+            original_txid
+        ))
+    })?;
+    let contract_id_str = first_record.contract_id.clone();
+    let contract_id = f1r3fly_rgb::ContractId::from_str(&contract_id_str)
+        .map_err(|e| TransferError::ContractNotFound(format!("{}: {}", contract_id_str, e)))?;
 
-    log::info!("✓ Consignment created");
+    let bp_tx = to_bp_tx(replacement)?;
 
     // ========================================================================
-    // Step 8: Serialize and Save Consignment
+    // Re-issue consignments with the replacement witness transaction
     // ========================================================================
-    log::info!("💾 Step 8: Saving consignment...");
+    std::fs::create_dir_all(&consignments_dir)?;
+
+    let mut consignment_paths = Vec::with_capacity(records.len());
+    let mut anchor = None;
+    let mut proof_opids = Vec::new();
+    for record in &records {
+        let consignment_bytes = std::fs::read(&record.consignment_path)?;
+        let mut consignment = f1r3fly_rgb::F1r3flyConsignment::from_bytes(&consignment_bytes)?;
+        consignment.witness_txs = vec![bp_tx.clone()];
+
+        let old_filename = record
+            .consignment_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let consignment_filename = if old_filename.contains(original_txid) {
+            old_filename.replace(original_txid, &new_txid)
+        } else {
+            format!("{}.json", new_txid)
+        };
+        let consignment_path = consignments_dir.join(&consignment_filename);
+        std::fs::write(&consignment_path, consignment.to_bytes()?)?;
 
-    let consignment_bytes = consignment.to_bytes()?;
-    let consignment_filename = format!("{}.json", txid);
+        log::debug!("  Re-issued consignment: {}", consignment_path.display());
 
-    // Ensure consignments directory exists
-    std::fs::create_dir_all(&consignments_dir)?;
+        if let Some(record_id) = record.id {
+            contracts_manager
+                .claim_storage_mut()
+                .update_transfer_witness(record_id, &new_txid, &consignment_path)?;
+        }
 
-    let consignment_path = consignments_dir.join(&consignment_filename);
-    std::fs::write(&consignment_path, &consignment_bytes)?;
+        let opid = rgb::Opid::from(consignment.f1r3fly_proof().state_hash);
+        if !proof_opids.contains(&opid) {
+            proof_opids.push(opid);
+        }
+        if anchor.is_none() {
+            anchor = Some(consignment.bitcoin_anchor.clone());
+        }
+        consignment_paths.push(consignment_path);
+    }
 
-    log::info!("✓ Consignment saved");
-    log::debug!("  Path: {}", consignment_path.display());
-    log::debug!("  Size: {} bytes", consignment_bytes.len());
+    log::info!("✓ {} consignment(s) re-issued", consignment_paths.len());
 
     // ========================================================================
-    // Step 9: Persist State
+    // Update the tracker's anchors
     // ========================================================================
-    log::info!("💾 Step 9: Persisting state...");
+    // The commitment output is unchanged, so the anchor itself is the same;
+    // registering it again binds every leg to the replacement witness.
+    // Transfers recorded before leg opids were kept fall back to the
+    // operations named by the consignment proofs.
+    let recorded_opids = contracts_manager
+        .claim_storage()
+        .get_transfer_opids(original_txid)?;
+    let opids = if recorded_opids.is_empty() {
+        proof_opids
+    } else {
+        recorded_opids
+            .iter()
+            .map(|opid_hex| parse_opid(opid_hex))
+            .collect::<Result<Vec<_>, _>>()?
+    };
 
-    bitcoin_wallet.persist()?;
-    contracts_manager
-        .save_state()
-        .map_err(|e| TransferError::ConsignmentFailed(format!("State save failed: {}", e)))?;
+    if let Some(anchor) = anchor {
+        let contract = contracts_manager
+            .contracts_mut()
+            .get_mut(&contract_id)
+            .ok_or_else(|| TransferError::ContractNotFound(contract_id_str.clone()))?;
+        for opid in &opids {
+            contract.tracker_mut().add_anchor(*opid, anchor.clone());
+        }
+        log::info!("✓ Anchor re-registered for {} operation(s)", opids.len());
+    }
 
-    log::info!("✓ State persisted");
+    let opid_strings: Vec<String> = opids.iter().map(hex::encode).collect();
+    contracts_manager
+        .claim_storage_mut()
+        .record_transfer_opids(&new_txid, &opid_strings)?;
 
     // ========================================================================
-    // Success!
+    // Rebind change claims to the replacement witness
     // ========================================================================
-    log::info!("✅ Transfer complete!");
+    let change_claims: Vec<PendingClaim> = contracts_manager
+        .claim_storage()
+        .get_all_claims(&contract_id_str)?
+        .into_iter()
+        .filter(|claim| claim.actual_txid.as_deref() == Some(original_txid))
+        .collect();
+
+    for claim in &change_claims {
+        let vout = claim.actual_vout.unwrap_or(claim.expected_vout);
+        rebind_claim(contracts_manager, contract_id, claim, &new_txid, vout)
+            .await
+            .map_err(|e| {
+                TransferError::ConsignmentFailed(format!("Change rebind failed: {}", e))
+            })?;
+        if let Some(claim_id) = claim.id {
+            contracts_manager
+                .claim_storage_mut()
+                .update_claim_actual_utxo(claim_id, &new_txid, vout)?;
+        }
+    }
 
-    Ok(TransferResponse {
-        bitcoin_txid: txid.to_string(),
-        consignment_filename,
-        consignment_path,
-        consignment_size: consignment_bytes.len(),
-        status: "broadcasted".to_string(),
-        amount,
-        change_amount,
-    })
+    contracts_manager
+        .save_state()
+        .map_err(|e| TransferError::ConsignmentFailed(format!("State save failed: {}", e)))?;
+
+    log::info!("✅ Witness transaction replaced");
+
+    Ok(consignment_paths)
 }
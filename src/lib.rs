@@ -6,7 +6,9 @@
 pub mod bitcoin;
 pub mod cli;
 pub mod config;
+pub mod daemon;
 pub mod f1r3fly;
 pub mod manager;
+pub mod rpc;
 pub mod storage;
 pub mod types;
@@ -3,9 +3,18 @@
 //! Command-line interface for managing Bitcoin wallets with RGB smart contracts support
 
 use clap::Parser;
-use f1r3fly_rgb_wallet::cli::args::{Cli, Commands, ConfigAction, WalletAction};
-use f1r3fly_rgb_wallet::cli::commands;
+use f1r3fly_rgb_wallet::cli::args::{Cli, Commands, ConfigAction, DaemonAction, WalletAction};
+use f1r3fly_rgb_wallet::cli::password::WALLET_PASSWORD_ENV;
+use f1r3fly_rgb_wallet::cli::{commands, runtime};
 use f1r3fly_rgb_wallet::config::{ConfigOverrides, NetworkType};
+#[cfg(unix)]
+use f1r3fly_rgb_wallet::daemon::{
+    self,
+    client::{DaemonClient, ForwardedOutput},
+};
+use f1r3fly_rgb_wallet::f1r3fly::RestoreOptions;
+use f1r3fly_rgb_wallet::storage::keystore::KEYSTORE_PASSWORD_ENV;
+use std::path::PathBuf;
 use std::process;
 
 fn main() {
@@ -17,20 +26,43 @@ fn main() {
 
     let cli = Cli::parse();
 
-    // Parse network string to NetworkType
-    let network = cli.network.as_ref().and_then(|n| match n.as_str() {
-        "regtest" => Some(NetworkType::Regtest),
-        "signet" => Some(NetworkType::Signet),
-        "testnet" => Some(NetworkType::Testnet),
-        "mainnet" => Some(NetworkType::Mainnet),
-        _ => {
-            eprintln!(
-                "Error: Invalid network '{}'. Use: regtest, signet, testnet, or mainnet",
-                n
-            );
+    // Hand the command to a daemon holding the wallet unlocked, if any
+    #[cfg(unix)]
+    if let Some(output) = forward_to_daemon(&cli) {
+        print!("{}", output.stdout);
+        eprint!("{}", output.stderr);
+        if let Some(error) = output.error {
+            eprintln!("Error: {}", error);
             process::exit(1);
         }
-    });
+        return;
+    }
+
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Dispatch a parsed command
+///
+/// Also runs commands forwarded to the daemon, so it must not exit the process.
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    // Parse network string to NetworkType
+    let network = cli
+        .network
+        .as_ref()
+        .map(|n| match n.as_str() {
+            "regtest" => Ok(NetworkType::Regtest),
+            "signet" => Ok(NetworkType::Signet),
+            "testnet" => Ok(NetworkType::Testnet),
+            "mainnet" => Ok(NetworkType::Mainnet),
+            _ => Err(format!(
+                "Invalid network '{}'. Use: regtest, signet, testnet, or mainnet",
+                n
+            )),
+        })
+        .transpose()?;
 
     // Build config overrides from global arguments
     let overrides = ConfigOverrides {
@@ -40,67 +72,258 @@ fn main() {
         f1r3node_http_port: cli.f1r3node_http_port,
         esplora_url: cli.esplora_url.clone(),
         wallets_dir: cli.data_dir.clone(),
+        f1r3node_master_key: None,
     };
 
-    let result: Result<(), Box<dyn std::error::Error>> = match cli.command {
+    // Wallet the command runs against (lent by the daemon when it holds it)
+    let wallet = cli.wallet.clone();
+
+    match cli.command {
         Commands::Config { action } => match action {
             ConfigAction::Init { network } => commands::config::init(network).map_err(Into::into),
+            ConfigAction::SetAnchor { method } => {
+                commands::config::set_anchor(method).map_err(Into::into)
+            }
+            ConfigAction::ImportMasterKey {
+                id,
+                key,
+                password,
+                key_file,
+            } => {
+                let password = match key_file {
+                    Some(_) => None,
+                    None => {
+                        Some(password.read(KEYSTORE_PASSWORD_ENV, "Keystore password", true)?)
+                    }
+                };
+                commands::config::import_master_key(id, key, password, key_file).map_err(Into::into)
+            }
+            ConfigAction::SetKeyMode { mode } => {
+                commands::config::set_key_mode(mode).map_err(Into::into)
+            }
+            ConfigAction::SetKdf { kdf } => commands::config::set_kdf(kdf).map_err(Into::into),
         },
 
         Commands::Wallet { action } => match action {
             WalletAction::Create { name, password } => {
-                commands::wallet::create(name, password, overrides).map_err(Into::into)
+                commands::wallet::create(name, password.new_wallet_password()?, overrides)
+                    .map_err(Into::into)
             }
 
             WalletAction::Import {
                 name,
                 mnemonic,
                 password,
-            } => commands::wallet::import(name, mnemonic, password, overrides).map_err(Into::into),
+            } => {
+                commands::wallet::import(name, mnemonic, password.new_wallet_password()?, overrides)
+                    .map_err(Into::into)
+            }
+
+            WalletAction::Restore {
+                name,
+                backup: Some(backup),
+                force,
+                password,
+                ..
+            } => commands::wallet::restore_from_backup(
+                backup,
+                name,
+                password.read(WALLET_PASSWORD_ENV, "Backup password", false)?,
+                force,
+                overrides,
+            )
+            .map_err(Into::into),
+
+            WalletAction::Restore {
+                name,
+                mnemonic,
+                password,
+                consignments_dir,
+                stop_gap,
+                derivation_start,
+                derivation_scan_limit,
+                ..
+            } => runtime()?
+                .block_on(commands::wallet::restore(
+                    name.unwrap_or_default(),
+                    mnemonic.unwrap_or_default(),
+                    password.new_wallet_password()?,
+                    RestoreOptions {
+                        consignments_dir: consignments_dir.map(PathBuf::from),
+                        stop_gap,
+                        derivation_start,
+                        derivation_scan_limit,
+                    },
+                    overrides,
+                ))
+                .map_err(Into::into),
+
+            WalletAction::ChangePassword {
+                name,
+                mut password,
+                old_password,
+                new_password,
+            } => {
+                // --old-password is the deprecated spelling of --password
+                password.password = password.password.or(old_password);
+                let password = password.wallet_password(Some(&name))?;
+                commands::wallet::change_password(
+                    name,
+                    password,
+                    new_password.new_wallet_password()?,
+                    overrides,
+                )
+                .map_err(Into::into)
+            }
+
+            WalletAction::Backup {
+                name,
+                output,
+                password,
+            } => {
+                let password = password.wallet_password(Some(&name))?;
+                commands::wallet::backup(name, output, password, overrides).map_err(Into::into)
+            }
 
             WalletAction::List => commands::wallet::list(overrides).map_err(Into::into),
 
             WalletAction::GetF1r3flyPubkey => {
-                commands::wallet::get_f1r3fly_pubkey(overrides).map_err(Into::into)
+                commands::wallet::get_f1r3fly_pubkey(cli.wallet, overrides).map_err(Into::into)
             }
-        },
 
-        Commands::Sync { password } => match tokio::runtime::Runtime::new() {
-            Ok(runtime) => runtime
-                .block_on(commands::bitcoin::sync(cli.wallet, password, overrides))
-                .map_err(Into::into),
-            Err(e) => Err(Box::new(e) as Box<dyn std::error::Error>),
+            WalletAction::SetAnchor { name, method } => {
+                commands::wallet::set_anchor(name, method, overrides).map_err(Into::into)
+            }
         },
 
-        Commands::GetBalance { password } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::bitcoin::get_balance(
-                    cli.wallet, password, overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
+        #[cfg(unix)]
+        Commands::Daemon { action } => match action {
+            DaemonAction::Start => {
+                commands::daemon::start(cli.socket, overrides, run).map_err(Into::into)
+            }
+            DaemonAction::Unlock { timeout, password } => commands::daemon::unlock(
+                cli.wallet,
+                timeout,
+                password.wallet_password(wallet.as_deref())?,
+                cli.socket,
+            )
+            .map_err(Into::into),
+            DaemonAction::Lock => commands::daemon::lock(cli.socket).map_err(Into::into),
+            DaemonAction::Status => commands::daemon::status(cli.socket).map_err(Into::into),
+            DaemonAction::Stop => commands::daemon::stop(cli.socket).map_err(Into::into),
         },
 
-        Commands::GetAddresses { count, password } => {
-            commands::bitcoin::get_addresses(cli.wallet, count, password, overrides)
-                .map_err(Into::into)
-        }
+        #[cfg(not(unix))]
+        Commands::Daemon { .. } => Err("The daemon requires Unix domain sockets".into()),
+
+        #[cfg(unix)]
+        Commands::Rpc {
+            rpc_socket,
+            password,
+        } => commands::rpc::serve(
+            cli.wallet,
+            rpc_socket,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
+
+        #[cfg(not(unix))]
+        Commands::Rpc { .. } => Err("The RPC server requires Unix domain sockets".into()),
+
+        Commands::Sync { password } => runtime()?
+            .block_on(commands::bitcoin::sync(
+                cli.wallet,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::GetBalance { password } => runtime()?
+            .block_on(commands::bitcoin::get_balance(
+                cli.wallet,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::GetAddresses { count, password } => commands::bitcoin::get_addresses(
+            cli.wallet,
+            count,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
 
         Commands::CreateUtxo {
             amount,
             fee_rate,
             password,
-        } => commands::bitcoin::create_utxo(cli.wallet, amount, fee_rate, password, overrides)
-            .map_err(Into::into),
+        } => commands::bitcoin::create_utxo(
+            cli.wallet,
+            amount,
+            fee_rate,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
 
         Commands::SendBitcoin {
             to,
             amount,
             fee_rate,
             password,
-        } => commands::bitcoin::send_bitcoin(cli.wallet, to, amount, fee_rate, password, overrides)
+        } => commands::bitcoin::send_bitcoin(
+            cli.wallet,
+            to,
+            amount,
+            fee_rate,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
+
+        Commands::BumpFee {
+            txid,
+            fee_rate,
+            password,
+        } => runtime()?
+            .block_on(commands::bitcoin::bump_fee(
+                cli.wallet,
+                txid,
+                fee_rate,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
             .map_err(Into::into),
 
+        Commands::Cpfp {
+            txid,
+            fee_rate,
+            password,
+        } => runtime()?
+            .block_on(commands::bitcoin::cpfp(
+                cli.wallet,
+                txid,
+                fee_rate,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::SignPsbt {
+            psbt,
+            output,
+            password,
+        } => commands::bitcoin::sign_psbt(
+            cli.wallet,
+            psbt,
+            output,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
+
         Commands::IssueAsset {
             ticker,
             name,
@@ -109,29 +332,30 @@ fn main() {
             genesis_utxo,
             password,
         } => match cli.wallet.as_deref() {
-            Some(wallet_name) => match tokio::runtime::Runtime::new() {
-                Ok(rt) => rt
-                    .block_on(commands::rgb::issue_asset(
-                        wallet_name,
-                        &ticker,
-                        &name,
-                        supply,
-                        precision,
-                        &genesis_utxo,
-                        &password,
-                        &overrides,
-                    ))
-                    .map(|_| ())
-                    .map_err(Into::into),
-                Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-            },
+            Some(wallet_name) => runtime()?
+                .block_on(commands::rgb::issue_asset(
+                    wallet_name,
+                    &ticker,
+                    &name,
+                    supply,
+                    precision,
+                    &genesis_utxo,
+                    &password.wallet_password(Some(wallet_name))?,
+                    &overrides,
+                ))
+                .map(|_| ())
+                .map_err(Into::into),
             None => Err("Wallet name required (use --wallet <name>)".into()),
         },
 
         Commands::ListAssets { password } => match cli.wallet.as_deref() {
-            Some(wallet_name) => commands::rgb::list_assets(wallet_name, &password, &overrides)
-                .map(|_| ())
-                .map_err(Into::into),
+            Some(wallet_name) => commands::rgb::list_assets(
+                wallet_name,
+                &password.wallet_password(Some(wallet_name))?,
+                &overrides,
+            )
+            .map(|_| ())
+            .map_err(Into::into),
             None => Err("Wallet name required (use --wallet <name>)".into()),
         },
 
@@ -139,18 +363,15 @@ fn main() {
             contract_id,
             password,
         } => match cli.wallet.as_deref() {
-            Some(wallet_name) => match tokio::runtime::Runtime::new() {
-                Ok(rt) => rt
-                    .block_on(commands::rgb::rgb_balance(
-                        wallet_name,
-                        contract_id.as_deref(),
-                        &password,
-                        &overrides,
-                    ))
-                    .map(|_| ())
-                    .map_err(Into::into),
-                Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-            },
+            Some(wallet_name) => runtime()?
+                .block_on(commands::rgb::rgb_balance(
+                    wallet_name,
+                    contract_id.as_deref(),
+                    &password.wallet_password(Some(wallet_name))?,
+                    &overrides,
+                ))
+                .map(|_| ())
+                .map_err(Into::into),
             None => Err("Wallet name required (use --wallet <name>)".into()),
         },
 
@@ -158,11 +379,14 @@ fn main() {
             contract_id,
             password,
         } => match cli.wallet.as_deref() {
-            Some(wallet_name) => {
-                commands::rgb::get_contract_info(wallet_name, &contract_id, &password, &overrides)
-                    .map(|_| ())
-                    .map_err(Into::into)
-            }
+            Some(wallet_name) => commands::rgb::get_contract_info(
+                wallet_name,
+                &contract_id,
+                &password.wallet_password(Some(wallet_name))?,
+                &overrides,
+            )
+            .map(|_| ())
+            .map_err(Into::into),
             None => Err("Wallet name required (use --wallet <name>)".into()),
         },
 
@@ -170,20 +394,21 @@ fn main() {
             contract_id,
             amount,
             address,
+            expires_in,
+            blinded,
             password,
-        } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::generate_invoice_cmd(
-                    cli.wallet,
-                    contract_id,
-                    amount,
-                    address,
-                    password,
-                    overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-        },
+        } => runtime()?
+            .block_on(commands::generate_invoice_cmd(
+                cli.wallet,
+                contract_id,
+                amount,
+                address,
+                expires_in,
+                blinded,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
 
         Commands::ParseInvoice { invoice, network } => {
             let net = network.as_ref().and_then(|n| match n.as_str() {
@@ -194,14 +419,26 @@ fn main() {
                 _ => None,
             });
 
-            match tokio::runtime::Runtime::new() {
-                Ok(rt) => rt
-                    .block_on(commands::parse_invoice_cmd(invoice, net))
-                    .map_err(Into::into),
-                Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-            }
+            runtime()?
+                .block_on(commands::parse_invoice_cmd(invoice, net))
+                .map_err(Into::into)
         }
 
+        Commands::ListInvoices {
+            contract_id,
+            status,
+            format,
+            password,
+        } => commands::list_invoices_cmd(
+            cli.wallet,
+            contract_id,
+            status,
+            format,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
+
         Commands::ListUtxos {
             available_only,
             rgb_only,
@@ -209,96 +446,168 @@ fn main() {
             min_amount,
             format,
             password,
-        } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::bitcoin::list_utxos(
-                    cli.wallet,
-                    password,
-                    available_only,
-                    rgb_only,
-                    confirmed_only,
-                    min_amount,
-                    format,
-                    overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-        },
+        } => runtime()?
+            .block_on(commands::bitcoin::list_utxos(
+                cli.wallet,
+                password.wallet_password(wallet.as_deref())?,
+                available_only,
+                rgb_only,
+                confirmed_only,
+                min_amount,
+                format,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::ListTransactions { format, password } => commands::bitcoin::list_transactions(
+            cli.wallet,
+            password.wallet_password(wallet.as_deref())?,
+            format,
+            overrides,
+        )
+        .map_err(Into::into),
 
         Commands::SendTransfer {
             invoice,
             recipient_pubkey,
             fee_rate,
+            anchor,
+            psbt_out,
+            dry_run,
             password,
-        } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::transfer::send_transfer(
-                    cli.wallet,
-                    invoice,
-                    recipient_pubkey,
-                    fee_rate,
-                    password,
-                    overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-        },
+        } => runtime()?
+            .block_on(commands::transfer::send_transfer(
+                cli.wallet,
+                invoice,
+                recipient_pubkey,
+                fee_rate,
+                anchor,
+                psbt_out,
+                dry_run,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::SendTransferBatch {
+            recipients_file,
+            fee_rate,
+            anchor,
+            psbt_out,
+            dry_run,
+            password,
+        } => runtime()?
+            .block_on(commands::transfer::send_transfer_batch(
+                cli.wallet,
+                recipients_file,
+                fee_rate,
+                anchor,
+                psbt_out,
+                dry_run,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::FinalizeAndBroadcast { psbt, password } => runtime()?
+            .block_on(commands::transfer::finalize_and_broadcast(
+                cli.wallet,
+                psbt,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::RecoverTransfers { password } => runtime()?
+            .block_on(commands::transfer::recover_transfers(
+                cli.wallet,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
 
         Commands::AcceptConsignment {
             consignment_path,
             password,
-        } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::transfer::accept_consignment(
-                    cli.wallet,
-                    consignment_path,
-                    password,
-                    overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-        },
+        } => runtime()?
+            .block_on(commands::transfer::accept_consignment(
+                cli.wallet,
+                consignment_path,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
 
         Commands::ListClaims {
             contract_id,
             format,
             password,
-        } => match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt
-                .block_on(commands::transfer::list_claims(
-                    cli.wallet,
-                    contract_id,
-                    format,
-                    password,
-                    overrides,
-                ))
-                .map_err(Into::into),
-            Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-        },
+        } => runtime()?
+            .block_on(commands::transfer::list_claims(
+                cli.wallet,
+                contract_id,
+                format,
+                password.wallet_password(wallet.as_deref())?,
+                overrides,
+            ))
+            .map_err(Into::into),
+
+        Commands::ListTransfers {
+            contract_id,
+            from,
+            to,
+            format,
+            password,
+        } => commands::transfer::list_transfers(
+            cli.wallet,
+            contract_id,
+            from,
+            to,
+            format,
+            password.wallet_password(wallet.as_deref())?,
+            overrides,
+        )
+        .map_err(Into::into),
 
         Commands::ExportGenesis {
             contract_id,
             output,
             password,
         } => match cli.wallet.as_deref() {
-            Some(wallet_name) => match tokio::runtime::Runtime::new() {
-                Ok(rt) => rt
-                    .block_on(commands::rgb::export_genesis(
-                        wallet_name.to_string(),
-                        contract_id,
-                        output,
-                        password,
-                        overrides,
-                    ))
-                    .map_err(Into::into),
-                Err(e) => Err(format!("Failed to create async runtime: {}", e).into()),
-            },
+            Some(wallet_name) => runtime()?
+                .block_on(commands::rgb::export_genesis(
+                    wallet_name.to_string(),
+                    contract_id,
+                    output,
+                    password.wallet_password(Some(wallet_name))?,
+                    overrides,
+                ))
+                .map_err(Into::into),
             None => Err("Wallet name required (use --wallet <name>)".into()),
         },
-    };
+    }
+}
 
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+/// Forward the invocation to a daemon holding its wallet unlocked
+///
+/// # Returns
+///
+/// The daemon's output, or `None` to run the command locally
+#[cfg(unix)]
+fn forward_to_daemon(cli: &Cli) -> Option<ForwardedOutput> {
+    // Commands that create, restore or re-key wallets always run locally
+    let forwardable = !matches!(
+        cli.command,
+        Commands::Config { .. }
+            | Commands::Wallet { .. }
+            | Commands::Daemon { .. }
+            | Commands::Rpc { .. }
+            | Commands::ParseInvoice { .. }
+    );
+    if cli.no_daemon || !forwardable || cli.wallet.is_none() {
+        return None;
     }
+
+    let socket = daemon::socket_path(cli.socket.as_deref()).ok()?;
+    DaemonClient::new(socket).forward(std::env::args().collect())
 }
@@ -3,30 +3,39 @@
 //! Coordinates between config, storage, Bitcoin, and F1r3fly layers
 
 use crate::bitcoin::{
-    create_utxo, get_addresses, get_balance, sync_wallet, AddressInfo, Balance, BalanceError,
-    BitcoinWallet, BitcoinWalletError, EsploraClient, FeeRateConfig, NetworkError, SyncError,
-    SyncResult, UtxoError, UtxoOperationResult,
+    build_cpfp, build_fee_bump, create_utxo, finalize_psbt, full_scan_wallet, get_addresses,
+    get_balance, get_recommended_fee_rates, list_transactions, load_psbt, mark_rgb_occupied,
+    save_psbt, sign_psbt, sync_wallet, unmark_rgb_occupied, AddressInfo, Balance, BalanceError,
+    BitcoinWallet, BitcoinWalletError, EsploraClient, FeeRateConfig, NetworkError, PsbtError,
+    PsbtSummary, SyncError, SyncResult, UtxoError, UtxoOperationResult,
 };
 use crate::config::{ConfigError, GlobalConfig};
 use crate::f1r3fly::balance::BalanceError as RgbBalanceError;
 use crate::f1r3fly::executor::F1r3flyExecutorError;
 use crate::f1r3fly::{
     attempt_claim, get_asset_balance, get_asset_info, get_occupied_utxos, get_rgb_balance,
-    get_rgb_seal_info, issue_asset, list_assets, AssetBalance, AssetError, AssetInfo,
+    get_rgb_seal_info, issue_asset, list_assets, AnchorMethod, AssetBalance, AssetError, AssetInfo,
     AssetListItem, ClaimError, ContractsManagerError, F1r3flyContractsManager,
-    F1r3flyExecutorManager, IssueAssetRequest, RgbOccupiedUtxo,
+    F1r3flyExecutorManager, IssueAssetRequest, RestoreError, RestoreOptions, RestoreReport,
+    RgbOccupiedUtxo, TransferError,
 };
 use crate::storage::{
-    file_system::{create_wallet_directory, load_wallet, save_wallet, wallet_dir, FileSystemError},
+    file_system::{
+        create_wallet_directory, load_wallet_with_kdf, save_wallet_with_kdf,
+        update_wallet_metadata, wallet_dir, FileSystemError,
+    },
     keys::{generate_mnemonic, KeyError},
     models::{WalletKeys, WalletMetadata},
-    ClaimStatus,
+    ClaimStatus, PendingClaim, SealRebind, TransferDirection, WitnessReplacement,
+};
+use crate::types::{
+    CpfpResponse, FeeBumpResponse, PsbtBroadcastResponse, RgbTxAnnotation, RgbTxKind,
+    TransactionInfo, TxDirection, UtxoFilter, UtxoInfo, UtxoStatus,
 };
-use crate::types::{UtxoFilter, UtxoInfo, UtxoStatus};
 use bdk_wallet::bitcoin::OutPoint;
 #[allow(deprecated)]
 use bdk_wallet::{KeychainKind, SignOptions};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 /// Errors that can occur in the wallet manager
@@ -56,6 +65,9 @@ pub enum ManagerError {
     #[error("UTXO error: {0}")]
     Utxo(#[from] UtxoError),
 
+    #[error("PSBT error: {0}")]
+    Psbt(#[from] PsbtError),
+
     #[error("Asset error: {0}")]
     Asset(#[from] AssetError),
 
@@ -71,6 +83,9 @@ pub enum ManagerError {
     #[error("Storage error: {0}")]
     Storage(#[from] crate::storage::StorageError),
 
+    #[error("Restore error: {0}")]
+    Restore(#[from] RestoreError),
+
     #[error("Wallet not loaded")]
     WalletNotLoaded,
 
@@ -82,6 +97,16 @@ pub enum ManagerError {
 
     #[error("F1r3fly not initialized - wallet must be loaded first")]
     F1r3flyNotInitialized,
+
+    #[error(
+        "Failed to broadcast replacement: {broadcast}; restoring witness {txid} also failed \
+         (repaired on next sync): {rollback}"
+    )]
+    WitnessRollbackFailed {
+        txid: String,
+        broadcast: String,
+        rollback: String,
+    },
 }
 
 /// Main wallet manager
@@ -114,6 +139,9 @@ pub struct WalletManager {
     esplora_client: EsploraClient,
 
     /// Set of UTXOs marked as RGB-occupied
+    ///
+    /// Mirrors the `rgb_occupied_utxos` table in the wallet's claims database
+    /// and is rebuilt whenever a wallet is loaded.
     rgb_occupied: HashSet<OutPoint>,
 }
 
@@ -184,19 +212,22 @@ impl WalletManager {
         let mnemonic = generate_mnemonic()?;
         let wallet_keys = WalletKeys::from_mnemonic(&mnemonic, self.config.bitcoin.network)?;
 
-        // Create metadata
-        let metadata = WalletMetadata::new(name.to_string(), self.config.bitcoin.network);
+        // Create metadata (key mode is fixed for the wallet's lifetime)
+        let mut metadata = WalletMetadata::new(name.to_string(), self.config.bitcoin.network);
+        metadata.key_mode = self.config.f1r3node.key_mode;
 
         // Save encrypted wallet
-        save_wallet(name, &wallet_keys, &metadata, password, wallets_dir)?;
+        let kdf = self.config.kdf.unwrap_or_default();
+        save_wallet_with_kdf(name, &wallet_keys, &metadata, password, &kdf, wallets_dir)?;
 
-        // Initialize BDK wallet
+        // Initialize BDK wallet watch-only, then load the decrypted signing keys
         let wallet_path = wallet_dir(name, wallets_dir)?;
         let mut bitcoin_wallet = BitcoinWallet::new(
-            wallet_keys.bitcoin_descriptor.clone(),
+            wallet_keys.bitcoin_public_descriptor.clone(),
             self.config.bitcoin.network,
             &wallet_path,
         )?;
+        bitcoin_wallet.unlock(&wallet_keys.bitcoin_descriptor)?;
 
         // Reveal the first external address so the wallet is ready to receive funds
         // This ensures get_addresses() returns a tracked address that BDK will sync
@@ -214,6 +245,9 @@ impl WalletManager {
         // Initialize F1r3fly managers
         self.initialize_f1r3fly(name, &wallet_keys)?;
 
+        // Restore RGB-occupied UTXO protection
+        self.rebuild_rgb_occupied()?;
+
         // Return mnemonic for user backup
         Ok(mnemonic.to_string())
     }
@@ -255,19 +289,22 @@ impl WalletManager {
         // Derive keys from mnemonic
         let wallet_keys = WalletKeys::from_mnemonic(&mnemonic, self.config.bitcoin.network)?;
 
-        // Create metadata
-        let metadata = WalletMetadata::new(name.to_string(), self.config.bitcoin.network);
+        // Create metadata (key mode is fixed for the wallet's lifetime)
+        let mut metadata = WalletMetadata::new(name.to_string(), self.config.bitcoin.network);
+        metadata.key_mode = self.config.f1r3node.key_mode;
 
         // Save encrypted wallet
-        save_wallet(name, &wallet_keys, &metadata, password, wallets_dir)?;
+        let kdf = self.config.kdf.unwrap_or_default();
+        save_wallet_with_kdf(name, &wallet_keys, &metadata, password, &kdf, wallets_dir)?;
 
-        // Initialize BDK wallet
+        // Initialize BDK wallet watch-only, then load the decrypted signing keys
         let wallet_path = wallet_dir(name, wallets_dir)?;
         let mut bitcoin_wallet = BitcoinWallet::new(
-            wallet_keys.bitcoin_descriptor.clone(),
+            wallet_keys.bitcoin_public_descriptor.clone(),
             self.config.bitcoin.network,
             &wallet_path,
         )?;
+        bitcoin_wallet.unlock(&wallet_keys.bitcoin_descriptor)?;
 
         // Reveal the first external address so the wallet is ready to receive funds
         // This ensures get_addresses() returns a tracked address that BDK will sync
@@ -285,13 +322,156 @@ impl WalletManager {
         // Initialize F1r3fly managers
         self.initialize_f1r3fly(name, &wallet_keys)?;
 
+        // Restore RGB-occupied UTXO protection
+        self.rebuild_rgb_occupied()?;
+
         Ok(())
     }
 
+    /// Restore a wallet from its mnemonic, including RGB state
+    ///
+    /// `import_wallet` only recreates the Bitcoin wallet. This additionally:
+    /// 1. Rescans the chain with a BDK full scan
+    /// 2. Re-imports the consignments in `options.consignments_dir`
+    ///    (genesis consignments, then transfers received by this wallet)
+    /// 3. Recovers the contracts this wallet issued by walking the deployer's
+    ///    child keys: their registry URIs are matched against imported
+    ///    contracts or looked up on F1r3node's registry
+    /// 4. Saves the rebuilt `f1r3fly_state.json` and re-protects every UTXO
+    ///    carrying tokens
+    ///
+    /// Received assets whose consignments are not provided cannot be recovered.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Wallet name
+    /// * `mnemonic` - BIP39 mnemonic phrase
+    /// * `password` - Password for key encryption
+    /// * `options` - Consignments directory and scan limits
+    ///
+    /// # Returns
+    ///
+    /// `RestoreReport` listing restored contracts, transfers and skipped files
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = RestoreOptions {
+    ///     consignments_dir: Some(PathBuf::from("backup/consignments")),
+    ///     ..Default::default()
+    /// };
+    /// let report = manager.restore_wallet("restored", mnemonic, "password123", options).await?;
+    /// println!("Restored {} contracts", report.contracts_restored.len());
+    /// ```
+    pub async fn restore_wallet(
+        &mut self,
+        name: &str,
+        mnemonic: &str,
+        password: &str,
+        options: RestoreOptions,
+    ) -> Result<RestoreReport, ManagerError> {
+        // ====================================================================
+        // Step 1: Recreate Keys and Bitcoin Wallet
+        // ====================================================================
+        self.import_wallet(name, mnemonic, password)?;
+
+        // ====================================================================
+        // Step 2: Full Scan of the Blockchain
+        // ====================================================================
+        let scan = {
+            let wallet = self
+                .bitcoin_wallet
+                .as_mut()
+                .ok_or(ManagerError::WalletNotLoaded)?;
+            full_scan_wallet(wallet, &self.esplora_client, options.stop_gap)?
+        };
+
+        let mut report = RestoreReport {
+            scan_height: scan.height,
+            transactions_found: scan.new_txs,
+            ..Default::default()
+        };
+
+        // ====================================================================
+        // Step 3: Re-import Consignments and Recover Derivation Indices
+        // ====================================================================
+        let derivation_start = options.derivation_start.unwrap_or_else(|| {
+            self.f1r3fly_executor
+                .as_ref()
+                .and_then(|executor| executor.initial_derivation_index())
+                .unwrap_or(0)
+        });
+
+        {
+            let contracts_manager = self
+                .f1r3fly_contracts
+                .as_mut()
+                .ok_or(ManagerError::F1r3flyNotInitialized)?;
+            let bitcoin_wallet = self
+                .bitcoin_wallet
+                .as_mut()
+                .ok_or(ManagerError::WalletNotLoaded)?;
+
+            if let Some(dir) = &options.consignments_dir {
+                crate::f1r3fly::restore_consignments(
+                    contracts_manager,
+                    bitcoin_wallet,
+                    dir,
+                    &mut report,
+                )
+                .await?;
+            }
+
+            crate::f1r3fly::recover_issued_contracts(
+                contracts_manager,
+                bitcoin_wallet,
+                derivation_start,
+                options.derivation_scan_limit,
+                &mut report,
+            )
+            .await?;
+            report.next_derivation_index =
+                contracts_manager.contracts().executor().derivation_index();
+
+            contracts_manager.save_state()?;
+        }
+
+        // ====================================================================
+        // Step 4: Protect Token-Carrying UTXOs
+        // ====================================================================
+        let balances = self.get_rgb_balance().await?;
+        if let Some(contracts_manager) = self.f1r3fly_contracts.as_mut() {
+            for asset in &balances {
+                for utxo in asset.utxo_balances.iter().filter(|utxo| utxo.amount > 0) {
+                    let Some((txid, vout)) = utxo.outpoint.split_once(':') else {
+                        continue;
+                    };
+                    if let Ok(vout) = vout.parse::<u32>() {
+                        contracts_manager
+                            .claim_storage_mut()
+                            .mark_rgb_occupied_utxo(txid, vout, Some(&asset.contract_id))?;
+                    }
+                }
+            }
+        }
+        self.rebuild_rgb_occupied()?;
+
+        log::info!(
+            "✅ Wallet '{}' restored: {} contract(s), {} transfer(s), {} skipped",
+            name,
+            report.contracts_restored.len(),
+            report.transfers_restored,
+            report.skipped.len()
+        );
+
+        Ok(report)
+    }
+
     /// Load an existing wallet
     ///
     /// Loads wallet keys and metadata from disk, decrypts keys,
-    /// and initializes the BDK wallet.
+    /// and initializes the BDK wallet. Keys encrypted with a KDF other
+    /// than the configured one are re-encrypted with it.
     ///
     /// # Arguments
     ///
@@ -306,15 +486,17 @@ impl WalletManager {
     pub fn load_wallet(&mut self, name: &str, password: &str) -> Result<(), ManagerError> {
         // Load wallet from disk
         let wallets_dir = self.config.wallets_dir.as_deref();
-        let (wallet_keys, metadata) = load_wallet(name, password, wallets_dir)?;
+        let (wallet_keys, metadata) =
+            load_wallet_with_kdf(name, password, self.config.kdf.as_ref(), wallets_dir)?;
 
-        // Initialize BDK wallet
+        // Initialize BDK wallet watch-only, then load the decrypted signing keys
         let wallet_path = wallet_dir(name, wallets_dir)?;
-        let bitcoin_wallet = BitcoinWallet::new(
-            wallet_keys.bitcoin_descriptor.clone(),
+        let mut bitcoin_wallet = BitcoinWallet::new(
+            wallet_keys.bitcoin_public_descriptor.clone(),
             self.config.bitcoin.network,
             &wallet_path,
         )?;
+        bitcoin_wallet.unlock(&wallet_keys.bitcoin_descriptor)?;
 
         // BDK automatically restores revealed address state from the database
         // No manual intervention needed
@@ -327,6 +509,20 @@ impl WalletManager {
         // Initialize F1r3fly managers
         self.initialize_f1r3fly(name, &wallet_keys)?;
 
+        // Restore RGB-occupied UTXO protection
+        self.rebuild_rgb_occupied()?;
+
+        // Transfers interrupted by a crash keep their tokens on witness
+        // placeholders until recovered
+        match self.incomplete_transfers() {
+            Ok(entries) if !entries.is_empty() => log::warn!(
+                "⚠️  {} incomplete transfer(s) in the journal. Run 'recover-transfers' to resume them.",
+                entries.len()
+            ),
+            Ok(_) => {}
+            Err(e) => log::warn!("⚠️  Failed to read transfer journal: {}", e),
+        }
+
         Ok(())
     }
 
@@ -335,6 +531,45 @@ impl WalletManager {
         self.wallet_metadata.as_ref()
     }
 
+    /// Resolve the default RGB anchor method for transfers
+    ///
+    /// Priority (highest to lowest):
+    /// 1. Loaded wallet's `anchor_method` (wallet.json)
+    /// 2. Global config `anchor_method`
+    /// 3. Tapret
+    pub fn default_anchor_method(&self) -> AnchorMethod {
+        self.wallet_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.anchor_method)
+            .or(self.config.anchor_method)
+            .unwrap_or_default()
+    }
+
+    /// Set the loaded wallet's default RGB anchor method
+    ///
+    /// Pass `None` to fall back to the global config. Persisted to the
+    /// wallet's metadata file.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// manager.set_wallet_anchor_method(Some(AnchorMethod::OpReturn))?;
+    /// ```
+    pub fn set_wallet_anchor_method(
+        &mut self,
+        anchor_method: Option<AnchorMethod>,
+    ) -> Result<(), ManagerError> {
+        let metadata = self
+            .wallet_metadata
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        metadata.anchor_method = anchor_method;
+        update_wallet_metadata(&metadata.name, metadata, self.config.wallets_dir.as_deref())?;
+
+        Ok(())
+    }
+
     /// Get reference to currently loaded Bitcoin wallet
     pub fn bitcoin_wallet(&self) -> Option<&BitcoinWallet> {
         self.bitcoin_wallet.as_ref()
@@ -385,12 +620,189 @@ impl WalletManager {
 
         // Retry pending claims after Bitcoin sync
         if self.f1r3fly_contracts.is_some() && self.bitcoin_wallet.is_some() {
+            // Witness replacements interrupted by a failed fee bump
+            self.repair_witness_replacements().await?;
+
+            // Seal moves interrupted after a CPFP broadcast
+            self.replay_seal_rebinds().await?;
+
             self.retry_pending_claims().await?;
+
+            // Newly claimed UTXOs must be protected from Bitcoin spends
+            self.rebuild_rgb_occupied()?;
         }
 
         Ok(result)
     }
 
+    /// Settle witness replacements journaled by `bump_fee()`
+    ///
+    /// Called automatically by `sync_wallet()`. A replacement known to the
+    /// wallet reached the network and the journal entry is dropped; otherwise
+    /// the transfer is moved back to the original witness. Entries that
+    /// cannot be settled yet stay journaled for the next sync.
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error if claim storage access fails
+    async fn repair_witness_replacements(&mut self) -> Result<(), ManagerError> {
+        let consignments_dir = self.consignments_dir()?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let replacements = contracts_manager
+            .claim_storage()
+            .get_witness_replacements()?;
+
+        for replacement in replacements {
+            let known = |txid: &str| {
+                bdk_wallet::bitcoin::Txid::from_str(txid)
+                    .ok()
+                    .and_then(|txid| bitcoin_wallet.inner().get_tx(txid))
+                    .map(|tx| (*tx.tx_node.tx).clone())
+            };
+
+            if known(&replacement.txid).is_some() {
+                contracts_manager
+                    .claim_storage_mut()
+                    .remove_witness_replacement(&replacement.original_txid)?;
+                continue;
+            }
+
+            let Some(original_tx) = known(&replacement.original_txid) else {
+                log::warn!(
+                    "⚠️  Witness replacement {} -> {}: neither transaction known yet",
+                    replacement.original_txid,
+                    replacement.txid
+                );
+                continue;
+            };
+
+            match crate::f1r3fly::replace_witness_transaction(
+                contracts_manager,
+                &replacement.txid,
+                &original_tx,
+                consignments_dir.clone(),
+            )
+            .await
+            {
+                // Nothing left on the replacement: the transfer never moved
+                Ok(_) | Err(TransferError::WitnessNotFound(_)) => {
+                    log::info!(
+                        "✓ Restored witness {} (replacement {} never broadcast)",
+                        replacement.original_txid,
+                        replacement.txid
+                    );
+                    contracts_manager
+                        .claim_storage_mut()
+                        .remove_witness_replacement(&replacement.original_txid)?;
+                }
+                Err(e) => log::warn!(
+                    "⚠️  Failed to restore witness {} (will retry): {}",
+                    replacement.original_txid,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply seal moves journaled by `cpfp()` but not completed
+    ///
+    /// Called automatically by `sync_wallet()`. A rebind whose child is known
+    /// to the wallet is applied; one whose seal output is still unspent was
+    /// never broadcast and is dropped. Anything else (or a failed rebind)
+    /// stays journaled for the next sync.
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error if claim storage access fails
+    async fn replay_seal_rebinds(&mut self) -> Result<(), ManagerError> {
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let rebinds = contracts_manager.claim_storage().get_seal_rebinds()?;
+        if rebinds.is_empty() {
+            return Ok(());
+        }
+
+        log::info!("🔄 Replaying {} seal rebind(s)...", rebinds.len());
+
+        let claims = contracts_manager
+            .claim_storage()
+            .get_all_claims_unfiltered()?;
+        let mut applied = false;
+
+        for rebind in rebinds {
+            let child_known = bdk_wallet::bitcoin::Txid::from_str(&rebind.txid)
+                .ok()
+                .and_then(|child_txid| bitcoin_wallet.inner().get_tx(child_txid))
+                .is_some();
+
+            if !child_known {
+                let seal_unspent = bitcoin_wallet.inner().list_unspent().any(|utxo| {
+                    utxo.outpoint.txid.to_string() == rebind.spent_txid
+                        && utxo.outpoint.vout == rebind.spent_vout
+                });
+                if seal_unspent {
+                    log::info!(
+                        "Dropping seal rebind for claim {}: child {} was never broadcast",
+                        rebind.claim_id,
+                        rebind.txid
+                    );
+                    contracts_manager
+                        .claim_storage_mut()
+                        .remove_seal_rebind(rebind.claim_id)?;
+                }
+                continue;
+            }
+
+            let claim = match claims
+                .iter()
+                .find(|claim| claim.id == Some(rebind.claim_id))
+            {
+                Some(claim) => claim,
+                None => {
+                    contracts_manager
+                        .claim_storage_mut()
+                        .remove_seal_rebind(rebind.claim_id)?;
+                    continue;
+                }
+            };
+
+            match apply_seal_rebind(contracts_manager, claim, &rebind).await {
+                Ok(()) => applied = true,
+                Err(e) => log::warn!(
+                    "⚠️  Seal rebind for claim {} failed (will retry): {}",
+                    rebind.claim_id,
+                    e
+                ),
+            }
+        }
+
+        if applied {
+            contracts_manager.save_state()?;
+        }
+
+        Ok(())
+    }
+
     /// Retry pending claims after wallet sync
     ///
     /// Called automatically by `sync_wallet()` to attempt to claim any pending
@@ -443,11 +855,17 @@ impl WalletManager {
                 })?;
 
             match attempt_claim(contracts_manager, bitcoin_wallet, contract_id, &claim).await {
-                Ok(_) => {
+                Ok(claim_result) => {
                     log::info!("✅ Claim succeeded for {}", claim.witness_id);
                     contracts_manager
                         .claim_storage_mut()
                         .mark_claim_completed(claim.id.unwrap())?;
+                    contracts_manager
+                        .claim_storage_mut()
+                        .set_transfer_amount_for_claim(
+                            claim.id.unwrap(),
+                            claim_result.migrated_balance,
+                        )?;
                 }
                 Err(ClaimError::UtxoNotFound) => {
                     log::warn!(
@@ -590,6 +1008,11 @@ impl WalletManager {
             mark_rgb,
         )?;
 
+        // Persist the new marking so later runs keep protecting this output
+        if mark_rgb {
+            self.persist_rgb_occupied(&result.outpoint, None)?;
+        }
+
         Ok(result)
     }
 
@@ -702,115 +1125,319 @@ impl WalletManager {
     }
 
     /// Get mutable reference to RGB-occupied outpoints
+    ///
+    /// Changes made through this reference are in-memory only. Use
+    /// `mark_rgb_occupied` / `unmark_rgb_occupied` to persist them.
     pub fn rgb_occupied_mut(&mut self) -> &mut HashSet<OutPoint> {
         &mut self.rgb_occupied
     }
 
-    /// Set F1r3fly contract derivation index for test isolation
+    /// Mark UTXOs as RGB-occupied and persist them
     ///
-    /// Sets the starting derivation index for contract key derivation.
-    /// This is primarily used by tests to ensure parallel test runs don't
-    /// deploy contracts to the same registry URI.
+    /// Marked UTXOs are excluded from Bitcoin coin selection in this and
+    /// all future sessions until explicitly unmarked.
     ///
     /// # Arguments
     ///
-    /// * `index` - Starting derivation index
+    /// * `outpoints` - Outpoints to protect
     ///
     /// # Errors
     ///
-    /// Returns error if F1r3fly managers are not initialized
+    /// Returns error if F1r3fly managers are not initialized or the database write fails
     ///
     /// # Example
     ///
     /// ```ignore
-    /// // In tests: use test-specific index for isolation
-    /// manager.set_f1r3fly_derivation_index(12345)?;
+    /// manager.mark_rgb_occupied([outpoint])?;
     /// ```
-    pub fn set_f1r3fly_derivation_index(&mut self, index: u32) -> Result<(), ManagerError> {
+    pub fn mark_rgb_occupied<I>(&mut self, outpoints: I) -> Result<(), ManagerError>
+    where
+        I: IntoIterator<Item = OutPoint>,
+    {
+        for outpoint in outpoints {
+            self.persist_rgb_occupied(&outpoint, None)?;
+            mark_rgb_occupied(&mut self.rgb_occupied, [outpoint]);
+        }
+
+        Ok(())
+    }
+
+    /// Unmark UTXOs as RGB-occupied and remove them from persistent storage
+    ///
+    /// # Arguments
+    ///
+    /// * `outpoints` - Outpoints to release
+    ///
+    /// # Errors
+    ///
+    /// Returns error if F1r3fly managers are not initialized or the database write fails
+    pub fn unmark_rgb_occupied<I>(&mut self, outpoints: I) -> Result<(), ManagerError>
+    where
+        I: IntoIterator<Item = OutPoint>,
+    {
         let contracts_manager = self
             .f1r3fly_contracts
             .as_mut()
             .ok_or(ManagerError::F1r3flyNotInitialized)?;
 
-        contracts_manager
-            .contracts_mut()
-            .executor_mut()
-            .set_derivation_index(index);
+        for outpoint in outpoints {
+            contracts_manager
+                .claim_storage_mut()
+                .unmark_rgb_occupied_utxo(&outpoint.txid.to_string(), outpoint.vout)?;
+            unmark_rgb_occupied(&mut self.rgb_occupied, [outpoint]);
+        }
 
         Ok(())
     }
 
-    // ========================================================================
-    // F1r3fly-RGB Asset Management
-    // ========================================================================
-
-    /// Initialize F1r3fly managers
-    ///
-    /// Creates F1r3flyExecutorManager and F1r3flyContractsManager for the loaded wallet.
-    /// This is called internally when a wallet is loaded/created/imported.
-    ///
-    /// # Arguments
-    ///
-    /// * `wallet_name` - Name of the wallet
-    /// * `wallet_keys` - Decrypted wallet keys (includes F1r3fly private key)
-    fn initialize_f1r3fly(
+    /// Write a single RGB-occupied outpoint to the wallet database
+    fn persist_rgb_occupied(
         &mut self,
-        wallet_name: &str,
-        wallet_keys: &WalletKeys,
+        outpoint: &OutPoint,
+        contract_id: Option<&str>,
     ) -> Result<(), ManagerError> {
-        // Create executor manager with F1r3fly key
-        let executor_manager = F1r3flyExecutorManager::new(&self.config, wallet_keys)?;
-
-        // Get wallet directory
-        let wallets_dir = self.config.wallets_dir.as_deref();
-        let wallet_path = wallet_dir(wallet_name, wallets_dir)?;
-
-        // Create or load contracts manager
-        let contracts_manager =
-            if F1r3flyContractsManager::get_state_file_path(&wallet_path).exists() {
-                // Load existing state
-                F1r3flyContractsManager::load(&executor_manager, &wallet_path)?
-            } else {
-                // Create new state
-                F1r3flyContractsManager::new(&executor_manager, &wallet_path)?
-            };
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
 
-        // Store in manager
-        self.f1r3fly_executor = Some(executor_manager);
-        self.f1r3fly_contracts = Some(contracts_manager);
+        contracts_manager
+            .claim_storage_mut()
+            .mark_rgb_occupied_utxo(&outpoint.txid.to_string(), outpoint.vout, contract_id)?;
 
         Ok(())
     }
 
-    /// Issue a new RGB asset
-    ///
-    /// Creates a new fungible token by deploying a RHO20 contract to F1r3node.
-    ///
-    /// # Arguments
-    ///
-    /// * `request` - Asset issuance parameters (ticker, name, supply, precision, genesis UTXO)
-    ///
-    /// # Returns
-    ///
-    /// `AssetInfo` with contract ID, verified metadata, and genesis seal
-    ///
-    /// # Errors
+    /// Rebuild the RGB-occupied set from persistent sources
     ///
-    /// Returns error if:
-    /// - Wallet is not loaded
-    /// - F1r3fly managers are not initialized
-    /// - Genesis UTXO doesn't exist in Bitcoin wallet
-    /// - Genesis UTXO is already RGB-occupied
-    /// - Contract deployment fails
+    /// Unions the persisted `rgb_occupied_utxos` table (explicit markings and
+    /// change seals) with every genesis UTXO and every successfully claimed
+    /// UTXO, then writes the union back so the table stays authoritative.
     ///
-    /// # Example
+    /// Genesis UTXOs of imported (foreign) contracts are included as well;
+    /// outpoints the wallet does not own are inert in coin selection.
     ///
-    /// ```ignore
-    /// let request = IssueAssetRequest {
-    ///     ticker: "TEST".to_string(),
-    ///     name: "Test Token".to_string(),
-    ///     supply: 1000,
-    ///     precision: 0,
+    /// UTXOs behind unpaid blinded seals are reserved in memory only: they
+    /// carry no tokens yet, and their claim persists them once paid.
+    fn rebuild_rgb_occupied(&mut self) -> Result<(), ManagerError> {
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        // (txid, vout, contract_id)
+        let mut sources: Vec<(String, u32, Option<String>)> = Vec::new();
+
+        // 1. Previously persisted markings
+        for (txid, vout) in contracts_manager.claim_storage().get_rgb_occupied_utxos()? {
+            sources.push((txid, vout, None));
+        }
+
+        // 2. Genesis UTXOs
+        for (contract_id, genesis) in contracts_manager.genesis_utxos() {
+            sources.push((
+                genesis.txid.clone(),
+                genesis.vout,
+                Some(contract_id.clone()),
+            ));
+        }
+
+        // 3. Claimed UTXOs
+        for claim in contracts_manager
+            .claim_storage()
+            .get_all_claims_unfiltered()?
+        {
+            if claim.status != ClaimStatus::Claimed {
+                continue;
+            }
+            if let (Some(txid), Some(vout)) = (claim.actual_txid, claim.actual_vout) {
+                sources.push((txid, vout, Some(claim.contract_id)));
+            }
+        }
+
+        let mut occupied = HashSet::new();
+        for (txid_str, vout, contract_id) in sources {
+            let txid = match bdk_wallet::bitcoin::Txid::from_str(&txid_str) {
+                Ok(txid) => txid,
+                Err(e) => {
+                    log::warn!(
+                        "⚠️  Skipping invalid RGB-occupied txid '{}': {}",
+                        txid_str,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            contracts_manager
+                .claim_storage_mut()
+                .mark_rgb_occupied_utxo(&txid_str, vout, contract_id.as_deref())?;
+            occupied.insert(OutPoint { txid, vout });
+        }
+
+        // 4. UTXOs awaiting a payment to a blinded seal
+        for seal in contracts_manager
+            .claim_storage()
+            .get_open_blinded_seals(None)?
+        {
+            match bdk_wallet::bitcoin::Txid::from_str(&seal.txid) {
+                Ok(txid) => {
+                    occupied.insert(OutPoint {
+                        txid,
+                        vout: seal.vout,
+                    });
+                }
+                Err(e) => log::warn!(
+                    "⚠️  Skipping invalid blinded seal txid '{}': {}",
+                    seal.txid,
+                    e
+                ),
+            }
+        }
+
+        log::debug!("🔒 Restored {} RGB-occupied UTXO(s)", occupied.len());
+
+        self.rgb_occupied = occupied;
+
+        // 5. Inputs of signed witnesses not yet broadcast (not persisted:
+        //    they are released when the journal entry completes)
+        self.reserve_journaled_inputs();
+
+        Ok(())
+    }
+
+    /// Keep the inputs of incomplete journaled transfers out of coin selection
+    ///
+    /// A transfer stopped after signing its witness is resumed with that
+    /// exact transaction, so its inputs must not be spent elsewhere.
+    fn reserve_journaled_inputs(&mut self) {
+        let entries = match self.incomplete_transfers() {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("⚠️  Failed to read transfer journal: {}", e);
+                return;
+            }
+        };
+
+        for entry in &entries {
+            for outpoint in entry.reserved_outpoints() {
+                if self.rgb_occupied.insert(outpoint) {
+                    log::debug!("🔒 Reserved {} for transfer {}", outpoint, entry.id);
+                }
+            }
+        }
+    }
+
+    /// Set F1r3fly contract derivation index for test isolation
+    ///
+    /// Sets the starting derivation index for contract key derivation.
+    /// This is primarily used by tests to ensure parallel test runs don't
+    /// deploy contracts to the same registry URI.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Starting derivation index
+    ///
+    /// # Errors
+    ///
+    /// Returns error if F1r3fly managers are not initialized
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In tests: use test-specific index for isolation
+    /// manager.set_f1r3fly_derivation_index(12345)?;
+    /// ```
+    pub fn set_f1r3fly_derivation_index(&mut self, index: u32) -> Result<(), ManagerError> {
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        contracts_manager
+            .contracts_mut()
+            .executor_mut()
+            .set_derivation_index(index);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // F1r3fly-RGB Asset Management
+    // ========================================================================
+
+    /// Initialize F1r3fly managers
+    ///
+    /// Creates F1r3flyExecutorManager and F1r3flyContractsManager for the loaded wallet.
+    /// This is called internally when a wallet is loaded/created/imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_name` - Name of the wallet
+    /// * `wallet_keys` - Decrypted wallet keys (includes F1r3fly private key)
+    fn initialize_f1r3fly(
+        &mut self,
+        wallet_name: &str,
+        wallet_keys: &WalletKeys,
+    ) -> Result<(), ManagerError> {
+        // Create executor manager with F1r3fly key and the wallet's key mode
+        let key_mode = self
+            .wallet_metadata
+            .as_ref()
+            .map(|metadata| metadata.key_mode)
+            .unwrap_or_default();
+        let executor_manager = F1r3flyExecutorManager::new(&self.config, wallet_keys, key_mode)?;
+
+        // Get wallet directory
+        let wallets_dir = self.config.wallets_dir.as_deref();
+        let wallet_path = wallet_dir(wallet_name, wallets_dir)?;
+
+        // Create or load contracts manager
+        let contracts_manager =
+            if F1r3flyContractsManager::get_state_file_path(&wallet_path).exists() {
+                // Load existing state
+                F1r3flyContractsManager::load(&executor_manager, &wallet_path)?
+            } else {
+                // Create new state
+                F1r3flyContractsManager::new(&executor_manager, &wallet_path)?
+            };
+
+        // Store in manager
+        self.f1r3fly_executor = Some(executor_manager);
+        self.f1r3fly_contracts = Some(contracts_manager);
+
+        Ok(())
+    }
+
+    /// Issue a new RGB asset
+    ///
+    /// Creates a new fungible token by deploying a RHO20 contract to F1r3node.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Asset issuance parameters (ticker, name, supply, precision, genesis UTXO)
+    ///
+    /// # Returns
+    ///
+    /// `AssetInfo` with contract ID, verified metadata, and genesis seal
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Wallet is not loaded
+    /// - F1r3fly managers are not initialized
+    /// - Genesis UTXO doesn't exist in Bitcoin wallet
+    /// - Genesis UTXO is already RGB-occupied
+    /// - Contract deployment fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let request = IssueAssetRequest {
+    ///     ticker: "TEST".to_string(),
+    ///     name: "Test Token".to_string(),
+    ///     supply: 1000,
+    ///     precision: 0,
     ///     genesis_utxo: "txid:vout".to_string(),
     /// };
     /// let asset_info = manager.issue_asset(request).await?;
@@ -861,6 +1488,13 @@ impl WalletManager {
         })?;
 
         let outpoint = bdk_wallet::bitcoin::OutPoint { txid, vout };
+        contracts_manager
+            .claim_storage_mut()
+            .mark_rgb_occupied_utxo(
+                &txid.to_string(),
+                vout,
+                Some(asset_info.contract_id.as_str()),
+            )?;
         self.rgb_occupied.insert(outpoint);
         log::info!(
             "Marked genesis UTXO as RGB-occupied: {}:{} (contract: {})",
@@ -1163,10 +1797,99 @@ impl WalletManager {
         Ok(filtered_utxos)
     }
 
+    /// List wallet Bitcoin transactions with RGB annotations
+    ///
+    /// Each transaction from the BDK tx graph is tagged when it is:
+    /// - an RGB witness: the witness txid of a recorded transfer (sent or
+    ///   received) or the actual txid of a claimed seal. The anchor tracker
+    ///   keys anchors by opid, so these wallet records are what tie an anchor
+    ///   to its Bitcoin transaction.
+    /// - an asset genesis: the transaction holding a contract's genesis seal
+    /// - a `create-utxo` self-send: all outputs belong to the wallet and the
+    ///   transaction is not an RGB witness
+    ///
+    /// # Returns
+    ///
+    /// Vector of `TransactionInfo`, unconfirmed first, then newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if wallet not loaded or storage queries fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for tx in manager.list_transactions()? {
+    ///     println!("{} {} sats", tx.txid, tx.net_sats);
+    ///     for annotation in &tx.rgb {
+    ///         println!("  - {}", annotation.kind);
+    ///     }
+    /// }
+    /// ```
+    pub fn list_transactions(&self) -> Result<Vec<TransactionInfo>, ManagerError> {
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let mut transactions = list_transactions(bitcoin_wallet)?;
+
+        // Collect RGB-relevant txids (txid -> contract_id)
+        let mut witness_txids: HashMap<String, String> = HashMap::new();
+        let mut genesis_txids: HashMap<String, String> = HashMap::new();
+
+        if let Some(contracts_manager) = self.f1r3fly_contracts.as_ref() {
+            let storage = contracts_manager.claim_storage();
+
+            for transfer in storage.get_transfers(None, None, None)? {
+                witness_txids.insert(transfer.witness_txid, transfer.contract_id);
+            }
+
+            for claim in storage.get_all_claims_unfiltered()? {
+                if claim.status != ClaimStatus::Claimed {
+                    continue;
+                }
+                if let Some(txid) = claim.actual_txid {
+                    witness_txids.entry(txid).or_insert(claim.contract_id);
+                }
+            }
+
+            for (contract_id, genesis) in contracts_manager.genesis_utxos() {
+                genesis_txids.insert(genesis.txid.clone(), contract_id.clone());
+            }
+        }
+
+        for tx in &mut transactions {
+            if let Some(contract_id) = witness_txids.get(&tx.txid) {
+                tx.rgb.push(RgbTxAnnotation {
+                    kind: RgbTxKind::Witness,
+                    contract_id: Some(contract_id.clone()),
+                });
+            }
+
+            if let Some(contract_id) = genesis_txids.get(&tx.txid) {
+                tx.rgb.push(RgbTxAnnotation {
+                    kind: RgbTxKind::Genesis,
+                    contract_id: Some(contract_id.clone()),
+                });
+            }
+
+            if tx.direction == TxDirection::SelfTransfer && !witness_txids.contains_key(&tx.txid) {
+                tx.rgb.push(RgbTxAnnotation {
+                    kind: RgbTxKind::CreateUtxo,
+                    contract_id: None,
+                });
+            }
+        }
+
+        Ok(transactions)
+    }
+
     /// Generate RGB invoice with recipient's public key
     ///
     /// Generates a standard RGB invoice and includes the recipient's F1r3fly public key
-    /// for transfer authorization.
+    /// for transfer authorization. Equivalent to `generate_invoice_with_expiry` without
+    /// an expiry.
     ///
     /// # Arguments
     ///
@@ -1188,6 +1911,35 @@ impl WalletManager {
         contract_id: &str,
         amount: u64,
     ) -> Result<crate::f1r3fly::InvoiceWithPubkey, ManagerError> {
+        self.generate_invoice_with_expiry(contract_id, amount, None, None)
+    }
+
+    /// Generate RGB invoice with recipient's public key and optional expiry
+    ///
+    /// The invoice gets a fresh nonce and is recorded in the invoice registry
+    /// (see `list_invoices`).
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Contract ID string
+    /// * `amount` - Amount to receive
+    /// * `expires_in_secs` - Seconds from now until the invoice expires (None = never)
+    /// * `address_override` - Specific Bitcoin address to use (None = next wallet address)
+    pub fn generate_invoice_with_expiry(
+        &mut self,
+        contract_id: &str,
+        amount: u64,
+        expires_in_secs: Option<u64>,
+        address_override: Option<String>,
+    ) -> Result<crate::f1r3fly::InvoiceWithPubkey, ManagerError> {
+        let expiry = expires_in_secs.map(|secs| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + secs
+        });
+
         let bitcoin_wallet = self
             .bitcoin_wallet
             .as_mut()
@@ -1195,7 +1947,7 @@ impl WalletManager {
 
         let contracts_manager = self
             .f1r3fly_contracts
-            .as_ref()
+            .as_mut()
             .ok_or(ManagerError::F1r3flyNotInitialized)?;
 
         crate::f1r3fly::generate_invoice_with_pubkey(
@@ -1203,6 +1955,8 @@ impl WalletManager {
             contracts_manager,
             contract_id,
             amount,
+            address_override,
+            expiry,
         )
         .map_err(|e| {
             ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
@@ -1214,6 +1968,67 @@ impl WalletManager {
         })
     }
 
+    /// Generate RGB invoice paying a blinded seal over a wallet UTXO
+    ///
+    /// The sender sees neither the UTXO nor a Bitcoin address. The UTXO is
+    /// kept out of coin selection from now on, so it is still unspent when
+    /// the tokens arrive.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Contract ID string
+    /// * `amount` - Amount to receive
+    /// * `expires_in_secs` - Seconds from now until the invoice expires (None = never)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the wallet has no UTXO that is free to blind
+    pub fn generate_blinded_invoice(
+        &mut self,
+        contract_id: &str,
+        amount: u64,
+        expires_in_secs: Option<u64>,
+    ) -> Result<crate::f1r3fly::InvoiceWithPubkey, ManagerError> {
+        let expiry = expires_in_secs.map(|secs| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + secs
+        });
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let invoice = crate::f1r3fly::generate_blinded_invoice(
+            bitcoin_wallet,
+            contracts_manager,
+            contract_id,
+            amount,
+            expiry,
+            &self.rgb_occupied,
+        )
+        .map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Invoice generation failed: {}",
+                    e
+                )),
+            ))
+        })?;
+
+        self.rebuild_rgb_occupied()?;
+
+        Ok(invoice)
+    }
+
     /// Send RGB asset transfer
     ///
     /// Executes a complete RGB transfer flow:
@@ -1226,8 +2041,10 @@ impl WalletManager {
     /// # Arguments
     ///
     /// * `invoice_str` - RGB invoice string from recipient
-    /// * `recipient_pubkey_hex` - Recipient's F1r3fly public key (for transfer authorization)
+    /// * `recipient_pubkey_hex` - Recipient's F1r3fly public key (for transfer authorization);
+    ///   `None` uses the key embedded in the invoice
     /// * `fee_rate` - Bitcoin transaction fee rate
+    /// * `anchor_method` - Anchoring method (None uses `default_anchor_method()`)
     ///
     /// # Returns
     ///
@@ -1244,20 +2061,23 @@ impl WalletManager {
     /// # Example
     ///
     /// ```ignore
-    /// let invoice = "rgb:...";
-    /// let recipient_pubkey = "04f1r3fly...";
+    /// let invoice = "contract:...?pubkey=04f1r3fly...";
     /// let fee_rate = FeeRateConfig::medium_priority();
-    /// let response = manager.send_transfer(invoice, recipient_pubkey, &fee_rate).await?;
+    /// let response = manager.send_transfer(invoice, None, &fee_rate, None).await?;
     /// println!("Transfer sent: {}", response.bitcoin_txid);
     /// println!("Consignment: {}", response.consignment_path.display());
     /// ```
     pub async fn send_transfer(
         &mut self,
         invoice_str: &str,
-        recipient_pubkey_hex: String,
+        recipient_pubkey_hex: impl Into<Option<String>>,
         fee_rate: &FeeRateConfig,
         anchor_method: Option<crate::f1r3fly::AnchorMethod>,
     ) -> Result<crate::f1r3fly::TransferResponse, ManagerError> {
+        let consignments_dir = self.consignments_dir()?;
+        let journal = self.transfer_journal()?;
+        let anchor_method = anchor_method.unwrap_or_else(|| self.default_anchor_method());
+
         let bitcoin_wallet = self
             .bitcoin_wallet
             .as_mut()
@@ -1268,36 +2088,6 @@ impl WalletManager {
             .as_mut()
             .ok_or(ManagerError::F1r3flyNotInitialized)?;
 
-        // Get wallet directory for consignments
-        let wallet_name = self
-            .wallet_metadata
-            .as_ref()
-            .ok_or(ManagerError::WalletNotLoaded)?
-            .name
-            .clone();
-
-        // Construct wallet directory path
-        let wallets_base = if let Some(dir_str) = &self.config.wallets_dir {
-            std::path::PathBuf::from(dir_str)
-        } else {
-            std::path::PathBuf::from(".f1r3fly-rgb-wallet/wallets")
-        };
-
-        let wallet_dir = wallets_base.join(&wallet_name);
-
-        // VALIDATE: Wallet directory must exist (wallet is loaded)
-        if !wallet_dir.exists() {
-            return Err(ManagerError::FileSystem(FileSystemError::WalletNotFound(
-                format!(
-                    "Wallet directory not found: {}. Wallet state may be corrupted.",
-                    wallet_dir.display()
-                ),
-            )));
-        }
-
-        // Consignments subdirectory (will be created if needed during transfer)
-        let consignments_dir = wallet_dir.join("consignments");
-
         // CRITICAL SAFETY: Check if any UTXOs are RGB-occupied
         if !self.rgb_occupied.is_empty() {
             log::warn!(
@@ -1307,27 +2097,896 @@ impl WalletManager {
         }
 
         // Execute transfer
-        let response = crate::f1r3fly::send_transfer(
+        let result = crate::f1r3fly::send_transfer(
             bitcoin_wallet,
             &self.esplora_client,
             contracts_manager,
+            &journal,
             invoice_str,
-            recipient_pubkey_hex,
+            recipient_pubkey_hex.into(),
             fee_rate,
             consignments_dir,
             &self.rgb_occupied,
-            anchor_method,
+            Some(anchor_method),
         )
-        .await
-        .map_err(|e| {
+        .await;
+
+        // A transfer stopped after signing resumes with the same witness
+        if result.is_err() {
+            self.reserve_journaled_inputs();
+        }
+
+        let response = result.map_err(|e| {
             ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
                 f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!("Transfer failed: {}", e)),
             ))
         })?;
 
+        // Protect the change seal (multi-input transfers) from Bitcoin spends
+        if let Some(change_seal) = &response.change_seal {
+            match OutPoint::from_str(change_seal) {
+                Ok(outpoint) => self.mark_rgb_occupied([outpoint])?,
+                Err(e) => log::warn!("Invalid change seal {}: {}", change_seal, e),
+            }
+        }
+
         Ok(response)
     }
 
+    /// Send RGB asset transfers to several invoices in one witness transaction
+    ///
+    /// All invoices must be for the same contract. The payments share one
+    /// Bitcoin transaction and one anchor; each recipient gets its own
+    /// consignment.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipients` - Invoices and recipient F1r3fly public keys
+    /// * `fee_rate` - Bitcoin transaction fee rate
+    /// * `anchor_method` - Anchoring method (None uses `default_anchor_method()`)
+    ///
+    /// # Returns
+    ///
+    /// `BatchTransferResponse` with transaction ID and per-recipient consignments
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Wallet not loaded
+    /// - Batch empty, mixing contracts or repeating a recipient
+    /// - Insufficient balance for the batch total
+    /// - Transaction fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recipients = vec![
+    ///     TransferRecipient { invoice: bob_invoice, recipient_pubkey_hex: None },
+    ///     TransferRecipient { invoice: carol_invoice, recipient_pubkey_hex: None },
+    /// ];
+    /// let response = manager.send_transfer_batch(&recipients, &fee_rate, None).await?;
+    /// for transfer in &response.transfers {
+    ///     println!("Consignment: {}", transfer.consignment_path.display());
+    /// }
+    /// ```
+    pub async fn send_transfer_batch(
+        &mut self,
+        recipients: &[crate::f1r3fly::TransferRecipient],
+        fee_rate: &FeeRateConfig,
+        anchor_method: Option<crate::f1r3fly::AnchorMethod>,
+    ) -> Result<crate::f1r3fly::BatchTransferResponse, ManagerError> {
+        let consignments_dir = self.consignments_dir()?;
+        let journal = self.transfer_journal()?;
+        let anchor_method = anchor_method.unwrap_or_else(|| self.default_anchor_method());
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        // Execute batch transfer
+        let result = crate::f1r3fly::send_transfer_batch(
+            bitcoin_wallet,
+            &self.esplora_client,
+            contracts_manager,
+            &journal,
+            recipients,
+            fee_rate,
+            consignments_dir,
+            &self.rgb_occupied,
+            Some(anchor_method),
+        )
+        .await;
+
+        // A transfer stopped after signing resumes with the same witness
+        if result.is_err() {
+            self.reserve_journaled_inputs();
+        }
+
+        let response = result.map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Batch transfer failed: {}",
+                    e
+                )),
+            ))
+        })?;
+
+        // Protect the change seal (multi-input transfers) from Bitcoin spends
+        if let Some(change_seal) = &response.change_seal {
+            match OutPoint::from_str(change_seal) {
+                Ok(outpoint) => self.mark_rgb_occupied([outpoint])?,
+                Err(e) => log::warn!("Invalid change seal {}: {}", change_seal, e),
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Preview RGB transfers without executing them
+    ///
+    /// Parses the invoices, selects input seals and estimates the witness
+    /// fee; no contract method is called on F1r3node and nothing is signed
+    /// or broadcast.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipients` - Invoices and recipient F1r3fly public keys
+    /// * `fee_rate` - Bitcoin transaction fee rate
+    /// * `anchor_method` - Anchoring method (None uses `default_anchor_method()`)
+    ///
+    /// # Returns
+    ///
+    /// `TransferPreview` with amounts, input seals, fee and touched RGB UTXOs
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let preview = manager.preview_transfer_batch(&recipients, &fee_rate, None).await?;
+    /// println!("Fee: {} sats", preview.fee_sats);
+    /// ```
+    pub async fn preview_transfer_batch(
+        &mut self,
+        recipients: &[crate::f1r3fly::TransferRecipient],
+        fee_rate: &FeeRateConfig,
+        anchor_method: Option<crate::f1r3fly::AnchorMethod>,
+    ) -> Result<crate::f1r3fly::TransferPreview, ManagerError> {
+        let anchor_method = anchor_method.unwrap_or_else(|| self.default_anchor_method());
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        crate::f1r3fly::preview_transfer_batch(
+            bitcoin_wallet,
+            contracts_manager,
+            recipients,
+            fee_rate,
+            &self.rgb_occupied,
+            Some(anchor_method),
+        )
+        .await
+        .map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Transfer preview failed: {}",
+                    e
+                )),
+            ))
+        })
+    }
+
+    /// List inline transfers interrupted before completion
+    ///
+    /// # Returns
+    ///
+    /// Journal entries, oldest first (empty when every transfer completed)
+    pub fn incomplete_transfers(
+        &self,
+    ) -> Result<Vec<crate::f1r3fly::TransferJournalEntry>, ManagerError> {
+        self.transfer_journal()?.list().map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Transfer journal unreadable: {}",
+                    e
+                )),
+            ))
+        })
+    }
+
+    /// Resume or report inline transfers interrupted by a crash
+    ///
+    /// Each journaled transfer is completed from its last recorded phase
+    /// (signed, broadcast, anchored, consignment written). Transfers that
+    /// stopped during their contract calls are dropped if no balance moved,
+    /// and reported otherwise. Safe to run repeatedly.
+    ///
+    /// # Returns
+    ///
+    /// One result per journaled transfer, oldest first
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for recovered in manager.recover_transfers().await? {
+    ///     println!("{}: {:?}", recovered.id, recovered.outcome);
+    /// }
+    /// ```
+    pub async fn recover_transfers(
+        &mut self,
+    ) -> Result<Vec<crate::f1r3fly::RecoveredTransfer>, ManagerError> {
+        let consignments_dir = self.consignments_dir()?;
+        let journal = self.transfer_journal()?;
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let result = crate::f1r3fly::recover_transfers(
+            bitcoin_wallet,
+            &self.esplora_client,
+            contracts_manager,
+            &journal,
+            consignments_dir,
+            &self.rgb_occupied,
+        )
+        .await;
+
+        // Transfers still incomplete resume with their signed witness
+        self.reserve_journaled_inputs();
+
+        let recovered = result.map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Transfer recovery failed: {}",
+                    e
+                )),
+            ))
+        })?;
+
+        // Protect recovered change seals (multi-input transfers) from Bitcoin spends
+        for transfer in &recovered {
+            if let crate::f1r3fly::RecoveryOutcome::Completed(response) = &transfer.outcome {
+                if let Some(change_seal) = &response.change_seal {
+                    match OutPoint::from_str(change_seal) {
+                        Ok(outpoint) => self.mark_rgb_occupied([outpoint])?,
+                        Err(e) => log::warn!("Invalid change seal {}: {}", change_seal, e),
+                    }
+                }
+            }
+        }
+
+        Ok(recovered)
+    }
+
+    /// Prepare RGB transfers for offline signing
+    ///
+    /// Same as [`send_transfer_batch`](Self::send_transfer_batch) up to the
+    /// witness PSBT, which is written unsigned to `psbt_path` instead of
+    /// being signed with the wallet's keys. Sign it with `sign_psbt` (e.g. on
+    /// an air-gapped machine) and complete the transfer with
+    /// `finalize_and_broadcast`.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipients` - Invoices and recipient F1r3fly public keys
+    /// * `fee_rate` - Bitcoin transaction fee rate
+    /// * `anchor_method` - Anchoring method (None uses `default_anchor_method()`)
+    /// * `psbt_path` - File receiving the unsigned witness PSBT
+    ///
+    /// # Returns
+    ///
+    /// The prepared transfer (witness txid, staged consignments)
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let prepared = manager
+    ///     .prepare_transfer_batch(&recipients, &fee_rate, None, Path::new("witness.psbt"))
+    ///     .await?;
+    /// println!("Sign witness {} offline", prepared.txid);
+    /// ```
+    pub async fn prepare_transfer_batch(
+        &mut self,
+        recipients: &[crate::f1r3fly::TransferRecipient],
+        fee_rate: &FeeRateConfig,
+        anchor_method: Option<crate::f1r3fly::AnchorMethod>,
+        psbt_path: &std::path::Path,
+    ) -> Result<crate::f1r3fly::PreparedTransfer, ManagerError> {
+        let pending_dir = self.pending_transfers_dir()?;
+        let anchor_method = anchor_method.unwrap_or_else(|| self.default_anchor_method());
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let (prepared, psbt) = crate::f1r3fly::prepare_transfer_batch(
+            bitcoin_wallet,
+            contracts_manager,
+            recipients,
+            fee_rate,
+            pending_dir,
+            &self.rgb_occupied,
+            Some(anchor_method),
+        )
+        .await
+        .map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Transfer preparation failed: {}",
+                    e
+                )),
+            ))
+        })?;
+
+        save_psbt(&psbt, psbt_path)?;
+
+        Ok(prepared)
+    }
+
+    /// Sign a PSBT file with the loaded wallet's keys
+    ///
+    /// Works offline: only the wallet's descriptor is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `psbt_path` - PSBT to sign
+    /// * `output_path` - File receiving the signed PSBT (may equal `psbt_path`)
+    ///
+    /// # Returns
+    ///
+    /// Summary of the signed PSBT (outputs, fee, finalized inputs)
+    pub fn sign_psbt(
+        &self,
+        psbt_path: &std::path::Path,
+        output_path: &std::path::Path,
+    ) -> Result<PsbtSummary, ManagerError> {
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let mut psbt = load_psbt(psbt_path)?;
+        let summary = sign_psbt(bitcoin_wallet, &mut psbt)?;
+        save_psbt(&psbt, output_path)?;
+
+        Ok(summary)
+    }
+
+    /// Finalize a signed PSBT and broadcast it
+    ///
+    /// If the transaction is the witness of a transfer prepared with
+    /// `prepare_transfer_batch`, the transfer is completed: anchor
+    /// registration, consignments, history and change claim. Any other PSBT
+    /// is simply broadcast.
+    ///
+    /// # Arguments
+    ///
+    /// * `psbt_path` - Signed PSBT
+    ///
+    /// # Returns
+    ///
+    /// Broadcast txid and, for RGB witnesses, the completed transfer
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let response = manager.finalize_and_broadcast(Path::new("witness.signed.psbt")).await?;
+    /// if let Some(transfer) = &response.transfer {
+    ///     println!("Consignments: {}", transfer.transfers.len());
+    /// }
+    /// ```
+    pub async fn finalize_and_broadcast(
+        &mut self,
+        psbt_path: &std::path::Path,
+    ) -> Result<PsbtBroadcastResponse, ManagerError> {
+        let pending_dir = self.pending_transfers_dir()?;
+        let consignments_dir = self.consignments_dir()?;
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let psbt = load_psbt(psbt_path)?;
+        let tx = finalize_psbt(bitcoin_wallet, psbt)?;
+        let txid = tx.compute_txid().to_string();
+
+        let prepared =
+            crate::f1r3fly::load_prepared_transfer(&pending_dir, &txid).map_err(|e| {
+                ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                    f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                        "Invalid prepared transfer: {}",
+                        e
+                    )),
+                ))
+            })?;
+
+        if prepared.is_none() {
+            self.esplora_client
+                .inner()
+                .broadcast(&tx)
+                .map_err(|e| UtxoError::BroadcastFailed(format!("Failed to broadcast: {}", e)))?;
+
+            log::info!("✓ Transaction broadcasted: {}", txid);
+
+            return Ok(PsbtBroadcastResponse {
+                txid,
+                transfer: None,
+            });
+        }
+
+        let contracts_manager = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+        let response = crate::f1r3fly::finalize_transfer(
+            bitcoin_wallet,
+            &self.esplora_client,
+            contracts_manager,
+            &tx,
+            pending_dir,
+            consignments_dir,
+        )
+        .await
+        .map_err(|e| {
+            ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                    "Transfer finalization failed: {}",
+                    e
+                )),
+            ))
+        })?;
+
+        // Protect the change seal (multi-input transfers) from Bitcoin spends
+        if let Some(change_seal) = &response.change_seal {
+            match OutPoint::from_str(change_seal) {
+                Ok(outpoint) => self.mark_rgb_occupied([outpoint])?,
+                Err(e) => log::warn!("Invalid change seal {}: {}", change_seal, e),
+            }
+        }
+
+        Ok(PsbtBroadcastResponse {
+            txid,
+            transfer: Some(response),
+        })
+    }
+
+    /// Bump the fee of an unconfirmed transaction (RBF)
+    ///
+    /// Works for plain Bitcoin sends and for RGB witness transactions sent by
+    /// this wallet. For a witness, the replacement keeps the commitment
+    /// output, and the transfer's consignments are re-issued against the new
+    /// txid (recipients must accept the new consignment). RGB-occupied
+    /// outpoints of the old transaction move to the new one.
+    ///
+    /// The replacement is signed and its consignments written before it is
+    /// broadcast; if the broadcast fails, the transfer is moved back to the
+    /// original witness. The replacement is journaled first, so a move that
+    /// cannot be undone here is repaired by the next `sync_wallet()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `txid` - Unconfirmed transaction to replace
+    /// * `fee_rate` - New fee rate (must exceed the original)
+    ///
+    /// # Returns
+    ///
+    /// `FeeBumpResponse` with the replacement txid and re-issued consignments
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Wallet not loaded
+    /// - Transaction unknown to the wallet or already confirmed
+    /// - Replacement cannot be built or broadcast
+    /// - Consignment re-issue fails
+    /// - Broadcast fails and the transfer cannot be moved back
+    ///   (`WitnessRollbackFailed`, carrying both failures)
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bump = manager.bump_fee(&stuck_txid, &FeeRateConfig::new(25.0)?).await?;
+    /// for path in &bump.consignments {
+    ///     println!("Send {} to the recipient", path.display());
+    /// }
+    /// ```
+    pub async fn bump_fee(
+        &mut self,
+        txid: &str,
+        fee_rate: &FeeRateConfig,
+    ) -> Result<FeeBumpResponse, ManagerError> {
+        let original_txid = bdk_wallet::bitcoin::Txid::from_str(txid).map_err(|e| {
+            ManagerError::Utxo(UtxoError::TransactionNotFound(format!(
+                "Invalid txid '{}': {}",
+                txid, e
+            )))
+        })?;
+        let consignments_dir = self.consignments_dir()?;
+
+        // RGB witness if this wallet sent a transfer anchored to it
+        let rgb_witness = match self.f1r3fly_contracts.as_ref() {
+            Some(contracts_manager) => contracts_manager
+                .claim_storage()
+                .get_transfers(None, None, None)?
+                .iter()
+                .any(|record| {
+                    record.direction == TransferDirection::Outgoing && record.witness_txid == txid
+                }),
+            None => false,
+        };
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        // Build and sign only: consignments are re-issued before broadcast
+        let bump = build_fee_bump(bitcoin_wallet, original_txid, fee_rate, &self.rgb_occupied)?;
+        let new_txid = bump.txid();
+
+        let mut consignments = Vec::new();
+        if rgb_witness {
+            let contracts_manager = self
+                .f1r3fly_contracts
+                .as_mut()
+                .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+            // Journaled first: sync moves the transfer back if this bump
+            // never reaches the network
+            contracts_manager
+                .claim_storage_mut()
+                .insert_witness_replacement(&WitnessReplacement {
+                    original_txid: txid.to_string(),
+                    txid: new_txid.to_string(),
+                    created_at: std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                })?;
+
+            consignments = crate::f1r3fly::replace_witness_transaction(
+                contracts_manager,
+                txid,
+                &bump.tx,
+                consignments_dir.clone(),
+            )
+            .await
+            .map_err(|e| {
+                ManagerError::Asset(crate::f1r3fly::AssetError::F1r3flyRgb(
+                    f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                        "Witness replacement failed: {}",
+                        e
+                    )),
+                ))
+            })?;
+        }
+
+        if let Err(e) = self.esplora_client.inner().broadcast(&bump.tx) {
+            // The original stays in the mempool: move the transfer back to it
+            if rgb_witness {
+                let contracts_manager = self
+                    .f1r3fly_contracts
+                    .as_mut()
+                    .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+                let original_tx = bitcoin_wallet
+                    .inner()
+                    .get_tx(original_txid)
+                    .map(|tx| (*tx.tx_node.tx).clone());
+                let rollback = match original_tx {
+                    Some(original_tx) => crate::f1r3fly::replace_witness_transaction(
+                        contracts_manager,
+                        &new_txid.to_string(),
+                        &original_tx,
+                        consignments_dir,
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                    None => Err(format!("Transaction {} no longer in the wallet", txid)),
+                };
+
+                // On failure the journal entry stays for sync to repair
+                if let Err(rollback) = rollback {
+                    log::warn!("⚠️  Failed to restore witness {}: {}", txid, rollback);
+                    return Err(ManagerError::WitnessRollbackFailed {
+                        txid: txid.to_string(),
+                        broadcast: e.to_string(),
+                        rollback,
+                    });
+                }
+
+                contracts_manager
+                    .claim_storage_mut()
+                    .remove_witness_replacement(txid)?;
+            }
+
+            return Err(UtxoError::BroadcastFailed(format!("Failed to broadcast: {}", e)).into());
+        }
+        bitcoin_wallet.persist()?;
+
+        if rgb_witness {
+            if let Some(contracts_manager) = self.f1r3fly_contracts.as_mut() {
+                contracts_manager
+                    .claim_storage_mut()
+                    .remove_witness_replacement(txid)?;
+            }
+        }
+
+        log::info!("✓ Fee bumped: {} -> {}", original_txid, new_txid);
+
+        // RGB-occupied outputs keep their vout in the replacement
+        let moved: Vec<OutPoint> = self
+            .rgb_occupied
+            .iter()
+            .filter(|outpoint| outpoint.txid == original_txid)
+            .copied()
+            .collect();
+        if !moved.is_empty() {
+            self.unmark_rgb_occupied(moved.iter().copied())?;
+            self.mark_rgb_occupied(moved.iter().map(|outpoint| OutPoint {
+                txid: new_txid,
+                vout: outpoint.vout,
+            }))?;
+        }
+
+        Ok(FeeBumpResponse {
+            original_txid: txid.to_string(),
+            txid: new_txid.to_string(),
+            fee_sats: bump.fee,
+            fee_rate: bump.fee_rate,
+            rgb_witness,
+            consignments,
+        })
+    }
+
+    /// Accelerate an unconfirmed transaction with a child (CPFP)
+    ///
+    /// Spends a wallet-owned output of `txid` - typically BTC change, or the
+    /// output holding an incoming seal when nothing else is available - so
+    /// the parent + child package pays the target fee rate. When a seal
+    /// output is spent, it is carried forward to the child and every claim
+    /// bound to it is moved to the new outpoint (pending claims are simply
+    /// re-pointed; claimed tokens are moved with an owner-signed transfer).
+    ///
+    /// Seal moves are journaled before the child is broadcast; a move that
+    /// fails afterwards is replayed by the next `sync_wallet()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `txid` - Unconfirmed parent transaction ID
+    /// * `fee_rate` - Target package fee rate (defaults to the high-priority estimate)
+    ///
+    /// # Returns
+    ///
+    /// The child transaction and the effective package fee rate
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let accel = manager.cpfp(&incoming_txid, None).await?;
+    /// println!("Package now pays {:.2} sat/vB", accel.package_fee_rate);
+    /// ```
+    pub async fn cpfp(
+        &mut self,
+        txid: &str,
+        fee_rate: Option<&FeeRateConfig>,
+    ) -> Result<CpfpResponse, ManagerError> {
+        let parent_txid = bdk_wallet::bitcoin::Txid::from_str(txid).map_err(|e| {
+            ManagerError::Utxo(UtxoError::TransactionNotFound(format!(
+                "Invalid txid '{}': {}",
+                txid, e
+            )))
+        })?;
+
+        let fee_rate = match fee_rate {
+            Some(rate) => *rate,
+            None => get_recommended_fee_rates(&self.esplora_client)?.2,
+        };
+
+        // Seals bound to the parent, including incoming claims not yet claimed
+        let bound_claims = match self.f1r3fly_contracts.as_ref() {
+            Some(contracts_manager) => contracts_manager
+                .claim_storage()
+                .get_all_claims_unfiltered()?
+                .into_iter()
+                .filter(|claim| {
+                    claim.status != ClaimStatus::Failed
+                        && claim.actual_txid.as_deref() == Some(txid)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut protected = self.rgb_occupied.clone();
+        protected.extend(bound_claims.iter().filter_map(|claim| {
+            claim.actual_vout.map(|vout| OutPoint {
+                txid: parent_txid,
+                vout,
+            })
+        }));
+
+        let bitcoin_wallet = self
+            .bitcoin_wallet
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        // Build and sign only: seal moves are journaled before broadcast
+        let result = build_cpfp(
+            bitcoin_wallet,
+            &self.esplora_client,
+            parent_txid,
+            &fee_rate,
+            &protected,
+        )?;
+        let child_txid = result.txid().to_string();
+
+        let rebinds: Vec<SealRebind> = match result.carried_to {
+            Some(new_seal) => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                bound_claims
+                    .iter()
+                    .filter(|claim| claim.actual_vout == Some(result.spent.vout))
+                    .filter_map(|claim| {
+                        claim.id.map(|claim_id| SealRebind {
+                            claim_id,
+                            contract_id: claim.contract_id.clone(),
+                            spent_txid: txid.to_string(),
+                            spent_vout: result.spent.vout,
+                            txid: child_txid.clone(),
+                            vout: new_seal.vout,
+                            created_at: now,
+                        })
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        if !rebinds.is_empty() {
+            self.f1r3fly_contracts
+                .as_mut()
+                .ok_or(ManagerError::F1r3flyNotInitialized)?
+                .claim_storage_mut()
+                .insert_seal_rebinds(&rebinds)?;
+        }
+
+        if let Err(e) = self.esplora_client.inner().broadcast(&result.tx) {
+            // The seal never moved: drop the journaled rebinds
+            if let Some(contracts_manager) = self.f1r3fly_contracts.as_mut() {
+                for rebind in &rebinds {
+                    contracts_manager
+                        .claim_storage_mut()
+                        .remove_seal_rebind(rebind.claim_id)?;
+                }
+            }
+
+            return Err(UtxoError::BroadcastFailed(format!("Failed to broadcast: {}", e)).into());
+        }
+        bitcoin_wallet.persist()?;
+
+        log::info!(
+            "✓ CPFP child {} broadcast ({:.2} sat/vB package)",
+            child_txid,
+            result.package_fee_rate
+        );
+
+        let mut rebound_seal = None;
+        if let Some(new_seal) = result.carried_to {
+            if self.rgb_occupied.contains(&result.spent) {
+                self.unmark_rgb_occupied(std::iter::once(result.spent))?;
+                self.mark_rgb_occupied(std::iter::once(new_seal))?;
+            }
+
+            if !rebinds.is_empty() {
+                let contracts_manager = self
+                    .f1r3fly_contracts
+                    .as_mut()
+                    .ok_or(ManagerError::F1r3flyNotInitialized)?;
+
+                // A failed rebind stays journaled and is replayed on sync
+                for rebind in &rebinds {
+                    if let Some(claim) = bound_claims
+                        .iter()
+                        .find(|claim| claim.id == Some(rebind.claim_id))
+                    {
+                        apply_seal_rebind(contracts_manager, claim, rebind).await?;
+                    }
+                }
+
+                contracts_manager.save_state()?;
+            }
+
+            log::info!("✓ Seal moved: {} -> {}", result.spent, new_seal);
+            rebound_seal = Some(new_seal.to_string());
+        }
+
+        Ok(CpfpResponse {
+            parent_txid: txid.to_string(),
+            txid: child_txid,
+            spent_outpoint: result.spent.to_string(),
+            fee_sats: result.fee,
+            package_fee_rate: result.package_fee_rate,
+            rebound_seal,
+        })
+    }
+
+    /// Consignments directory of the loaded wallet
+    ///
+    /// The directory itself is created on demand when a consignment is saved.
+    fn consignments_dir(&self) -> Result<std::path::PathBuf, ManagerError> {
+        // Get wallet directory for consignments
+        let wallet_name = self
+            .wallet_metadata
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?
+            .name
+            .clone();
+
+        // Construct wallet directory path
+        let wallets_base = if let Some(dir_str) = &self.config.wallets_dir {
+            std::path::PathBuf::from(dir_str)
+        } else {
+            std::path::PathBuf::from(".f1r3fly-rgb-wallet/wallets")
+        };
+
+        let wallet_dir = wallets_base.join(&wallet_name);
+
+        // VALIDATE: Wallet directory must exist (wallet is loaded)
+        if !wallet_dir.exists() {
+            return Err(ManagerError::FileSystem(FileSystemError::WalletNotFound(
+                format!(
+                    "Wallet directory not found: {}. Wallet state may be corrupted.",
+                    wallet_dir.display()
+                ),
+            )));
+        }
+
+        // Consignments subdirectory (will be created if needed during transfer)
+        Ok(wallet_dir.join("consignments"))
+    }
+
+    /// Staging directory for transfers awaiting offline signatures
+    fn pending_transfers_dir(&self) -> Result<std::path::PathBuf, ManagerError> {
+        Ok(self.consignments_dir()?.with_file_name("pending_transfers"))
+    }
+
+    /// Journal of inline transfers in progress
+    fn transfer_journal(&self) -> Result<crate::f1r3fly::TransferJournal, ManagerError> {
+        Ok(crate::f1r3fly::TransferJournal::new(
+            self.consignments_dir()?.with_file_name("transfer_journal"),
+        ))
+    }
+
     /// Export genesis consignment for an issued asset
     ///
     /// Creates a genesis consignment that can be sent to recipients to enable
@@ -1474,6 +3133,9 @@ impl WalletManager {
             ))
         })?;
 
+        // A successful claim puts tokens on a new wallet UTXO - protect it
+        self.rebuild_rgb_occupied()?;
+
         Ok(response)
     }
 
@@ -1516,6 +3178,125 @@ impl WalletManager {
                 .map_err(ManagerError::Storage)
         }
     }
+
+    /// List RGB transfer history with optional filters
+    ///
+    /// Returns sent and received transfers recorded by `send_transfer`,
+    /// `send_transfer_batch` and `accept_consignment`, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Optional contract ID to filter transfers
+    /// * `from` - Optional start time (Unix seconds, inclusive)
+    /// * `until` - Optional end time (Unix seconds, inclusive)
+    ///
+    /// # Returns
+    ///
+    /// Vector of transfer records
+    ///
+    /// # Errors
+    ///
+    /// Returns error if wallet not loaded or storage query fails
+    pub fn list_transfers(
+        &self,
+        contract_id: Option<&str>,
+        from: Option<u64>,
+        until: Option<u64>,
+    ) -> Result<Vec<crate::storage::TransferRecord>, ManagerError> {
+        let contracts = self
+            .f1r3fly_contracts
+            .as_ref()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        contracts
+            .claim_storage()
+            .get_transfers(contract_id, from, until)
+            .map_err(ManagerError::Storage)
+    }
+
+    /// List invoices issued by this wallet
+    ///
+    /// Open invoices past their expiry are marked expired first.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Optional contract ID to filter invoices
+    /// * `status` - Optional status to filter invoices
+    ///
+    /// # Returns
+    ///
+    /// Matching invoices, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if wallet not loaded or storage query fails
+    pub fn list_invoices(
+        &mut self,
+        contract_id: Option<&str>,
+        status: Option<crate::storage::InvoiceStatus>,
+    ) -> Result<Vec<crate::storage::InvoiceRecord>, ManagerError> {
+        let contracts = self
+            .f1r3fly_contracts
+            .as_mut()
+            .ok_or(ManagerError::WalletNotLoaded)?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        contracts.claim_storage_mut().expire_invoices(now)?;
+
+        contracts
+            .claim_storage()
+            .get_invoices(contract_id, status)
+            .map_err(ManagerError::Storage)
+    }
+}
+
+/// Move a claim onto the child output recorded in a journaled seal rebind
+///
+/// Moves claimed tokens (see [`crate::f1r3fly::rebind_claim`]), re-points the
+/// stored outpoint and clears the journal entry. Safe to repeat: tokens that
+/// already moved leave nothing at the old seal.
+///
+/// # Arguments
+///
+/// * `contracts_manager` - Contracts manager with the claim's contract
+/// * `claim` - Claim bound to the spent seal
+/// * `rebind` - Journaled seal move
+async fn apply_seal_rebind(
+    contracts_manager: &mut F1r3flyContractsManager,
+    claim: &PendingClaim,
+    rebind: &SealRebind,
+) -> Result<(), ManagerError> {
+    let contract_id = hypersonic::ContractId::from_str(&rebind.contract_id).map_err(|e| {
+        ManagerError::Asset(AssetError::F1r3flyRgb(
+            f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!("Invalid contract ID: {}", e)),
+        ))
+    })?;
+
+    crate::f1r3fly::rebind_claim(
+        contracts_manager,
+        contract_id,
+        claim,
+        &rebind.txid,
+        rebind.vout,
+    )
+    .await
+    .map_err(|e| {
+        ManagerError::Asset(AssetError::F1r3flyRgb(
+            f1r3fly_rgb::F1r3flyRgbError::InvalidResponse(format!(
+                "Seal rebind failed (retried on next sync): {}",
+                e
+            )),
+        ))
+    })?;
+
+    let claim_storage = contracts_manager.claim_storage_mut();
+    claim_storage.update_claim_actual_utxo(rebind.claim_id, &rebind.txid, rebind.vout)?;
+    claim_storage.remove_seal_rebind(rebind.claim_id)?;
+
+    Ok(())
 }
 
 /// Apply filters to a list of UTXOs
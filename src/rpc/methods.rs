@@ -0,0 +1,263 @@
+//! RPC method dispatch
+//!
+//! Each method maps onto one `WalletManager` operation. Parameters may be
+//! passed by name (object) or by position (array, in field order).
+//!
+//! | Method                         | Params                           | Result                      |
+//! |--------------------------------|----------------------------------|-----------------------------|
+//! | `sync_wallet`                  | -                                | `SyncResult`                |
+//! | `get_rgb_balance`              | -                                | `[AssetBalance]`            |
+//! | `generate_invoice_with_pubkey` | `GenerateInvoiceParams`          | `InvoiceWithPubkey`         |
+//! | `send_transfer`                | `SendTransferParams`             | `TransferResponse`          |
+//! | `preview_transfer`             | `SendTransferParams`             | `TransferPreview`           |
+//! | `accept_consignment`           | `AcceptConsignmentParams`        | `AcceptConsignmentResponse` |
+//! | `list_claims`                  | `ListClaimsParams` (optional)    | `[PendingClaim]`            |
+//! | `list_invoices`                | `ListInvoicesParams` (optional)  | `[InvoiceRecord]`           |
+//! | `list_utxos`                   | `UtxoFilter` (optional)          | `[UtxoInfo]`                |
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::protocol::{RpcError, RpcRequest, RpcResponse, JSONRPC_VERSION};
+use crate::bitcoin::FeeRateConfig;
+use crate::f1r3fly::{AnchorMethod, TransferRecipient};
+use crate::manager::{ManagerError, WalletManager};
+use crate::storage::InvoiceStatus;
+use crate::types::UtxoFilter;
+
+/// Supported method names
+pub const METHODS: &[&str] = &[
+    "sync_wallet",
+    "get_rgb_balance",
+    "generate_invoice_with_pubkey",
+    "send_transfer",
+    "preview_transfer",
+    "accept_consignment",
+    "list_claims",
+    "list_invoices",
+    "list_utxos",
+];
+
+/// Parameters for `generate_invoice_with_pubkey`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateInvoiceParams {
+    pub contract_id: String,
+    pub amount: u64,
+
+    /// Seconds until the invoice expires (default: never)
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+
+    /// Pay to a blinded seal over a wallet UTXO instead of a revealed address
+    #[serde(default)]
+    pub blinded: bool,
+}
+
+/// Parameters for `send_transfer` and `preview_transfer`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendTransferParams {
+    pub invoice: String,
+
+    /// Recipient's F1r3fly public key (hex); overrides the key embedded in
+    /// the invoice
+    #[serde(default)]
+    pub recipient_pubkey: Option<String>,
+
+    /// Fee rate in sat/vB (default: medium priority)
+    #[serde(default)]
+    pub fee_rate: Option<f64>,
+
+    /// Anchoring method (default: wallet setting)
+    #[serde(default)]
+    pub anchor_method: Option<AnchorMethod>,
+}
+
+/// Parameters for `accept_consignment`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptConsignmentParams {
+    /// Consignment file path on the server's filesystem
+    pub consignment_path: String,
+}
+
+/// Parameters for `list_claims`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListClaimsParams {
+    /// Only claims for this contract
+    pub contract_id: Option<String>,
+}
+
+/// Parameters for `list_invoices`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListInvoicesParams {
+    /// Only invoices for this contract
+    pub contract_id: Option<String>,
+
+    /// Only invoices with this status
+    pub status: Option<InvoiceStatus>,
+}
+
+/// Handle one line received from a client
+///
+/// # Returns
+///
+/// The response to send, or `None` for a notification
+pub async fn handle_message(manager: &mut WalletManager, message: &str) -> Option<RpcResponse> {
+    let value: Value = match serde_json::from_str(message) {
+        Ok(value) => value,
+        Err(e) => return Some(RpcResponse::failure(Value::Null, RpcError::parse_error(e))),
+    };
+
+    if value.is_array() {
+        return Some(RpcResponse::failure(
+            Value::Null,
+            RpcError::invalid_request("batch requests are not supported"),
+        ));
+    }
+
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: RpcRequest = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => return Some(RpcResponse::failure(id, RpcError::invalid_request(e))),
+    };
+
+    handle_request(manager, request).await
+}
+
+/// Handle a parsed request
+///
+/// # Returns
+///
+/// The response to send, or `None` for a notification
+pub async fn handle_request(
+    manager: &mut WalletManager,
+    request: RpcRequest,
+) -> Option<RpcResponse> {
+    if request.jsonrpc != JSONRPC_VERSION {
+        return Some(RpcResponse::failure(
+            request.id.unwrap_or(Value::Null),
+            RpcError::invalid_request(format!("unsupported jsonrpc version '{}'", request.jsonrpc)),
+        ));
+    }
+
+    let result = call(manager, &request.method, request.params).await;
+    let id = request.id?;
+
+    Some(match result {
+        Ok(result) => RpcResponse::success(id, result),
+        Err(error) => RpcResponse::failure(id, error),
+    })
+}
+
+/// Run one method against the wallet
+pub async fn call(
+    manager: &mut WalletManager,
+    method: &str,
+    params: Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "sync_wallet" => to_result(manager.sync_wallet().await),
+
+        "get_rgb_balance" => to_result(manager.get_rgb_balance().await),
+
+        "generate_invoice_with_pubkey" => {
+            let params: GenerateInvoiceParams = parse_params(params)?;
+            let invoice = if params.blinded {
+                manager.generate_blinded_invoice(
+                    &params.contract_id,
+                    params.amount,
+                    params.expires_in,
+                )
+            } else {
+                manager.generate_invoice_with_expiry(
+                    &params.contract_id,
+                    params.amount,
+                    params.expires_in,
+                    None,
+                )
+            };
+
+            // Persist the revealed receive address, as the CLI does
+            if invoice.is_ok() {
+                manager
+                    .bitcoin_wallet_mut()
+                    .ok_or(ManagerError::WalletNotLoaded)?
+                    .persist()
+                    .map_err(ManagerError::from)?;
+            }
+            to_result(invoice)
+        }
+
+        "send_transfer" => {
+            let params: SendTransferParams = parse_params(params)?;
+            let fee_rate = match params.fee_rate {
+                Some(rate) => FeeRateConfig::new(rate).map_err(RpcError::invalid_params)?,
+                None => FeeRateConfig::medium_priority(),
+            };
+            to_result(
+                manager
+                    .send_transfer(
+                        &params.invoice,
+                        params.recipient_pubkey,
+                        &fee_rate,
+                        params.anchor_method,
+                    )
+                    .await,
+            )
+        }
+
+        "preview_transfer" => {
+            let params: SendTransferParams = parse_params(params)?;
+            let fee_rate = match params.fee_rate {
+                Some(rate) => FeeRateConfig::new(rate).map_err(RpcError::invalid_params)?,
+                None => FeeRateConfig::medium_priority(),
+            };
+            let recipients = [TransferRecipient {
+                invoice: params.invoice,
+                recipient_pubkey_hex: params.recipient_pubkey,
+            }];
+            to_result(
+                manager
+                    .preview_transfer_batch(&recipients, &fee_rate, params.anchor_method)
+                    .await,
+            )
+        }
+
+        "accept_consignment" => {
+            let params: AcceptConsignmentParams = parse_params(params)?;
+            to_result(manager.accept_consignment(&params.consignment_path).await)
+        }
+
+        "list_claims" => {
+            let params: ListClaimsParams = parse_params(params)?;
+            to_result(manager.list_claims(params.contract_id.as_deref()))
+        }
+
+        "list_invoices" => {
+            let params: ListInvoicesParams = parse_params(params)?;
+            to_result(manager.list_invoices(params.contract_id.as_deref(), params.status))
+        }
+
+        "list_utxos" => {
+            let filter: UtxoFilter = parse_params(params)?;
+            to_result(manager.list_utxos(filter).await)
+        }
+
+        _ => Err(RpcError::method_not_found(method)),
+    }
+}
+
+/// Deserialize method parameters (missing params read as `{}`)
+fn parse_params<P: DeserializeOwned>(params: Value) -> Result<P, RpcError> {
+    let params = match params {
+        Value::Null => Value::Object(Default::default()),
+        params => params,
+    };
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+fn to_result<T: Serialize>(result: Result<T, ManagerError>) -> Result<Value, RpcError> {
+    serde_json::to_value(result?).map_err(RpcError::internal)
+}
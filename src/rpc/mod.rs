@@ -0,0 +1,49 @@
+//! JSON-RPC API
+//!
+//! Exposes `WalletManager` operations to backend services as JSON-RPC 2.0
+//! over a local Unix socket, one message per line. Parameters and results
+//! are the wallet's own serde types, and failures carry an error code
+//! derived from the `ManagerError` variant, so integrations no longer have
+//! to scrape CLI output.
+
+pub mod methods;
+pub mod protocol;
+
+#[cfg(unix)]
+pub mod server;
+
+use std::path::PathBuf;
+
+use crate::config::ConfigError;
+
+/// Environment variable overriding the RPC socket path
+pub const RPC_SOCKET_ENV: &str = "FIREFLY_RPC_SOCKET";
+
+/// RPC server errors
+#[derive(Debug, thiserror::Error)]
+pub enum RpcServerError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("RPC server already running at {0}")]
+    AlreadyRunning(String),
+}
+
+/// Get the RPC socket path (custom, `FIREFLY_RPC_SOCKET`, or default)
+///
+/// Default: `~/.f1r3fly-rgb-wallet/rpc.sock`
+pub fn socket_path(custom: Option<&str>) -> Result<PathBuf, RpcServerError> {
+    if let Some(path) = custom {
+        return Ok(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var(RPC_SOCKET_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(crate::config::default_config_dir()?.join("rpc.sock"))
+}
@@ -0,0 +1,185 @@
+//! JSON-RPC 2.0 envelopes and error codes
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::manager::ManagerError;
+
+/// Protocol version carried by every message
+pub const JSONRPC_VERSION: &str = "2.0";
+
+// Standard JSON-RPC error codes
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+// Wallet error codes (one per `ManagerError` variant)
+pub const WALLET_NOT_LOADED: i64 = -32001;
+pub const F1R3FLY_NOT_INITIALIZED: i64 = -32002;
+pub const WALLET_ALREADY_EXISTS: i64 = -32003;
+pub const INVALID_MNEMONIC: i64 = -32004;
+pub const CONFIG_ERROR: i64 = -32010;
+pub const FILE_SYSTEM_ERROR: i64 = -32011;
+pub const KEY_ERROR: i64 = -32012;
+pub const STORAGE_ERROR: i64 = -32013;
+pub const RESTORE_ERROR: i64 = -32014;
+pub const BITCOIN_WALLET_ERROR: i64 = -32020;
+pub const NETWORK_ERROR: i64 = -32021;
+pub const SYNC_ERROR: i64 = -32022;
+pub const BALANCE_ERROR: i64 = -32023;
+pub const UTXO_ERROR: i64 = -32024;
+pub const PSBT_ERROR: i64 = -32025;
+pub const WITNESS_ROLLBACK_FAILED: i64 = -32026;
+pub const ASSET_ERROR: i64 = -32030;
+pub const CONTRACTS_MANAGER_ERROR: i64 = -32031;
+pub const F1R3FLY_EXECUTOR_ERROR: i64 = -32032;
+pub const RGB_BALANCE_ERROR: i64 = -32033;
+
+/// JSON-RPC request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+
+    pub method: String,
+
+    /// Named (object) or positional (array) parameters
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+
+    /// Request ID; notifications omit it and get no response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+}
+
+impl RpcRequest {
+    /// Create a request
+    pub fn new(id: impl Into<Value>, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            method: method.to_string(),
+            params,
+            id: Some(id.into()),
+        }
+    }
+}
+
+/// JSON-RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+
+    /// ID of the request (null if it could not be read)
+    pub id: Value,
+}
+
+impl RpcResponse {
+    /// Successful response
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            result: Some(result),
+            error: None,
+            id,
+        }
+    }
+
+    /// Error response
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            result: None,
+            error: Some(error),
+            id,
+        }
+    }
+}
+
+/// JSON-RPC error object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+
+    pub message: String,
+
+    /// `{"kind": "<ManagerError variant>"}` for wallet errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Create an error without data
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn parse_error(message: impl std::fmt::Display) -> Self {
+        Self::new(PARSE_ERROR, format!("Parse error: {}", message))
+    }
+
+    pub fn invalid_request(message: impl std::fmt::Display) -> Self {
+        Self::new(INVALID_REQUEST, format!("Invalid request: {}", message))
+    }
+
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))
+    }
+
+    pub fn invalid_params(message: impl std::fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, format!("Invalid params: {}", message))
+    }
+
+    pub fn internal(message: impl std::fmt::Display) -> Self {
+        Self::new(INTERNAL_ERROR, format!("Internal error: {}", message))
+    }
+}
+
+impl From<ManagerError> for RpcError {
+    fn from(error: ManagerError) -> Self {
+        let (code, kind) = manager_error_code(&error);
+        Self {
+            code,
+            message: error.to_string(),
+            data: Some(serde_json::json!({ "kind": kind })),
+        }
+    }
+}
+
+/// Error code and variant name for a `ManagerError`
+pub fn manager_error_code(error: &ManagerError) -> (i64, &'static str) {
+    match error {
+        ManagerError::WalletNotLoaded => (WALLET_NOT_LOADED, "WalletNotLoaded"),
+        ManagerError::F1r3flyNotInitialized => (F1R3FLY_NOT_INITIALIZED, "F1r3flyNotInitialized"),
+        ManagerError::WalletAlreadyExists(_) => (WALLET_ALREADY_EXISTS, "WalletAlreadyExists"),
+        ManagerError::InvalidMnemonic(_) => (INVALID_MNEMONIC, "InvalidMnemonic"),
+        ManagerError::Config(_) => (CONFIG_ERROR, "Config"),
+        ManagerError::FileSystem(_) => (FILE_SYSTEM_ERROR, "FileSystem"),
+        ManagerError::Key(_) => (KEY_ERROR, "Key"),
+        ManagerError::Storage(_) => (STORAGE_ERROR, "Storage"),
+        ManagerError::Restore(_) => (RESTORE_ERROR, "Restore"),
+        ManagerError::BitcoinWallet(_) => (BITCOIN_WALLET_ERROR, "BitcoinWallet"),
+        ManagerError::Network(_) => (NETWORK_ERROR, "Network"),
+        ManagerError::Sync(_) => (SYNC_ERROR, "Sync"),
+        ManagerError::Balance(_) => (BALANCE_ERROR, "Balance"),
+        ManagerError::Utxo(_) => (UTXO_ERROR, "Utxo"),
+        ManagerError::Psbt(_) => (PSBT_ERROR, "Psbt"),
+        ManagerError::WitnessRollbackFailed { .. } => {
+            (WITNESS_ROLLBACK_FAILED, "WitnessRollbackFailed")
+        }
+        ManagerError::Asset(_) => (ASSET_ERROR, "Asset"),
+        ManagerError::ContractsManager(_) => (CONTRACTS_MANAGER_ERROR, "ContractsManager"),
+        ManagerError::F1r3flyExecutor(_) => (F1R3FLY_EXECUTOR_ERROR, "F1r3flyExecutor"),
+        ManagerError::RgbBalance(_) => (RGB_BALANCE_ERROR, "RgbBalance"),
+    }
+}
@@ -0,0 +1,102 @@
+//! RPC server
+//!
+//! Serves one connection at a time over a Unix socket. A connection may send
+//! any number of requests, one per line; each is answered on its own line
+//! in order. A connection idle for `IDLE_TIMEOUT` is closed.
+
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::methods::handle_message;
+use super::RpcServerError;
+use crate::daemon::server::bind_private_socket;
+use crate::manager::WalletManager;
+
+/// How long a connection may stay silent before it is closed
+///
+/// Connections are served one at a time, so an idle client would otherwise
+/// lock every other client out.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// JSON-RPC server over a loaded wallet
+pub struct RpcServer {
+    listener: UnixListener,
+    socket_path: PathBuf,
+    manager: WalletManager,
+}
+
+impl RpcServer {
+    /// Bind the RPC socket
+    ///
+    /// A stale socket left by a crashed server is replaced; a live one is an
+    /// error. The socket is only accessible to the current user.
+    ///
+    /// # Arguments
+    ///
+    /// * `socket_path` - Socket to listen on
+    /// * `manager` - Manager with the wallet to serve loaded
+    pub fn bind(socket_path: &Path, manager: WalletManager) -> Result<Self, RpcServerError> {
+        let listener = bind_private_socket(socket_path).map_err(|e| match e.kind() {
+            ErrorKind::AddrInUse => {
+                RpcServerError::AlreadyRunning(socket_path.display().to_string())
+            }
+            _ => e.into(),
+        })?;
+
+        Ok(Self {
+            listener,
+            socket_path: socket_path.to_path_buf(),
+            manager,
+        })
+    }
+
+    /// Serve connections until the process is stopped
+    pub fn run(mut self) -> Result<(), RpcServerError> {
+        let runtime = crate::cli::runtime()?;
+
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            if let Err(e) = self.serve_connection(stream, runtime) {
+                log::warn!("RPC connection failed: {}", e);
+            }
+        }
+    }
+
+    fn serve_connection(
+        &mut self,
+        stream: UnixStream,
+        runtime: &tokio::runtime::Runtime,
+    ) -> Result<(), RpcServerError> {
+        stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+
+        for line in BufReader::new(stream).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    log::debug!("Closing idle RPC connection");
+                    break;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(response) = runtime.block_on(handle_message(&mut self.manager, &line)) {
+                writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for RpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
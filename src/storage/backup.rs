@@ -0,0 +1,466 @@
+//! Encrypted single-file wallet backups
+//!
+//! A backup snapshots the whole wallet directory (keys, metadata, BDK
+//! database, F1r3fly state, claims database, consignments) into one file
+//! encrypted with the wallet password (see `keys::encrypt_data`).
+//!
+//! Archive layout: a JSON envelope whose `payload` decrypts to
+//! `u32 manifest length (big-endian) || manifest JSON || file contents`,
+//! with files concatenated in manifest order. The manifest records each
+//! file's size and SHA-256, and is checked before anything is unpacked.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::NetworkType;
+use crate::storage::file_system::{
+    load_wallet, wallet_dir, wallets_dir, write_file_atomic, FileSystemError,
+};
+use crate::storage::keys::{decrypt_data, encrypt_data, KeyError};
+use crate::storage::models::{EncryptedWalletKeys, WalletMetadata};
+
+/// Current backup format version
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Format identifier stored in the archive envelope
+const BACKUP_FORMAT: &str = "f1r3fly-rgb-wallet-backup";
+
+/// Backup errors
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Key error: {0}")]
+    Key(#[from] KeyError),
+
+    #[error("File system error: {0}")]
+    FileSystem(#[from] FileSystemError),
+
+    #[error("Database snapshot failed: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Invalid backup archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("Checksum mismatch for {0}")]
+    ChecksumMismatch(String),
+
+    #[error("Wallet '{0}' has changes newer than the backup (use --force to overwrite)")]
+    NewerWallet(String),
+
+    #[error("Backup holds wallet '{found}', not '{expected}'")]
+    NameMismatch { expected: String, found: String },
+
+    #[error("Wallet '{0}' holds different keys than the backup (use --force to overwrite)")]
+    WalletMismatch(String),
+}
+
+/// One file in a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFileEntry {
+    /// Path relative to the wallet directory ('/'-separated)
+    pub path: String,
+
+    /// Size in bytes
+    pub size: u64,
+
+    /// SHA-256 of the contents (hex)
+    pub sha256: String,
+}
+
+/// Manifest describing a backup's contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Backup format version (see `BACKUP_FORMAT_VERSION`)
+    pub version: u32,
+
+    /// Name of the backed-up wallet
+    pub wallet_name: String,
+
+    /// Network of the backed-up wallet
+    pub network: NetworkType,
+
+    /// When the backup was taken
+    pub created_at: DateTime<Utc>,
+
+    /// Files in archive order
+    pub files: Vec<BackupFileEntry>,
+}
+
+impl BackupManifest {
+    /// Total size of the backed-up files in bytes
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// Outer (unencrypted) archive envelope
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupEnvelope {
+    format: String,
+    version: u32,
+    wallet_name: String,
+    created_at: DateTime<Utc>,
+
+    /// `encrypt_data` output over the manifest and file contents
+    payload: String,
+}
+
+/// Create an encrypted backup of a wallet
+///
+/// SQLite databases are copied with `VACUUM INTO`, so the snapshot is
+/// consistent even if another process has the wallet open. Temporary and
+/// journal files are left out.
+///
+/// # Arguments
+///
+/// * `wallet_name` - Name of the wallet
+/// * `password` - Wallet password (verified, then used to encrypt the archive)
+/// * `custom_base` - Optional custom wallets directory
+/// * `output` - Archive file to write
+///
+/// # Returns
+///
+/// The manifest written into the archive
+///
+/// # Example
+///
+/// ```ignore
+/// let manifest = create_backup("my_wallet", "password", None, Path::new("my_wallet.backup"))?;
+/// println!("Backed up {} files", manifest.files.len());
+/// ```
+pub fn create_backup(
+    wallet_name: &str,
+    password: &str,
+    custom_base: Option<&str>,
+    output: &Path,
+) -> Result<BackupManifest, BackupError> {
+    // Fails on a wrong password before anything is written
+    let (_, metadata) = load_wallet(wallet_name, password, custom_base)?;
+    let wallet_path = wallet_dir(wallet_name, custom_base)?;
+
+    let mut files = Vec::new();
+    let mut contents = Vec::new();
+    for relative in collect_wallet_files(&wallet_path)? {
+        let data = read_snapshot(&wallet_path.join(&relative))?;
+        files.push(BackupFileEntry {
+            path: relative_to_string(&relative),
+            size: data.len() as u64,
+            sha256: hex::encode(Sha256::digest(&data)),
+        });
+        contents.extend_from_slice(&data);
+    }
+
+    let manifest = BackupManifest {
+        version: BACKUP_FORMAT_VERSION,
+        wallet_name: wallet_name.to_string(),
+        network: metadata.network,
+        created_at: Utc::now(),
+        files,
+    };
+
+    let manifest_json = serde_json::to_vec(&manifest)?;
+    let mut payload = (manifest_json.len() as u32).to_be_bytes().to_vec();
+    payload.extend_from_slice(&manifest_json);
+    payload.extend_from_slice(&contents);
+
+    let envelope = BackupEnvelope {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_FORMAT_VERSION,
+        wallet_name: wallet_name.to_string(),
+        created_at: manifest.created_at,
+        payload: encrypt_data(&payload, password)?,
+    };
+    write_file_atomic(output, &serde_json::to_vec(&envelope)?)?;
+
+    Ok(manifest)
+}
+
+/// Decrypt a backup and verify every file against its manifest
+///
+/// # Arguments
+///
+/// * `archive` - Backup file
+/// * `password` - Password the backup was created with
+///
+/// # Returns
+///
+/// The manifest and the verified file contents (in manifest order)
+///
+/// # Errors
+///
+/// Returns `Key(Decryption)` on a wrong password, `ChecksumMismatch` or
+/// `InvalidArchive` if the archive is corrupted.
+pub fn read_backup(
+    archive: &Path,
+    password: &str,
+) -> Result<(BackupManifest, Vec<Vec<u8>>), BackupError> {
+    let envelope: BackupEnvelope = serde_json::from_slice(&fs::read(archive)?)
+        .map_err(|e| BackupError::InvalidArchive(e.to_string()))?;
+
+    if envelope.format != BACKUP_FORMAT {
+        return Err(BackupError::InvalidArchive(format!(
+            "unknown format '{}'",
+            envelope.format
+        )));
+    }
+    if envelope.version > BACKUP_FORMAT_VERSION {
+        return Err(BackupError::InvalidArchive(format!(
+            "version {} is newer than supported version {}",
+            envelope.version, BACKUP_FORMAT_VERSION
+        )));
+    }
+
+    let payload = decrypt_data(&envelope.payload, password)?;
+
+    let (length, rest) = payload
+        .split_first_chunk::<4>()
+        .ok_or_else(|| BackupError::InvalidArchive("truncated payload".to_string()))?;
+    let manifest_len = u32::from_be_bytes(*length) as usize;
+    if rest.len() < manifest_len {
+        return Err(BackupError::InvalidArchive(
+            "truncated manifest".to_string(),
+        ));
+    }
+    let (manifest_json, mut data) = rest.split_at(manifest_len);
+    let manifest: BackupManifest = serde_json::from_slice(manifest_json)?;
+
+    let mut files = Vec::with_capacity(manifest.files.len());
+    for entry in &manifest.files {
+        safe_relative_path(&entry.path)?;
+
+        let size = entry.size as usize;
+        if data.len() < size {
+            return Err(BackupError::InvalidArchive(format!(
+                "truncated contents of {}",
+                entry.path
+            )));
+        }
+        let (file_data, remaining) = data.split_at(size);
+        if hex::encode(Sha256::digest(file_data)) != entry.sha256 {
+            return Err(BackupError::ChecksumMismatch(entry.path.clone()));
+        }
+        files.push(file_data.to_vec());
+        data = remaining;
+    }
+
+    if !data.is_empty() {
+        return Err(BackupError::InvalidArchive(
+            "unexpected data after last file".to_string(),
+        ));
+    }
+
+    Ok((manifest, files))
+}
+
+/// Restore a wallet from an encrypted backup
+///
+/// The archive is fully verified and unpacked into a staging directory
+/// before the wallet directory is replaced. An existing wallet is only
+/// replaced if it holds the same keys and has not changed since the
+/// backup was taken, unless `force` is set.
+///
+/// # Arguments
+///
+/// * `archive` - Backup file
+/// * `password` - Password the backup was created with
+/// * `expected_name` - Wallet name the backup must hold (None: any)
+/// * `custom_base` - Optional custom wallets directory
+/// * `force` - Overwrite a newer or different existing wallet
+///
+/// # Returns
+///
+/// The manifest of the restored backup
+///
+/// # Example
+///
+/// ```ignore
+/// let manifest = restore_backup(Path::new("my_wallet.backup"), "password", None, None, false)?;
+/// println!("Restored wallet '{}'", manifest.wallet_name);
+/// ```
+pub fn restore_backup(
+    archive: &Path,
+    password: &str,
+    expected_name: Option<&str>,
+    custom_base: Option<&str>,
+    force: bool,
+) -> Result<BackupManifest, BackupError> {
+    let (manifest, files) = read_backup(archive, password)?;
+    let name = manifest.wallet_name.as_str();
+
+    // Stored claim and transfer paths point into the original wallet directory
+    if let Some(expected) = expected_name.filter(|expected| *expected != name) {
+        return Err(BackupError::NameMismatch {
+            expected: expected.to_string(),
+            found: name.to_string(),
+        });
+    }
+
+    // Backed-up keys must open with the password and match the metadata
+    let keys_index = manifest
+        .files
+        .iter()
+        .position(|entry| entry.path == "keys.json")
+        .ok_or_else(|| BackupError::InvalidArchive("missing keys.json".to_string()))?;
+    let metadata_index = manifest
+        .files
+        .iter()
+        .position(|entry| entry.path == "wallet.json")
+        .ok_or_else(|| BackupError::InvalidArchive("missing wallet.json".to_string()))?;
+    let backup_keys: EncryptedWalletKeys = serde_json::from_slice(&files[keys_index])?;
+    let backup_metadata: WalletMetadata = serde_json::from_slice(&files[metadata_index])?;
+    backup_keys.to_keys(password, backup_metadata.network)?;
+
+    let target = wallet_dir(name, custom_base)?;
+    if target.exists() && !force {
+        check_replaceable(&target, name, &backup_keys, manifest.created_at)?;
+    }
+
+    // Unpack next to the target so the final renames stay on one file system
+    let base = wallets_dir(custom_base)?;
+    fs::create_dir_all(&base)?;
+    let staging = base.join(format!(".{}.restore", name));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+
+    let backup_time = SystemTime::from(manifest.created_at);
+    for (entry, data) in manifest.files.iter().zip(&files) {
+        let path = staging.join(safe_relative_path(&entry.path)?);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, data)?;
+        // Keep the backup time so an untouched restore is not seen as newer
+        fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(backup_time)?;
+    }
+
+    if target.exists() {
+        let replaced = base.join(format!(".{}.replaced", name));
+        if replaced.exists() {
+            fs::remove_dir_all(&replaced)?;
+        }
+        fs::rename(&target, &replaced)?;
+        fs::rename(&staging, &target)?;
+        fs::remove_dir_all(&replaced)?;
+    } else {
+        fs::rename(&staging, &target)?;
+    }
+
+    Ok(manifest)
+}
+
+/// Refuse to replace a wallet that differs from, or is newer than, the backup
+fn check_replaceable(
+    target: &Path,
+    name: &str,
+    backup_keys: &EncryptedWalletKeys,
+    backup_time: DateTime<Utc>,
+) -> Result<(), BackupError> {
+    let keys_path = target.join("keys.json");
+    if keys_path.exists() {
+        let existing: EncryptedWalletKeys = serde_json::from_str(&fs::read_to_string(keys_path)?)?;
+        if existing.bitcoin_public_descriptor != backup_keys.bitcoin_public_descriptor {
+            return Err(BackupError::WalletMismatch(name.to_string()));
+        }
+    }
+
+    let backup_time = SystemTime::from(backup_time);
+    for relative in collect_wallet_files(target)? {
+        if fs::metadata(target.join(&relative))?.modified()? > backup_time {
+            return Err(BackupError::NewerWallet(name.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// List the files of a wallet directory to back up (relative paths, sorted)
+fn collect_wallet_files(wallet_path: &Path) -> Result<Vec<PathBuf>, BackupError> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = pending.pop() {
+        for entry in fs::read_dir(wallet_path.join(&relative_dir))? {
+            let entry = entry?;
+            let relative = relative_dir.join(entry.file_name());
+            let file_type = entry.file_type()?;
+
+            if file_type.is_dir() {
+                pending.push(relative);
+            } else if file_type.is_file() && !is_transient(&relative) {
+                files.push(relative);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Temporary files and SQLite journals (covered by the database snapshot)
+fn is_transient(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tmp")
+        || name.ends_with("-journal")
+        || name.ends_with("-wal")
+        || name.ends_with("-shm")
+}
+
+/// Read a file, snapshotting SQLite databases through `VACUUM INTO`
+fn read_snapshot(path: &Path) -> Result<Vec<u8>, BackupError> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("db") {
+        return Ok(fs::read(path)?);
+    }
+
+    let snapshot = path.with_extension("db.snapshot.tmp");
+    if snapshot.exists() {
+        fs::remove_file(&snapshot)?;
+    }
+
+    let conn =
+        rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy()])?;
+    drop(conn);
+
+    let data = fs::read(&snapshot)?;
+    fs::remove_file(&snapshot)?;
+    Ok(data)
+}
+
+/// Manifest path ('/'-separated) of a relative path
+fn relative_to_string(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Reject manifest paths that could escape the wallet directory
+fn safe_relative_path(path: &str) -> Result<PathBuf, BackupError> {
+    let relative = PathBuf::from(path);
+    let safe = !path.is_empty()
+        && relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+    if safe {
+        Ok(relative)
+    } else {
+        Err(BackupError::InvalidArchive(format!(
+            "unsafe path '{}'",
+            path
+        )))
+    }
+}
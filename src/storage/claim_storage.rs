@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
 /// Status of a witness claim
@@ -51,10 +51,12 @@ pub struct PendingClaim {
     /// Database ID (None if not yet inserted)
     pub id: Option<i64>,
 
-    /// Witness identifier (e.g. "witness:a3467636:0")
+    /// Witness identifier (e.g. "witness:a3467636:0", or "blinded:at:..."
+    /// for a blinded seal)
     pub witness_id: String,
 
-    /// Recipient's Bitcoin address (from invoice, pre-Tapret)
+    /// Recipient's Bitcoin address (from invoice, pre-Tapret), or the auth
+    /// token of a blinded seal
     pub recipient_address: String,
 
     /// Expected vout in the Bitcoin transaction
@@ -86,6 +88,325 @@ pub struct PendingClaim {
     pub actual_vout: Option<u32>,
 }
 
+/// Direction of an RGB transfer relative to this wallet
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    /// Tokens sent by this wallet
+    Outgoing,
+    /// Tokens received by this wallet (accepted consignment)
+    Incoming,
+}
+
+impl TransferDirection {
+    /// Convert to database string representation
+    fn to_db_string(&self) -> &'static str {
+        match self {
+            TransferDirection::Outgoing => "outgoing",
+            TransferDirection::Incoming => "incoming",
+        }
+    }
+
+    /// Parse from database string
+    fn from_db_string(s: &str) -> Result<Self, StorageError> {
+        match s {
+            "outgoing" => Ok(TransferDirection::Outgoing),
+            "incoming" => Ok(TransferDirection::Incoming),
+            _ => Err(StorageError::InvalidData(format!(
+                "Invalid transfer direction: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Status of a recorded RGB transfer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    /// Outgoing witness transaction was broadcast
+    Broadcasted,
+    /// Incoming transfer accepted, claim not executed yet
+    Pending,
+    /// Incoming transfer claimed (tokens on the real UTXO)
+    Claimed,
+    /// Incoming claim failed (will retry on next sync)
+    Failed,
+}
+
+impl TransferStatus {
+    /// Convert to database string representation
+    fn to_db_string(&self) -> &'static str {
+        match self {
+            TransferStatus::Broadcasted => "broadcasted",
+            TransferStatus::Pending => "pending",
+            TransferStatus::Claimed => "claimed",
+            TransferStatus::Failed => "failed",
+        }
+    }
+
+    /// Parse from database string
+    fn from_db_string(s: &str) -> Result<Self, StorageError> {
+        match s {
+            "broadcasted" => Ok(TransferStatus::Broadcasted),
+            "pending" => Ok(TransferStatus::Pending),
+            "claimed" => Ok(TransferStatus::Claimed),
+            "failed" => Ok(TransferStatus::Failed),
+            _ => Err(StorageError::InvalidData(format!(
+                "Invalid transfer status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl From<&ClaimStatus> for TransferStatus {
+    fn from(status: &ClaimStatus) -> Self {
+        match status {
+            ClaimStatus::Pending => TransferStatus::Pending,
+            ClaimStatus::Claimed => TransferStatus::Claimed,
+            ClaimStatus::Failed => TransferStatus::Failed,
+        }
+    }
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_db_string())
+    }
+}
+
+impl std::fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_db_string())
+    }
+}
+
+/// Transfer history entry
+///
+/// One record per sent invoice (batch transfers produce one record per
+/// recipient) and one per accepted transfer consignment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRecord {
+    /// Database ID (None if not yet inserted)
+    pub id: Option<i64>,
+
+    /// Outgoing or incoming
+    pub direction: TransferDirection,
+
+    /// Contract ID
+    pub contract_id: String,
+
+    /// Token amount (unknown for incoming transfers until claimed)
+    pub amount: Option<u64>,
+
+    /// Counterparty public key (recipient pubkey for outgoing transfers)
+    pub counterparty_pubkey: Option<String>,
+
+    /// Bitcoin witness transaction ID
+    pub witness_txid: String,
+
+    /// Anchor method ("tapret" / "opreturn"), if known
+    pub anchor_method: Option<String>,
+
+    /// Path to the consignment file
+    pub consignment_path: PathBuf,
+
+    /// Current status
+    pub status: TransferStatus,
+
+    /// Linked witness claim (incoming transfers only)
+    pub claim_id: Option<i64>,
+
+    /// Unix timestamp when the transfer was recorded
+    pub created_at: u64,
+
+    /// Unix timestamp of the last status change
+    pub updated_at: u64,
+}
+
+/// Status of an issued invoice
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    /// Nothing received yet
+    Open,
+    /// Some tokens received, less than the invoiced amount
+    PartiallyPaid,
+    /// Invoiced amount received in full
+    Paid,
+    /// Expiry passed before it was paid in full
+    Expired,
+}
+
+impl InvoiceStatus {
+    /// Convert to database string representation
+    fn to_db_string(&self) -> &'static str {
+        match self {
+            InvoiceStatus::Open => "open",
+            InvoiceStatus::PartiallyPaid => "partially_paid",
+            InvoiceStatus::Paid => "paid",
+            InvoiceStatus::Expired => "expired",
+        }
+    }
+
+    /// Parse from database string
+    fn from_db_string(s: &str) -> Result<Self, StorageError> {
+        match s {
+            "open" => Ok(InvoiceStatus::Open),
+            "partially_paid" => Ok(InvoiceStatus::PartiallyPaid),
+            "paid" => Ok(InvoiceStatus::Paid),
+            "expired" => Ok(InvoiceStatus::Expired),
+            _ => Err(StorageError::InvalidData(format!(
+                "Invalid invoice status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for InvoiceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_db_string())
+    }
+}
+
+impl std::str::FromStr for InvoiceStatus {
+    type Err = StorageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_db_string(&s.to_lowercase().replace('-', "_"))
+    }
+}
+
+/// Invoice issued by this wallet
+///
+/// Incoming transfer consignments are matched to invoices by contract and
+/// recipient address; amounts are credited once their claims complete.
+///
+/// Expiry semantics:
+/// - A payment counts if its consignment was accepted before the expiry,
+///   even when its claim completes afterwards
+/// - Payments accepted at or after the expiry are recorded but never
+///   credited, so a late payment never marks an invoice paid
+/// - At expiry, open and partially paid invoices become `Expired`; a
+///   partially paid one keeps the amount received in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceRecord {
+    /// Database ID (None if not yet inserted)
+    pub id: Option<i64>,
+
+    /// Invoice string as shared with the sender
+    pub invoice: String,
+
+    /// Contract ID
+    pub contract_id: String,
+
+    /// Invoiced token amount
+    pub amount: u64,
+
+    /// Bitcoin address receiving the seal output, or the auth token of a
+    /// blinded seal
+    pub address: String,
+
+    /// Invoice nonce (unique per wallet)
+    pub nonce: u64,
+
+    /// Recipient F1r3fly public key embedded in the invoice
+    pub recipient_pubkey: Option<String>,
+
+    /// Unix timestamp after which the invoice must not be paid
+    pub expiry: Option<u64>,
+
+    /// Current status
+    pub status: InvoiceStatus,
+
+    /// Tokens received before the expiry (claimed transfers only)
+    pub amount_received: u64,
+
+    /// Unix timestamp when the invoice was issued
+    pub created_at: u64,
+
+    /// Unix timestamp of the last status change
+    pub updated_at: u64,
+}
+
+/// Blinded seal over a wallet UTXO, issued in an invoice
+///
+/// Senders only see the auth token; the outpoint and the noise concealing
+/// it never leave the wallet. Tokens paid to the seal are claimed onto the
+/// outpoint when the transfer consignment is accepted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlindedSeal {
+    /// Database ID (None if not yet inserted)
+    pub id: Option<i64>,
+
+    /// Auth token of the seal, as shown in the invoice
+    pub auth_token: String,
+
+    /// Contract ID of the invoice
+    pub contract_id: String,
+
+    /// Wallet UTXO transaction ID (display format)
+    pub txid: String,
+
+    /// Wallet UTXO output index
+    pub vout: u32,
+
+    /// Secret noise concealing the outpoint (hex)
+    pub noise: String,
+
+    /// Claim moving the received tokens onto the UTXO (None until paid)
+    pub claim_id: Option<i64>,
+
+    /// Unix timestamp when the seal was issued
+    pub created_at: u64,
+}
+
+/// Seal move recorded before a CPFP child spending the seal is broadcast
+///
+/// Cleared once the claim is bound to the child output; an entry left
+/// behind (e.g. the rebind failed after broadcast) is replayed on sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealRebind {
+    /// Claim bound to the spent seal
+    pub claim_id: i64,
+
+    /// Contract ID of the claim
+    pub contract_id: String,
+
+    /// Seal outpoint spent by the child (display format txid)
+    pub spent_txid: String,
+
+    /// Seal output index spent by the child
+    pub spent_vout: u32,
+
+    /// Child transaction ID carrying the seal forward
+    pub txid: String,
+
+    /// Child output index carrying the seal forward
+    pub vout: u32,
+
+    /// Unix timestamp when the rebind was recorded
+    pub created_at: u64,
+}
+
+/// RBF replacement of an RGB witness, recorded before the transfer moves
+///
+/// Cleared once the replacement is broadcast or the transfer is moved back
+/// to the original witness; an entry left behind is repaired on sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WitnessReplacement {
+    /// Witness transaction being replaced
+    pub original_txid: String,
+
+    /// Replacement transaction ID
+    pub txid: String,
+
+    /// Unix timestamp when the replacement was recorded
+    pub created_at: u64,
+}
+
 /// Storage errors
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
@@ -201,6 +522,131 @@ impl ClaimStorage {
             [],
         )?;
 
+        // RGB-occupied UTXOs table (coin-selection protection survives restarts)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rgb_occupied_utxos (
+                txid TEXT NOT NULL,
+                vout INTEGER NOT NULL,
+                contract_id TEXT,
+                marked_at INTEGER NOT NULL,
+                PRIMARY KEY (txid, vout)
+            )",
+            [],
+        )?;
+
+        // Transfer history table (sent and received RGB transfers)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transfer_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                direction TEXT NOT NULL CHECK(direction IN ('outgoing', 'incoming')),
+                contract_id TEXT NOT NULL,
+                amount INTEGER,
+                counterparty_pubkey TEXT,
+                witness_txid TEXT NOT NULL,
+                anchor_method TEXT,
+                consignment_path TEXT NOT NULL,
+                status TEXT NOT NULL CHECK(status IN ('broadcasted', 'pending', 'claimed', 'failed')),
+                claim_id INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_transfer_history_contract ON transfer_history(contract_id)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_transfer_history_created ON transfer_history(created_at)",
+            [],
+        )?;
+
+        // Operations each outgoing witness transaction anchors
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transfer_opids (
+                witness_txid TEXT NOT NULL,
+                opid TEXT NOT NULL,
+                PRIMARY KEY (witness_txid, opid)
+            )",
+            [],
+        )?;
+
+        // Invoice registry (invoices issued by this wallet)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS invoices (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                invoice TEXT NOT NULL,
+                contract_id TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                address TEXT NOT NULL,
+                nonce INTEGER NOT NULL UNIQUE,
+                recipient_pubkey TEXT,
+                expiry INTEGER,
+                status TEXT NOT NULL CHECK(status IN ('open', 'partially_paid', 'paid', 'expired')),
+                amount_received INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_invoices_address ON invoices(contract_id, address)",
+            [],
+        )?;
+
+        // Claims paying an invoice (amount set once the claim completes)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS invoice_payments (
+                invoice_id INTEGER NOT NULL,
+                claim_id INTEGER NOT NULL UNIQUE,
+                amount INTEGER,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Blinded seals issued in invoices (secret noise kept locally)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS blinded_seals (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                auth_token TEXT NOT NULL UNIQUE,
+                contract_id TEXT NOT NULL,
+                txid TEXT NOT NULL,
+                vout INTEGER NOT NULL,
+                noise TEXT NOT NULL,
+                claim_id INTEGER,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Seal moves of CPFP children (recorded before broadcast)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS seal_rebinds (
+                claim_id INTEGER PRIMARY KEY,
+                contract_id TEXT NOT NULL,
+                spent_txid TEXT NOT NULL,
+                spent_vout INTEGER NOT NULL,
+                txid TEXT NOT NULL,
+                vout INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // RBF witness replacements (recorded before the transfer moves)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS witness_replacements (
+                original_txid TEXT PRIMARY KEY,
+                txid TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         log::debug!("✓ Database schema initialized");
 
         Ok(())
@@ -334,6 +780,12 @@ impl ClaimStorage {
             params![status.to_db_string(), error, claimed_at, id],
         )?;
 
+        // Keep linked transfer history records in sync
+        tx.execute(
+            "UPDATE transfer_history SET status = ?1, updated_at = ?2 WHERE claim_id = ?3",
+            params![TransferStatus::from(&status).to_db_string(), now, id],
+        )?;
+
         tx.commit()?;
 
         log::debug!("✓ Updated claim status in DB (id={})", id);
@@ -353,6 +805,41 @@ impl ClaimStorage {
         Ok(())
     }
 
+    /// Point a claim at a new seal outpoint (write-through)
+    ///
+    /// Used when the seal's transaction was replaced (RBF, same vout) or its
+    /// output was spent forward by a CPFP child (new txid and vout).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Database row ID
+    /// * `actual_txid` - TXID of the transaction now holding the seal
+    /// * `actual_vout` - Output index of the seal in that transaction
+    pub fn update_claim_actual_utxo(
+        &mut self,
+        id: i64,
+        actual_txid: &str,
+        actual_vout: u32,
+    ) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE pending_claims SET actual_txid = ?1, actual_vout = ?2 WHERE id = ?3",
+            params![actual_txid, actual_vout, id],
+        )?;
+
+        log::debug!("✓ Updated claim seal in DB (id={})", id);
+
+        let mut cache = self.cache.write().unwrap();
+        for claims in cache.values_mut() {
+            if let Some(claim) = claims.iter_mut().find(|c| c.id == Some(id)) {
+                claim.actual_txid = Some(actual_txid.to_string());
+                claim.actual_vout = Some(actual_vout);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Mark claim as completed with write-through
     ///
     /// Convenience method for updating status to Claimed
@@ -522,4 +1009,780 @@ impl ClaimStorage {
 
         Ok(())
     }
+
+    /// Mark a UTXO as RGB-occupied
+    ///
+    /// Persists the outpoint so it stays excluded from Bitcoin coin selection
+    /// across process restarts. Marking an already-occupied UTXO is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `txid` - Transaction ID (display format)
+    /// * `vout` - Output index
+    /// * `contract_id` - Contract whose tokens the UTXO carries (if known)
+    pub fn mark_rgb_occupied_utxo(
+        &mut self,
+        txid: &str,
+        vout: u32,
+        contract_id: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.conn.execute(
+            "INSERT OR IGNORE INTO rgb_occupied_utxos (txid, vout, contract_id, marked_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![txid, vout, contract_id, now],
+        )?;
+
+        log::debug!("✓ Persisted RGB-occupied UTXO {}:{}", txid, vout);
+
+        Ok(())
+    }
+
+    /// Remove a UTXO from the persisted RGB-occupied set
+    ///
+    /// # Arguments
+    ///
+    /// * `txid` - Transaction ID (display format)
+    /// * `vout` - Output index
+    pub fn unmark_rgb_occupied_utxo(&mut self, txid: &str, vout: u32) -> Result<(), StorageError> {
+        self.conn.execute(
+            "DELETE FROM rgb_occupied_utxos WHERE txid = ?1 AND vout = ?2",
+            params![txid, vout],
+        )?;
+
+        log::debug!("✓ Removed RGB-occupied UTXO {}:{}", txid, vout);
+
+        Ok(())
+    }
+
+    /// Get all persisted RGB-occupied UTXOs
+    ///
+    /// # Returns
+    ///
+    /// Vector of (txid, vout) tuples
+    pub fn get_rgb_occupied_utxos(&self) -> Result<Vec<(String, u32)>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT txid, vout FROM rgb_occupied_utxos ORDER BY marked_at")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let result: Result<Vec<_>, _> = rows.collect();
+        Ok(result?)
+    }
+
+    /// Record a transfer in the history table
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Transfer to record (`id` is ignored)
+    ///
+    /// # Returns
+    ///
+    /// Database row ID of the inserted record
+    pub fn insert_transfer_record(&mut self, record: &TransferRecord) -> Result<i64, StorageError> {
+        self.conn.execute(
+            "INSERT INTO transfer_history (direction, contract_id, amount, counterparty_pubkey,
+             witness_txid, anchor_method, consignment_path, status, claim_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                record.direction.to_db_string(),
+                &record.contract_id,
+                record.amount,
+                &record.counterparty_pubkey,
+                &record.witness_txid,
+                &record.anchor_method,
+                record.consignment_path.to_str(),
+                record.status.to_db_string(),
+                record.claim_id,
+                record.created_at,
+                record.updated_at,
+            ],
+        )?;
+
+        let row_id = self.conn.last_insert_rowid();
+
+        log::debug!("✓ Recorded {} transfer (id={})", record.direction, row_id);
+
+        Ok(row_id)
+    }
+
+    /// Point a transfer record at a replacement witness transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Transfer record database ID
+    /// * `witness_txid` - TXID of the replacement witness transaction
+    /// * `consignment_path` - Consignment re-issued with the new witness
+    pub fn update_transfer_witness(
+        &mut self,
+        id: i64,
+        witness_txid: &str,
+        consignment_path: &Path,
+    ) -> Result<(), StorageError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.conn.execute(
+            "UPDATE transfer_history SET witness_txid = ?1, consignment_path = ?2, updated_at = ?3
+             WHERE id = ?4",
+            params![witness_txid, consignment_path.to_str(), now, id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Record the operations anchored by an outgoing witness transaction
+    ///
+    /// Recording an already-known opid is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `witness_txid` - TXID of the witness transaction
+    /// * `opids` - Operation IDs of every transfer leg (hex)
+    pub fn record_transfer_opids(
+        &mut self,
+        witness_txid: &str,
+        opids: &[String],
+    ) -> Result<(), StorageError> {
+        let tx = self.conn.transaction()?;
+        for opid in opids {
+            tx.execute(
+                "INSERT OR IGNORE INTO transfer_opids (witness_txid, opid) VALUES (?1, ?2)",
+                params![witness_txid, opid],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Get the operations anchored by an outgoing witness transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `witness_txid` - TXID of the witness transaction
+    ///
+    /// # Returns
+    ///
+    /// Operation IDs (hex); empty for transfers recorded before opids were kept
+    pub fn get_transfer_opids(&self, witness_txid: &str) -> Result<Vec<String>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT opid FROM transfer_opids WHERE witness_txid = ?1 ORDER BY rowid")?;
+        let rows = stmt.query_map(params![witness_txid], |row| row.get(0))?;
+
+        let result: Result<Vec<_>, _> = rows.collect();
+        Ok(result?)
+    }
+
+    /// Set the amount of the transfer linked to a claim
+    ///
+    /// Incoming amounts are only known once the claim migrates the balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `claim_id` - Linked claim database ID
+    /// * `amount` - Claimed token amount
+    pub fn set_transfer_amount_for_claim(
+        &mut self,
+        claim_id: i64,
+        amount: u64,
+    ) -> Result<(), StorageError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.conn.execute(
+            "UPDATE transfer_history SET amount = ?1, updated_at = ?2 WHERE claim_id = ?3",
+            params![amount, now, claim_id],
+        )?;
+
+        // Credit the invoice this claim pays, if any
+        let updated = self.conn.execute(
+            "UPDATE invoice_payments SET amount = ?1 WHERE claim_id = ?2",
+            params![amount, claim_id],
+        )?;
+        if updated > 0 {
+            let invoice_id: i64 = self.conn.query_row(
+                "SELECT invoice_id FROM invoice_payments WHERE claim_id = ?1",
+                params![claim_id],
+                |row| row.get(0),
+            )?;
+            self.refresh_invoice_status(invoice_id, now)?;
+        }
+
+        Ok(())
+    }
+
+    /// Query transfer history
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Optional contract ID to filter by
+    /// * `from` - Optional lower bound on `created_at` (Unix seconds, inclusive)
+    /// * `until` - Optional upper bound on `created_at` (Unix seconds, inclusive)
+    ///
+    /// # Returns
+    ///
+    /// Matching transfers, oldest first
+    pub fn get_transfers(
+        &self,
+        contract_id: Option<&str>,
+        from: Option<u64>,
+        until: Option<u64>,
+    ) -> Result<Vec<TransferRecord>, StorageError> {
+        let mut query = "SELECT id, direction, contract_id, amount, counterparty_pubkey,
+                         witness_txid, anchor_method, consignment_path, status, claim_id,
+                         created_at, updated_at
+                         FROM transfer_history WHERE 1=1"
+            .to_string();
+
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(cid) = contract_id {
+            query.push_str(" AND contract_id = ?");
+            params_vec.push(Box::new(cid.to_string()));
+        }
+
+        if let Some(from) = from {
+            query.push_str(" AND created_at >= ?");
+            params_vec.push(Box::new(from));
+        }
+
+        if let Some(until) = until {
+            query.push_str(" AND created_at <= ?");
+            params_vec.push(Box::new(until));
+        }
+
+        query.push_str(" ORDER BY created_at, id");
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec
+            .iter()
+            .map(|p| &**p as &dyn rusqlite::ToSql)
+            .collect();
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let records = stmt.query_map(&params_refs[..], |row| {
+            Ok(TransferRecord {
+                id: Some(row.get(0)?),
+                direction: TransferDirection::from_db_string(&row.get::<_, String>(1)?)
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+                contract_id: row.get(2)?,
+                amount: row.get(3)?,
+                counterparty_pubkey: row.get(4)?,
+                witness_txid: row.get(5)?,
+                anchor_method: row.get(6)?,
+                consignment_path: PathBuf::from(row.get::<_, String>(7)?),
+                status: TransferStatus::from_db_string(&row.get::<_, String>(8)?)
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+                claim_id: row.get(9)?,
+                created_at: row.get(10)?,
+                updated_at: row.get(11)?,
+            })
+        })?;
+
+        let result: Result<Vec<_>, _> = records.collect();
+        Ok(result?)
+    }
+
+    /// Next unused invoice nonce
+    pub fn next_invoice_nonce(&self) -> Result<u64, StorageError> {
+        let nonce: u64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(nonce), 0) + 1 FROM invoices",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(nonce)
+    }
+
+    /// Record an issued invoice
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Invoice to record (`id` is ignored)
+    ///
+    /// # Returns
+    ///
+    /// Database row ID of the inserted invoice
+    pub fn insert_invoice(&mut self, record: &InvoiceRecord) -> Result<i64, StorageError> {
+        Self::insert_invoice_row(&self.conn, record)
+    }
+
+    /// Record an invoice paying a blinded seal, together with the seal
+    ///
+    /// Both rows are written in one transaction, so a seal is never left
+    /// open (and its UTXO reserved) without an invoice.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Invoice to record (`id` is ignored)
+    /// * `seal` - Blinded seal the invoice pays (`id` is ignored)
+    ///
+    /// # Returns
+    ///
+    /// Database row ID of the inserted invoice
+    pub fn insert_blinded_invoice(
+        &mut self,
+        record: &InvoiceRecord,
+        seal: &BlindedSeal,
+    ) -> Result<i64, StorageError> {
+        let tx = self.conn.transaction()?;
+        let row_id = Self::insert_invoice_row(&tx, record)?;
+        Self::insert_blinded_seal_row(&tx, seal)?;
+        tx.commit()?;
+
+        Ok(row_id)
+    }
+
+    fn insert_invoice_row(conn: &Connection, record: &InvoiceRecord) -> Result<i64, StorageError> {
+        conn.execute(
+            "INSERT INTO invoices (invoice, contract_id, amount, address, nonce, recipient_pubkey,
+             expiry, status, amount_received, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                &record.invoice,
+                &record.contract_id,
+                record.amount,
+                &record.address,
+                record.nonce,
+                &record.recipient_pubkey,
+                record.expiry,
+                record.status.to_db_string(),
+                record.amount_received,
+                record.created_at,
+                record.updated_at,
+            ],
+        )?;
+
+        let row_id = conn.last_insert_rowid();
+
+        log::debug!("✓ Recorded invoice (id={}, nonce={})", row_id, record.nonce);
+
+        Ok(row_id)
+    }
+
+    /// Link a claim to the invoice it pays
+    ///
+    /// The invoice is the one issued for this contract and address, preferring
+    /// the oldest one neither paid in full nor expired. Linking the same claim
+    /// twice is a no-op. A claim linked to an expired invoice is recorded but
+    /// never credited (see [`InvoiceRecord`] for the expiry semantics).
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Contract ID of the incoming transfer
+    /// * `address` - Recipient address from the consignment (pre-Tapret)
+    /// * `claim_id` - Claim holding the incoming tokens
+    ///
+    /// # Returns
+    ///
+    /// ID of the matched invoice, if any
+    pub fn match_invoice_payment(
+        &mut self,
+        contract_id: &str,
+        address: &str,
+        claim_id: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        let invoice_id: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT id FROM invoices WHERE contract_id = ?1 AND address = ?2
+                 ORDER BY status IN ('paid', 'expired'), created_at, id LIMIT 1",
+                params![contract_id, address],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(invoice_id) = invoice_id else {
+            return Ok(None);
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.conn.execute(
+            "INSERT OR IGNORE INTO invoice_payments (invoice_id, claim_id, amount, created_at)
+             VALUES (?1, ?2, NULL, ?3)",
+            params![invoice_id, claim_id, now],
+        )?;
+        self.refresh_invoice_status(invoice_id, now)?;
+
+        log::debug!("✓ Claim {} matched to invoice {}", claim_id, invoice_id);
+
+        Ok(Some(invoice_id))
+    }
+
+    /// Mark open and partially paid invoices past their expiry as expired
+    ///
+    /// Amounts received before the expiry are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current Unix time
+    ///
+    /// # Returns
+    ///
+    /// Number of invoices that expired
+    pub fn expire_invoices(&mut self, now: u64) -> Result<usize, StorageError> {
+        let expired = self.conn.execute(
+            "UPDATE invoices SET status = ?1, updated_at = ?2
+             WHERE status IN (?3, ?4) AND expiry IS NOT NULL AND expiry <= ?2",
+            params![
+                InvoiceStatus::Expired.to_db_string(),
+                now,
+                InvoiceStatus::Open.to_db_string(),
+                InvoiceStatus::PartiallyPaid.to_db_string(),
+            ],
+        )?;
+        Ok(expired)
+    }
+
+    /// Query issued invoices
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Optional contract ID to filter by
+    /// * `status` - Optional status to filter by
+    ///
+    /// # Returns
+    ///
+    /// Matching invoices, oldest first
+    pub fn get_invoices(
+        &self,
+        contract_id: Option<&str>,
+        status: Option<InvoiceStatus>,
+    ) -> Result<Vec<InvoiceRecord>, StorageError> {
+        let mut query = "SELECT id, invoice, contract_id, amount, address, nonce, recipient_pubkey,
+                         expiry, status, amount_received, created_at, updated_at
+                         FROM invoices WHERE 1=1"
+            .to_string();
+
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(cid) = contract_id {
+            query.push_str(" AND contract_id = ?");
+            params_vec.push(Box::new(cid.to_string()));
+        }
+
+        if let Some(status) = status {
+            query.push_str(" AND status = ?");
+            params_vec.push(Box::new(status.to_db_string().to_string()));
+        }
+
+        query.push_str(" ORDER BY created_at, id");
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec
+            .iter()
+            .map(|p| &**p as &dyn rusqlite::ToSql)
+            .collect();
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let invoices = stmt.query_map(&params_refs[..], |row| {
+            Ok(InvoiceRecord {
+                id: Some(row.get(0)?),
+                invoice: row.get(1)?,
+                contract_id: row.get(2)?,
+                amount: row.get(3)?,
+                address: row.get(4)?,
+                nonce: row.get(5)?,
+                recipient_pubkey: row.get(6)?,
+                expiry: row.get(7)?,
+                status: InvoiceStatus::from_db_string(&row.get::<_, String>(8)?)
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+                amount_received: row.get(9)?,
+                created_at: row.get(10)?,
+                updated_at: row.get(11)?,
+            })
+        })?;
+
+        let result: Result<Vec<_>, _> = invoices.collect();
+        Ok(result?)
+    }
+
+    /// Record a blinded seal issued in an invoice
+    ///
+    /// # Arguments
+    ///
+    /// * `seal` - Blinded seal to record (`id` is ignored)
+    ///
+    /// # Returns
+    ///
+    /// Database row ID of the inserted seal
+    pub fn insert_blinded_seal(&mut self, seal: &BlindedSeal) -> Result<i64, StorageError> {
+        Self::insert_blinded_seal_row(&self.conn, seal)
+    }
+
+    fn insert_blinded_seal_row(conn: &Connection, seal: &BlindedSeal) -> Result<i64, StorageError> {
+        conn.execute(
+            "INSERT INTO blinded_seals (auth_token, contract_id, txid, vout, noise, claim_id,
+             created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                &seal.auth_token,
+                &seal.contract_id,
+                &seal.txid,
+                seal.vout,
+                &seal.noise,
+                seal.claim_id,
+                seal.created_at,
+            ],
+        )?;
+
+        let row_id = conn.last_insert_rowid();
+
+        log::debug!(
+            "✓ Recorded blinded seal (id={}, {}:{})",
+            row_id,
+            seal.txid,
+            seal.vout
+        );
+
+        Ok(row_id)
+    }
+
+    /// Blinded seals not paid yet
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id` - Optional contract ID to filter by
+    ///
+    /// # Returns
+    ///
+    /// Unclaimed blinded seals, oldest first
+    pub fn get_open_blinded_seals(
+        &self,
+        contract_id: Option<&str>,
+    ) -> Result<Vec<BlindedSeal>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, auth_token, contract_id, txid, vout, noise, claim_id, created_at
+             FROM blinded_seals
+             WHERE claim_id IS NULL AND (?1 IS NULL OR contract_id = ?1)
+             ORDER BY created_at, id",
+        )?;
+        let seals = stmt.query_map(params![contract_id], |row| {
+            Ok(BlindedSeal {
+                id: Some(row.get(0)?),
+                auth_token: row.get(1)?,
+                contract_id: row.get(2)?,
+                txid: row.get(3)?,
+                vout: row.get(4)?,
+                noise: row.get(5)?,
+                claim_id: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+
+        let result: Result<Vec<_>, _> = seals.collect();
+        Ok(result?)
+    }
+
+    /// Link a blinded seal to the claim receiving its tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Blinded seal database ID
+    /// * `claim_id` - Claim moving the tokens onto the seal's UTXO
+    pub fn set_blinded_seal_claim(&mut self, id: i64, claim_id: i64) -> Result<(), StorageError> {
+        let rows_affected = self.conn.execute(
+            "UPDATE blinded_seals SET claim_id = ?1 WHERE id = ?2",
+            params![claim_id, id],
+        )?;
+
+        if rows_affected == 0 {
+            return Err(StorageError::InvalidData(format!(
+                "Blinded seal {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Record seal moves before the transaction carrying them is broadcast
+    ///
+    /// All rebinds are written in one transaction; an existing entry for the
+    /// same claim is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `rebinds` - Seal moves to record
+    pub fn insert_seal_rebinds(&mut self, rebinds: &[SealRebind]) -> Result<(), StorageError> {
+        let tx = self.conn.transaction()?;
+
+        for rebind in rebinds {
+            tx.execute(
+                "INSERT OR REPLACE INTO seal_rebinds (claim_id, contract_id, spent_txid,
+                 spent_vout, txid, vout, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    rebind.claim_id,
+                    &rebind.contract_id,
+                    &rebind.spent_txid,
+                    rebind.spent_vout,
+                    &rebind.txid,
+                    rebind.vout,
+                    rebind.created_at,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+
+        log::debug!("✓ Recorded {} seal rebind(s)", rebinds.len());
+
+        Ok(())
+    }
+
+    /// Seal moves not applied yet
+    ///
+    /// # Returns
+    ///
+    /// Outstanding rebinds, oldest first
+    pub fn get_seal_rebinds(&self) -> Result<Vec<SealRebind>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT claim_id, contract_id, spent_txid, spent_vout, txid, vout, created_at
+             FROM seal_rebinds
+             ORDER BY created_at, claim_id",
+        )?;
+        let rebinds = stmt.query_map([], |row| {
+            Ok(SealRebind {
+                claim_id: row.get(0)?,
+                contract_id: row.get(1)?,
+                spent_txid: row.get(2)?,
+                spent_vout: row.get(3)?,
+                txid: row.get(4)?,
+                vout: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?;
+
+        let result: Result<Vec<_>, _> = rebinds.collect();
+        Ok(result?)
+    }
+
+    /// Drop a seal move once applied (or abandoned)
+    ///
+    /// # Arguments
+    ///
+    /// * `claim_id` - Claim the rebind belongs to
+    pub fn remove_seal_rebind(&mut self, claim_id: i64) -> Result<(), StorageError> {
+        self.conn.execute(
+            "DELETE FROM seal_rebinds WHERE claim_id = ?1",
+            params![claim_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Record a witness replacement before the transfer is moved to it
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - Replacement to record (replaces an entry for the
+    ///   same original witness)
+    pub fn insert_witness_replacement(
+        &mut self,
+        replacement: &WitnessReplacement,
+    ) -> Result<(), StorageError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO witness_replacements (original_txid, txid, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![
+                &replacement.original_txid,
+                &replacement.txid,
+                replacement.created_at,
+            ],
+        )?;
+
+        log::debug!(
+            "✓ Recorded witness replacement {} -> {}",
+            replacement.original_txid,
+            replacement.txid
+        );
+
+        Ok(())
+    }
+
+    /// Witness replacements not settled yet
+    ///
+    /// # Returns
+    ///
+    /// Outstanding replacements, oldest first
+    pub fn get_witness_replacements(&self) -> Result<Vec<WitnessReplacement>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT original_txid, txid, created_at
+             FROM witness_replacements
+             ORDER BY created_at, original_txid",
+        )?;
+        let replacements = stmt.query_map([], |row| {
+            Ok(WitnessReplacement {
+                original_txid: row.get(0)?,
+                txid: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?;
+
+        let result: Result<Vec<_>, _> = replacements.collect();
+        Ok(result?)
+    }
+
+    /// Drop a witness replacement once settled
+    ///
+    /// # Arguments
+    ///
+    /// * `original_txid` - Witness transaction that was being replaced
+    pub fn remove_witness_replacement(&mut self, original_txid: &str) -> Result<(), StorageError> {
+        self.conn.execute(
+            "DELETE FROM witness_replacements WHERE original_txid = ?1",
+            params![original_txid],
+        )?;
+
+        Ok(())
+    }
+
+    /// Internal: Recompute an invoice's received amount and status
+    ///
+    /// Payments whose claim has not completed yet carry no amount and leave
+    /// the invoice open.
+    fn refresh_invoice_status(&mut self, invoice_id: i64, now: u64) -> Result<(), StorageError> {
+        // Only payments accepted before the expiry are credited
+        let (amount, expiry, status, received): (u64, Option<u64>, String, u64) =
+            self.conn.query_row(
+                "SELECT amount, expiry, status,
+                        (SELECT COALESCE(SUM(p.amount), 0) FROM invoice_payments p
+                         WHERE p.invoice_id = invoices.id
+                           AND (invoices.expiry IS NULL OR p.created_at < invoices.expiry))
+                 FROM invoices WHERE id = ?1",
+                params![invoice_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )?;
+        let expired = status == InvoiceStatus::Expired.to_db_string()
+            || expiry.is_some_and(|expiry| expiry <= now);
+
+        let status = if received >= amount {
+            InvoiceStatus::Paid
+        } else if expired {
+            InvoiceStatus::Expired
+        } else if received > 0 {
+            InvoiceStatus::PartiallyPaid
+        } else {
+            InvoiceStatus::Open
+        };
+
+        self.conn.execute(
+            "UPDATE invoices SET status = ?1, amount_received = ?2, updated_at = ?3 WHERE id = ?4",
+            params![status.to_db_string(), received, now, invoice_id],
+        )?;
+
+        Ok(())
+    }
 }
@@ -4,9 +4,9 @@
 //! and wallet metadata.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::storage::keys::KeyError;
+use crate::storage::keys::{Kdf, KeyError};
 use crate::storage::models::{EncryptedWalletKeys, WalletKeys, WalletMetadata};
 
 /// File system errors
@@ -29,6 +29,9 @@ pub enum FileSystemError {
 
     #[error("Wallets directory not found")]
     WalletsDirectoryNotFound,
+
+    #[error("Re-encrypted keys failed verification: {0}")]
+    VerificationFailed(String),
 }
 
 /// Get the default wallets directory path
@@ -107,7 +110,9 @@ pub fn create_wallet_directory(
 /// Saves:
 /// 1. Encrypted keys to `keys.json`
 /// 2. Metadata to `wallet.json`
-/// 3. Bitcoin descriptor to `descriptor.txt`
+/// 3. Public (xpub) Bitcoin descriptor to `descriptor.txt`
+///
+/// The xprv-bearing descriptor is only ever stored encrypted in `keys.json`.
 ///
 /// # Arguments
 ///
@@ -130,6 +135,33 @@ pub fn save_wallet(
     metadata: &WalletMetadata,
     password: &str,
     custom_base: Option<&str>,
+) -> Result<(), FileSystemError> {
+    save_wallet_with_kdf(
+        wallet_name,
+        keys,
+        metadata,
+        password,
+        &Kdf::default(),
+        custom_base,
+    )
+}
+
+/// Save wallet to disk, deriving the encryption key with `kdf`
+///
+/// Same as `save_wallet` with an explicit key derivation function.
+///
+/// # Example
+///
+/// ```ignore
+/// save_wallet_with_kdf("my_wallet", &keys, &metadata, "password", &Kdf::argon2id(), None)?;
+/// ```
+pub fn save_wallet_with_kdf(
+    wallet_name: &str,
+    keys: &WalletKeys,
+    metadata: &WalletMetadata,
+    password: &str,
+    kdf: &Kdf,
+    custom_base: Option<&str>,
 ) -> Result<(), FileSystemError> {
     let wallet_path = wallet_dir(wallet_name, custom_base)?;
 
@@ -139,7 +171,7 @@ pub fn save_wallet(
     }
 
     // 1. Encrypt and save keys
-    let encrypted_keys = EncryptedWalletKeys::from_keys(keys, password)?;
+    let encrypted_keys = EncryptedWalletKeys::from_keys_with_kdf(keys, password, kdf)?;
     let keys_json = serde_json::to_string_pretty(&encrypted_keys)?;
     fs::write(wallet_path.join("keys.json"), keys_json)?;
 
@@ -147,15 +179,20 @@ pub fn save_wallet(
     let metadata_json = serde_json::to_string_pretty(metadata)?;
     fs::write(wallet_path.join("wallet.json"), metadata_json)?;
 
-    // 3. Save Bitcoin descriptor (for BDK)
-    fs::write(wallet_path.join("descriptor.txt"), &keys.bitcoin_descriptor)?;
+    // 3. Save public Bitcoin descriptor (for watch-only BDK access)
+    fs::write(
+        wallet_path.join("descriptor.txt"),
+        &keys.bitcoin_public_descriptor,
+    )?;
 
     Ok(())
 }
 
 /// Load wallet from disk
 ///
-/// Loads and decrypts wallet keys and metadata.
+/// Loads and decrypts wallet keys and metadata. Wallet directories written
+/// by an older key format (see `KEYS_FORMAT_VERSION`) are migrated in place
+/// once the password has been verified.
 ///
 /// # Arguments
 ///
@@ -183,6 +220,26 @@ pub fn load_wallet(
     wallet_name: &str,
     password: &str,
     custom_base: Option<&str>,
+) -> Result<(WalletKeys, WalletMetadata), FileSystemError> {
+    load_wallet_with_kdf(wallet_name, password, None, custom_base)
+}
+
+/// Load wallet from disk, upgrading its KDF if requested
+///
+/// Same as `load_wallet`. When `kdf` is set and any secret in `keys.json`
+/// was encrypted with a different KDF (or predates the envelope format),
+/// the keys are re-encrypted with `kdf` once the password has been verified.
+///
+/// # Example
+///
+/// ```ignore
+/// let (keys, metadata) = load_wallet_with_kdf("my_wallet", "password", Some(&Kdf::argon2id()), None)?;
+/// ```
+pub fn load_wallet_with_kdf(
+    wallet_name: &str,
+    password: &str,
+    kdf: Option<&Kdf>,
+    custom_base: Option<&str>,
 ) -> Result<(WalletKeys, WalletMetadata), FileSystemError> {
     let wallet_path = wallet_dir(wallet_name, custom_base)?;
 
@@ -202,9 +259,139 @@ pub fn load_wallet(
     let encrypted_keys: EncryptedWalletKeys = serde_json::from_str(&keys_json)?;
     let keys = encrypted_keys.to_keys(password, metadata.network)?;
 
+    // 3. Migrate legacy key formats and upgrade the KDF in place
+    let kdf_upgrade = kdf.filter(|kdf| !encrypted_keys.uses_kdf(kdf));
+    if encrypted_keys.needs_migration() || kdf_upgrade.is_some() {
+        let target_kdf = match kdf_upgrade {
+            Some(kdf) => *kdf,
+            None => encrypted_keys.kdf()?.unwrap_or_default(),
+        };
+        migrate_wallet_files(&wallet_path, &keys, password, &target_kdf)?;
+    }
+
     Ok((keys, metadata))
 }
 
+/// Rewrite `keys.json` and `descriptor.txt` in the current format
+///
+/// Both files are replaced atomically so an interrupted migration leaves
+/// either the old or the new file, never a truncated one.
+fn migrate_wallet_files(
+    wallet_path: &Path,
+    keys: &WalletKeys,
+    password: &str,
+    kdf: &Kdf,
+) -> Result<(), FileSystemError> {
+    let encrypted_keys = EncryptedWalletKeys::from_keys_with_kdf(keys, password, kdf)?;
+    let keys_json = serde_json::to_string_pretty(&encrypted_keys)?;
+    write_file_atomic(&wallet_path.join("keys.json"), keys_json.as_bytes())?;
+
+    write_file_atomic(
+        &wallet_path.join("descriptor.txt"),
+        keys.bitcoin_public_descriptor.as_bytes(),
+    )?;
+
+    Ok(())
+}
+
+/// Change the password protecting a wallet's keys
+///
+/// Decrypts `keys.json` with the old password and re-encrypts every secret
+/// (mnemonic, private descriptor, F1r3fly private key) with the new one,
+/// using fresh salts and nonces and keeping the current KDF. The new file is
+/// decrypted and compared against the original keys before it atomically
+/// replaces `keys.json`, so a failure at any point leaves the wallet under
+/// the old password.
+///
+/// # Arguments
+///
+/// * `wallet_name` - Name of the wallet
+/// * `old_password` - Current password
+/// * `new_password` - New password
+/// * `custom_base` - Optional custom base directory (for testing)
+///
+/// # Errors
+///
+/// Returns error if:
+/// - Wallet not found
+/// - Old password is wrong
+/// - Re-encrypted keys do not decrypt to the original keys
+///
+/// # Example
+///
+/// ```ignore
+/// change_password("my_wallet", "old_password", "new_password", None)?;
+/// ```
+pub fn change_password(
+    wallet_name: &str,
+    old_password: &str,
+    new_password: &str,
+    custom_base: Option<&str>,
+) -> Result<(), FileSystemError> {
+    // 1. Decrypt with the old password (also migrates legacy formats)
+    let (keys, metadata) = load_wallet(wallet_name, old_password, custom_base)?;
+    let wallet_path = wallet_dir(wallet_name, custom_base)?;
+
+    // 2. Re-encrypt (every encryption draws a new salt and nonce)
+    let kdf = load_encrypted_keys(wallet_name, custom_base)?
+        .kdf()?
+        .unwrap_or_default();
+    let encrypted_keys = EncryptedWalletKeys::from_keys_with_kdf(&keys, new_password, &kdf)?;
+
+    // 3. Verify the new ciphertexts before touching the wallet
+    let verified = encrypted_keys.to_keys(new_password, metadata.network)?;
+    if verified.mnemonic != keys.mnemonic {
+        return Err(FileSystemError::VerificationFailed("mnemonic".to_string()));
+    }
+    if verified.bitcoin_descriptor != keys.bitcoin_descriptor {
+        return Err(FileSystemError::VerificationFailed(
+            "bitcoin descriptor".to_string(),
+        ));
+    }
+    if verified.f1r3fly_private_key != keys.f1r3fly_private_key {
+        return Err(FileSystemError::VerificationFailed(
+            "F1r3fly private key".to_string(),
+        ));
+    }
+
+    // 4. Replace keys.json atomically
+    let keys_json = serde_json::to_string_pretty(&encrypted_keys)?;
+    write_file_atomic(&wallet_path.join("keys.json"), keys_json.as_bytes())?;
+
+    Ok(())
+}
+
+/// Write a file via a temporary sibling and rename
+pub(crate) fn write_file_atomic(path: &Path, contents: &[u8]) -> Result<(), FileSystemError> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Load a wallet's `keys.json` without decrypting it
+///
+/// Gives access to the public fields (public descriptor, F1r3fly public key)
+/// without a password.
+///
+/// # Arguments
+///
+/// * `wallet_name` - Name of the wallet
+/// * `custom_base` - Optional custom base directory (for testing)
+pub fn load_encrypted_keys(
+    wallet_name: &str,
+    custom_base: Option<&str>,
+) -> Result<EncryptedWalletKeys, FileSystemError> {
+    let wallet_path = wallet_dir(wallet_name, custom_base)?;
+
+    if !wallet_path.exists() {
+        return Err(FileSystemError::WalletNotFound(wallet_name.to_string()));
+    }
+
+    let keys_json = fs::read_to_string(wallet_path.join("keys.json"))?;
+    Ok(serde_json::from_str(&keys_json)?)
+}
+
 /// List all wallets
 ///
 /// Returns metadata for all wallets found in the wallets directory.
@@ -332,15 +519,19 @@ pub fn wallet_exists(wallet_name: &str, custom_base: Option<&str>) -> bool {
 /// # Arguments
 ///
 /// * `wallet_name` - Name of the wallet
+/// * `custom_base` - Optional custom base directory (for testing)
 ///
 /// # Example
 ///
 /// ```ignore
-/// let metadata = load_wallet_metadata("my_wallet")?;
+/// let metadata = load_wallet_metadata("my_wallet", None)?;
 /// println!("Network: {:?}", metadata.network);
 /// ```
-pub fn load_wallet_metadata(wallet_name: &str) -> Result<WalletMetadata, FileSystemError> {
-    let wallet_path = wallet_dir(wallet_name, None)?;
+pub fn load_wallet_metadata(
+    wallet_name: &str,
+    custom_base: Option<&str>,
+) -> Result<WalletMetadata, FileSystemError> {
+    let wallet_path = wallet_dir(wallet_name, custom_base)?;
 
     if !wallet_path.exists() {
         return Err(FileSystemError::WalletNotFound(wallet_name.to_string()));
@@ -361,19 +552,21 @@ pub fn load_wallet_metadata(wallet_name: &str) -> Result<WalletMetadata, FileSys
 ///
 /// * `wallet_name` - Name of the wallet
 /// * `metadata` - Updated metadata
+/// * `custom_base` - Optional custom base directory (for testing)
 ///
 /// # Example
 ///
 /// ```ignore
-/// let mut metadata = load_wallet_metadata("my_wallet")?;
+/// let mut metadata = load_wallet_metadata("my_wallet", None)?;
 /// metadata.update_sync_time();
-/// update_wallet_metadata("my_wallet", &metadata)?;
+/// update_wallet_metadata("my_wallet", &metadata, None)?;
 /// ```
 pub fn update_wallet_metadata(
     wallet_name: &str,
     metadata: &WalletMetadata,
+    custom_base: Option<&str>,
 ) -> Result<(), FileSystemError> {
-    let wallet_path = wallet_dir(wallet_name, None)?;
+    let wallet_path = wallet_dir(wallet_name, custom_base)?;
 
     if !wallet_path.exists() {
         return Err(FileSystemError::WalletNotFound(wallet_name.to_string()));
@@ -4,15 +4,16 @@
 //! custom F1r3fly key derivation, and AES-GCM encryption for secure storage.
 
 use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
+    aead::{Aead, KeyInit, OsRng, Payload},
     Aes256Gcm, Nonce,
 };
-use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv};
 use bitcoin::secp256k1::Secp256k1;
 use bitcoin::Network as BitcoinNetwork;
 use pbkdf2::pbkdf2_hmac;
 use rand::RngCore;
 use secp256k1::{PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::str::FromStr;
 
@@ -124,26 +125,23 @@ pub fn derive_bitcoin_keys(
     Ok(account_key)
 }
 
-/// Derive F1r3fly key from mnemonic at custom path m/1337'/0'/0'/0/0
+/// Derive the F1r3fly key chain from mnemonic at custom path m/1337'/0'/0'/0
 ///
-/// Derives a single secp256k1 keypair for F1r3node operations.
-/// Uses a custom derivation path to separate from Bitcoin keys.
+/// Child `i` of this chain is the key at m/1337'/0'/0'/0/i. Child 0 is the
+/// wallet's F1r3fly key (see `derive_f1r3fly_key`); further children own
+/// contracts deployed by wallets in `KeyMode::Wallet`.
 ///
 /// # Arguments
 ///
 /// * `mnemonic` - BIP39 mnemonic phrase
 ///
-/// # Returns
-///
-/// Tuple of (private_key, public_key_hex) for F1r3node authentication
-///
 /// # Example
 ///
 /// ```ignore
-/// let mnemonic = generate_mnemonic()?;
-/// let (privkey, pubkey_hex) = derive_f1r3fly_key(&mnemonic)?;
+/// let chain = derive_f1r3fly_chain(&mnemonic)?;
+/// let contract_key = f1r3fly_child_key(&chain, 7)?;
 /// ```
-pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, String), KeyError> {
+pub fn derive_f1r3fly_chain(mnemonic: &bip39::Mnemonic) -> Result<Xpriv, KeyError> {
     // Convert to seed
     let seed = mnemonic.to_seed("");
 
@@ -152,20 +150,57 @@ pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, Stri
     let master_key = Xpriv::new_master(BitcoinNetwork::Testnet, &seed)
         .map_err(|e| KeyError::Bip32(format!("Failed to create master key: {}", e)))?;
 
-    // Custom F1r3fly path: m/1337'/0'/0'/0/0
-    let path_str = "m/1337'/0'/0'/0/0";
-    let derivation_path = DerivationPath::from_str(path_str)
+    // Custom F1r3fly chain path: m/1337'/0'/0'/0
+    let derivation_path = DerivationPath::from_str("m/1337'/0'/0'/0")
         .map_err(|e| KeyError::Bip32(format!("Invalid derivation path: {}", e)))?;
 
-    // Derive the key
-    let derived_key = master_key
+    master_key
         .derive_priv(&secp, &derivation_path)
+        .map_err(|e| KeyError::Bip32(format!("F1r3fly key derivation failed: {}", e)))
+}
+
+/// Derive child `index` of a F1r3fly key chain
+///
+/// # Arguments
+///
+/// * `chain` - Chain key from `derive_f1r3fly_chain`
+/// * `index` - Non-hardened child index
+pub fn f1r3fly_child_key(chain: &Xpriv, index: u32) -> Result<SecretKey, KeyError> {
+    let secp = Secp256k1::new();
+    let child_number = ChildNumber::from_normal_idx(index)
+        .map_err(|e| KeyError::Bip32(format!("Invalid child index {}: {}", index, e)))?;
+
+    let derived_key = chain
+        .derive_priv(&secp, &[child_number])
         .map_err(|e| KeyError::Bip32(format!("F1r3fly key derivation failed: {}", e)))?;
 
-    // Extract secp256k1 keys
-    let private_key = derived_key.private_key;
-    let secret_key = SecretKey::from_slice(private_key.as_ref())
-        .map_err(|e| KeyError::Secp256k1(format!("Invalid secret key: {}", e)))?;
+    SecretKey::from_slice(derived_key.private_key.as_ref())
+        .map_err(|e| KeyError::Secp256k1(format!("Invalid secret key: {}", e)))
+}
+
+/// Derive F1r3fly key from mnemonic at custom path m/1337'/0'/0'/0/0
+///
+/// Derives a single secp256k1 keypair for F1r3node operations.
+/// Uses a custom derivation path to separate from Bitcoin keys.
+///
+/// # Arguments
+///
+/// * `mnemonic` - BIP39 mnemonic phrase
+///
+/// # Returns
+///
+/// Tuple of (private_key, public_key_hex) for F1r3node authentication
+///
+/// # Example
+///
+/// ```ignore
+/// let mnemonic = generate_mnemonic()?;
+/// let (privkey, pubkey_hex) = derive_f1r3fly_key(&mnemonic)?;
+/// ```
+pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, String), KeyError> {
+    // Child 0 of the custom F1r3fly chain: m/1337'/0'/0'/0/0
+    let chain = derive_f1r3fly_chain(mnemonic)?;
+    let secret_key = f1r3fly_child_key(&chain, 0)?;
 
     // Derive public key
     let secp_ctx = secp256k1::Secp256k1::new();
@@ -176,13 +211,180 @@ pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, Stri
     Ok((secret_key, public_key_hex))
 }
 
+/// Current version of the encrypted data envelope (see `encrypt_data_with_kdf`)
+pub const ENCRYPTION_FORMAT_VERSION: u8 = 1;
+
+/// Magic bytes opening a versioned envelope (legacy blobs open with a random salt)
+const ENVELOPE_MAGIC: [u8; 4] = *b"F1KD";
+
+/// PBKDF2 iterations (OWASP recommendation as of 2023; fixed for legacy blobs)
+pub const PBKDF2_DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// Argon2id memory cost in KiB (OWASP recommendation)
+pub const ARGON2ID_DEFAULT_MEMORY_KIB: u32 = 19_456;
+
+/// Argon2id passes (OWASP recommendation)
+pub const ARGON2ID_DEFAULT_ITERATIONS: u32 = 2;
+
+/// Argon2id lanes (OWASP recommendation)
+pub const ARGON2ID_DEFAULT_PARALLELISM: u32 = 1;
+
+/// Key derivation function turning a password into the AES-256 key
+///
+/// Recorded with its parameters in every envelope, so blobs stay readable
+/// when the default changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "kebab-case")]
+pub enum Kdf {
+    /// PBKDF2-HMAC-SHA256
+    Pbkdf2Sha256 { iterations: u32 },
+
+    /// Argon2id (memory-hard)
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Self::Pbkdf2Sha256 {
+            iterations: PBKDF2_DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+impl Kdf {
+    /// Argon2id with the recommended parameters
+    pub fn argon2id() -> Self {
+        Self::Argon2id {
+            memory_kib: ARGON2ID_DEFAULT_MEMORY_KIB,
+            iterations: ARGON2ID_DEFAULT_ITERATIONS,
+            parallelism: ARGON2ID_DEFAULT_PARALLELISM,
+        }
+    }
+
+    /// Derive a 256-bit key from a password and salt
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<[u8; 32], KeyError> {
+        let mut key_bytes = [0u8; 32];
+        match *self {
+            Self::Pbkdf2Sha256 { iterations } => {
+                if iterations == 0 {
+                    return Err(KeyError::Encryption(
+                        "PBKDF2 iterations must be positive".to_string(),
+                    ));
+                }
+                pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key_bytes);
+            }
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                let params = argon2::Params::new(memory_kib, iterations, parallelism, Some(32))
+                    .map_err(|e| {
+                        KeyError::Encryption(format!("Invalid Argon2id parameters: {}", e))
+                    })?;
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut key_bytes)
+                    .map_err(|e| KeyError::Encryption(e.to_string()))?;
+            }
+        }
+        Ok(key_bytes)
+    }
+
+    /// Serialize as `kdf id || parameters` (u32 big-endian each)
+    fn header_bytes(&self) -> Vec<u8> {
+        match *self {
+            Self::Pbkdf2Sha256 { iterations } => {
+                let mut bytes = vec![1];
+                bytes.extend_from_slice(&iterations.to_be_bytes());
+                bytes
+            }
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                let mut bytes = vec![2];
+                bytes.extend_from_slice(&memory_kib.to_be_bytes());
+                bytes.extend_from_slice(&iterations.to_be_bytes());
+                bytes.extend_from_slice(&parallelism.to_be_bytes());
+                bytes
+            }
+        }
+    }
+
+    /// Parse `kdf id || parameters`, returning the KDF and the remaining bytes
+    fn parse_header(bytes: &[u8]) -> Result<(Self, &[u8]), KeyError> {
+        let (&id, rest) = bytes
+            .split_first()
+            .ok_or_else(|| KeyError::Decryption("Missing KDF id".to_string()))?;
+        let param_count = match id {
+            1 => 1,
+            2 => 3,
+            _ => return Err(KeyError::Decryption(format!("Unknown KDF id {}", id))),
+        };
+        if rest.len() < param_count * 4 {
+            return Err(KeyError::Decryption("Truncated KDF parameters".to_string()));
+        }
+
+        let (params, rest) = rest.split_at(param_count * 4);
+        let param = |i: usize| u32::from_be_bytes(params[i * 4..i * 4 + 4].try_into().unwrap());
+        let kdf = match id {
+            1 => Self::Pbkdf2Sha256 {
+                iterations: param(0),
+            },
+            _ => Self::Argon2id {
+                memory_kib: param(0),
+                iterations: param(1),
+                parallelism: param(2),
+            },
+        };
+        Ok((kdf, rest))
+    }
+}
+
+impl std::fmt::Display for Kdf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pbkdf2Sha256 { iterations } => {
+                write!(f, "pbkdf2-sha256 ({} iterations)", iterations)
+            }
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => write!(
+                f,
+                "argon2id ({} KiB, {} iterations, {} lanes)",
+                memory_kib, iterations, parallelism
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for Kdf {
+    type Err = String;
+
+    /// Parse a KDF name (`pbkdf2` or `argon2id`) into its default parameters
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pbkdf2" | "pbkdf2-sha256" => Ok(Self::default()),
+            "argon2id" => Ok(Self::argon2id()),
+            _ => Err(format!(
+                "Invalid KDF '{}': expected 'pbkdf2' or 'argon2id'",
+                s
+            )),
+        }
+    }
+}
+
 /// Encrypt data using AES-256-GCM with password-derived key
 ///
-/// Uses production-grade password-based encryption:
-/// - PBKDF2-HMAC-SHA256 with 600,000 iterations
-/// - Random 128-bit salt
-/// - Random 96-bit nonce for each encryption
-/// - Returns: salt (16 bytes) || nonce (12 bytes) || ciphertext || tag (16 bytes)
+/// Uses the default KDF (PBKDF2-HMAC-SHA256, 600,000 iterations).
+/// See `encrypt_data_with_kdf` for the output format.
 ///
 /// # Arguments
 ///
@@ -191,7 +393,7 @@ pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, Stri
 ///
 /// # Returns
 ///
-/// Encrypted data as hex string (salt + nonce + ciphertext + tag)
+/// Encrypted data as hex string
 ///
 /// # Example
 ///
@@ -200,14 +402,47 @@ pub fn derive_f1r3fly_key(mnemonic: &bip39::Mnemonic) -> Result<(SecretKey, Stri
 /// let decrypted = decrypt_data(&encrypted, "my_password")?;
 /// ```
 pub fn encrypt_data(data: &[u8], password: &str) -> Result<String, KeyError> {
+    encrypt_data_with_kdf(data, password, &Kdf::default())
+}
+
+/// Encrypt data using AES-256-GCM with a key derived by `kdf`
+///
+/// Output is a self-describing envelope:
+/// - Magic `F1KD` (4 bytes) || format version (1 byte)
+/// - KDF id (1 byte) || KDF parameters (u32 big-endian each)
+/// - Random salt (16 bytes) || random nonce (12 bytes)
+/// - Ciphertext || tag (16 bytes)
+///
+/// The header is authenticated as associated data, so KDF parameters
+/// cannot be altered without failing decryption.
+///
+/// # Arguments
+///
+/// * `data` - Plaintext bytes to encrypt
+/// * `password` - Password for encryption
+/// * `kdf` - Key derivation function and parameters
+///
+/// # Returns
+///
+/// Encrypted envelope as hex string
+///
+/// # Example
+///
+/// ```ignore
+/// let encrypted = encrypt_data_with_kdf(b"secret", "my_password", &Kdf::argon2id())?;
+/// assert_eq!(encryption_kdf(&encrypted)?, Some(Kdf::argon2id()));
+/// ```
+pub fn encrypt_data_with_kdf(data: &[u8], password: &str, kdf: &Kdf) -> Result<String, KeyError> {
+    let mut header = ENVELOPE_MAGIC.to_vec();
+    header.push(ENCRYPTION_FORMAT_VERSION);
+    header.extend_from_slice(&kdf.header_bytes());
+
     // Generate random salt (128 bits / 16 bytes)
     let mut salt = [0u8; 16];
     OsRng.fill_bytes(&mut salt);
 
-    // Derive 256-bit key from password using PBKDF2-HMAC-SHA256
-    // 600,000 iterations (OWASP recommendation as of 2023)
-    let mut key_bytes = [0u8; 32];
-    pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, 600_000, &mut key_bytes);
+    // Derive 256-bit key from password
+    let key_bytes = kdf.derive_key(password, &salt)?;
     let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
 
     // Create cipher
@@ -218,13 +453,20 @@ pub fn encrypt_data(data: &[u8], password: &str) -> Result<String, KeyError> {
     OsRng.fill_bytes(&mut nonce_bytes);
     let nonce = Nonce::from_slice(&nonce_bytes);
 
-    // Encrypt
+    // Encrypt, authenticating the header
     let ciphertext = cipher
-        .encrypt(nonce, data)
+        .encrypt(
+            nonce,
+            Payload {
+                msg: data,
+                aad: &header,
+            },
+        )
         .map_err(|e| KeyError::Encryption(e.to_string()))?;
 
-    // Combine: salt || nonce || ciphertext
-    let mut result = salt.to_vec();
+    // Combine: header || salt || nonce || ciphertext
+    let mut result = header;
+    result.extend_from_slice(&salt);
     result.extend_from_slice(&nonce_bytes);
     result.extend_from_slice(&ciphertext);
 
@@ -234,9 +476,12 @@ pub fn encrypt_data(data: &[u8], password: &str) -> Result<String, KeyError> {
 
 /// Decrypt data encrypted with encrypt_data()
 ///
+/// Reads versioned envelopes (any supported KDF) as well as legacy blobs
+/// (`salt || nonce || ciphertext`, PBKDF2 with 600,000 iterations).
+///
 /// # Arguments
 ///
-/// * `encrypted_hex` - Hex-encoded encrypted data (salt + nonce + ciphertext + tag)
+/// * `encrypted_hex` - Hex-encoded envelope or legacy blob
 /// * `password` - Password used for encryption
 ///
 /// # Returns
@@ -262,16 +507,31 @@ pub fn decrypt_data(encrypted_hex: &str, password: &str) -> Result<Vec<u8>, KeyE
         ));
     }
 
+    // Split off the envelope header (legacy blobs have none)
+    let (kdf, header, body) = match parse_envelope(&encrypted_bytes)? {
+        Some((kdf, header_len)) => {
+            let (header, body) = encrypted_bytes.split_at(header_len);
+            (kdf, header, body)
+        }
+        None => (Kdf::default(), &[][..], &encrypted_bytes[..]),
+    };
+    if body.len() < 44 {
+        return Err(KeyError::Decryption(
+            "Data too short (minimum 44 bytes required)".to_string(),
+        ));
+    }
+
     // Extract salt (first 16 bytes)
-    let (salt, rest) = encrypted_bytes.split_at(16);
+    let (salt, rest) = body.split_at(16);
 
     // Extract nonce (next 12 bytes)
     let (nonce_bytes, ciphertext) = rest.split_at(12);
     let nonce = Nonce::from_slice(nonce_bytes);
 
-    // Derive key from password using same PBKDF2 parameters
-    let mut key_bytes = [0u8; 32];
-    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, 600_000, &mut key_bytes);
+    // Derive key from password using the recorded KDF
+    let key_bytes = kdf
+        .derive_key(password, salt)
+        .map_err(|e| KeyError::Decryption(e.to_string()))?;
     let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
 
     // Create cipher
@@ -279,12 +539,53 @@ pub fn decrypt_data(encrypted_hex: &str, password: &str) -> Result<Vec<u8>, KeyE
 
     // Decrypt
     let plaintext = cipher
-        .decrypt(nonce, ciphertext)
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
         .map_err(|e| KeyError::Decryption(format!("Decryption failed (wrong password?): {}", e)))?;
 
     Ok(plaintext)
 }
 
+/// KDF an encrypted blob was produced with
+///
+/// # Returns
+///
+/// The recorded KDF, or `None` for a legacy blob without envelope
+pub fn encryption_kdf(encrypted_hex: &str) -> Result<Option<Kdf>, KeyError> {
+    let encrypted_bytes =
+        hex::decode(encrypted_hex).map_err(|e| KeyError::Decryption(e.to_string()))?;
+    Ok(parse_envelope(&encrypted_bytes)?.map(|(kdf, _)| kdf))
+}
+
+/// Parse an envelope header
+///
+/// # Returns
+///
+/// The KDF and header length, or `None` if the bytes carry no envelope magic
+fn parse_envelope(bytes: &[u8]) -> Result<Option<(Kdf, usize)>, KeyError> {
+    let Some(rest) = bytes.strip_prefix(&ENVELOPE_MAGIC[..]) else {
+        return Ok(None);
+    };
+
+    let (&version, rest) = rest
+        .split_first()
+        .ok_or_else(|| KeyError::Decryption("Missing envelope version".to_string()))?;
+    if version != ENCRYPTION_FORMAT_VERSION {
+        return Err(KeyError::Decryption(format!(
+            "Unsupported envelope version {} (expected {})",
+            version, ENCRYPTION_FORMAT_VERSION
+        )));
+    }
+
+    let (kdf, rest) = Kdf::parse_header(rest)?;
+    Ok(Some((kdf, bytes.len() - rest.len())))
+}
+
 /// Encrypt mnemonic phrase for secure storage
 ///
 /// Convenience wrapper around encrypt_data for mnemonics.
@@ -0,0 +1,295 @@
+//! Encrypted keystore for the F1r3node master key
+//!
+//! The phlo-paying master key is stored encrypted (see `keys::encrypt_data`)
+//! under `~/.f1r3fly-rgb-wallet/keystore/<id>.json` and referenced from the
+//! config by id, so config files can be shared without leaking the key.
+//! An entry is unlocked with a password or with the contents of a key file.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::GlobalConfig;
+use crate::storage::keys::{decrypt_data, encrypt_data, KeyError};
+
+/// Current keystore entry format version
+pub const KEYSTORE_FORMAT_VERSION: u32 = 1;
+
+/// Environment variable holding the keystore password
+pub const KEYSTORE_PASSWORD_ENV: &str = "FIREFLY_KEYSTORE_PASSWORD";
+
+/// Environment variable holding the path of a keystore key file
+pub const KEYSTORE_KEY_FILE_ENV: &str = "FIREFLY_KEYSTORE_KEY_FILE";
+
+/// Keystore errors
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Key error: {0}")]
+    Key(#[from] KeyError),
+
+    #[error("Keystore entry not found: {0}")]
+    NotFound(String),
+
+    #[error("Keystore entry already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Invalid keystore id '{0}': use letters, digits, '-' or '_'")]
+    InvalidId(String),
+
+    #[error("Invalid master key: {0}")]
+    InvalidKey(String),
+
+    #[error("Key file is empty: {0}")]
+    EmptyKeyFile(String),
+
+    #[error(
+        "Keystore entry '{0}' is locked: set {pw} or {file}, or configure master_key_file",
+        pw = KEYSTORE_PASSWORD_ENV,
+        file = KEYSTORE_KEY_FILE_ENV
+    )]
+    Locked(String),
+
+    #[error(
+        "No F1r3node master key configured: set FIREFLY_PRIVATE_KEY or run `config import-master-key`"
+    )]
+    NotConfigured,
+
+    #[error("Keystore directory not found")]
+    DirectoryNotFound,
+}
+
+/// Secret used to unlock a keystore entry
+#[derive(Debug, Clone)]
+pub enum KeystoreUnlock {
+    /// Password typed by the user
+    Password(String),
+
+    /// File whose (trimmed) contents act as the password
+    KeyFile(PathBuf),
+}
+
+impl KeystoreUnlock {
+    /// Resolve to the secret passed to `encrypt_data`/`decrypt_data`
+    fn secret(&self) -> Result<String, KeystoreError> {
+        match self {
+            KeystoreUnlock::Password(password) => Ok(password.clone()),
+            KeystoreUnlock::KeyFile(path) => {
+                let contents = fs::read_to_string(path)?;
+                let secret = contents.trim();
+                if secret.is_empty() {
+                    return Err(KeystoreError::EmptyKeyFile(path.display().to_string()));
+                }
+                Ok(secret.to_string())
+            }
+        }
+    }
+}
+
+/// Keystore entry as stored in `<keystore>/<id>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    /// Entry format version (see `KEYSTORE_FORMAT_VERSION`)
+    pub version: u32,
+
+    /// Entry id (referenced by `F1r3nodeConfig::master_key_id`)
+    pub id: String,
+
+    /// Public key of the stored key (hex, uncompressed)
+    pub public_key: String,
+
+    /// Encrypted private key (hex-encoded `encrypt_data` output)
+    pub encrypted_key: String,
+
+    /// When the key was imported
+    pub created_at: DateTime<Utc>,
+}
+
+/// Get the default keystore directory path
+///
+/// Returns: `~/.f1r3fly-rgb-wallet/keystore/`
+pub fn default_keystore_dir() -> Result<PathBuf, KeystoreError> {
+    let config_dir =
+        crate::config::default_config_dir().map_err(|_| KeystoreError::DirectoryNotFound)?;
+    Ok(config_dir.join("keystore"))
+}
+
+/// Get the keystore directory (custom or default)
+pub fn keystore_dir(custom_dir: Option<&str>) -> Result<PathBuf, KeystoreError> {
+    match custom_dir {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => default_keystore_dir(),
+    }
+}
+
+/// Encrypt a master key into a new keystore entry
+///
+/// # Arguments
+///
+/// * `dir` - Keystore directory (created if missing)
+/// * `id` - Entry id
+/// * `key_hex` - Master private key (hex-encoded, 32 bytes)
+/// * `unlock` - Password or key file protecting the entry
+///
+/// # Errors
+///
+/// Returns error if the id is taken or the key is not a valid secp256k1 key.
+///
+/// # Example
+///
+/// ```ignore
+/// let dir = keystore_dir(None)?;
+/// let entry = import_key(&dir, "devnet", &key_hex, &KeystoreUnlock::Password(pw))?;
+/// println!("Imported {} ({})", entry.id, entry.public_key);
+/// ```
+pub fn import_key(
+    dir: &Path,
+    id: &str,
+    key_hex: &str,
+    unlock: &KeystoreUnlock,
+) -> Result<KeystoreEntry, KeystoreError> {
+    validate_id(id)?;
+
+    let path = entry_path(dir, id);
+    if path.exists() {
+        return Err(KeystoreError::AlreadyExists(id.to_string()));
+    }
+
+    // Validate the key and derive its public key for display
+    let key_bytes = hex::decode(key_hex.trim())
+        .map_err(|e| KeystoreError::InvalidKey(format!("not hex: {}", e)))?;
+    let secret_key = secp256k1::SecretKey::from_slice(&key_bytes)
+        .map_err(|e| KeystoreError::InvalidKey(e.to_string()))?;
+    let secp = secp256k1::Secp256k1::new();
+    let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+
+    let entry = KeystoreEntry {
+        version: KEYSTORE_FORMAT_VERSION,
+        id: id.to_string(),
+        public_key: hex::encode(public_key.serialize_uncompressed()),
+        encrypted_key: encrypt_data(hex::encode(key_bytes).as_bytes(), &unlock.secret()?)?,
+        created_at: Utc::now(),
+    };
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, serde_json::to_string_pretty(&entry)?)?;
+
+    Ok(entry)
+}
+
+/// Load a keystore entry without decrypting it
+pub fn load_entry(dir: &Path, id: &str) -> Result<KeystoreEntry, KeystoreError> {
+    validate_id(id)?;
+
+    let path = entry_path(dir, id);
+    if !path.exists() {
+        return Err(KeystoreError::NotFound(id.to_string()));
+    }
+
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Decrypt a keystore entry
+///
+/// # Returns
+///
+/// The master private key (hex-encoded)
+///
+/// # Errors
+///
+/// Returns `Key(Decryption)` on a wrong password or key file.
+pub fn unlock_key(dir: &Path, id: &str, unlock: &KeystoreUnlock) -> Result<String, KeystoreError> {
+    let entry = load_entry(dir, id)?;
+    let bytes = decrypt_data(&entry.encrypted_key, &unlock.secret()?)?;
+    String::from_utf8(bytes).map_err(|e| KeystoreError::Key(KeyError::Decryption(e.to_string())))
+}
+
+/// List all keystore entries (sorted by id)
+pub fn list_keys(dir: &Path) -> Result<Vec<KeystoreEntry>, KeystoreError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+
+        match fs::read_to_string(&path)
+            .map_err(KeystoreError::from)
+            .and_then(|json| Ok(serde_json::from_str::<KeystoreEntry>(&json)?))
+        {
+            Ok(entry) => entries.push(entry),
+            Err(e) => eprintln!("Warning: Failed to read keystore entry {:?}: {}", path, e),
+        }
+    }
+
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(entries)
+}
+
+/// Resolve the F1r3node master key for a configuration
+///
+/// Priority:
+/// 1. Plaintext `master_key` (from `FIREFLY_PRIVATE_KEY` or a legacy config file)
+/// 2. Keystore entry `master_key_id`, unlocked with `FIREFLY_KEYSTORE_PASSWORD`,
+///    then `master_key_file`, then `FIREFLY_KEYSTORE_KEY_FILE`
+///
+/// # Returns
+///
+/// The master private key (hex-encoded)
+pub fn resolve_master_key(config: &GlobalConfig) -> Result<String, KeystoreError> {
+    if !config.f1r3node.master_key.is_empty() {
+        return Ok(config.f1r3node.master_key.clone());
+    }
+
+    let id = config
+        .f1r3node
+        .master_key_id
+        .as_deref()
+        .ok_or(KeystoreError::NotConfigured)?;
+
+    let unlock = if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV) {
+        KeystoreUnlock::Password(password)
+    } else if let Some(key_file) = config
+        .f1r3node
+        .master_key_file
+        .clone()
+        .or_else(|| std::env::var(KEYSTORE_KEY_FILE_ENV).ok())
+    {
+        KeystoreUnlock::KeyFile(PathBuf::from(key_file))
+    } else {
+        return Err(KeystoreError::Locked(id.to_string()));
+    };
+
+    let dir = keystore_dir(config.keystore_dir.as_deref())?;
+    unlock_key(&dir, id, &unlock)
+}
+
+/// Path of the entry file for an id
+fn entry_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// Reject ids that could escape the keystore directory
+fn validate_id(id: &str) -> Result<(), KeystoreError> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(KeystoreError::InvalidId(id.to_string()))
+    }
+}
@@ -2,10 +2,15 @@
 //!
 //! Manages key derivation, encryption, and persistence.
 
+pub mod backup;
 pub mod claim_storage;
 pub mod file_system;
 pub mod keys;
+pub mod keystore;
 pub mod models;
 
 // Re-export claim storage types for external use
-pub use claim_storage::{ClaimStatus, ClaimStorage, PendingClaim, StorageError};
+pub use claim_storage::{
+    BlindedSeal, ClaimStatus, ClaimStorage, InvoiceRecord, InvoiceStatus, PendingClaim, SealRebind,
+    StorageError, TransferDirection, TransferRecord, TransferStatus, WitnessReplacement,
+};
@@ -2,11 +2,13 @@
 //!
 //! Defines wallet-related data structures for persistence and user output.
 
-use bitcoin::bip32::Xpriv;
+use bitcoin::bip32::{Xpriv, Xpub};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::config::NetworkType;
+use crate::config::{KeyMode, NetworkType};
+use crate::f1r3fly::AnchorMethod;
+use crate::storage::keys::Kdf;
 
 /// Complete wallet key material (kept in memory during operations)
 ///
@@ -27,9 +29,12 @@ pub struct WalletKeys {
     /// Bitcoin extended private key at account level (m/84'/coin_type'/0')
     pub bitcoin_xprv: Xpriv,
 
-    /// Bitcoin descriptor for BDK wallet
+    /// Bitcoin descriptor for BDK wallet (contains the xprv - never written in plaintext)
     pub bitcoin_descriptor: String,
 
+    /// Public (xpub) form of the Bitcoin descriptor, safe to store unencrypted
+    pub bitcoin_public_descriptor: String,
+
     /// F1r3fly private key (derived at m/1337'/0'/0'/0/0)
     pub f1r3fly_private_key: secp256k1::SecretKey,
 
@@ -64,6 +69,7 @@ impl WalletKeys {
         // Create taproot descriptor for BDK (BIP86)
         // tr(...) format is required for Tapret commitments in RGB protocol
         let bitcoin_descriptor = format!("tr({}/0/*)", bitcoin_xprv);
+        let bitcoin_public_descriptor = public_descriptor(&bitcoin_xprv);
 
         // Derive F1r3fly keys
         let (f1r3fly_private_key, f1r3fly_public_key) =
@@ -73,6 +79,7 @@ impl WalletKeys {
             mnemonic: mnemonic.clone(),
             bitcoin_xprv,
             bitcoin_descriptor,
+            bitcoin_public_descriptor,
             f1r3fly_private_key,
             f1r3fly_public_key,
         })
@@ -119,6 +126,16 @@ impl WalletKeys {
     }
 }
 
+/// Build the watch-only taproot descriptor for an account xprv
+///
+/// Mirrors the `tr(<xprv>/0/*)` layout used for signing, so BDK sees the
+/// same public descriptor whether the wallet is opened locked or unlocked.
+pub fn public_descriptor(bitcoin_xprv: &Xpriv) -> String {
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let xpub = Xpub::from_priv(&secp, bitcoin_xprv);
+    format!("tr({}/0/*)", xpub)
+}
+
 /// Wallet metadata (non-sensitive information)
 ///
 /// Stored unencrypted for fast wallet listing and network verification.
@@ -136,6 +153,13 @@ pub struct WalletMetadata {
 
     /// Last blockchain sync timestamp
     pub last_sync: Option<DateTime<Utc>>,
+
+    /// Wallet-specific default RGB anchor method (overrides the global config)
+    pub anchor_method: Option<AnchorMethod>,
+
+    /// Key that owns this wallet's contracts and seals (fixed at creation)
+    #[serde(default)]
+    pub key_mode: KeyMode,
 }
 
 impl WalletMetadata {
@@ -146,6 +170,8 @@ impl WalletMetadata {
             network,
             created_at: Utc::now(),
             last_sync: None,
+            anchor_method: None,
+            key_mode: KeyMode::Shared,
         }
     }
 
@@ -200,17 +226,38 @@ impl WalletInfo {
     }
 }
 
+/// Current `keys.json` format version
+///
+/// - `0`: legacy format with the xprv descriptor stored in plaintext
+/// - `1`: public descriptor in plaintext, private descriptor encrypted
+pub const KEYS_FORMAT_VERSION: u32 = 1;
+
 /// Encrypted wallet keys storage format
 ///
 /// This is what gets saved to disk in `keys.json`.
 /// All sensitive fields are encrypted with user's password.
+/// Files written before versioning deserialize as version 0 and are
+/// rewritten by `file_system::load_wallet`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedWalletKeys {
+    /// Storage format version (see `KEYS_FORMAT_VERSION`)
+    #[serde(default)]
+    pub version: u32,
+
     /// Encrypted BIP39 mnemonic (hex-encoded)
     pub encrypted_mnemonic: String,
 
-    /// Bitcoin descriptor (not encrypted - derived from xprv anyway)
-    pub bitcoin_descriptor: String,
+    /// Public (xpub) Bitcoin descriptor (not encrypted - watch-only)
+    #[serde(default)]
+    pub bitcoin_public_descriptor: String,
+
+    /// Encrypted private (xprv) Bitcoin descriptor (hex-encoded)
+    #[serde(default)]
+    pub encrypted_bitcoin_descriptor: String,
+
+    /// Legacy plaintext xprv descriptor (version 0 only, never written)
+    #[serde(default, skip_serializing)]
+    pub bitcoin_descriptor: Option<String>,
 
     /// F1r3fly public key (not encrypted - it's public)
     pub f1r3fly_public_key: String,
@@ -220,23 +267,74 @@ pub struct EncryptedWalletKeys {
 }
 
 impl EncryptedWalletKeys {
-    /// Encrypt wallet keys with password
+    /// Encrypt wallet keys with password (default KDF)
     pub fn from_keys(
         keys: &WalletKeys,
         password: &str,
     ) -> Result<Self, crate::storage::keys::KeyError> {
+        Self::from_keys_with_kdf(keys, password, &Kdf::default())
+    }
+
+    /// Encrypt wallet keys with password, deriving the key with `kdf`
+    pub fn from_keys_with_kdf(
+        keys: &WalletKeys,
+        password: &str,
+        kdf: &Kdf,
+    ) -> Result<Self, crate::storage::keys::KeyError> {
+        use crate::storage::keys::encrypt_data_with_kdf;
+
         Ok(Self {
-            encrypted_mnemonic: crate::storage::keys::encrypt_mnemonic(&keys.mnemonic, password)?,
-            bitcoin_descriptor: keys.bitcoin_descriptor.clone(),
+            version: KEYS_FORMAT_VERSION,
+            encrypted_mnemonic: encrypt_data_with_kdf(
+                keys.mnemonic.to_string().as_bytes(),
+                password,
+                kdf,
+            )?,
+            bitcoin_public_descriptor: keys.bitcoin_public_descriptor.clone(),
+            encrypted_bitcoin_descriptor: encrypt_data_with_kdf(
+                keys.bitcoin_descriptor.as_bytes(),
+                password,
+                kdf,
+            )?,
+            bitcoin_descriptor: None,
             f1r3fly_public_key: keys.f1r3fly_public_key.clone(),
-            encrypted_f1r3fly_private_key: crate::storage::keys::encrypt_secret_key(
-                &keys.f1r3fly_private_key,
+            encrypted_f1r3fly_private_key: encrypt_data_with_kdf(
+                &keys.f1r3fly_private_key.secret_bytes(),
                 password,
+                kdf,
             )?,
         })
     }
 
+    /// KDF the secrets are encrypted with (`None` for legacy blobs)
+    pub fn kdf(&self) -> Result<Option<Kdf>, crate::storage::keys::KeyError> {
+        crate::storage::keys::encryption_kdf(&self.encrypted_mnemonic)
+    }
+
+    /// Whether every secret is encrypted with `kdf`
+    pub fn uses_kdf(&self, kdf: &Kdf) -> bool {
+        [
+            &self.encrypted_mnemonic,
+            &self.encrypted_bitcoin_descriptor,
+            &self.encrypted_f1r3fly_private_key,
+        ]
+        .iter()
+        .all(|blob| {
+            crate::storage::keys::encryption_kdf(blob)
+                .map(|used| used.as_ref() == Some(kdf))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether this file predates `KEYS_FORMAT_VERSION` and must be rewritten
+    pub fn needs_migration(&self) -> bool {
+        self.version < KEYS_FORMAT_VERSION
+    }
+
     /// Decrypt to WalletKeys
+    ///
+    /// Accepts every format version; legacy files fall back to the
+    /// plaintext descriptor (or re-derive it from the mnemonic).
     pub fn to_keys(
         &self,
         password: &str,
@@ -254,10 +352,23 @@ impl EncryptedWalletKeys {
         // Derive Bitcoin keys from mnemonic
         let bitcoin_xprv = crate::storage::keys::derive_bitcoin_keys(&mnemonic, network)?;
 
+        // Decrypt the private descriptor (version 1+) or take the legacy plaintext copy
+        let bitcoin_descriptor = if self.encrypted_bitcoin_descriptor.is_empty() {
+            self.bitcoin_descriptor
+                .clone()
+                .unwrap_or_else(|| format!("tr({}/0/*)", bitcoin_xprv))
+        } else {
+            let bytes =
+                crate::storage::keys::decrypt_data(&self.encrypted_bitcoin_descriptor, password)?;
+            String::from_utf8(bytes)
+                .map_err(|e| crate::storage::keys::KeyError::Decryption(e.to_string()))?
+        };
+
         Ok(WalletKeys {
             mnemonic,
             bitcoin_xprv,
-            bitcoin_descriptor: self.bitcoin_descriptor.clone(),
+            bitcoin_descriptor,
+            bitcoin_public_descriptor: public_descriptor(&bitcoin_xprv),
             f1r3fly_private_key,
             f1r3fly_public_key: self.f1r3fly_public_key.clone(),
         })
@@ -64,7 +64,8 @@ pub struct RgbSealInfo {
 }
 
 /// Filter options for UTXO listing
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UtxoFilter {
     /// Only show available (non-RGB) UTXOs
     pub available_only: bool,
@@ -127,3 +128,146 @@ impl std::fmt::Display for OutputFormat {
         }
     }
 }
+
+/// Direction of a Bitcoin transaction relative to the wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxDirection {
+    /// Wallet received funds (spent none of its own outputs)
+    Incoming,
+
+    /// Wallet paid funds to external outputs
+    Outgoing,
+
+    /// Every output belongs to the wallet (only the fee leaves it)
+    SelfTransfer,
+}
+
+impl std::fmt::Display for TxDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxDirection::Incoming => write!(f, "Incoming"),
+            TxDirection::Outgoing => write!(f, "Outgoing"),
+            TxDirection::SelfTransfer => write!(f, "Self"),
+        }
+    }
+}
+
+/// RGB role of a Bitcoin transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RgbTxKind {
+    /// Witness transaction of an RGB transfer (sent or claimed)
+    Witness,
+
+    /// Transaction creating an asset's genesis seal
+    Genesis,
+
+    /// Self-send creating a UTXO for RGB use (`create-utxo`)
+    CreateUtxo,
+}
+
+impl std::fmt::Display for RgbTxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RgbTxKind::Witness => write!(f, "RGB witness"),
+            RgbTxKind::Genesis => write!(f, "RGB genesis"),
+            RgbTxKind::CreateUtxo => write!(f, "create-utxo"),
+        }
+    }
+}
+
+/// RGB annotation attached to a Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RgbTxAnnotation {
+    /// What the transaction does for RGB
+    pub kind: RgbTxKind,
+
+    /// Related contract (None for `CreateUtxo`)
+    pub contract_id: Option<String>,
+}
+
+/// Information about a wallet transaction with RGB annotations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    /// Transaction ID
+    pub txid: String,
+
+    /// Block height where the transaction confirmed (None if unconfirmed)
+    pub confirmation_height: Option<u32>,
+
+    /// Number of confirmations (0 if unconfirmed)
+    pub confirmations: u32,
+
+    /// Sum of wallet-owned inputs spent (satoshis)
+    pub sent_sats: u64,
+
+    /// Sum of wallet-owned outputs created (satoshis)
+    pub received_sats: u64,
+
+    /// Net effect on the wallet balance (received - sent, satoshis)
+    pub net_sats: i64,
+
+    /// Transaction fee (None if some inputs are not known to the wallet)
+    pub fee_sats: Option<u64>,
+
+    /// Direction relative to the wallet
+    pub direction: TxDirection,
+
+    /// RGB annotations (empty for plain Bitcoin transactions)
+    pub rgb: Vec<RgbTxAnnotation>,
+}
+
+/// Result of a replace-by-fee bump
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeBumpResponse {
+    /// Replaced transaction ID
+    pub original_txid: String,
+
+    /// Replacement transaction ID
+    pub txid: String,
+
+    /// Fee paid by the replacement (satoshis)
+    pub fee_sats: u64,
+
+    /// Effective fee rate of the replacement (sat/vB)
+    pub fee_rate: f64,
+
+    /// Whether the replaced transaction was an RGB witness
+    pub rgb_witness: bool,
+
+    /// Consignments re-issued with the replacement witness (RGB only)
+    pub consignments: Vec<std::path::PathBuf>,
+}
+
+/// Result of a child-pays-for-parent acceleration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpfpResponse {
+    /// Accelerated parent transaction ID
+    pub parent_txid: String,
+
+    /// Child transaction ID
+    pub txid: String,
+
+    /// Parent output spent by the child ("txid:vout")
+    pub spent_outpoint: String,
+
+    /// Fee paid by the child (satoshis)
+    pub fee_sats: u64,
+
+    /// Effective fee rate of parent + child (sat/vB)
+    pub package_fee_rate: f64,
+
+    /// New seal outpoint if an RGB seal was spent forward ("txid:vout")
+    pub rebound_seal: Option<String>,
+}
+
+/// Result of broadcasting a signed PSBT
+#[derive(Debug, Clone)]
+pub struct PsbtBroadcastResponse {
+    /// Broadcast transaction ID
+    pub txid: String,
+
+    /// Completed RGB transfer, if the PSBT was a prepared witness transaction
+    pub transfer: Option<crate::f1r3fly::BatchTransferResponse>,
+}
@@ -0,0 +1,136 @@
+//! Wallet Backup Tests
+//!
+//! Tests the encrypted single-file backup: round trip of every wallet file
+//! (including SQLite databases and consignments), password and integrity
+//! checks, and refusal to overwrite a newer or different wallet.
+
+use f1r3fly_rgb_wallet::config::NetworkType;
+use f1r3fly_rgb_wallet::storage::backup::{
+    create_backup, read_backup, restore_backup, BackupError,
+};
+use f1r3fly_rgb_wallet::storage::file_system::save_wallet;
+use f1r3fly_rgb_wallet::storage::keys::generate_mnemonic;
+use f1r3fly_rgb_wallet::storage::models::{WalletKeys, WalletMetadata};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const PASSWORD: &str = "backup_password";
+
+/// Create a wallet with a claims database and a consignment
+fn create_test_wallet(base: &str, name: &str) -> PathBuf {
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    let keys =
+        WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys");
+    let metadata = WalletMetadata::new(name.to_string(), NetworkType::Regtest);
+    save_wallet(name, &keys, &metadata, PASSWORD, Some(base)).expect("Failed to save wallet");
+
+    let wallet_path = PathBuf::from(base).join(name);
+    let conn = rusqlite::Connection::open(wallet_path.join("f1r3fly_claims.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE claims (id INTEGER PRIMARY KEY, witness_id TEXT);
+         INSERT INTO claims (witness_id) VALUES ('witness-1');",
+    )
+    .unwrap();
+    drop(conn);
+
+    fs::create_dir_all(wallet_path.join("consignments")).unwrap();
+    fs::write(
+        wallet_path.join("consignments/transfer.rgbc"),
+        b"consignment",
+    )
+    .unwrap();
+
+    wallet_path
+}
+
+#[test]
+fn test_backup_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().join("wallets");
+    let base = base.to_str().unwrap();
+    let archive = temp_dir.path().join("alice.backup");
+
+    let wallet_path = create_test_wallet(base, "alice");
+    let keys_json = fs::read(wallet_path.join("keys.json")).unwrap();
+
+    let manifest = create_backup("alice", PASSWORD, Some(base), &archive).expect("Backup failed");
+    assert_eq!(manifest.wallet_name, "alice");
+    let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+    assert!(paths.contains(&"keys.json"));
+    assert!(paths.contains(&"f1r3fly_claims.db"));
+    assert!(paths.contains(&"consignments/transfer.rgbc"));
+
+    // Nothing readable outside the encrypted payload
+    let archive_text = fs::read_to_string(&archive).unwrap();
+    assert!(!archive_text.contains("witness-1"));
+    assert!(!archive_text.contains("consignments/transfer.rgbc"));
+
+    // Wrong password and tampered payload are rejected
+    assert!(matches!(
+        read_backup(&archive, "wrong_password"),
+        Err(BackupError::Key(_))
+    ));
+    let tampered = temp_dir.path().join("tampered.backup");
+    let mut envelope: serde_json::Value = serde_json::from_str(&archive_text).unwrap();
+    let payload = envelope["payload"].as_str().unwrap().to_string();
+    let flipped = if payload.ends_with('0') { "1" } else { "0" };
+    envelope["payload"] = format!("{}{}", &payload[..payload.len() - 1], flipped).into();
+    fs::write(&tampered, envelope.to_string()).unwrap();
+    assert!(read_backup(&tampered, PASSWORD).is_err());
+
+    // Restore into an empty wallets directory
+    fs::remove_dir_all(&wallet_path).unwrap();
+    let restored =
+        restore_backup(&archive, PASSWORD, None, Some(base), false).expect("Restore failed");
+    assert_eq!(restored.files.len(), manifest.files.len());
+    assert_eq!(fs::read(wallet_path.join("keys.json")).unwrap(), keys_json);
+    assert_eq!(
+        fs::read(wallet_path.join("consignments/transfer.rgbc")).unwrap(),
+        b"consignment"
+    );
+
+    let conn = rusqlite::Connection::open(wallet_path.join("f1r3fly_claims.db")).unwrap();
+    let witness: String = conn
+        .query_row("SELECT witness_id FROM claims", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(witness, "witness-1");
+
+    // A backup only restores under its own name
+    assert!(matches!(
+        restore_backup(&archive, PASSWORD, Some("bob"), Some(base), false),
+        Err(BackupError::NameMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_restore_refuses_newer_or_different_wallet() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().join("wallets");
+    let base = base.to_str().unwrap();
+    let archive = temp_dir.path().join("alice.backup");
+
+    let wallet_path = create_test_wallet(base, "alice");
+    create_backup("alice", PASSWORD, Some(base), &archive).expect("Backup failed");
+
+    // Unchanged wallet can be restored over
+    restore_backup(&archive, PASSWORD, None, Some(base), false)
+        .expect("Restore over unchanged wallet failed");
+
+    // Wallet changed after the backup
+    fs::write(wallet_path.join("consignments/later.rgbc"), b"later").unwrap();
+    assert!(matches!(
+        restore_backup(&archive, PASSWORD, None, Some(base), false),
+        Err(BackupError::NewerWallet(_))
+    ));
+    restore_backup(&archive, PASSWORD, None, Some(base), true).expect("Forced restore failed");
+    assert!(!wallet_path.join("consignments/later.rgbc").exists());
+
+    // Different wallet under the same name
+    fs::remove_dir_all(&wallet_path).unwrap();
+    create_test_wallet(base, "alice");
+    assert!(matches!(
+        restore_backup(&archive, PASSWORD, None, Some(base), false),
+        Err(BackupError::WalletMismatch(_))
+    ));
+}
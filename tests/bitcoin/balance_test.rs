@@ -7,11 +7,14 @@ use bdk_wallet::KeychainKind;
 use f1r3fly_rgb_wallet::bitcoin::balance::{
     get_addresses, get_balance, list_utxos, mark_rgb_occupied, unmark_rgb_occupied,
 };
+use f1r3fly_rgb_wallet::bitcoin::history::list_transactions;
 use f1r3fly_rgb_wallet::bitcoin::sync::sync_wallet;
+use f1r3fly_rgb_wallet::bitcoin::utxo::{create_utxo, FeeRateConfig};
 use f1r3fly_rgb_wallet::bitcoin::wallet::BitcoinWallet;
 use f1r3fly_rgb_wallet::config::NetworkType;
 use f1r3fly_rgb_wallet::storage::keys::generate_mnemonic;
 use f1r3fly_rgb_wallet::storage::models::WalletKeys;
+use f1r3fly_rgb_wallet::types::TxDirection;
 use std::collections::HashSet;
 
 use crate::common::TestBitcoinEnv;
@@ -604,3 +607,75 @@ async fn test_list_utxos_excludes_spent() {
         final_balance.confirmed
     );
 }
+
+/// Test 3.7: Verify transaction history reports direction, net amount and fee
+#[tokio::test]
+async fn test_list_transactions_incoming_and_self_send() {
+    // Step 1: Create and fund wallet with 0.5 BTC
+    let env = TestBitcoinEnv::new("list_transactions");
+
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    let keys =
+        WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys");
+
+    let wallet_dir = env.wallet_dir("tx_history_wallet");
+    let mut wallet = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &wallet_dir,
+    )
+    .expect("Failed to create BitcoinWallet");
+
+    let address = wallet
+        .inner_mut()
+        .reveal_next_address(KeychainKind::External)
+        .address
+        .to_string();
+
+    let funding_txid = env
+        .fund_address(&address, 0.5)
+        .expect("Failed to fund address");
+    env.wait_for_confirmation(&funding_txid, 1)
+        .await
+        .expect("Failed to confirm funding");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync wallet");
+
+    // Step 2: Create a UTXO (self-send)
+    let result = create_utxo(
+        &mut wallet,
+        &env.esplora_client,
+        10_000,
+        &FeeRateConfig::medium_priority(),
+        None,
+        false,
+    )
+    .expect("Failed to create UTXO");
+    env.wait_for_confirmation(&result.txid.to_string(), 1)
+        .await
+        .expect("Failed to confirm UTXO creation");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync after UTXO creation");
+
+    // Step 3: List transactions
+    let transactions = list_transactions(&wallet).expect("Failed to list transactions");
+    assert_eq!(transactions.len(), 2, "Should list funding and self-send");
+
+    // Newest first: the self-send comes before the funding transaction
+    let self_send = &transactions[0];
+    assert_eq!(self_send.txid, result.txid.to_string());
+    assert_eq!(self_send.direction, TxDirection::SelfTransfer);
+    let fee = self_send.fee_sats.expect("Self-send fee should be known");
+    assert_eq!(
+        self_send.net_sats,
+        -(fee as i64),
+        "Self-send should only lose the fee"
+    );
+    assert!(self_send.confirmation_height.is_some());
+    assert!(self_send.rgb.is_empty(), "Bitcoin layer adds no RGB data");
+
+    let funding = &transactions[1];
+    assert_eq!(funding.txid, funding_txid);
+    assert_eq!(funding.direction, TxDirection::Incoming);
+    assert_eq!(funding.net_sats, 50_000_000);
+    assert_eq!(funding.sent_sats, 0);
+    assert!(funding.confirmations >= 2);
+}
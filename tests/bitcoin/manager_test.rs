@@ -3,7 +3,12 @@
 //! Tests for WalletManager integration with all wallet functionality.
 //! Covers wallet creation, import, loading, sync, addresses, UTXO operations, and sending.
 
+use bdk_wallet::bitcoin::consensus::encode::serialize_hex;
+use bdk_wallet::bitcoin::{absolute, transaction, Amount, ScriptBuf, Transaction, TxIn, TxOut};
 use f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig;
+use f1r3fly_rgb_wallet::f1r3fly::{
+    AnchorMethod, TransferJournal, TransferJournalEntry, TransferPhase,
+};
 use f1r3fly_rgb_wallet::manager::WalletManager;
 use f1r3fly_rgb_wallet::storage::keys::generate_mnemonic;
 
@@ -521,3 +526,102 @@ async fn test_manager_multiple_wallets_isolated() {
         expected_sats
     );
 }
+
+/// Test 6.9: Verify a signed but unbroadcast transfer keeps its inputs reserved
+#[tokio::test]
+async fn test_manager_reserves_signed_transfer_inputs() {
+    // Step 1: Create wallet via manager and fund it with a single UTXO
+    let env = TestBitcoinEnv::new("manager_signed_reserve");
+
+    let config = env.config().clone();
+
+    let mut manager = WalletManager::new(config.clone()).expect("Failed to create manager");
+
+    let wallet_name = format!("reserve_wallet_{}", uuid::Uuid::new_v4());
+    let password = "reserve_password_123";
+
+    manager
+        .create_wallet(&wallet_name, password)
+        .expect("Failed to create wallet");
+
+    let address = manager.get_new_address().expect("Failed to get address");
+
+    let txid = env
+        .fund_address(&address.to_string(), 1.0)
+        .expect("Failed to fund wallet");
+
+    env.wait_for_confirmation(&txid, 1)
+        .await
+        .expect("Failed to confirm funding");
+
+    manager.sync_wallet().await.expect("Failed to sync wallet");
+
+    let funding = manager
+        .bitcoin_wallet()
+        .expect("Wallet should be loaded")
+        .inner()
+        .list_unspent()
+        .next()
+        .expect("Wallet should have a UTXO")
+        .outpoint;
+
+    // Step 2: Journal a transfer stopped right after signing its witness
+    let witness = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: funding,
+            ..Default::default()
+        }],
+        output: vec![TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: ScriptBuf::new(),
+        }],
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut entry = TransferJournalEntry {
+        id: TransferJournal::new_id(),
+        phase: TransferPhase::Signed,
+        contract_id: "contract".to_string(),
+        anchor_method: AnchorMethod::Tapret,
+        fee_rate_sat_per_vb: 2.0,
+        recipients: Vec::new(),
+        inputs: Vec::new(),
+        total_amount: 0,
+        change_amount: 0,
+        change: None,
+        legs: Vec::new(),
+        execution: None,
+        txid: Some(witness.compute_txid().to_string()),
+        signed_tx_hex: Some(serialize_hex(&witness)),
+        transfers: Vec::new(),
+        last_error: None,
+        created_at: now,
+        updated_at: now,
+    };
+    TransferJournal::new(env.wallet_dir(&wallet_name).join("transfer_journal"))
+        .save(&mut entry)
+        .expect("Failed to journal transfer");
+
+    // Step 3: Reload the wallet (simulates a restart)
+    let mut reloaded = WalletManager::new(config).expect("Failed to create manager");
+    reloaded
+        .load_wallet(&wallet_name, password)
+        .expect("Failed to reload wallet");
+    reloaded.sync_wallet().await.expect("Failed to sync wallet");
+
+    // Step 4: The witness input is reserved and never selected
+    assert!(
+        reloaded.rgb_occupied().contains(&funding),
+        "Signed witness input should be reserved"
+    );
+    assert!(
+        reloaded
+            .create_utxo(25_000, &FeeRateConfig::medium_priority(), false)
+            .is_err(),
+        "Coin selection must not spend the signed witness input"
+    );
+}
@@ -7,6 +7,7 @@ pub mod balance_test;
 pub mod cli_flow_test;
 pub mod manager_test;
 pub mod network_sync_test;
+pub mod psbt_test;
 pub mod send_test;
 pub mod utxo_test;
 pub mod wallet_test;
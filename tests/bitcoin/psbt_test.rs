@@ -0,0 +1,116 @@
+//! Module 7: PSBT Offline Signing Tests
+//!
+//! Tests the export/sign/finalize flow used for offline signing.
+//! Covers save_psbt(), load_psbt(), sign_psbt() and finalize_psbt().
+
+use bdk_wallet::KeychainKind;
+use f1r3fly_rgb_wallet::bitcoin::psbt::{
+    finalize_psbt, load_psbt, save_psbt, sign_psbt, summarize_psbt, PsbtError,
+};
+use f1r3fly_rgb_wallet::bitcoin::sync::sync_wallet;
+use f1r3fly_rgb_wallet::bitcoin::wallet::BitcoinWallet;
+use f1r3fly_rgb_wallet::config::NetworkType;
+use f1r3fly_rgb_wallet::storage::keys::generate_mnemonic;
+use f1r3fly_rgb_wallet::storage::models::WalletKeys;
+
+use crate::common::TestBitcoinEnv;
+
+/// Test 7.1: Verify a PSBT signed by a separate wallet instance broadcasts
+#[tokio::test]
+async fn test_psbt_signed_offline_and_broadcast() {
+    // Step 1: Create funded online wallet with 1 BTC
+    let env = TestBitcoinEnv::new("psbt_offline_signing");
+
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    let keys =
+        WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys");
+
+    let mut online = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &env.wallet_dir("psbt_online"),
+    )
+    .expect("Failed to create online wallet");
+
+    let address = online
+        .inner_mut()
+        .reveal_next_address(KeychainKind::External)
+        .address
+        .to_string();
+
+    let txid = env
+        .fund_address(&address, 1.0)
+        .expect("Failed to fund wallet");
+    env.wait_for_confirmation(&txid, 1)
+        .await
+        .expect("Failed to confirm funding");
+    sync_wallet(&mut online, &env.esplora_client).expect("Failed to sync wallet");
+
+    // Step 2: Build an unsigned PSBT and export it
+    use bdk_wallet::bitcoin::Address;
+    let recipient: Address = env
+        .get_new_test_address()
+        .expect("Failed to generate recipient address")
+        .parse::<Address<_>>()
+        .expect("Failed to parse recipient address")
+        .assume_checked();
+
+    let mut tx_builder = online.inner_mut().build_tx();
+    tx_builder.add_recipient(
+        recipient.script_pubkey(),
+        bdk_wallet::bitcoin::Amount::from_sat(25_000),
+    );
+    let psbt = tx_builder.finish().expect("Failed to build PSBT");
+
+    let psbt_path = env.wallet_dir("psbt_files").join("unsigned.psbt");
+    std::fs::create_dir_all(psbt_path.parent().unwrap()).expect("Failed to create dir");
+    save_psbt(&psbt, &psbt_path).expect("Failed to save PSBT");
+
+    let unsigned = load_psbt(&psbt_path).expect("Failed to load PSBT");
+    assert_eq!(unsigned.unsigned_tx, psbt.unsigned_tx);
+
+    // Step 3: An unsigned PSBT cannot be finalized
+    let result = finalize_psbt(&online, unsigned.clone());
+    assert!(
+        matches!(result, Err(PsbtError::Incomplete { signed: 0, .. })),
+        "Unsigned PSBT must be rejected"
+    );
+
+    // Step 4: Sign with a never-synced wallet instance (the offline signer)
+    let offline = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &env.wallet_dir("psbt_offline"),
+    )
+    .expect("Failed to create offline wallet");
+
+    let mut signed = load_psbt(&psbt_path).expect("Failed to load PSBT");
+    let summary = sign_psbt(&offline, &mut signed).expect("Failed to sign PSBT");
+    assert!(summary.is_finalized(), "All inputs should be signed");
+    assert_eq!(
+        summary.txid,
+        psbt.unsigned_tx.compute_txid().to_string(),
+        "Signing must not change the txid"
+    );
+    assert!(summary
+        .outputs
+        .iter()
+        .any(|output| output.amount_sats == 25_000 && !output.is_mine));
+
+    let signed_path = psbt_path.with_file_name("signed.psbt");
+    save_psbt(&signed, &signed_path).expect("Failed to save signed PSBT");
+
+    // Step 5: Finalize on the online wallet and broadcast
+    let signed = load_psbt(&signed_path).expect("Failed to load signed PSBT");
+    assert!(summarize_psbt(&online, &signed).is_finalized());
+
+    let tx = finalize_psbt(&online, signed).expect("Failed to finalize PSBT");
+    env.esplora_client
+        .inner()
+        .broadcast(&tx)
+        .expect("Failed to broadcast");
+
+    env.wait_for_confirmation(&tx.compute_txid().to_string(), 1)
+        .await
+        .expect("Failed to confirm transaction");
+}
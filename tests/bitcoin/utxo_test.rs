@@ -1,13 +1,13 @@
 //! Module 4: UTXO Operations Tests
 //!
 //! Tests for UTXO creation, unlocking, fee rate handling, and RGB UTXO marking.
-//! Covers create_utxo(), unlock_utxo(), and fee rate estimation.
+//! Covers create_utxo(), unlock_utxo(), fee bumping (RBF/CPFP), and fee rate estimation.
 
 use bdk_wallet::KeychainKind;
 use f1r3fly_rgb_wallet::bitcoin::balance::{get_balance, list_utxos};
 use f1r3fly_rgb_wallet::bitcoin::sync::sync_wallet;
 use f1r3fly_rgb_wallet::bitcoin::utxo::{
-    create_utxo, get_recommended_fee_rates, unlock_utxo, FeeRateConfig,
+    bump_fee, cpfp, create_utxo, get_recommended_fee_rates, unlock_utxo, FeeRateConfig,
 };
 use f1r3fly_rgb_wallet::bitcoin::wallet::BitcoinWallet;
 use f1r3fly_rgb_wallet::config::NetworkType;
@@ -655,3 +655,191 @@ async fn test_get_recommended_fee_rates_from_esplora() {
         high.sat_per_vb
     );
 }
+
+/// Test 4.7: Verify RBF bump replaces the transaction and preserves its outputs
+#[tokio::test]
+async fn test_bump_fee_replaces_unconfirmed_transaction() {
+    // Step 1: Create and fund wallet with 1 BTC
+    let env = TestBitcoinEnv::new("bump_fee_rbf");
+
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    let keys =
+        WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys");
+
+    let wallet_dir = env.wallet_dir("bump_fee_wallet");
+    let mut wallet = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &wallet_dir,
+    )
+    .expect("Failed to create BitcoinWallet");
+
+    let address = wallet
+        .inner_mut()
+        .reveal_next_address(KeychainKind::External)
+        .address
+        .to_string();
+
+    let txid = env
+        .fund_address(&address, 1.0)
+        .expect("Failed to fund wallet");
+    env.wait_for_confirmation(&txid, 1)
+        .await
+        .expect("Failed to confirm funding");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync wallet");
+
+    // Step 2: Create a UTXO at a low fee rate and leave it unconfirmed
+    let original = create_utxo(
+        &mut wallet,
+        &env.esplora_client,
+        10_000,
+        &FeeRateConfig::low_priority(),
+        None,
+        false,
+    )
+    .expect("Failed to create UTXO");
+
+    // Step 3: Sync so the wallet knows the mempool transaction
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync mempool tx");
+
+    // Step 4: Bump to a higher fee rate
+    let rgb_occupied = HashSet::new();
+    let bump = bump_fee(
+        &mut wallet,
+        &env.esplora_client,
+        original.outpoint.txid,
+        &FeeRateConfig::high_priority(),
+        &rgb_occupied,
+    )
+    .expect("Failed to bump fee");
+
+    assert_eq!(bump.original_txid, original.outpoint.txid);
+    assert_ne!(
+        bump.txid(),
+        original.outpoint.txid,
+        "Replacement needs a new txid"
+    );
+    assert!(
+        bump.fee > original.fee,
+        "Replacement fee ({} sats) should exceed original ({} sats)",
+        bump.fee,
+        original.fee
+    );
+
+    // Step 5: The created output keeps its index and value
+    let vout = original.outpoint.vout as usize;
+    assert_eq!(bump.tx.output[vout].value.to_sat(), 10_000);
+
+    // Step 6: Confirm the replacement and verify the UTXO moved to it
+    env.wait_for_confirmation(&bump.txid().to_string(), 1)
+        .await
+        .expect("Failed to confirm replacement");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync after bump");
+
+    let utxos = list_utxos(&wallet, &rgb_occupied).expect("Failed to list UTXOs");
+    assert!(utxos
+        .iter()
+        .any(|u| u.outpoint.txid == bump.txid() && u.outpoint.vout == original.outpoint.vout));
+    assert!(
+        !utxos
+            .iter()
+            .any(|u| u.outpoint.txid == original.outpoint.txid),
+        "Replaced transaction outputs must be gone"
+    );
+}
+
+/// Test 4.8: Verify CPFP spends a non-RGB parent output at the package fee rate
+#[tokio::test]
+async fn test_cpfp_accelerates_unconfirmed_parent() {
+    // Step 1: Create and fund wallet with 1 BTC
+    let env = TestBitcoinEnv::new("cpfp_child");
+
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    let keys =
+        WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys");
+
+    let wallet_dir = env.wallet_dir("cpfp_wallet");
+    let mut wallet = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &wallet_dir,
+    )
+    .expect("Failed to create BitcoinWallet");
+
+    let address = wallet
+        .inner_mut()
+        .reveal_next_address(KeychainKind::External)
+        .address
+        .to_string();
+
+    let txid = env
+        .fund_address(&address, 1.0)
+        .expect("Failed to fund wallet");
+    env.wait_for_confirmation(&txid, 1)
+        .await
+        .expect("Failed to confirm funding");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync wallet");
+
+    // Step 2: Create an RGB-occupied UTXO at a low fee rate, leave it unconfirmed
+    let parent = create_utxo(
+        &mut wallet,
+        &env.esplora_client,
+        10_000,
+        &FeeRateConfig::low_priority(),
+        None,
+        false,
+    )
+    .expect("Failed to create UTXO");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync mempool tx");
+
+    let mut rgb_occupied = HashSet::new();
+    rgb_occupied.insert(parent.outpoint);
+
+    // Step 3: Accelerate the parent through its change output
+    let target = FeeRateConfig::high_priority();
+    let result = cpfp(
+        &mut wallet,
+        &env.esplora_client,
+        parent.outpoint.txid,
+        &target,
+        &rgb_occupied,
+    )
+    .expect("Failed to create CPFP child");
+
+    assert_eq!(result.parent_txid, parent.outpoint.txid);
+    assert_eq!(result.spent.txid, parent.outpoint.txid);
+    assert_ne!(
+        result.spent, parent.outpoint,
+        "Child must not spend the RGB-occupied output"
+    );
+    assert!(result.carried_to.is_none());
+    assert!(
+        result.package_fee_rate >= target.sat_per_vb * 0.95,
+        "Package fee rate {:.2} sat/vB should reach the {:.2} sat/vB target",
+        result.package_fee_rate,
+        target.sat_per_vb
+    );
+
+    // Step 4: Both transactions confirm; the occupied output is untouched
+    env.wait_for_confirmation(&result.txid().to_string(), 1)
+        .await
+        .expect("Failed to confirm child");
+    sync_wallet(&mut wallet, &env.esplora_client).expect("Failed to sync after CPFP");
+
+    let utxos = list_utxos(&wallet, &rgb_occupied).expect("Failed to list UTXOs");
+    let occupied = utxos
+        .iter()
+        .find(|u| u.outpoint == parent.outpoint)
+        .expect("RGB-occupied output should still be unspent");
+    assert!(occupied.is_confirmed);
+
+    // Step 5: A confirmed parent can no longer be accelerated
+    let err = cpfp(
+        &mut wallet,
+        &env.esplora_client,
+        parent.outpoint.txid,
+        &target,
+        &rgb_occupied,
+    );
+    assert!(err.is_err(), "Confirmed parent should be rejected");
+}
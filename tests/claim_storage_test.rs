@@ -2,7 +2,10 @@
 //!
 //! Tests SQLite persistence + in-memory cache for witness claim tracking
 
-use f1r3fly_rgb_wallet::storage::claim_storage::{ClaimStatus, ClaimStorage, PendingClaim};
+use f1r3fly_rgb_wallet::storage::claim_storage::{
+    BlindedSeal, ClaimStatus, ClaimStorage, InvoiceRecord, InvoiceStatus, PendingClaim, SealRebind,
+    TransferDirection, TransferRecord, TransferStatus, WitnessReplacement,
+};
 use std::path::PathBuf;
 
 #[test]
@@ -300,3 +303,580 @@ fn test_multiple_contracts_query_filtering() {
         "Should have 1 claimed"
     );
 }
+
+#[test]
+fn test_rgb_occupied_utxos_persist_across_reopen() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let txid_a = "a".repeat(64);
+    let txid_b = "b".repeat(64);
+
+    {
+        let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+
+        // Mark two UTXOs (second one twice - must be idempotent)
+        storage
+            .mark_rgb_occupied_utxo(&txid_a, 0, Some("contract_A"))
+            .unwrap();
+        storage.mark_rgb_occupied_utxo(&txid_b, 1, None).unwrap();
+        storage.mark_rgb_occupied_utxo(&txid_b, 1, None).unwrap();
+
+        let occupied = storage.get_rgb_occupied_utxos().unwrap();
+        assert_eq!(occupied.len(), 2, "Duplicate marks should be ignored");
+    } // Storage drops here, closing the connection
+
+    // Reopen (simulates a fresh CLI invocation)
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+    let occupied = storage.get_rgb_occupied_utxos().unwrap();
+    assert_eq!(occupied.len(), 2, "Occupied UTXOs should survive reopen");
+    assert!(occupied.contains(&(txid_a.clone(), 0)));
+    assert!(occupied.contains(&(txid_b.clone(), 1)));
+
+    // Unmark one and verify
+    storage.unmark_rgb_occupied_utxo(&txid_a, 0).unwrap();
+    let occupied = storage.get_rgb_occupied_utxos().unwrap();
+    assert_eq!(occupied, vec![(txid_b, 1)]);
+}
+
+#[test]
+fn test_transfer_opids_recorded_per_witness() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+    let original_txid = "c".repeat(64);
+    let replacement_txid = "d".repeat(64);
+    let opids = vec!["1".repeat(64), "2".repeat(64)];
+
+    // Record every leg (repeating a record must be idempotent)
+    storage
+        .record_transfer_opids(&original_txid, &opids)
+        .unwrap();
+    storage
+        .record_transfer_opids(&original_txid, &opids[..1])
+        .unwrap();
+    assert_eq!(storage.get_transfer_opids(&original_txid).unwrap(), opids);
+
+    // A replacement witness carries the same legs
+    storage
+        .record_transfer_opids(&replacement_txid, &opids)
+        .unwrap();
+    assert_eq!(
+        storage.get_transfer_opids(&replacement_txid).unwrap(),
+        opids
+    );
+
+    // Unknown witnesses (or transfers recorded before opids were kept)
+    assert!(storage
+        .get_transfer_opids(&"e".repeat(64))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_transfer_history_filters_and_claim_sync() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+
+    let record = |direction, contract_id: &str, created_at, claim_id| TransferRecord {
+        id: None,
+        direction,
+        contract_id: contract_id.to_string(),
+        amount: None,
+        counterparty_pubkey: None,
+        witness_txid: "c".repeat(64),
+        anchor_method: Some("tapret".to_string()),
+        consignment_path: PathBuf::from("/tmp/transfer.json"),
+        status: TransferStatus::Broadcasted,
+        claim_id,
+        created_at,
+        updated_at: created_at,
+    };
+
+    let mut outgoing = record(TransferDirection::Outgoing, "contract_A", 1_000, None);
+    outgoing.amount = Some(250);
+    outgoing.counterparty_pubkey = Some("02".repeat(33));
+    storage.insert_transfer_record(&outgoing).unwrap();
+
+    // Incoming transfer linked to a pending claim
+    let claim = PendingClaim {
+        id: None,
+        witness_id: "witness:abc:0".to_string(),
+        recipient_address: "bcrt1qtest".to_string(),
+        expected_vout: 0,
+        contract_id: "contract_A".to_string(),
+        consignment_file: PathBuf::from("/tmp/transfer.json"),
+        status: ClaimStatus::Pending,
+        error: None,
+        created_at: 2_000,
+        claimed_at: None,
+        actual_txid: None,
+        actual_vout: None,
+    };
+    let claim_id = storage.insert_pending_claim(&claim).unwrap();
+    let mut incoming = record(
+        TransferDirection::Incoming,
+        "contract_A",
+        2_000,
+        Some(claim_id),
+    );
+    incoming.status = TransferStatus::Pending;
+    storage.insert_transfer_record(&incoming).unwrap();
+
+    storage
+        .insert_transfer_record(&record(
+            TransferDirection::Outgoing,
+            "contract_B",
+            3_000,
+            None,
+        ))
+        .unwrap();
+
+    // Filters
+    assert_eq!(storage.get_transfers(None, None, None).unwrap().len(), 3);
+    assert_eq!(
+        storage
+            .get_transfers(Some("contract_A"), None, None)
+            .unwrap()
+            .len(),
+        2
+    );
+    let ranged = storage
+        .get_transfers(None, Some(1_500), Some(3_000))
+        .unwrap();
+    assert_eq!(ranged.len(), 2, "Date range should be inclusive");
+    assert_eq!(ranged[0].direction, TransferDirection::Incoming);
+
+    // Completing the claim updates the linked transfer
+    storage.mark_claim_completed(claim_id).unwrap();
+    storage
+        .set_transfer_amount_for_claim(claim_id, 100)
+        .unwrap();
+
+    let transfers = storage
+        .get_transfers(Some("contract_A"), None, None)
+        .unwrap();
+    assert_eq!(transfers[0].status, TransferStatus::Broadcasted);
+    assert_eq!(transfers[0].amount, Some(250));
+    assert_eq!(transfers[1].status, TransferStatus::Claimed);
+    assert_eq!(transfers[1].amount, Some(100));
+    assert_eq!(transfers[1].claim_id, Some(claim_id));
+}
+
+#[test]
+fn test_invoice_registry_matching_and_status() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+
+    let invoice = |nonce, address: &str, expiry| InvoiceRecord {
+        id: None,
+        invoice: format!("contract:tb@contract_A/100@at:seal{}/", nonce),
+        contract_id: "contract_A".to_string(),
+        amount: 100,
+        address: address.to_string(),
+        nonce,
+        recipient_pubkey: Some("04".repeat(33)),
+        expiry,
+        status: InvoiceStatus::Open,
+        amount_received: 0,
+        created_at: 1_000 + nonce,
+        updated_at: 1_000 + nonce,
+    };
+
+    // Nonces are allocated sequentially and never reused
+    assert_eq!(storage.next_invoice_nonce().unwrap(), 1);
+    let paid_id = storage
+        .insert_invoice(&invoice(1, "bcrt1qpaid", None))
+        .unwrap();
+    storage
+        .insert_invoice(&invoice(2, "bcrt1qexpired", Some(5_000)))
+        .unwrap();
+    assert_eq!(storage.next_invoice_nonce().unwrap(), 3);
+    assert!(storage
+        .insert_invoice(&invoice(2, "bcrt1qother", None))
+        .is_err());
+
+    // Two incoming transfers pay the first invoice
+    let claim = |witness_id: &str| PendingClaim {
+        id: None,
+        witness_id: witness_id.to_string(),
+        recipient_address: "bcrt1qpaid".to_string(),
+        expected_vout: 0,
+        contract_id: "contract_A".to_string(),
+        consignment_file: PathBuf::from("/tmp/transfer.json"),
+        status: ClaimStatus::Pending,
+        error: None,
+        created_at: 2_000,
+        claimed_at: None,
+        actual_txid: None,
+        actual_vout: None,
+    };
+    let first = storage.insert_pending_claim(&claim("witness:a:0")).unwrap();
+    let second = storage.insert_pending_claim(&claim("witness:b:0")).unwrap();
+
+    assert_eq!(
+        storage
+            .match_invoice_payment("contract_A", "bcrt1qpaid", first)
+            .unwrap(),
+        Some(paid_id)
+    );
+    assert_eq!(
+        storage
+            .match_invoice_payment("contract_B", "bcrt1qpaid", second)
+            .unwrap(),
+        None,
+        "Other contracts must not match"
+    );
+    storage
+        .match_invoice_payment("contract_A", "bcrt1qpaid", second)
+        .unwrap();
+
+    // Unclaimed payments leave the invoice open
+    let status_of = |storage: &ClaimStorage, id| {
+        storage
+            .get_invoices(None, None)
+            .unwrap()
+            .into_iter()
+            .find(|invoice| invoice.id == Some(id))
+            .unwrap()
+    };
+    assert_eq!(status_of(&storage, paid_id).status, InvoiceStatus::Open);
+
+    storage.set_transfer_amount_for_claim(first, 40).unwrap();
+    let partial = status_of(&storage, paid_id);
+    assert_eq!(partial.status, InvoiceStatus::PartiallyPaid);
+    assert_eq!(partial.amount_received, 40);
+
+    storage.set_transfer_amount_for_claim(second, 60).unwrap();
+    let paid = status_of(&storage, paid_id);
+    assert_eq!(paid.status, InvoiceStatus::Paid);
+    assert_eq!(paid.amount_received, 100);
+
+    // Only open invoices past their expiry expire
+    assert_eq!(storage.expire_invoices(4_999).unwrap(), 0);
+    assert_eq!(storage.expire_invoices(5_000).unwrap(), 1);
+    let expired = storage
+        .get_invoices(Some("contract_A"), Some(InvoiceStatus::Expired))
+        .unwrap();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].nonce, 2);
+    assert_eq!(
+        storage
+            .get_invoices(None, Some(InvoiceStatus::Paid))
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Status filters accept CLI spellings
+    assert_eq!(
+        "partially-paid".parse::<InvoiceStatus>().unwrap(),
+        InvoiceStatus::PartiallyPaid
+    );
+}
+
+#[test]
+fn test_invoice_expiry_with_partial_and_late_payments() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let expiry = now + 3_600;
+
+    let invoice = |nonce, address: &str, expiry| InvoiceRecord {
+        id: None,
+        invoice: format!("contract:tb@contract_A/100@at:seal{}/", nonce),
+        contract_id: "contract_A".to_string(),
+        amount: 100,
+        address: address.to_string(),
+        nonce,
+        recipient_pubkey: None,
+        expiry: Some(expiry),
+        status: InvoiceStatus::Open,
+        amount_received: 0,
+        created_at: 1_000 + nonce,
+        updated_at: 1_000 + nonce,
+    };
+    let claim = |witness_id: &str, address: &str| PendingClaim {
+        id: None,
+        witness_id: witness_id.to_string(),
+        recipient_address: address.to_string(),
+        expected_vout: 0,
+        contract_id: "contract_A".to_string(),
+        consignment_file: PathBuf::from("/tmp/transfer.json"),
+        status: ClaimStatus::Pending,
+        error: None,
+        created_at: 2_000,
+        claimed_at: None,
+        actual_txid: None,
+        actual_vout: None,
+    };
+    let invoice_of = |storage: &ClaimStorage, id| {
+        storage
+            .get_invoices(None, None)
+            .unwrap()
+            .into_iter()
+            .find(|invoice| invoice.id == Some(id))
+            .unwrap()
+    };
+
+    let in_time_id = storage
+        .insert_invoice(&invoice(1, "bcrt1qintime", expiry))
+        .unwrap();
+    let partial_id = storage
+        .insert_invoice(&invoice(2, "bcrt1qpartial", expiry))
+        .unwrap();
+    let late_id = storage
+        .insert_invoice(&invoice(3, "bcrt1qlate", 5_000))
+        .unwrap();
+
+    // A payment accepted after the expiry is recorded but never credited
+    assert_eq!(storage.expire_invoices(now).unwrap(), 1);
+    let late_claim = storage
+        .insert_pending_claim(&claim("witness:c:0", "bcrt1qlate"))
+        .unwrap();
+    assert_eq!(
+        storage
+            .match_invoice_payment("contract_A", "bcrt1qlate", late_claim)
+            .unwrap(),
+        Some(late_id)
+    );
+    storage
+        .set_transfer_amount_for_claim(late_claim, 100)
+        .unwrap();
+    let late = invoice_of(&storage, late_id);
+    assert_eq!(late.status, InvoiceStatus::Expired);
+    assert_eq!(late.amount_received, 0);
+
+    // Payments accepted before the expiry
+    let in_time_claim = storage
+        .insert_pending_claim(&claim("witness:a:0", "bcrt1qintime"))
+        .unwrap();
+    let partial_claim = storage
+        .insert_pending_claim(&claim("witness:b:0", "bcrt1qpartial"))
+        .unwrap();
+    storage
+        .match_invoice_payment("contract_A", "bcrt1qintime", in_time_claim)
+        .unwrap();
+    storage
+        .match_invoice_payment("contract_A", "bcrt1qpartial", partial_claim)
+        .unwrap();
+    storage
+        .set_transfer_amount_for_claim(partial_claim, 40)
+        .unwrap();
+    assert_eq!(
+        invoice_of(&storage, partial_id).status,
+        InvoiceStatus::PartiallyPaid
+    );
+
+    // At expiry, open and partially paid invoices expire; the partial
+    // amount received in time is kept
+    assert_eq!(storage.expire_invoices(expiry).unwrap(), 2);
+    let partial = invoice_of(&storage, partial_id);
+    assert_eq!(partial.status, InvoiceStatus::Expired);
+    assert_eq!(partial.amount_received, 40);
+    assert_eq!(
+        invoice_of(&storage, in_time_id).status,
+        InvoiceStatus::Expired
+    );
+
+    // A payment accepted in time still pays once its claim completes
+    storage
+        .set_transfer_amount_for_claim(in_time_claim, 100)
+        .unwrap();
+    let paid = invoice_of(&storage, in_time_id);
+    assert_eq!(paid.status, InvoiceStatus::Paid);
+    assert_eq!(paid.amount_received, 100);
+
+    // Paid invoices never expire
+    assert_eq!(storage.expire_invoices(expiry + 1).unwrap(), 0);
+}
+
+#[test]
+fn test_blinded_seals_close_when_claimed() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+
+    let seal = |token: &str, contract_id: &str, vout| BlindedSeal {
+        id: None,
+        auth_token: token.to_string(),
+        contract_id: contract_id.to_string(),
+        txid: "a".repeat(64),
+        vout,
+        noise: "00".repeat(40),
+        claim_id: None,
+        created_at: 1_000 + vout as u64,
+    };
+
+    let first = storage
+        .insert_blinded_seal(&seal("at:first", "contract_A", 0))
+        .unwrap();
+    storage
+        .insert_blinded_seal(&seal("at:second", "contract_A", 1))
+        .unwrap();
+    storage
+        .insert_blinded_seal(&seal("at:other", "contract_B", 2))
+        .unwrap();
+    assert!(
+        storage
+            .insert_blinded_seal(&seal("at:first", "contract_A", 3))
+            .is_err(),
+        "Auth tokens must be unique"
+    );
+
+    let open = storage.get_open_blinded_seals(Some("contract_A")).unwrap();
+    assert_eq!(open.len(), 2);
+    assert_eq!(open[0].auth_token, "at:first");
+    assert_eq!(open[0].noise, "00".repeat(40));
+    assert_eq!(storage.get_open_blinded_seals(None).unwrap().len(), 3);
+
+    // A claimed seal is no longer open
+    let claim_id = storage
+        .insert_pending_claim(&PendingClaim {
+            id: None,
+            witness_id: "blinded:at:first".to_string(),
+            recipient_address: "at:first".to_string(),
+            expected_vout: 0,
+            contract_id: "contract_A".to_string(),
+            consignment_file: PathBuf::from("/tmp/transfer.json"),
+            status: ClaimStatus::Pending,
+            error: None,
+            created_at: 2_000,
+            claimed_at: None,
+            actual_txid: Some("a".repeat(64)),
+            actual_vout: Some(0),
+        })
+        .unwrap();
+    storage.set_blinded_seal_claim(first, claim_id).unwrap();
+
+    let open = storage.get_open_blinded_seals(Some("contract_A")).unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].auth_token, "at:second");
+    assert!(storage.set_blinded_seal_claim(999, claim_id).is_err());
+}
+
+#[test]
+fn test_blinded_invoice_and_seal_recorded_together() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+
+    let invoice = |nonce, token: &str| InvoiceRecord {
+        id: None,
+        invoice: format!("contract:tb@contract_A/100@{}/", token),
+        contract_id: "contract_A".to_string(),
+        amount: 100,
+        address: token.to_string(),
+        nonce,
+        recipient_pubkey: Some("04".repeat(33)),
+        expiry: None,
+        status: InvoiceStatus::Open,
+        amount_received: 0,
+        created_at: 1_000 + nonce,
+        updated_at: 1_000 + nonce,
+    };
+    let seal = |token: &str, vout| BlindedSeal {
+        id: None,
+        auth_token: token.to_string(),
+        contract_id: "contract_A".to_string(),
+        txid: "a".repeat(64),
+        vout,
+        noise: "00".repeat(40),
+        claim_id: None,
+        created_at: 1_000,
+    };
+
+    storage
+        .insert_blinded_invoice(&invoice(1, "at:first"), &seal("at:first", 0))
+        .unwrap();
+
+    // A reused nonce fails the invoice, and its seal is not left open
+    assert!(storage
+        .insert_blinded_invoice(&invoice(1, "at:second"), &seal("at:second", 1))
+        .is_err());
+
+    let open = storage.get_open_blinded_seals(None).unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].auth_token, "at:first");
+    assert_eq!(storage.next_invoice_nonce().unwrap(), 2);
+}
+
+#[test]
+fn test_seal_rebinds_persist_until_removed() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let rebind = |claim_id, vout| SealRebind {
+        claim_id,
+        contract_id: "contract_A".to_string(),
+        spent_txid: "a".repeat(64),
+        spent_vout: 1,
+        txid: "b".repeat(64),
+        vout,
+        created_at: 1_000 + claim_id as u64,
+    };
+
+    {
+        let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+        storage
+            .insert_seal_rebinds(&[rebind(1, 0), rebind(2, 0)])
+            .unwrap();
+
+        // Re-journaling a claim replaces its entry
+        storage.insert_seal_rebinds(&[rebind(2, 3)]).unwrap();
+    }
+
+    // Entries survive a restart (replayed on the next sync)
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+    let rebinds = storage.get_seal_rebinds().unwrap();
+    assert_eq!(rebinds.len(), 2);
+    assert_eq!(rebinds[0].claim_id, 1);
+    assert_eq!(rebinds[1].claim_id, 2);
+    assert_eq!(rebinds[1].vout, 3);
+    assert_eq!(rebinds[1].spent_txid, "a".repeat(64));
+
+    storage.remove_seal_rebind(1).unwrap();
+    let rebinds = storage.get_seal_rebinds().unwrap();
+    assert_eq!(rebinds.len(), 1);
+    assert_eq!(rebinds[0].claim_id, 2);
+}
+
+#[test]
+fn test_witness_replacements_persist_until_removed() {
+    // Setup
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    {
+        let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+        storage
+            .insert_witness_replacement(&WitnessReplacement {
+                original_txid: "a".repeat(64),
+                txid: "b".repeat(64),
+                created_at: 1_000,
+            })
+            .unwrap();
+
+        // Bumping the same witness again replaces the entry
+        storage
+            .insert_witness_replacement(&WitnessReplacement {
+                original_txid: "a".repeat(64),
+                txid: "c".repeat(64),
+                created_at: 1_001,
+            })
+            .unwrap();
+    }
+
+    // Entries survive a restart (repaired on the next sync)
+    let mut storage = ClaimStorage::new(temp_dir.path()).unwrap();
+    let replacements = storage.get_witness_replacements().unwrap();
+    assert_eq!(replacements.len(), 1);
+    assert_eq!(replacements[0].txid, "c".repeat(64));
+
+    storage.remove_witness_replacement(&"a".repeat(64)).unwrap();
+    assert!(storage.get_witness_replacements().unwrap().is_empty());
+}
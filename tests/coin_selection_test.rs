@@ -0,0 +1,64 @@
+//! RGB Coin Selection Tests
+//!
+//! Tests input seal selection for transfers. Pure logic, no F1r3node or
+//! Bitcoin network required.
+
+use f1r3fly_rgb_wallet::f1r3fly::{select_rgb_inputs, UtxoBalance};
+
+fn seal(n: u8, amount: u64) -> UtxoBalance {
+    UtxoBalance {
+        outpoint: format!("{}:0", hex::encode([n; 32])),
+        amount,
+    }
+}
+
+#[test]
+fn test_single_seal_prefers_smallest_sufficient() {
+    let balances = vec![seal(1, 1_000), seal(2, 300), seal(3, 500)];
+
+    let selection = select_rgb_inputs(&balances, 400).expect("Selection should succeed");
+
+    assert_eq!(selection.inputs.len(), 1);
+    assert_eq!(selection.inputs[0].outpoint, balances[2].outpoint);
+    assert_eq!(selection.total, 500);
+    assert_eq!(selection.change(), 100);
+    assert!(!selection.is_multi_input());
+}
+
+#[test]
+fn test_multi_input_largest_first() {
+    let balances = vec![seal(1, 100), seal(2, 400), seal(3, 0), seal(4, 300)];
+
+    let selection = select_rgb_inputs(&balances, 650).expect("Selection should succeed");
+
+    assert!(selection.is_multi_input());
+    let amounts: Vec<u64> = selection.inputs.iter().map(|input| input.amount).collect();
+    assert_eq!(amounts, vec![400, 300]);
+    assert_eq!(selection.total, 700);
+    assert_eq!(selection.change(), 50);
+}
+
+#[test]
+fn test_multi_input_exact_amount_has_no_change() {
+    let balances = vec![seal(1, 100), seal(2, 400), seal(3, 300)];
+
+    let selection = select_rgb_inputs(&balances, 800).expect("Selection should succeed");
+
+    assert_eq!(selection.inputs.len(), 3);
+    assert_eq!(selection.change(), 0);
+}
+
+#[test]
+fn test_insufficient_balance_returns_none() {
+    let balances = vec![seal(1, 100), seal(2, 200)];
+
+    assert!(select_rgb_inputs(&balances, 301).is_none());
+    assert!(select_rgb_inputs(&[], 1).is_none());
+}
+
+#[test]
+fn test_zero_amount_returns_none() {
+    let balances = vec![seal(1, 100)];
+
+    assert!(select_rgb_inputs(&balances, 0).is_none());
+}
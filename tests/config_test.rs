@@ -0,0 +1,127 @@
+//! Configuration Tests
+//!
+//! Tests config file parsing and the RGB anchor method defaults
+//! (global config and per-wallet metadata).
+
+use f1r3fly_rgb_wallet::config::{
+    load_config, ConfigOverrides, GlobalConfig, KeyMode, NetworkType,
+};
+use f1r3fly_rgb_wallet::f1r3fly::AnchorMethod;
+use f1r3fly_rgb_wallet::storage::file_system::{load_wallet_metadata, update_wallet_metadata};
+use f1r3fly_rgb_wallet::storage::models::WalletMetadata;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+/// Config file written before `anchor_method` existed
+const LEGACY_CONFIG: &str = r#"{
+  "f1r3node": {
+    "host": "localhost",
+    "grpc_port": 40401,
+    "http_port": 40403,
+    "master_key": "0000000000000000000000000000000000000000000000000000000000000001"
+  },
+  "bitcoin": {
+    "network": "regtest",
+    "esplora_url": "http://localhost:3002"
+  },
+  "wallets_dir": null
+}"#;
+
+#[test]
+fn test_anchor_method_parse_and_display() {
+    assert_eq!(
+        AnchorMethod::from_str("tapret").unwrap(),
+        AnchorMethod::Tapret
+    );
+    assert_eq!(
+        AnchorMethod::from_str("OpReturn").unwrap(),
+        AnchorMethod::OpReturn
+    );
+    assert_eq!(
+        AnchorMethod::from_str("op_return").unwrap(),
+        AnchorMethod::OpReturn
+    );
+    assert!(AnchorMethod::from_str("taproot").is_err());
+
+    // Display output parses back
+    for method in [AnchorMethod::Tapret, AnchorMethod::OpReturn] {
+        assert_eq!(AnchorMethod::from_str(&method.to_string()).unwrap(), method);
+    }
+
+    // Serialized form matches the CLI names
+    assert_eq!(
+        serde_json::to_string(&AnchorMethod::OpReturn).unwrap(),
+        "\"opreturn\""
+    );
+}
+
+#[test]
+fn test_legacy_config_has_no_anchor_default() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("config.json");
+    std::fs::write(&config_path, LEGACY_CONFIG).expect("Failed to write config");
+
+    let config =
+        load_config(Some(&config_path), ConfigOverrides::new()).expect("Failed to load config");
+
+    assert_eq!(config.bitcoin.network, NetworkType::Regtest);
+    assert_eq!(config.anchor_method, None);
+    assert_eq!(config.f1r3node.key_mode, KeyMode::Shared);
+}
+
+#[test]
+fn test_key_mode_parse_and_legacy_metadata_default() {
+    assert_eq!(KeyMode::from_str("wallet").unwrap(), KeyMode::Wallet);
+    assert_eq!(KeyMode::from_str("Shared").unwrap(), KeyMode::Shared);
+    assert!(KeyMode::from_str("mnemonic").is_err());
+    assert_eq!(
+        KeyMode::from_str(&KeyMode::Wallet.to_string()).unwrap(),
+        KeyMode::Wallet
+    );
+
+    // Wallets created before key modes existed keep the shared master key
+    let legacy_metadata = r#"{
+  "name": "legacy",
+  "network": "regtest",
+  "created_at": "2025-01-01T00:00:00Z",
+  "last_sync": null,
+  "anchor_method": null
+}"#;
+    let metadata: WalletMetadata = serde_json::from_str(legacy_metadata).unwrap();
+    assert_eq!(metadata.key_mode, KeyMode::Shared);
+}
+
+#[test]
+fn test_config_anchor_method_round_trip() {
+    let mut config: GlobalConfig =
+        serde_json::from_str(LEGACY_CONFIG).expect("Failed to parse config");
+    config.anchor_method = Some(AnchorMethod::OpReturn);
+
+    let json = serde_json::to_string_pretty(&config).expect("Failed to serialize config");
+    assert!(json.contains("\"anchor_method\": \"opreturn\""));
+
+    let reloaded: GlobalConfig = serde_json::from_str(&json).expect("Failed to parse config");
+    assert_eq!(reloaded.anchor_method, Some(AnchorMethod::OpReturn));
+}
+
+#[test]
+fn test_wallet_anchor_method_persists_in_metadata() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().to_str().expect("Invalid temp path");
+    let wallet_name = "anchor_wallet";
+
+    std::fs::create_dir_all(temp_dir.path().join(wallet_name)).expect("Failed to create dir");
+    let metadata = WalletMetadata::new(wallet_name.to_string(), NetworkType::Regtest);
+    assert_eq!(metadata.anchor_method, None);
+    update_wallet_metadata(wallet_name, &metadata, Some(base)).expect("Failed to save metadata");
+
+    let mut loaded =
+        load_wallet_metadata(wallet_name, Some(base)).expect("Failed to load metadata");
+    assert_eq!(loaded.anchor_method, None);
+
+    loaded.anchor_method = Some(AnchorMethod::OpReturn);
+    update_wallet_metadata(wallet_name, &loaded, Some(base)).expect("Failed to save metadata");
+
+    let reloaded = load_wallet_metadata(wallet_name, Some(base)).expect("Failed to load metadata");
+    assert_eq!(reloaded.anchor_method, Some(AnchorMethod::OpReturn));
+}
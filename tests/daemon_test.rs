@@ -0,0 +1,114 @@
+//! Daemon Tests
+//!
+//! Covers command output capture and the daemon socket protocol without a
+//! wallet: status, lock, forwarding to a locked daemon and shutdown.
+
+#![cfg(unix)]
+
+use f1r3fly_rgb_wallet::cli::args::Cli;
+use f1r3fly_rgb_wallet::cli::output;
+use f1r3fly_rgb_wallet::config::GlobalConfig;
+use f1r3fly_rgb_wallet::daemon::client::DaemonClient;
+use f1r3fly_rgb_wallet::daemon::protocol::{DaemonRequest, DaemonResponse};
+use f1r3fly_rgb_wallet::daemon::server::DaemonServer;
+use f1r3fly_rgb_wallet::daemon::DaemonError;
+use f1r3fly_rgb_wallet::{errln, outln};
+use tempfile::TempDir;
+
+fn noop_runner(_cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
+}
+
+#[test]
+fn test_capture_collects_command_output() {
+    let (value, captured) = output::capture(|| {
+        outln!("balance: {}", 42);
+        errln!("warning");
+        7
+    });
+
+    assert_eq!(value, 7);
+    assert_eq!(captured.stdout, "balance: 42\n");
+    assert_eq!(captured.stderr, "warning\n");
+
+    // Nested captures keep their output separate
+    let (_, outer) = output::capture(|| {
+        outln!("outer");
+        let (_, inner) = output::capture(|| outln!("inner"));
+        assert_eq!(inner.stdout, "inner\n");
+    });
+    assert_eq!(outer.stdout, "outer\n");
+}
+
+#[test]
+fn test_daemon_round_trip_without_wallet() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let socket = temp_dir.path().join("daemon.sock");
+
+    let mut config = GlobalConfig::default_regtest();
+    config.wallets_dir = Some(temp_dir.path().join("wallets").display().to_string());
+
+    // Bind on the daemon thread; the server holds the wallet it unlocks
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let daemon_socket = socket.clone();
+    let daemon_config = config.clone();
+    let handle = std::thread::spawn(move || {
+        let server = DaemonServer::bind(&daemon_socket, daemon_config, noop_runner)?;
+        ready_tx.send(()).unwrap();
+        server.run()
+    });
+    ready_rx.recv().expect("Failed to bind daemon");
+
+    // A second daemon on the same socket is refused
+    assert!(matches!(
+        DaemonServer::bind(&socket, config, noop_runner),
+        Err(DaemonError::AlreadyRunning(_))
+    ));
+
+    let client = DaemonClient::new(&socket);
+
+    match client.request(&DaemonRequest::Status).unwrap() {
+        DaemonResponse::Status {
+            wallet,
+            expires_in_secs,
+        } => {
+            assert_eq!(wallet, None);
+            assert_eq!(expires_in_secs, None);
+        }
+        other => panic!("Unexpected response: {:?}", other),
+    }
+
+    // Nothing unlocked: forwarded commands run locally
+    let args = ["f1r3fly-rgb-wallet", "--wallet", "alice", "get-balance"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(client.forward(args).is_none());
+
+    assert!(matches!(
+        client.request(&DaemonRequest::Lock).unwrap(),
+        DaemonResponse::Locked
+    ));
+
+    // Unlocking a missing wallet reports the error and keeps serving
+    assert!(matches!(
+        client.request(&DaemonRequest::Unlock {
+            wallet: "missing".to_string(),
+            password: "password".to_string(),
+            timeout_secs: 60,
+        }),
+        Err(DaemonError::Remote(_))
+    ));
+
+    assert!(matches!(
+        client.request(&DaemonRequest::Stop).unwrap(),
+        DaemonResponse::Stopping
+    ));
+    handle.join().unwrap().expect("Daemon failed");
+
+    assert!(!socket.exists(), "Socket should be removed on shutdown");
+    assert!(matches!(
+        client.request(&DaemonRequest::Status),
+        Err(DaemonError::NotRunning(_))
+    ));
+}
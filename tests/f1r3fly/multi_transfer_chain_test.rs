@@ -6,6 +6,7 @@
 //! - State consistency is maintained throughout chain
 //! - Total supply conservation
 //! - RGB-occupied UTXO tracking for all parties
+//! - Transfers to blinded seals
 //!
 //! Prerequisites:
 //! - Running Bitcoin regtest (./scripts/start-regtest.sh)
@@ -522,3 +523,611 @@ async fn test_transfer_chain_with_explicit_sync() {
 
     println!("✓ Transfer chain with explicit sync completed successfully");
 }
+
+/// Test that a non-issuer can spend tokens received via a claimed seal
+///
+/// Flow:
+/// 1. Alice issues 1,000 tokens
+/// 2. Alice → Bob (400)
+/// 3. Bob → Carol (150) from his claimed seal (no genesis UTXO involved)
+///
+/// Verifies:
+/// - Input seal selection finds Bob's claimed UTXO
+/// - Bob keeps the remainder (250) on his seal
+/// - Carol receives 150
+#[tokio::test]
+async fn test_non_issuer_transfer_from_claimed_seal() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("non_issuer_transfer");
+
+    let wallets = setup_test_wallets(&env)
+        .await
+        .expect("Failed to setup test wallets");
+
+    let mut alice = wallets.alice;
+    let mut bob = wallets.bob;
+    let mut carol = wallets.carol;
+
+    // Alice creates genesis UTXO and issues asset
+    let fee_rate = f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig::medium_priority();
+
+    let genesis_result = alice
+        .create_utxo(1_000_000, &fee_rate, true)
+        .expect("Failed to create genesis UTXO");
+
+    env.wait_for_confirmation(&genesis_result.txid, 1)
+        .await
+        .expect("Failed to confirm genesis UTXO");
+
+    alice
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Alice wallet");
+
+    let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+        ticker: "HOP".to_string(),
+        name: "Hop Token".to_string(),
+        supply: 1_000,
+        precision: 0,
+        genesis_utxo: format!("{}:{}", genesis_result.txid, genesis_result.outpoint.vout),
+    };
+
+    let asset_info = alice
+        .issue_asset(request)
+        .await
+        .expect("Failed to issue asset");
+
+    // Bob and Carol accept genesis
+    let genesis_response = alice
+        .export_genesis(&asset_info.contract_id)
+        .await
+        .expect("Failed to export genesis");
+    let genesis_path = genesis_response
+        .consignment_path
+        .to_str()
+        .expect("Invalid path");
+
+    bob.accept_consignment(genesis_path)
+        .await
+        .expect("Bob failed to accept genesis");
+    carol
+        .accept_consignment(genesis_path)
+        .await
+        .expect("Carol failed to accept genesis");
+
+    // Transfer 1: Alice → Bob (400)
+    let bob_invoice = bob
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 400)
+        .expect("Failed to generate Bob's invoice");
+
+    bob.sync_wallet()
+        .await
+        .expect("Failed to sync Bob after invoice generation");
+
+    let transfer1 = alice
+        .send_transfer(
+            &bob_invoice.invoice_string,
+            bob_invoice.recipient_pubkey_hex.clone(),
+            &fee_rate,
+            None,
+        )
+        .await
+        .expect("Failed to send transfer 1");
+
+    env.wait_for_confirmation(&transfer1.bitcoin_txid, 1)
+        .await
+        .expect("Failed to confirm transfer 1");
+
+    bob.sync_wallet().await.expect("Failed to sync Bob");
+    bob.accept_consignment(transfer1.consignment_path.to_str().expect("Invalid path"))
+        .await
+        .expect("Bob failed to accept consignment");
+    bob.sync_wallet()
+        .await
+        .expect("Failed to sync Bob after acceptance");
+
+    verify_balance_with_retry(&mut bob, &asset_info.contract_id, 400, 20)
+        .await
+        .expect("Bob should have 400 tokens");
+
+    // Transfer 2: Bob → Carol (150) from Bob's claimed seal
+    let carol_invoice = carol
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 150)
+        .expect("Failed to generate Carol's invoice");
+
+    carol
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Carol after invoice generation");
+
+    let transfer2 = bob
+        .send_transfer(
+            &carol_invoice.invoice_string,
+            carol_invoice.recipient_pubkey_hex.clone(),
+            &fee_rate,
+            None,
+        )
+        .await
+        .expect("Bob should be able to spend received tokens");
+
+    assert_eq!(transfer2.amount, 150);
+    assert_eq!(transfer2.change_amount, 250);
+
+    env.wait_for_confirmation(&transfer2.bitcoin_txid, 1)
+        .await
+        .expect("Failed to confirm transfer 2");
+
+    carol.sync_wallet().await.expect("Failed to sync Carol");
+    carol
+        .accept_consignment(transfer2.consignment_path.to_str().expect("Invalid path"))
+        .await
+        .expect("Carol failed to accept consignment");
+    carol
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Carol after acceptance");
+
+    verify_balance_with_retry(&mut bob, &asset_info.contract_id, 250, 20)
+        .await
+        .expect("Bob should have 250 tokens left");
+
+    verify_balance_with_retry(&mut carol, &asset_info.contract_id, 150, 20)
+        .await
+        .expect("Carol should have 150 tokens");
+
+    println!("✓ Non-issuer transfer from claimed seal completed successfully");
+}
+
+/// Test multi-input transfer with change seal
+///
+/// Bob receives two seals (400 + 300) and sends 600 to Carol, which no
+/// single seal covers. Both seals are spent and the 100 token remainder
+/// lands on a fresh change seal owned by Bob.
+#[tokio::test]
+async fn test_multi_input_transfer_with_change_seal() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("multi_input_transfer");
+
+    let wallets = setup_test_wallets(&env)
+        .await
+        .expect("Failed to setup test wallets");
+
+    let mut alice = wallets.alice;
+    let mut bob = wallets.bob;
+    let mut carol = wallets.carol;
+
+    // Alice creates genesis UTXO and issues asset
+    let fee_rate = f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig::medium_priority();
+
+    let genesis_result = alice
+        .create_utxo(1_000_000, &fee_rate, true)
+        .expect("Failed to create genesis UTXO");
+
+    env.wait_for_confirmation(&genesis_result.txid, 1)
+        .await
+        .expect("Failed to confirm genesis UTXO");
+
+    alice
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Alice wallet");
+
+    let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+        ticker: "HOP".to_string(),
+        name: "Hop Token".to_string(),
+        supply: 1_000,
+        precision: 0,
+        genesis_utxo: format!("{}:{}", genesis_result.txid, genesis_result.outpoint.vout),
+    };
+
+    let asset_info = alice
+        .issue_asset(request)
+        .await
+        .expect("Failed to issue asset");
+
+    // Bob and Carol accept genesis
+    let genesis_response = alice
+        .export_genesis(&asset_info.contract_id)
+        .await
+        .expect("Failed to export genesis");
+    let genesis_path = genesis_response
+        .consignment_path
+        .to_str()
+        .expect("Invalid path");
+
+    bob.accept_consignment(genesis_path)
+        .await
+        .expect("Bob failed to accept genesis");
+    carol
+        .accept_consignment(genesis_path)
+        .await
+        .expect("Carol failed to accept genesis");
+
+    // Alice → Bob twice (400, then 300): Bob ends up with two seals
+    let mut bob_expected = 0;
+    for amount in [400, 300] {
+        let bob_invoice = bob
+            .generate_invoice_with_pubkey(&asset_info.contract_id, amount)
+            .expect("Failed to generate Bob's invoice");
+
+        bob.sync_wallet()
+            .await
+            .expect("Failed to sync Bob after invoice generation");
+
+        let transfer = alice
+            .send_transfer(
+                &bob_invoice.invoice_string,
+                bob_invoice.recipient_pubkey_hex.clone(),
+                &fee_rate,
+                None,
+            )
+            .await
+            .expect("Failed to send transfer to Bob");
+
+        env.wait_for_confirmation(&transfer.bitcoin_txid, 1)
+            .await
+            .expect("Failed to confirm transfer to Bob");
+
+        bob.sync_wallet().await.expect("Failed to sync Bob");
+        bob.accept_consignment(transfer.consignment_path.to_str().expect("Invalid path"))
+            .await
+            .expect("Bob failed to accept consignment");
+        bob.sync_wallet()
+            .await
+            .expect("Failed to sync Bob after acceptance");
+
+        bob_expected += amount;
+        verify_balance_with_retry(&mut bob, &asset_info.contract_id, bob_expected, 20)
+            .await
+            .expect("Bob balance mismatch");
+    }
+
+    // Bob → Carol (600): needs both seals
+    let carol_invoice = carol
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 600)
+        .expect("Failed to generate Carol's invoice");
+
+    carol
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Carol after invoice generation");
+
+    let transfer = bob
+        .send_transfer(
+            &carol_invoice.invoice_string,
+            carol_invoice.recipient_pubkey_hex.clone(),
+            &fee_rate,
+            None,
+        )
+        .await
+        .expect("Bob should be able to combine seals");
+
+    assert_eq!(transfer.inputs.len(), 2, "Both seals should be spent");
+    assert_eq!(transfer.change_amount, 100);
+    let change_seal = transfer
+        .change_seal
+        .clone()
+        .expect("Multi-input transfer should create a change seal");
+
+    env.wait_for_confirmation(&transfer.bitcoin_txid, 1)
+        .await
+        .expect("Failed to confirm transfer to Carol");
+
+    carol.sync_wallet().await.expect("Failed to sync Carol");
+    carol
+        .accept_consignment(transfer.consignment_path.to_str().expect("Invalid path"))
+        .await
+        .expect("Carol failed to accept consignment");
+    carol
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Carol after acceptance");
+
+    // Sync retries the change claim if it could not complete at send time
+    bob.sync_wallet().await.expect("Failed to sync Bob");
+
+    verify_balance_with_retry(&mut bob, &asset_info.contract_id, 100, 20)
+        .await
+        .expect("Bob should have 100 tokens on the change seal");
+
+    verify_balance_with_retry(&mut carol, &asset_info.contract_id, 600, 20)
+        .await
+        .expect("Carol should have 600 tokens");
+
+    let bob_balance = bob
+        .get_asset_balance(&asset_info.contract_id)
+        .await
+        .expect("Failed to get Bob's balance");
+    assert!(
+        bob_balance
+            .utxo_balances
+            .iter()
+            .any(|utxo| utxo.outpoint == change_seal && utxo.amount == 100),
+        "Change seal {} should hold Bob's remaining 100 tokens",
+        change_seal
+    );
+
+    println!("✓ Multi-input transfer with change seal completed successfully");
+}
+
+/// Test batch transfer to two recipients in one witness transaction
+///
+/// Flow:
+/// 1. Alice issues 1,000 tokens
+/// 2. Alice pays Bob (300) and Carol (200) with one batch transfer
+/// 3. Bob and Carol each accept their own consignment
+///
+/// Verifies:
+/// - One Bitcoin transaction, one consignment per recipient
+/// - Alice keeps the remainder (500) on her genesis seal
+/// - Both recipients receive their amounts
+#[tokio::test]
+async fn test_batch_transfer_to_multiple_recipients() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("batch_transfer");
+
+    let wallets = setup_test_wallets(&env)
+        .await
+        .expect("Failed to setup test wallets");
+
+    let mut alice = wallets.alice;
+    let mut bob = wallets.bob;
+    let mut carol = wallets.carol;
+
+    // Alice creates genesis UTXO and issues asset
+    let fee_rate = f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig::medium_priority();
+
+    let genesis_result = alice
+        .create_utxo(1_000_000, &fee_rate, true)
+        .expect("Failed to create genesis UTXO");
+
+    env.wait_for_confirmation(&genesis_result.txid, 1)
+        .await
+        .expect("Failed to confirm genesis UTXO");
+
+    alice
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Alice wallet");
+
+    let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+        ticker: "HOP".to_string(),
+        name: "Hop Token".to_string(),
+        supply: 1_000,
+        precision: 0,
+        genesis_utxo: format!("{}:{}", genesis_result.txid, genesis_result.outpoint.vout),
+    };
+
+    let asset_info = alice
+        .issue_asset(request)
+        .await
+        .expect("Failed to issue asset");
+
+    // Bob and Carol accept genesis
+    let genesis_response = alice
+        .export_genesis(&asset_info.contract_id)
+        .await
+        .expect("Failed to export genesis");
+    let genesis_path = genesis_response
+        .consignment_path
+        .to_str()
+        .expect("Invalid path");
+
+    bob.accept_consignment(genesis_path)
+        .await
+        .expect("Bob failed to accept genesis");
+    carol
+        .accept_consignment(genesis_path)
+        .await
+        .expect("Carol failed to accept genesis");
+
+    // Bob and Carol generate invoices
+    let bob_invoice = bob
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 300)
+        .expect("Failed to generate Bob's invoice");
+    let carol_invoice = carol
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 200)
+        .expect("Failed to generate Carol's invoice");
+
+    bob.sync_wallet()
+        .await
+        .expect("Failed to sync Bob after invoice generation");
+    carol
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Carol after invoice generation");
+
+    // Alice pays both in one batch
+    // Bob's key comes from his invoice; Carol's plain invoice needs hers passed
+    let recipients = vec![
+        f1r3fly_rgb_wallet::f1r3fly::TransferRecipient {
+            invoice: bob_invoice.invoice.clone(),
+            recipient_pubkey_hex: None,
+        },
+        f1r3fly_rgb_wallet::f1r3fly::TransferRecipient {
+            invoice: carol_invoice.invoice_string.clone(),
+            recipient_pubkey_hex: Some(carol_invoice.recipient_pubkey_hex.clone()),
+        },
+    ];
+
+    let batch = alice
+        .send_transfer_batch(&recipients, &fee_rate, None)
+        .await
+        .expect("Failed to send batch transfer");
+
+    assert_eq!(batch.transfers.len(), 2, "One consignment per recipient");
+    assert_eq!(batch.total_amount, 500);
+    assert_eq!(batch.change_amount, 500);
+    assert_ne!(
+        batch.transfers[0].consignment_path,
+        batch.transfers[1].consignment_path
+    );
+
+    env.wait_for_confirmation(&batch.bitcoin_txid, 1)
+        .await
+        .expect("Failed to confirm batch transfer");
+
+    // Each recipient accepts its own consignment
+    for (recipient, transfer) in [&mut bob, &mut carol].into_iter().zip(&batch.transfers) {
+        recipient
+            .sync_wallet()
+            .await
+            .expect("Failed to sync recipient");
+        recipient
+            .accept_consignment(transfer.consignment_path.to_str().expect("Invalid path"))
+            .await
+            .expect("Recipient failed to accept consignment");
+        recipient
+            .sync_wallet()
+            .await
+            .expect("Failed to sync recipient after acceptance");
+    }
+
+    verify_balance_with_retry(&mut alice, &asset_info.contract_id, 500, 20)
+        .await
+        .expect("Alice should have 500 tokens left");
+
+    verify_balance_with_retry(&mut bob, &asset_info.contract_id, 300, 20)
+        .await
+        .expect("Bob should have 300 tokens");
+
+    verify_balance_with_retry(&mut carol, &asset_info.contract_id, 200, 20)
+        .await
+        .expect("Carol should have 200 tokens");
+
+    println!("✓ Batch transfer to multiple recipients completed successfully");
+}
+
+/// Test a transfer to a blinded seal
+///
+/// Flow:
+/// 1. Alice issues 1,000 tokens
+/// 2. Bob generates a blinded invoice for 400
+/// 3. Alice pays it; the witness tx has no recipient output, so Alice's
+///    wallet hosts the anchor
+/// 4. Bob accepts the consignment
+///
+/// Verifies:
+/// - The blinded UTXO stays RGB-occupied after acceptance
+/// - Bob receives 400 and Alice keeps 600
+#[tokio::test]
+async fn test_blinded_transfer_accepted_on_wallet_utxo() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("blinded_transfer");
+
+    let wallets = setup_test_wallets(&env)
+        .await
+        .expect("Failed to setup test wallets");
+
+    let mut alice = wallets.alice;
+    let mut bob = wallets.bob;
+
+    // Alice creates genesis UTXO and issues asset
+    let fee_rate = f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig::medium_priority();
+
+    let genesis_result = alice
+        .create_utxo(1_000_000, &fee_rate, true)
+        .expect("Failed to create genesis UTXO");
+
+    env.wait_for_confirmation(&genesis_result.txid, 1)
+        .await
+        .expect("Failed to confirm genesis UTXO");
+
+    alice
+        .sync_wallet()
+        .await
+        .expect("Failed to sync Alice wallet");
+
+    let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+        ticker: "BLND".to_string(),
+        name: "Blinded Token".to_string(),
+        supply: 1_000,
+        precision: 0,
+        genesis_utxo: format!("{}:{}", genesis_result.txid, genesis_result.outpoint.vout),
+    };
+
+    let asset_info = alice
+        .issue_asset(request)
+        .await
+        .expect("Failed to issue asset");
+
+    // Bob accepts genesis
+    let genesis_response = alice
+        .export_genesis(&asset_info.contract_id)
+        .await
+        .expect("Failed to export genesis");
+
+    bob.accept_consignment(
+        genesis_response
+            .consignment_path
+            .to_str()
+            .expect("Invalid path"),
+    )
+    .await
+    .expect("Bob failed to accept genesis");
+
+    // Bob blinds one of his UTXOs
+    let bob_invoice = bob
+        .generate_blinded_invoice(&asset_info.contract_id, 400, None)
+        .expect("Failed to generate Bob's blinded invoice");
+    let blinded_utxo = bob_invoice
+        .blinded_utxo
+        .clone()
+        .expect("Blinded invoice should record its UTXO");
+
+    assert!(
+        bob.rgb_occupied()
+            .iter()
+            .any(|outpoint| outpoint.to_string() == blinded_utxo),
+        "Blinded UTXO should be reserved once the invoice exists"
+    );
+
+    // Alice pays the blinded seal; the key comes from the invoice
+    let transfer = alice
+        .send_transfer(&bob_invoice.invoice, None, &fee_rate, None)
+        .await
+        .expect("Failed to send blinded transfer");
+
+    env.wait_for_confirmation(&transfer.bitcoin_txid, 1)
+        .await
+        .expect("Failed to confirm blinded transfer");
+
+    bob.sync_wallet()
+        .await
+        .expect("Failed to sync Bob before acceptance");
+    bob.accept_consignment(transfer.consignment_path.to_str().expect("Invalid path"))
+        .await
+        .expect("Bob failed to accept blinded transfer");
+    bob.sync_wallet()
+        .await
+        .expect("Failed to sync Bob after acceptance");
+
+    assert!(
+        bob.rgb_occupied()
+            .iter()
+            .any(|outpoint| outpoint.to_string() == blinded_utxo),
+        "Blinded UTXO should hold the received tokens"
+    );
+
+    verify_balance_with_retry(&mut bob, &asset_info.contract_id, 400, 20)
+        .await
+        .expect("Bob should have 400 tokens");
+
+    verify_balance_with_retry(&mut alice, &asset_info.contract_id, 600, 20)
+        .await
+        .expect("Alice should have 600 tokens left");
+
+    println!("✓ Blinded transfer accepted on Bob's UTXO");
+}
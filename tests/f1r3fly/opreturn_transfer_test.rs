@@ -120,6 +120,11 @@ async fn test_opreturn_transfer_alice_to_bob() {
         transfer_response.amount, 2_500,
         "Transfer amount should match invoice"
     );
+    assert_eq!(
+        transfer_response.anchor_method,
+        AnchorMethod::OpReturn,
+        "Transfer result should record the anchor method"
+    );
     assert_eq!(
         transfer_response.change_amount, 7_500,
         "Change amount should be correct"
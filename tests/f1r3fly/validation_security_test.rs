@@ -6,6 +6,7 @@
 //! - Double-spend prevention
 //! - Invalid seal handling
 //! - Corrupted consignment file rejection
+//! - Zero-amount invoice rejection
 //!
 //! Prerequisites:
 //! - Running Bitcoin regtest (./scripts/start-regtest.sh)
@@ -674,3 +675,58 @@ async fn test_unauthorized_transfer_rejected() {
         "Alice should still have all 10,000 tokens after failed attack"
     );
 }
+
+/// Test 8: Reject a transfer paying a zero-amount invoice
+///
+/// Verifies:
+/// - A zero amount fails invoice validation, not coin selection
+/// - Alice's balance is unchanged
+#[tokio::test]
+async fn test_reject_zero_amount_transfer() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("zero_amount_transfer");
+
+    let (mut alice, asset_info, _) = issue_test_asset(&env, env.unique_wallet_name(), "ZERO", 1000)
+        .await
+        .expect("Failed to issue asset");
+
+    let mut bob = setup_recipient_wallet(&env, "bob", "password")
+        .await
+        .expect("Failed to setup Bob");
+
+    let invoice = bob
+        .generate_invoice_with_pubkey(&asset_info.contract_id, 0)
+        .expect("Failed to generate invoice");
+
+    let fee_rate = f1r3fly_rgb_wallet::bitcoin::utxo::FeeRateConfig::medium_priority();
+    let err = alice
+        .send_transfer(
+            &invoice.invoice_string,
+            invoice.recipient_pubkey_hex.clone(),
+            &fee_rate,
+            None,
+        )
+        .await
+        .expect_err("Zero-amount transfer should be rejected");
+
+    let message = err.to_string();
+    assert!(
+        message.contains("Invalid amount"),
+        "Expected an invalid amount error, got: {}",
+        message
+    );
+    assert!(
+        !message.contains("Insufficient balance"),
+        "Zero amount must not reach coin selection: {}",
+        message
+    );
+
+    let balance = alice
+        .get_asset_balance(&asset_info.contract_id)
+        .await
+        .expect("Failed to get Alice's balance");
+    assert_eq!(balance.total, 1000, "Alice's balance should be unchanged");
+}
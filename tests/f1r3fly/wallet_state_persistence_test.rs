@@ -3,7 +3,9 @@
 //! Tests for F1r3fly RGB state persistence across wallet reloads and isolation
 //! between multiple wallets.
 
-use super::{check_f1r3node_available, setup_wallet_with_genesis_utxo};
+use super::{
+    check_f1r3node_available, generate_test_derivation_index, setup_wallet_with_genesis_utxo,
+};
 use crate::common::TestBitcoinEnv;
 use f1r3fly_rgb_wallet::manager::WalletManager;
 
@@ -467,3 +469,200 @@ async fn test_genesis_utxo_tracking() {
     assert_eq!(genesis_info_1.vout.to_string(), parts_1[1]);
     assert_eq!(genesis_info_2.vout.to_string(), parts_2[1]);
 }
+
+/// Test a wallet restored from its mnemonic recovers its issued asset
+///
+/// Verifies:
+/// - Full scan finds the genesis UTXO again
+/// - Genesis consignment re-import restores contract metadata and genesis info
+/// - Deployment derivation index is recovered from the registry URI
+/// - Balance matches the issued supply and the genesis UTXO is protected
+#[tokio::test]
+async fn test_restore_wallet_from_mnemonic() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("restore_from_mnemonic");
+    let wallet_name = env.unique_wallet_name();
+    let password = "test_password";
+    let backup_dir = tempfile::TempDir::new().expect("Failed to create backup dir");
+
+    // Step 1: Issue an asset and keep its genesis consignment outside the wallet
+    let (contract_id, genesis_utxo, mnemonic) = {
+        let (mut manager, genesis_utxo) =
+            setup_wallet_with_genesis_utxo(&env, wallet_name, password)
+                .await
+                .expect("Failed to setup wallet");
+
+        let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+            ticker: "RST".to_string(),
+            name: "Restore Token".to_string(),
+            supply: 500_000,
+            precision: 0,
+            genesis_utxo: genesis_utxo.clone(),
+        };
+        let asset_info = manager
+            .issue_asset(request)
+            .await
+            .expect("Failed to issue asset");
+
+        let export = manager
+            .export_genesis(&asset_info.contract_id)
+            .await
+            .expect("Failed to export genesis");
+        std::fs::copy(
+            &export.consignment_path,
+            backup_dir.path().join("genesis.consignment"),
+        )
+        .expect("Failed to back up consignment");
+
+        let (keys, _) = f1r3fly_rgb_wallet::storage::file_system::load_wallet(
+            wallet_name,
+            password,
+            env.config().wallets_dir.as_deref(),
+        )
+        .expect("Failed to read wallet keys");
+
+        (
+            asset_info.contract_id,
+            genesis_utxo,
+            keys.mnemonic.to_string(),
+        )
+    };
+
+    // Step 2: Lose the wallet directory
+    std::fs::remove_dir_all(env.wallet_dir(wallet_name)).expect("Failed to remove wallet");
+
+    // Step 3: Restore from mnemonic and consignment backup
+    let mut manager = WalletManager::new(env.config().clone()).expect("Failed to create manager");
+    let options = f1r3fly_rgb_wallet::f1r3fly::RestoreOptions {
+        consignments_dir: Some(backup_dir.path().to_path_buf()),
+        derivation_start: Some(generate_test_derivation_index(wallet_name)),
+        ..Default::default()
+    };
+    let report = manager
+        .restore_wallet(wallet_name, &mnemonic, password, options)
+        .await
+        .expect("Failed to restore wallet");
+
+    assert!(report.transactions_found > 0, "Full scan found no history");
+    assert_eq!(report.contracts_restored, vec![contract_id.clone()]);
+    assert_eq!(
+        report.issued_contracts.get(&contract_id).copied(),
+        Some(generate_test_derivation_index(wallet_name)),
+        "Deployment index not recovered"
+    );
+    assert!(report.skipped.is_empty(), "Skipped: {:?}", report.skipped);
+
+    let balance = manager
+        .get_asset_balance(&contract_id)
+        .await
+        .expect("Failed to get balance");
+    assert_eq!(balance.total, 500_000);
+
+    let occupied = manager.rgb_occupied();
+    assert!(
+        occupied
+            .iter()
+            .any(|outpoint| outpoint.to_string() == genesis_utxo),
+        "Genesis UTXO should be RGB-occupied after restore"
+    );
+}
+
+/// Test a wallet restored from its mnemonic alone finds its issued asset on the registry
+///
+/// Verifies:
+/// - The derivation walk looks up the contract's registry URI on F1r3node
+/// - The contract is imported under its registry-derived ID with its metadata
+/// - Balance matches the issued supply and the genesis UTXO is protected
+#[tokio::test]
+async fn test_restore_wallet_from_registry() {
+    if !check_f1r3node_available() {
+        return;
+    }
+
+    let env = TestBitcoinEnv::new("restore_from_registry");
+    let wallet_name = env.unique_wallet_name();
+    let password = "test_password";
+
+    // Step 1: Issue an asset; no consignment is kept
+    let (registry_uri, genesis_utxo, mnemonic) = {
+        let (mut manager, genesis_utxo) =
+            setup_wallet_with_genesis_utxo(&env, wallet_name, password)
+                .await
+                .expect("Failed to setup wallet");
+
+        let request = f1r3fly_rgb_wallet::f1r3fly::IssueAssetRequest {
+            ticker: "REG".to_string(),
+            name: "Registry Token".to_string(),
+            supply: 250_000,
+            precision: 0,
+            genesis_utxo: genesis_utxo.clone(),
+        };
+        let asset_info = manager
+            .issue_asset(request)
+            .await
+            .expect("Failed to issue asset");
+
+        let (keys, _) = f1r3fly_rgb_wallet::storage::file_system::load_wallet(
+            wallet_name,
+            password,
+            env.config().wallets_dir.as_deref(),
+        )
+        .expect("Failed to read wallet keys");
+
+        (
+            asset_info.registry_uri,
+            genesis_utxo,
+            keys.mnemonic.to_string(),
+        )
+    };
+
+    // Step 2: Lose the wallet directory
+    std::fs::remove_dir_all(env.wallet_dir(wallet_name)).expect("Failed to remove wallet");
+
+    // Step 3: Restore from the mnemonic only
+    let mut manager = WalletManager::new(env.config().clone()).expect("Failed to create manager");
+    let options = f1r3fly_rgb_wallet::f1r3fly::RestoreOptions {
+        derivation_start: Some(generate_test_derivation_index(wallet_name)),
+        derivation_scan_limit: 10,
+        ..Default::default()
+    };
+    let report = manager
+        .restore_wallet(wallet_name, &mnemonic, password, options)
+        .await
+        .expect("Failed to restore wallet");
+
+    let contract_id = f1r3fly_rgb_wallet::f1r3fly::registry_contract_id(&registry_uri).to_string();
+    assert_eq!(report.contracts_restored, vec![contract_id.clone()]);
+    assert_eq!(
+        report.issued_contracts.get(&contract_id).copied(),
+        Some(generate_test_derivation_index(wallet_name)),
+        "Deployment index not recovered"
+    );
+    assert_eq!(
+        report.next_derivation_index,
+        generate_test_derivation_index(wallet_name) + 1
+    );
+
+    let assets = manager.list_assets().expect("Failed to list assets");
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].contract_id, contract_id);
+    assert_eq!(assets[0].ticker, "REG");
+    assert_eq!(assets[0].registry_uri, registry_uri);
+
+    let balance = manager
+        .get_asset_balance(&contract_id)
+        .await
+        .expect("Failed to get balance");
+    assert_eq!(balance.total, 250_000);
+
+    assert!(
+        manager
+            .rgb_occupied()
+            .iter()
+            .any(|outpoint| outpoint.to_string() == genesis_utxo),
+        "Genesis UTXO should be RGB-occupied after restore"
+    );
+}
@@ -0,0 +1,260 @@
+//! Key Storage Format Tests
+//!
+//! Verifies that no xprv reaches disk in plaintext, that legacy (version 0)
+//! wallet directories are migrated in place, that a BDK wallet opened
+//! from the public descriptor only signs after unlocking, that changing
+//! the password re-encrypts every secret, and that loading with a new KDF
+//! upgrades the key file.
+
+use f1r3fly_rgb_wallet::bitcoin::{BitcoinWallet, BitcoinWalletError};
+use f1r3fly_rgb_wallet::config::NetworkType;
+use f1r3fly_rgb_wallet::storage::file_system::{
+    change_password, load_wallet, load_wallet_with_kdf, save_wallet, FileSystemError,
+};
+use f1r3fly_rgb_wallet::storage::keys::{
+    encrypt_mnemonic, encrypt_secret_key, generate_mnemonic, Kdf,
+};
+use f1r3fly_rgb_wallet::storage::models::{
+    EncryptedWalletKeys, WalletKeys, WalletMetadata, KEYS_FORMAT_VERSION,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn new_keys() -> WalletKeys {
+    let mnemonic = generate_mnemonic().expect("Failed to generate mnemonic");
+    WalletKeys::from_mnemonic(&mnemonic, NetworkType::Regtest).expect("Failed to derive keys")
+}
+
+#[test]
+fn test_saved_wallet_has_no_plaintext_xprv() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().to_str().expect("Invalid temp path");
+    let wallet_name = "public_only";
+    let password = "test_password";
+
+    let keys = new_keys();
+    assert!(keys.bitcoin_descriptor.contains("tprv"));
+    assert!(keys.bitcoin_public_descriptor.starts_with("tr(tpub"));
+
+    let metadata = WalletMetadata::new(wallet_name.to_string(), NetworkType::Regtest);
+    save_wallet(wallet_name, &keys, &metadata, password, Some(base)).expect("Failed to save");
+
+    let wallet_path = temp_dir.path().join(wallet_name);
+    let descriptor = fs::read_to_string(wallet_path.join("descriptor.txt")).unwrap();
+    let keys_json = fs::read_to_string(wallet_path.join("keys.json")).unwrap();
+
+    assert_eq!(descriptor, keys.bitcoin_public_descriptor);
+    assert!(!keys_json.contains("tprv"), "keys.json leaks the xprv");
+
+    let stored: EncryptedWalletKeys = serde_json::from_str(&keys_json).unwrap();
+    assert_eq!(stored.version, KEYS_FORMAT_VERSION);
+    assert!(!stored.needs_migration());
+
+    let (loaded, _) = load_wallet(wallet_name, password, Some(base)).expect("Failed to load");
+    assert_eq!(loaded.bitcoin_descriptor, keys.bitcoin_descriptor);
+    assert_eq!(
+        loaded.bitcoin_public_descriptor,
+        keys.bitcoin_public_descriptor
+    );
+}
+
+#[test]
+fn test_legacy_wallet_migrated_on_load() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().to_str().expect("Invalid temp path");
+    let wallet_name = "legacy";
+    let password = "test_password";
+
+    // Write a directory in the pre-versioning layout
+    let keys = new_keys();
+    let wallet_path = temp_dir.path().join(wallet_name);
+    fs::create_dir_all(&wallet_path).unwrap();
+
+    let encrypted_mnemonic = encrypt_mnemonic(&keys.mnemonic, password).unwrap();
+    let encrypted_private_key = encrypt_secret_key(&keys.f1r3fly_private_key, password).unwrap();
+    let legacy_keys = serde_json::json!({
+        "encrypted_mnemonic": encrypted_mnemonic,
+        "bitcoin_descriptor": keys.bitcoin_descriptor,
+        "f1r3fly_public_key": keys.f1r3fly_public_key,
+        "encrypted_f1r3fly_private_key": encrypted_private_key,
+    });
+    fs::write(
+        wallet_path.join("keys.json"),
+        serde_json::to_string_pretty(&legacy_keys).unwrap(),
+    )
+    .unwrap();
+    fs::write(wallet_path.join("descriptor.txt"), &keys.bitcoin_descriptor).unwrap();
+
+    let metadata = WalletMetadata::new(wallet_name.to_string(), NetworkType::Regtest);
+    fs::write(
+        wallet_path.join("wallet.json"),
+        serde_json::to_string_pretty(&metadata).unwrap(),
+    )
+    .unwrap();
+
+    // Wrong password must not touch the files
+    assert!(load_wallet(wallet_name, "wrong_password", Some(base)).is_err());
+    let untouched = fs::read_to_string(wallet_path.join("keys.json")).unwrap();
+    assert!(untouched.contains("tprv"));
+
+    // Loading with the right password rewrites the directory
+    let (loaded, _) = load_wallet(wallet_name, password, Some(base)).expect("Failed to load");
+    assert_eq!(loaded.bitcoin_descriptor, keys.bitcoin_descriptor);
+
+    let keys_json = fs::read_to_string(wallet_path.join("keys.json")).unwrap();
+    let descriptor = fs::read_to_string(wallet_path.join("descriptor.txt")).unwrap();
+    assert!(
+        !keys_json.contains("tprv"),
+        "migrated keys.json leaks the xprv"
+    );
+    assert_eq!(descriptor, keys.bitcoin_public_descriptor);
+
+    let stored: EncryptedWalletKeys = serde_json::from_str(&keys_json).unwrap();
+    assert_eq!(stored.version, KEYS_FORMAT_VERSION);
+
+    // Migrated wallet still loads
+    let (reloaded, _) = load_wallet(wallet_name, password, Some(base)).expect("Failed to reload");
+    assert_eq!(reloaded.mnemonic.to_string(), keys.mnemonic.to_string());
+}
+
+#[test]
+fn test_public_descriptor_wallet_signs_only_after_unlock() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let wallet_dir = temp_dir.path().join("watch_only");
+
+    let keys = new_keys();
+    let other_keys = new_keys();
+
+    let mut wallet = BitcoinWallet::new(
+        keys.bitcoin_public_descriptor.clone(),
+        NetworkType::Regtest,
+        &wallet_dir,
+    )
+    .expect("Failed to open watch-only wallet");
+    assert!(!wallet.can_sign());
+
+    // Addresses match the private descriptor's
+    let expected = keys.first_address(NetworkType::Regtest).unwrap();
+    assert_eq!(wallet.peek_address().unwrap().to_string(), expected);
+
+    // Keys of another wallet are rejected
+    assert!(matches!(
+        wallet.unlock(&other_keys.bitcoin_descriptor),
+        Err(BitcoinWalletError::DescriptorMismatch)
+    ));
+    assert!(!wallet.can_sign());
+
+    wallet
+        .unlock(&keys.bitcoin_descriptor)
+        .expect("Failed to unlock wallet");
+    assert!(wallet.can_sign());
+    drop(wallet);
+
+    // A database created watch-only reopens with the private descriptor
+    let reopened = BitcoinWallet::new(
+        keys.bitcoin_descriptor.clone(),
+        NetworkType::Regtest,
+        &wallet_dir,
+    )
+    .expect("Failed to reopen wallet");
+    assert!(reopened.can_sign());
+}
+
+#[test]
+fn test_change_password_reencrypts_keys() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().to_str().expect("Invalid temp path");
+    let wallet_name = "rekeyed";
+
+    let keys = new_keys();
+    let metadata = WalletMetadata::new(wallet_name.to_string(), NetworkType::Regtest);
+    save_wallet(wallet_name, &keys, &metadata, "old_password", Some(base)).expect("Failed to save");
+
+    let keys_path = temp_dir.path().join(wallet_name).join("keys.json");
+    let before: EncryptedWalletKeys =
+        serde_json::from_str(&fs::read_to_string(&keys_path).unwrap()).unwrap();
+
+    // Wrong old password leaves keys.json untouched
+    assert!(matches!(
+        change_password(wallet_name, "wrong_password", "new_password", Some(base)),
+        Err(FileSystemError::Key(_))
+    ));
+    assert!(load_wallet(wallet_name, "old_password", Some(base)).is_ok());
+
+    change_password(wallet_name, "old_password", "new_password", Some(base))
+        .expect("Failed to change password");
+
+    // Every secret is re-encrypted (fresh salt), public fields are kept
+    let after: EncryptedWalletKeys =
+        serde_json::from_str(&fs::read_to_string(&keys_path).unwrap()).unwrap();
+    assert_ne!(after.encrypted_mnemonic, before.encrypted_mnemonic);
+    assert_ne!(
+        after.encrypted_bitcoin_descriptor,
+        before.encrypted_bitcoin_descriptor
+    );
+    assert_ne!(
+        after.encrypted_f1r3fly_private_key,
+        before.encrypted_f1r3fly_private_key
+    );
+    assert_eq!(
+        after.bitcoin_public_descriptor,
+        before.bitcoin_public_descriptor
+    );
+    assert!(!keys_path.with_extension("tmp").exists());
+
+    assert!(load_wallet(wallet_name, "old_password", Some(base)).is_err());
+    let (loaded, _) = load_wallet(wallet_name, "new_password", Some(base)).expect("Failed to load");
+    assert_eq!(loaded.mnemonic.to_string(), keys.mnemonic.to_string());
+    assert_eq!(loaded.f1r3fly_private_key, keys.f1r3fly_private_key);
+    assert_eq!(loaded.bitcoin_descriptor, keys.bitcoin_descriptor);
+}
+
+#[test]
+fn test_load_with_new_kdf_upgrades_keys() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base = temp_dir.path().to_str().expect("Invalid temp path");
+    let wallet_name = "upgraded";
+    let password = "test_password";
+    let argon2 = Kdf::Argon2id {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    let keys = new_keys();
+    let metadata = WalletMetadata::new(wallet_name.to_string(), NetworkType::Regtest);
+    save_wallet(wallet_name, &keys, &metadata, password, Some(base)).expect("Failed to save");
+
+    let keys_path = temp_dir.path().join(wallet_name).join("keys.json");
+    let read_keys = || -> EncryptedWalletKeys {
+        serde_json::from_str(&fs::read_to_string(&keys_path).unwrap()).unwrap()
+    };
+    assert_eq!(read_keys().kdf().unwrap(), Some(Kdf::default()));
+
+    // Loading without a target KDF, or with a wrong password, keeps the file
+    load_wallet(wallet_name, password, Some(base)).expect("Failed to load");
+    assert!(
+        load_wallet_with_kdf(wallet_name, "wrong_password", Some(&argon2), Some(base)).is_err()
+    );
+    assert!(read_keys().uses_kdf(&Kdf::default()));
+
+    // Unlocking with the configured KDF re-encrypts every secret
+    let (loaded, _) = load_wallet_with_kdf(wallet_name, password, Some(&argon2), Some(base))
+        .expect("Failed to load with new KDF");
+    assert_eq!(loaded.mnemonic.to_string(), keys.mnemonic.to_string());
+    assert!(read_keys().uses_kdf(&argon2));
+
+    // Already upgraded: no further rewrite
+    let before = fs::read_to_string(&keys_path).unwrap();
+    load_wallet_with_kdf(wallet_name, password, Some(&argon2), Some(base))
+        .expect("Failed to reload");
+    assert_eq!(fs::read_to_string(&keys_path).unwrap(), before);
+
+    // Password change keeps the upgraded KDF
+    change_password(wallet_name, password, "new_password", Some(base))
+        .expect("Failed to change password");
+    assert!(read_keys().uses_kdf(&argon2));
+    let (reloaded, _) =
+        load_wallet(wallet_name, "new_password", Some(base)).expect("Failed to load");
+    assert_eq!(reloaded.f1r3fly_private_key, keys.f1r3fly_private_key);
+}
@@ -1,13 +1,16 @@
 //! Integration tests for key management
 //!
 //! Tests BIP39 mnemonic generation, BIP32 Bitcoin key derivation,
-//! custom F1r3fly key derivation, and production-grade encryption.
+//! custom F1r3fly key derivation, production-grade encryption, and the
+//! versioned KDF envelope (including legacy blobs).
 
 use bip39::Mnemonic;
 use f1r3fly_rgb_wallet::config::NetworkType;
+use f1r3fly_rgb_wallet::f1r3fly::executor::wallet_derivation_start;
 use f1r3fly_rgb_wallet::storage::keys::{
-    decrypt_mnemonic, decrypt_secret_key, derive_bitcoin_keys, derive_f1r3fly_key,
-    encrypt_mnemonic, encrypt_secret_key, generate_mnemonic, KeyError,
+    decrypt_data, decrypt_mnemonic, decrypt_secret_key, derive_bitcoin_keys, derive_f1r3fly_chain,
+    derive_f1r3fly_key, encrypt_data, encrypt_data_with_kdf, encrypt_mnemonic, encrypt_secret_key,
+    encryption_kdf, f1r3fly_child_key, generate_mnemonic, Kdf, KeyError,
 };
 use std::str::FromStr;
 
@@ -383,3 +386,90 @@ fn test_decryption_fails_with_wrong_password_and_invalid_data() {
         "Empty password should work if used consistently"
     );
 }
+
+#[test]
+fn test_f1r3fly_chain_child_zero_is_wallet_key() {
+    let mnemonic = Mnemonic::from_str(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .expect("Valid test mnemonic");
+
+    let (wallet_key, wallet_pubkey_hex) =
+        derive_f1r3fly_key(&mnemonic).expect("Failed to derive F1r3fly key");
+    let chain = derive_f1r3fly_chain(&mnemonic).expect("Failed to derive F1r3fly chain");
+
+    // Child 0 of the chain is the wallet's F1r3fly key (m/1337'/0'/0'/0/0)
+    let child0 = f1r3fly_child_key(&chain, 0).expect("Failed to derive child 0");
+    assert_eq!(child0.secret_bytes(), wallet_key.secret_bytes());
+
+    // Contract keys are distinct and deterministic
+    let child7 = f1r3fly_child_key(&chain, 7).expect("Failed to derive child 7");
+    assert_ne!(child7.secret_bytes(), wallet_key.secret_bytes());
+    let again = derive_f1r3fly_chain(&mnemonic).unwrap();
+    assert_eq!(
+        f1r3fly_child_key(&again, 7).unwrap().secret_bytes(),
+        child7.secret_bytes()
+    );
+
+    // Hardened indices are rejected
+    assert!(f1r3fly_child_key(&chain, 1 << 31).is_err());
+
+    // Wallet key mode start index is deterministic and 24-bit
+    let start = wallet_derivation_start(&wallet_pubkey_hex);
+    assert_eq!(start, wallet_derivation_start(&wallet_pubkey_hex));
+    assert!(start < (1 << 24));
+}
+
+#[test]
+fn test_envelope_records_kdf_and_reads_legacy_blobs() {
+    use aes_gcm::aead::{Aead, KeyInit};
+    use aes_gcm::{Aes256Gcm, Nonce};
+    use pbkdf2::pbkdf2_hmac;
+    use sha2::Sha256;
+
+    let password = "envelope_password";
+    let data = b"wallet secret";
+
+    // Default encryption is a PBKDF2 envelope
+    let encrypted = encrypt_data(data, password).expect("Failed to encrypt");
+    assert_eq!(encryption_kdf(&encrypted).unwrap(), Some(Kdf::default()));
+    assert_eq!(decrypt_data(&encrypted, password).unwrap(), data);
+
+    // Argon2id round trip (small parameters to keep the test fast)
+    let argon2 = Kdf::Argon2id {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+    let encrypted = encrypt_data_with_kdf(data, password, &argon2).expect("Failed to encrypt");
+    assert_eq!(encryption_kdf(&encrypted).unwrap(), Some(argon2));
+    assert_eq!(decrypt_data(&encrypted, password).unwrap(), data);
+    assert!(decrypt_data(&encrypted, "wrong_password").is_err());
+
+    // The header is authenticated: weakening the parameters breaks decryption
+    let mut tampered = hex::decode(&encrypted).unwrap();
+    tampered[9] ^= 0x01; // low byte of memory_kib
+    assert!(decrypt_data(&hex::encode(tampered), password).is_err());
+
+    // Legacy blob: salt || nonce || ciphertext, PBKDF2 with 600,000 iterations
+    let salt = [7u8; 16];
+    let nonce_bytes = [9u8; 12];
+    let mut key_bytes = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, 600_000, &mut key_bytes);
+    let cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), &data[..])
+        .unwrap();
+    let mut legacy = salt.to_vec();
+    legacy.extend_from_slice(&nonce_bytes);
+    legacy.extend_from_slice(&ciphertext);
+    let legacy = hex::encode(legacy);
+
+    assert_eq!(encryption_kdf(&legacy).unwrap(), None);
+    assert_eq!(decrypt_data(&legacy, password).unwrap(), data);
+
+    // KDF names parse into their default parameters
+    assert_eq!(Kdf::from_str("pbkdf2").unwrap(), Kdf::default());
+    assert_eq!(Kdf::from_str("Argon2id").unwrap(), Kdf::argon2id());
+    assert!(Kdf::from_str("scrypt").is_err());
+}
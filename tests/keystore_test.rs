@@ -0,0 +1,104 @@
+//! Keystore Tests
+//!
+//! Tests the encrypted F1r3node master key keystore and that configs
+//! reference it by id without ever serializing the key.
+
+use f1r3fly_rgb_wallet::config::GlobalConfig;
+use f1r3fly_rgb_wallet::storage::keystore::{
+    import_key, list_keys, load_entry, resolve_master_key, unlock_key, KeystoreError,
+    KeystoreUnlock,
+};
+use tempfile::TempDir;
+
+const MASTER_KEY: &str = "5f668a7ee96d944a4494cc947e4005e172d7ab3461ee5538f1f2a45a835e9657";
+
+/// Config file referencing a keystore entry unlocked by a key file
+fn keystore_config(keystore_dir: &str, key_file: &str) -> GlobalConfig {
+    let json = format!(
+        r#"{{
+  "f1r3node": {{
+    "host": "localhost",
+    "grpc_port": 40401,
+    "http_port": 40403,
+    "master_key_id": "devnet",
+    "master_key_file": "{}"
+  }},
+  "bitcoin": {{
+    "network": "regtest",
+    "esplora_url": "http://localhost:3002"
+  }},
+  "wallets_dir": null,
+  "keystore_dir": "{}"
+}}"#,
+        key_file, keystore_dir
+    );
+    serde_json::from_str(&json).expect("Failed to parse config")
+}
+
+#[test]
+fn test_keystore_password_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let unlock = KeystoreUnlock::Password("keystore_password".to_string());
+
+    let entry = import_key(dir, "devnet", MASTER_KEY, &unlock).expect("Failed to import");
+    assert_eq!(entry.id, "devnet");
+    assert!(entry.public_key.starts_with("04"));
+
+    // The key never appears in the entry file
+    let json = std::fs::read_to_string(dir.join("devnet.json")).unwrap();
+    assert!(!json.contains(MASTER_KEY));
+
+    assert_eq!(unlock_key(dir, "devnet", &unlock).unwrap(), MASTER_KEY);
+    assert_eq!(
+        load_entry(dir, "devnet").unwrap().public_key,
+        entry.public_key
+    );
+    assert_eq!(list_keys(dir).unwrap().len(), 1);
+
+    // Wrong password, duplicate id and path-like ids are rejected
+    let wrong = KeystoreUnlock::Password("wrong".to_string());
+    assert!(matches!(
+        unlock_key(dir, "devnet", &wrong),
+        Err(KeystoreError::Key(_))
+    ));
+    assert!(matches!(
+        import_key(dir, "devnet", MASTER_KEY, &unlock),
+        Err(KeystoreError::AlreadyExists(_))
+    ));
+    assert!(matches!(
+        import_key(dir, "../escape", MASTER_KEY, &unlock),
+        Err(KeystoreError::InvalidId(_))
+    ));
+    assert!(matches!(
+        import_key(dir, "bad", "not-a-key", &unlock),
+        Err(KeystoreError::InvalidKey(_))
+    ));
+}
+
+#[test]
+fn test_config_resolves_master_key_from_keystore() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let keystore_dir = temp_dir.path().join("keystore");
+    let key_file = temp_dir.path().join("master.key");
+    std::fs::write(&key_file, "key-file-secret\n").unwrap();
+
+    import_key(
+        &keystore_dir,
+        "devnet",
+        MASTER_KEY,
+        &KeystoreUnlock::KeyFile(key_file.clone()),
+    )
+    .expect("Failed to import");
+
+    let config = keystore_config(keystore_dir.to_str().unwrap(), key_file.to_str().unwrap());
+    assert!(config.f1r3node.master_key.is_empty());
+    assert_eq!(resolve_master_key(&config).unwrap(), MASTER_KEY);
+
+    // Serialized config carries the reference, never the key
+    let mut unlocked = config.clone();
+    unlocked.f1r3node.master_key = MASTER_KEY.to_string();
+    let json = serde_json::to_string_pretty(&unlocked).unwrap();
+    assert!(!json.contains(MASTER_KEY));
+    assert!(json.contains("\"master_key_id\": \"devnet\""));
+}
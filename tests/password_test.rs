@@ -0,0 +1,115 @@
+//! Password Acquisition Tests
+//!
+//! Verifies the order of password sources (flag, file, environment) and
+//! how password files are read.
+
+use f1r3fly_rgb_wallet::cli::args::{NewPasswordArgs, PasswordArgs};
+use f1r3fly_rgb_wallet::cli::password::PasswordError;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_password_file_reads_first_line() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("password");
+    fs::write(&path, "secret with spaces\nignored\n").unwrap();
+
+    let args = PasswordArgs {
+        password_file: Some(path.to_str().unwrap().to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        args.read("F1R3FLY_TEST_PASSWORD_UNSET", "Password", false)
+            .unwrap(),
+        "secret with spaces"
+    );
+
+    // CRLF line endings are stripped as well
+    fs::write(&path, "windows\r\n").unwrap();
+    assert_eq!(args.wallet_password(None).unwrap(), "windows");
+
+    // Missing file is an error, not a prompt
+    let missing = PasswordArgs {
+        password_file: Some(
+            temp_dir
+                .path()
+                .join("missing")
+                .to_str()
+                .unwrap()
+                .to_string(),
+        ),
+        ..Default::default()
+    };
+    assert!(matches!(
+        missing.wallet_password(None),
+        Err(PasswordError::Read { .. })
+    ));
+}
+
+#[test]
+fn test_password_sources_priority() {
+    let env = "F1R3FLY_TEST_PASSWORD_PRIORITY";
+    std::env::set_var(env, "from_env");
+
+    // Environment is used when no flag is given
+    let args = PasswordArgs::default();
+    assert_eq!(args.read(env, "Password", false).unwrap(), "from_env");
+
+    // File beats environment
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("password");
+    fs::write(&path, "from_file").unwrap();
+    let args = PasswordArgs {
+        password_file: Some(path.to_str().unwrap().to_string()),
+        ..Default::default()
+    };
+    assert_eq!(args.read(env, "Password", false).unwrap(), "from_file");
+
+    // Deprecated flag beats everything
+    let args = PasswordArgs {
+        password: Some("from_flag".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(args.read(env, "Password", true).unwrap(), "from_flag");
+
+    std::env::remove_var(env);
+}
+
+#[test]
+fn test_new_password_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("new_password");
+    fs::write(&path, "new_secret\n").unwrap();
+
+    let args = NewPasswordArgs {
+        new_password_file: Some(path.to_str().unwrap().to_string()),
+        ..Default::default()
+    };
+    assert_eq!(args.new_wallet_password().unwrap(), "new_secret");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_password_fd_rejects_standard_and_closed_descriptors() {
+    // Standard streams are never taken over
+    for fd in 0..=2 {
+        let args = PasswordArgs {
+            password_fd: Some(fd),
+            ..Default::default()
+        };
+        assert!(matches!(
+            args.wallet_password(None),
+            Err(PasswordError::StandardStreamFd(rejected)) if rejected == fd
+        ));
+    }
+
+    // A descriptor that is not open is an error, not undefined behavior
+    let args = PasswordArgs {
+        password_fd: Some(4096),
+        ..Default::default()
+    };
+    assert!(matches!(
+        args.wallet_password(None),
+        Err(PasswordError::Read { .. })
+    ));
+}
@@ -0,0 +1,63 @@
+//! Wallet Restore Tests
+//!
+//! Tests the registry URI derivation used to match restored contracts to
+//! their deployment derivation index, and the restore defaults.
+
+use f1r3fly_rgb_wallet::bitcoin::DEFAULT_STOP_GAP;
+use f1r3fly_rgb_wallet::f1r3fly::{
+    registry_uri_for_public_key, RestoreOptions, DEFAULT_DERIVATION_SCAN_LIMIT,
+};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+const ZBASE32_ALPHABET: &str = "ybndrfg8ejkmcpqxot1uwisza345h769";
+
+fn public_key(byte: u8) -> PublicKey {
+    let secret_key = SecretKey::from_slice(&[byte; 32]).expect("Invalid secret key");
+    PublicKey::from_secret_key(&Secp256k1::new(), &secret_key)
+}
+
+#[test]
+fn test_registry_uri_format() {
+    let uri = registry_uri_for_public_key(&public_key(1));
+
+    let key = uri.strip_prefix("rho:id:").expect("Missing rho:id: scheme");
+    // 270 bits (256-bit hash + 14-bit checksum) in 5-bit characters
+    assert_eq!(key.len(), 54);
+    assert!(key.chars().all(|c| ZBASE32_ALPHABET.contains(c)));
+
+    // Deterministic per key, distinct across keys
+    assert_eq!(uri, registry_uri_for_public_key(&public_key(1)));
+    assert_ne!(uri, registry_uri_for_public_key(&public_key(2)));
+}
+
+#[test]
+fn test_registry_uri_known_vector() {
+    // Secp256k1 generator point (secret key 1), uncompressed
+    let public_key = PublicKey::from_slice(
+        &hex::decode(
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap(),
+    )
+    .expect("Invalid public key");
+
+    // Computed independently from F1r3node's Registry.buildURI: CRC14 of
+    // the blake2b256 hash, stored in bytes 32-33, then zbase32 of the first
+    // 270 bits. The checksum (0x0946) has bits 8-13 set, so the byte-33
+    // shift is covered.
+    assert_eq!(
+        registry_uri_for_public_key(&public_key),
+        "rho:id:hifba6bt1p1xkx98j9jiqoe4w4a6n185iernes878kugokpt4r4wcj"
+    );
+}
+
+#[test]
+fn test_restore_options_default() {
+    let options = RestoreOptions::default();
+
+    assert!(options.consignments_dir.is_none());
+    assert_eq!(options.stop_gap, DEFAULT_STOP_GAP);
+    assert!(options.derivation_start.is_none());
+    assert_eq!(options.derivation_scan_limit, DEFAULT_DERIVATION_SCAN_LIMIT);
+}
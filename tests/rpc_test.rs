@@ -0,0 +1,137 @@
+//! JSON-RPC API Tests
+//!
+//! Exercises request handling, parameter validation and error code mapping
+//! against a manager without a loaded wallet (no network required).
+
+use f1r3fly_rgb_wallet::config::GlobalConfig;
+use f1r3fly_rgb_wallet::manager::{ManagerError, WalletManager};
+use f1r3fly_rgb_wallet::rpc::methods::{handle_message, handle_request, SendTransferParams};
+use f1r3fly_rgb_wallet::rpc::protocol::{
+    RpcError, RpcRequest, RpcResponse, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND,
+    PARSE_ERROR, WALLET_ALREADY_EXISTS, WALLET_NOT_LOADED,
+};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn test_manager(temp_dir: &TempDir) -> WalletManager {
+    let mut config = GlobalConfig::default_regtest();
+    config.wallets_dir = Some(temp_dir.path().display().to_string());
+    WalletManager::new(config).expect("Failed to create WalletManager")
+}
+
+fn send(manager: &mut WalletManager, message: &str) -> Option<RpcResponse> {
+    f1r3fly_rgb_wallet::cli::runtime()
+        .unwrap()
+        .block_on(handle_message(manager, message))
+}
+
+fn error_code(response: &RpcResponse) -> i64 {
+    response.error.as_ref().expect("Expected an error").code
+}
+
+#[test]
+fn test_manager_errors_map_to_codes() {
+    let error = RpcError::from(ManagerError::WalletNotLoaded);
+    assert_eq!(error.code, WALLET_NOT_LOADED);
+    assert_eq!(error.message, "Wallet not loaded");
+    assert_eq!(error.data, Some(json!({ "kind": "WalletNotLoaded" })));
+
+    let error = RpcError::from(ManagerError::WalletAlreadyExists("alice".to_string()));
+    assert_eq!(error.code, WALLET_ALREADY_EXISTS);
+    assert_eq!(error.data, Some(json!({ "kind": "WalletAlreadyExists" })));
+}
+
+#[test]
+fn test_requests_without_loaded_wallet() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut manager = test_manager(&temp_dir);
+
+    // Wallet errors carry the request ID and the mapped code
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"2.0","id":7,"method":"list_claims"}"#,
+    )
+    .unwrap();
+    assert_eq!(response.id, json!(7));
+    assert!(response.result.is_none());
+    assert_eq!(error_code(&response), WALLET_NOT_LOADED);
+
+    // Positional params are accepted as well
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"2.0","id":"a","method":"list_claims","params":["contract"]}"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), WALLET_NOT_LOADED);
+
+    // Previews need the wallet too, although nothing is executed
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"2.0","id":8,"method":"preview_transfer","params":{"invoice":"rgb:invoice","recipient_pubkey":"04ab"}}"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), WALLET_NOT_LOADED);
+
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"2.0","id":1,"method":"get_balance"}"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), METHOD_NOT_FOUND);
+
+    // Missing required params are rejected before touching the wallet
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"2.0","id":2,"method":"generate_invoice_with_pubkey","params":{"contract_id":"x"}}"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), INVALID_PARAMS);
+
+    let response = send(&mut manager, "{not json").unwrap();
+    assert_eq!(response.id, Value::Null);
+    assert_eq!(error_code(&response), PARSE_ERROR);
+
+    let response = send(
+        &mut manager,
+        r#"{"jsonrpc":"1.0","id":3,"method":"sync_wallet"}"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+
+    let response = send(
+        &mut manager,
+        r#"[{"jsonrpc":"2.0","id":4,"method":"list_claims"}]"#,
+    )
+    .unwrap();
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+
+    // Notifications get no response
+    assert!(send(&mut manager, r#"{"jsonrpc":"2.0","method":"list_claims"}"#).is_none());
+}
+
+#[test]
+fn test_send_transfer_params_schema() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut manager = test_manager(&temp_dir);
+
+    let params: SendTransferParams = serde_json::from_value(json!({
+        "invoice": "rgb:invoice",
+        "recipient_pubkey": "04ab",
+        "anchor_method": "opreturn",
+    }))
+    .unwrap();
+    assert_eq!(params.fee_rate, None);
+
+    // A non-positive fee rate is a parameter error
+    let request = RpcRequest::new(
+        5,
+        "send_transfer",
+        json!({ "invoice": "rgb:invoice", "recipient_pubkey": "04ab", "fee_rate": 0.0 }),
+    );
+    let response = f1r3fly_rgb_wallet::cli::runtime()
+        .unwrap()
+        .block_on(handle_request(&mut manager, request))
+        .unwrap();
+    assert_eq!(response.id, json!(5));
+    assert_eq!(error_code(&response), INVALID_PARAMS);
+}
@@ -0,0 +1,157 @@
+//! Transfer Journal Tests
+//!
+//! Exercises journal persistence and phase tracking (no network required).
+
+use bdk_wallet::bitcoin::consensus::encode::serialize_hex;
+use bdk_wallet::bitcoin::hashes::Hash;
+use bdk_wallet::bitcoin::{
+    absolute, transaction, Amount, OutPoint, ScriptBuf, Transaction, TxIn, TxOut, Txid,
+};
+use f1r3fly_rgb_wallet::f1r3fly::{
+    AnchorMethod, JournalError, JournalLeg, TransferJournal, TransferJournalEntry, TransferPhase,
+    TransferRecipient, UtxoBalance,
+};
+use tempfile::TempDir;
+
+fn test_entry(created_at: u64) -> TransferJournalEntry {
+    TransferJournalEntry {
+        id: TransferJournal::new_id(),
+        phase: TransferPhase::Executing,
+        contract_id: "contract".to_string(),
+        anchor_method: AnchorMethod::Tapret,
+        fee_rate_sat_per_vb: 2.0,
+        recipients: vec![TransferRecipient {
+            invoice: "rgb:invoice".to_string(),
+            recipient_pubkey_hex: Some("04ab".to_string()),
+        }],
+        inputs: vec![UtxoBalance {
+            outpoint: format!("{}:0", "11".repeat(32)),
+            amount: 100,
+        }],
+        total_amount: 60,
+        change_amount: 40,
+        change: None,
+        legs: vec![JournalLeg {
+            from_seal_id: format!("{}:0", "11".repeat(32)),
+            to_seal_id: "witness:00:0".to_string(),
+            amount: 60,
+            to_pubkey_hex: "04ab".to_string(),
+            opid: None,
+        }],
+        execution: None,
+        txid: None,
+        signed_tx_hex: None,
+        transfers: Vec::new(),
+        last_error: None,
+        created_at,
+        updated_at: created_at,
+    }
+}
+
+#[test]
+fn test_journal_phases_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let journal = TransferJournal::new(temp_dir.path().join("transfer_journal"));
+
+    // Empty (and not yet created) journal
+    assert!(journal.list().unwrap().is_empty());
+
+    let mut entry = test_entry(1);
+    journal.save(&mut entry).unwrap();
+
+    entry.legs[0].opid = Some("ab".repeat(32));
+    entry.phase = TransferPhase::Executed;
+    journal.save(&mut entry).unwrap();
+
+    // Each save replaces the entry; no temporary file is left behind
+    let loaded = journal.load(&entry.id).unwrap();
+    assert_eq!(loaded.phase, TransferPhase::Executed);
+    assert_eq!(loaded.opids(), vec!["ab".repeat(32)]);
+    assert_eq!(std::fs::read_dir(journal.dir()).unwrap().count(), 1);
+
+    // Phases serialize in snake_case and are ordered by progress
+    let json = serde_json::to_value(&loaded).unwrap();
+    assert_eq!(json["phase"], "executed");
+    assert_eq!(
+        serde_json::to_value(TransferPhase::ConsignmentWritten).unwrap(),
+        "consignment_written"
+    );
+    assert!(TransferPhase::Signed < TransferPhase::Broadcast);
+    assert!(TransferPhase::Anchored < TransferPhase::ConsignmentWritten);
+}
+
+#[test]
+fn test_journal_list_and_remove() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let journal = TransferJournal::new(temp_dir.path());
+
+    let mut newer = test_entry(20);
+    let mut older = test_entry(10);
+    journal.save(&mut newer).unwrap();
+    journal.save(&mut older).unwrap();
+
+    // Staged consignments live next to the entry
+    let staging_dir = journal.staging_dir(&older.id);
+    std::fs::create_dir_all(&staging_dir).unwrap();
+    std::fs::write(staging_dir.join("consignment.json"), b"{}").unwrap();
+
+    let ids: Vec<String> = journal.list().unwrap().into_iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![older.id.clone(), newer.id.clone()]);
+
+    journal.remove(&older.id).unwrap();
+    assert!(!staging_dir.exists());
+    assert!(matches!(
+        journal.load(&older.id),
+        Err(JournalError::NotFound(_))
+    ));
+    assert_eq!(journal.list().unwrap().len(), 1);
+
+    // Removing twice is harmless
+    journal.remove(&older.id).unwrap();
+}
+
+#[test]
+fn test_signed_entry_reserves_witness_inputs() {
+    let spent = [
+        OutPoint::new(Txid::from_byte_array([0x22; 32]), 1),
+        OutPoint::new(Txid::from_byte_array([0x33; 32]), 0),
+    ];
+    let witness = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: spent
+            .iter()
+            .map(|outpoint| TxIn {
+                previous_output: *outpoint,
+                ..Default::default()
+            })
+            .collect(),
+        output: vec![TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: ScriptBuf::new(),
+        }],
+    };
+
+    // Nothing is reserved before the witness is signed
+    let mut entry = test_entry(1);
+    entry.phase = TransferPhase::Executed;
+    assert!(entry.reserved_outpoints().is_empty());
+
+    // A signed entry reserves every witness input, including fee inputs
+    entry.phase = TransferPhase::Signed;
+    entry.txid = Some(witness.compute_txid().to_string());
+    entry.signed_tx_hex = Some(serialize_hex(&witness));
+    assert_eq!(entry.reserved_outpoints(), spent.to_vec());
+
+    // Reservations survive a restart (reloaded from the journal)
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let journal = TransferJournal::new(temp_dir.path());
+    journal.save(&mut entry).unwrap();
+    let reserved: Vec<OutPoint> = journal
+        .list()
+        .unwrap()
+        .iter()
+        .flat_map(|entry| entry.reserved_outpoints())
+        .collect();
+    assert_eq!(reserved, spent.to_vec());
+}
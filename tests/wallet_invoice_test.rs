@@ -0,0 +1,93 @@
+//! Wallet Invoice Encoding Tests
+//!
+//! Exercises the pubkey/expiry extension of RGB invoices (no network required).
+
+use f1r3fly_rgb_wallet::f1r3fly::{InvoiceError, TransferRecipient, WalletInvoice};
+
+const RGB_INVOICE: &str = "contract:tb@fake-contract/100@at:seal/";
+
+#[test]
+fn test_wallet_invoice_round_trip() {
+    let invoice = WalletInvoice {
+        rgb_invoice: RGB_INVOICE.to_string(),
+        recipient_pubkey_hex: Some("04abcd".to_string()),
+        expiry: Some(1_700_000_000),
+    };
+
+    let encoded = invoice.to_string();
+    assert_eq!(
+        encoded,
+        format!("{}?pubkey=04abcd&expiry=1700000000", RGB_INVOICE)
+    );
+    assert_eq!(WalletInvoice::decode(&encoded).unwrap(), invoice);
+
+    // Plain RGB invoices decode without extensions and encode unchanged
+    let plain = WalletInvoice::decode(RGB_INVOICE).unwrap();
+    assert_eq!(plain.rgb_invoice, RGB_INVOICE);
+    assert_eq!(plain.recipient_pubkey_hex, None);
+    assert_eq!(plain.expiry, None);
+    assert_eq!(plain.to_string(), RGB_INVOICE);
+
+    // Other query parameters stay with the RGB invoice
+    let decoded = WalletInvoice::decode(&format!("{}?sig=x&pubkey=04AB", RGB_INVOICE)).unwrap();
+    assert_eq!(decoded.rgb_invoice, format!("{}?sig=x", RGB_INVOICE));
+    assert_eq!(decoded.recipient_pubkey_hex.as_deref(), Some("04ab"));
+    assert_eq!(
+        decoded.to_string(),
+        format!("{}?sig=x&pubkey=04ab", RGB_INVOICE)
+    );
+}
+
+#[test]
+fn test_wallet_invoice_rejects_bad_parameters() {
+    for invoice in [
+        format!("{}?pubkey=xyz", RGB_INVOICE),
+        format!("{}?pubkey=", RGB_INVOICE),
+        format!("{}?expiry=soon", RGB_INVOICE),
+    ] {
+        assert!(
+            matches!(
+                WalletInvoice::decode(&invoice),
+                Err(InvoiceError::InvalidParameter(_))
+            ),
+            "Should reject {}",
+            invoice
+        );
+    }
+
+    let invoice = WalletInvoice::decode(&format!("{}?expiry=100", RGB_INVOICE)).unwrap();
+    assert!(!invoice.is_expired(99));
+    assert!(invoice.is_expired(100));
+}
+
+#[test]
+fn test_recipient_pubkey_resolution() {
+    let embedded = format!("{}?pubkey=04ab", RGB_INVOICE);
+
+    // Taken from the invoice when not given
+    let recipient = TransferRecipient {
+        invoice: embedded.clone(),
+        recipient_pubkey_hex: None,
+    };
+    assert_eq!(recipient.resolve_pubkey().unwrap(), "04ab");
+
+    // An explicit key overrides the embedded one
+    let recipient = TransferRecipient {
+        invoice: embedded,
+        recipient_pubkey_hex: Some("04cd".to_string()),
+    };
+    assert_eq!(recipient.resolve_pubkey().unwrap(), "04cd");
+
+    // Plain invoices need an explicit key
+    let recipient = TransferRecipient {
+        invoice: RGB_INVOICE.to_string(),
+        recipient_pubkey_hex: None,
+    };
+    assert!(recipient.resolve_pubkey().is_err());
+
+    // Recipients files may omit the key
+    let recipient: TransferRecipient =
+        serde_json::from_str(r#"{"invoice": "contract:x?pubkey=04ef"}"#).unwrap();
+    assert_eq!(recipient.recipient_pubkey_hex, None);
+    assert_eq!(recipient.resolve_pubkey().unwrap(), "04ef");
+}